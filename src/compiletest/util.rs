@@ -34,6 +34,27 @@ pub fn get_os(triple: &str) -> &'static str {
     panic!("Cannot determine OS from triple");
 }
 
+/// Conversion table from the architecture at the start of a triple to the
+/// name `ignore-` directives use for it
+static ARCH_TABLE: &'static [(&'static str, &'static str)] = &[
+    ("aarch64", "aarch64"),
+    ("arm", "arm"),
+    ("i386", "x86"),
+    ("i686", "x86"),
+    ("mipsel", "mipsel"),
+    ("mips", "mips"),
+    ("x86_64", "x86_64"),
+];
+
+pub fn get_arch(triple: &str) -> &'static str {
+    for &(triple_arch, arch) in ARCH_TABLE.iter() {
+        if triple.starts_with(triple_arch) {
+            return arch
+        }
+    }
+    panic!("Cannot determine architecture from triple");
+}
+
 #[cfg(target_os = "windows")]
 pub fn make_new_path(path: &str) -> String {
 
@@ -171,6 +171,33 @@ extern "rust-intrinsic" {
     /// own, or if it does not enable any significant optimizations.
     pub fn assume(b: bool);
 
+    /// Hints to the optimizer that `b` is expected to be true, returning it
+    /// unchanged. The branch this feeds is laid out as the fall-through path.
+    pub fn likely(b: bool) -> bool;
+
+    /// Hints to the optimizer that `b` is expected to be false, returning it
+    /// unchanged.
+    pub fn unlikely(b: bool) -> bool;
+
+    /// Prefetches the data at `data` into the cache for reading.
+    ///
+    /// `locality` ranges from 0 (no temporal locality) to 3 (keep in all
+    /// levels of cache) and must be a constant. Prefetching has no effect on
+    /// the behavior of the program and `data` need not be valid.
+    pub fn prefetch_read_data<T>(data: *const T, locality: i32);
+
+    /// Prefetches the data at `data` into the cache for writing. See
+    /// `prefetch_read_data` for the meaning of `locality`.
+    pub fn prefetch_write_data<T>(data: *const T, locality: i32);
+
+    /// Prefetches the code at `data` into the instruction cache. See
+    /// `prefetch_read_data` for the meaning of `locality`.
+    pub fn prefetch_read_instruction<T>(data: *const T, locality: i32);
+
+    /// Prefetches the code at `data` into the instruction cache for writing.
+    /// See `prefetch_read_data` for the meaning of `locality`.
+    pub fn prefetch_write_instruction<T>(data: *const T, locality: i32);
+
     /// Execute a breakpoint trap, for inspection by a debugger.
     pub fn breakpoint();
 
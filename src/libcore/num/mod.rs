@@ -282,6 +282,7 @@ pub trait Int
     /// assert_eq!(5u16.checked_add(65530), Some(65535));
     /// assert_eq!(6u16.checked_add(65530), None);
     /// ```
+    #[must_use = "a `None` result would go unnoticed"]
     fn checked_add(self, other: Self) -> Option<Self>;
 
     /// Checked integer subtraction. Computes `self + other`, returning `None`
@@ -295,6 +296,7 @@ pub trait Int
     /// assert_eq!((-127i8).checked_sub(1), Some(-128));
     /// assert_eq!((-128i8).checked_sub(1), None);
     /// ```
+    #[must_use = "a `None` result would go unnoticed"]
     fn checked_sub(self, other: Self) -> Option<Self>;
 
     /// Checked integer multiplication. Computes `self + other`, returning
@@ -308,6 +310,7 @@ pub trait Int
     /// assert_eq!(5u8.checked_mul(51), Some(255));
     /// assert_eq!(5u8.checked_mul(52), None);
     /// ```
+    #[must_use = "a `None` result would go unnoticed"]
     fn checked_mul(self, other: Self) -> Option<Self>;
 
     /// Checked integer division. Computes `self + other` returning `None` if
@@ -323,6 +326,7 @@ pub trait Int
     /// assert_eq!((1i8).checked_div(0), None);
     /// ```
     #[inline]
+    #[must_use = "a `None` result would go unnoticed"]
     fn checked_div(self, other: Self) -> Option<Self>;
 
     /// Saturating integer addition. Computes `self + other`, saturating at
@@ -148,5 +148,10 @@ register_diagnostics!(
     E0169,
     E0170,
     E0171,
-    E0172
+    E0172,
+    E0173,
+    E0174,
+    E0178,
+    E0179,
+    E0180
 )
@@ -34,8 +34,8 @@ use middle::typeck::{mod, infer};
 use middle::{def, pat_util, stability};
 use middle::const_eval::{eval_const_expr_partial, const_int, const_uint};
 use util::ppaux::{ty_to_string};
-use util::nodemap::{FnvHashMap, NodeSet};
-use lint::{Context, LintPass, LintArray};
+use util::nodemap::{FnvHashMap, FnvHashSet, NodeSet};
+use lint::{Context, Level, Allow, Lint, LintPass, LintArray};
 
 use std::{cmp, slice};
 use std::collections::hash_map::{Occupied, Vacant};
@@ -43,7 +43,7 @@ use std::num::SignedInt;
 use std::{i8, i16, i32, i64, u8, u16, u32, u64, f32, f64};
 use syntax::{abi, ast, ast_map};
 use syntax::ast_util::{mod, is_shift_binop};
-use syntax::attr::{mod, AttrMetaMethods};
+use syntax::attr::{mod, AttrMetaMethods, AttributeMethods};
 use syntax::codemap::{Span, DUMMY_SP};
 use syntax::parse::token;
 use syntax::ast::{TyI, TyU, TyI8, TyU8, TyI16, TyU16, TyI32, TyU32, TyI64, TyU64};
@@ -104,6 +104,9 @@ declare_lint!(OVERFLOWING_LITERALS, Warn,
 declare_lint!(EXCEEDING_BITSHIFTS, Deny,
               "shift exceeds the type's number of bits")
 
+declare_lint!(LOSSY_INTEGER_CASTS, Allow,
+              "casts between integer types that can't represent all values of each other")
+
 pub struct TypeLimits {
     /// Id of the last visited negated expression
     negated_expr_id: ast::NodeId,
@@ -120,7 +123,7 @@ impl TypeLimits {
 impl LintPass for TypeLimits {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNSIGNED_NEGATION, UNUSED_COMPARISONS, OVERFLOWING_LITERALS,
-                    EXCEEDING_BITSHIFTS)
+                    EXCEEDING_BITSHIFTS, LOSSY_INTEGER_CASTS)
     }
 
     fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
@@ -243,9 +246,63 @@ impl LintPass for TypeLimits {
                     _ => ()
                 };
             },
+            ast::ExprCast(ref expr, _) => {
+                // Literals that don't fit are `overflowing_literals`
+                if let ast::ExprLit(_) = expr.node {
+                    return;
+                }
+                let from = ty::expr_ty(cx.tcx, &**expr);
+                let to = ty::expr_ty(cx.tcx, e);
+                let loss = match (&from.sty, &to.sty) {
+                    (&ty::ty_int(_), &ty::ty_uint(_)) => "change the sign of",
+                    (&ty::ty_int(_), &ty::ty_int(_)) |
+                    (&ty::ty_uint(_), &ty::ty_uint(_)) if max_bits(from) > min_bits(to) => {
+                        "truncate"
+                    }
+                    (&ty::ty_uint(_), &ty::ty_int(_)) if max_bits(from) >= min_bits(to) => {
+                        "change the sign of"
+                    }
+                    _ => return,
+                };
+                let to_str = ty_to_string(cx.tcx, to);
+                let help = match cx.sess().codemap().span_to_snippet(expr.span) {
+                    Some(snippet) => {
+                        format!("use `{}.to_{}()` to convert the value if it fits, \
+                                 from `std::num::ToPrimitive`", snippet, to_str)
+                    }
+                    None => {
+                        format!("use `ToPrimitive::to_{}` to convert the value if it fits",
+                                to_str)
+                    }
+                };
+                cx.struct_span_lint(LOSSY_INTEGER_CASTS, e.span,
+                                    format!("cast from `{}` to `{}` can {} the value",
+                                            ty_to_string(cx.tcx, from), to_str,
+                                            loss).as_slice())
+                  .span_help(e.span, help.as_slice())
+                  .emit();
+            },
             _ => ()
         };
 
+        // `int` and `uint` have 32 bits on some targets and 64 on others, so
+        // casts are checked for whichever is lossy.
+        fn min_bits(ty: Ty) -> u64 {
+            match ty.sty {
+                ty::ty_int(ast::TyI) | ty::ty_uint(ast::TyU) => 32,
+                ty::ty_int(t) => int_ty_bits(t, t),
+                ty::ty_uint(t) => uint_ty_bits(t, t),
+                _ => panic!()
+            }
+        }
+
+        fn max_bits(ty: Ty) -> u64 {
+            match ty.sty {
+                ty::ty_int(ast::TyI) | ty::ty_uint(ast::TyU) => 64,
+                _ => min_bits(ty)
+            }
+        }
+
         fn is_valid<T:cmp::PartialOrd>(binop: ast::BinOp, v: T,
                                 min: T, max: T) -> bool {
             match binop {
@@ -370,48 +427,190 @@ impl LintPass for TypeLimits {
 declare_lint!(IMPROPER_CTYPES, Warn,
               "proper use of libc types in foreign modules")
 
+declare_lint!(IMPROPER_CTYPES_DEFINITIONS, Allow,
+              "proper use of libc types in the signatures of Rust-defined extern fns")
+
+/// Why a type can't be used across the foreign-function interface.
+enum FfiUnsafety<'tcx> {
+    FfiSafe,
+    /// `int` or `uint`, with the C types to use instead
+    FfiRustInt(&'static str, &'static str),
+    /// A struct or enum without a `#[repr]` giving it a layout C knows
+    FfiNoRepr(Ty<'tcx>, ast::DefId),
+    /// Any other type that C has no equivalent for, with the reason
+    FfiUnsafe(Ty<'tcx>, &'static str),
+}
+
 struct ImproperCTypesVisitor<'a, 'tcx: 'a> {
-    cx: &'a Context<'a, 'tcx>
+    cx: &'a Context<'a, 'tcx>,
+    /// The lint to report under, which differs for foreign declarations and
+    /// for functions defined in Rust
+    lint: &'static Lint,
+    /// The types already checked, which are safe unless reported, so
+    /// recursive types are only walked once
+    seen: FnvHashSet<Ty<'tcx>>,
 }
 
 impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
-    fn check_def(&mut self, sp: Span, ty_id: ast::NodeId, path_id: ast::NodeId) {
-        match self.cx.tcx.def_map.borrow()[path_id].clone() {
-            def::DefPrimTy(ast::TyInt(ast::TyI)) => {
-                self.cx.span_lint(IMPROPER_CTYPES, sp,
-                                  "found rust type `int` in foreign module, while \
-                                   libc::c_int or libc::c_long should be used");
-            }
-            def::DefPrimTy(ast::TyUint(ast::TyU)) => {
-                self.cx.span_lint(IMPROPER_CTYPES, sp,
-                                  "found rust type `uint` in foreign module, while \
-                                   libc::c_uint or libc::c_ulong should be used");
-            }
-            def::DefTy(..) => {
-                let tty = match self.cx.tcx.ast_ty_to_ty_cache.borrow().get(&ty_id) {
-                    Some(&ty::atttce_resolved(t)) => t,
-                    _ => panic!("ast_ty_to_ty_cache was incomplete after typeck!")
-                };
+    /// Walks `ty`, and the types it's made of, for the first one that isn't
+    /// foreign-function-safe.
+    fn check_type(&mut self, ty: Ty<'tcx>) -> FfiUnsafety<'tcx> {
+        let tcx = self.cx.tcx;
+        if !self.seen.insert(ty) {
+            return FfiSafe;
+        }
+
+        match ty.sty {
+            ty::ty_int(ast::TyI) => FfiRustInt("int", "libc::c_int or libc::c_long"),
+            ty::ty_uint(ast::TyU) => FfiRustInt("uint", "libc::c_uint or libc::c_ulong"),
+
+            ty::ty_bool | ty::ty_char | ty::ty_int(_) | ty::ty_uint(_) | ty::ty_float(_) => {
+                FfiSafe
+            }
+
+            ty::ty_str => {
+                FfiUnsafe(ty, "string slices have no C equivalent, consider using \
+                               `*const libc::c_char` instead")
+            }
+            ty::ty_vec(_, None) => {
+                FfiUnsafe(ty, "slices have no C equivalent, consider passing a raw pointer \
+                               and a length instead")
+            }
+            ty::ty_vec(elem, Some(_)) => self.check_type(elem),
+
+            ty::ty_ptr(ref mt) | ty::ty_rptr(_, ref mt) => self.check_type(mt.ty),
+
+            ty::ty_uniq(_) => {
+                FfiUnsafe(ty, "boxes have no C equivalent, consider using a raw pointer instead")
+            }
+            ty::ty_trait(..) => FfiUnsafe(ty, "trait objects have no C equivalent"),
+            ty::ty_closure(..) | ty::ty_unboxed_closure(..) => {
+                FfiUnsafe(ty, "closures have no C equivalent, consider using an `extern fn` \
+                               and a context pointer instead")
+            }
 
-                if !ty::is_ffi_safe(self.cx.tcx, tty) {
-                    self.cx.span_lint(IMPROPER_CTYPES, sp,
-                                      "found type without foreign-function-safe
-                                      representation annotation in foreign module, consider \
-                                      adding a #[repr(...)] attribute to the type");
+            ty::ty_tup(ref elems) if elems.is_empty() => FfiSafe,
+            ty::ty_tup(..) => {
+                FfiUnsafe(ty, "tuples have no C equivalent, consider using a #[repr(C)] \
+                               struct instead")
+            }
+
+            ty::ty_bare_fn(ref f) => {
+                match f.abi {
+                    abi::Rust | abi::RustIntrinsic | abi::RustCall => {
+                        return FfiUnsafe(ty, "this function pointer has the Rust calling \
+                                              convention, consider using an `extern fn` \
+                                              type instead");
+                    }
+                    _ => {}
+                }
+                for &input in f.sig.inputs.iter() {
+                    match self.check_type(input) {
+                        FfiSafe => {}
+                        r => return r,
+                    }
+                }
+                match f.sig.output {
+                    ty::FnConverging(output) => self.check_type(output),
+                    ty::FnDiverging => FfiSafe,
                 }
             }
-            _ => ()
+
+            ty::ty_struct(did, ref substs) => {
+                if !ty::lookup_repr_hints(tcx, did).contains(&attr::ReprExtern) {
+                    return FfiNoRepr(ty, did);
+                }
+                for field in ty::struct_fields(tcx, did, substs).iter() {
+                    match self.check_type(field.mt.ty) {
+                        FfiSafe => {}
+                        r => return r,
+                    }
+                }
+                FfiSafe
+            }
+
+            ty::ty_enum(did, ref substs) => {
+                let variants = ty::substd_enum_variants(tcx, did, substs);
+                if variants.is_empty() {
+                    return FfiSafe;
+                }
+                let repr_hints = ty::lookup_repr_hints(tcx, did);
+                match repr_hints.as_slice() {
+                    [ref hint] if hint.is_ffi_safe() => return FfiSafe,
+                    [] => {}
+                    _ => return FfiNoRepr(ty, did),
+                }
+                // An enum like `Option<extern fn()>` is a nullable pointer
+                if variants.len() == 2 {
+                    let data_idx = if variants[0].args.len() == 0 { 1 } else { 0 };
+                    if variants[data_idx].args.len() == 1 {
+                        if let ty::ty_bare_fn(..) = variants[data_idx].args[0].sty {
+                            return self.check_type(variants[data_idx].args[0]);
+                        }
+                    }
+                }
+                if variants.iter().any(|variant| !variant.args.is_empty()) {
+                    FfiUnsafe(ty, "enums with fields have no C equivalent")
+                } else {
+                    FfiNoRepr(ty, did)
+                }
+            }
+
+            ty::ty_param(..) | ty::ty_open(..) | ty::ty_infer(..) | ty::ty_err => FfiSafe,
         }
     }
-}
 
-impl<'a, 'tcx, 'v> Visitor<'v> for ImproperCTypesVisitor<'a, 'tcx> {
-    fn visit_ty(&mut self, ty: &ast::Ty) {
-        match ty.node {
-            ast::TyPath(_, id) => self.check_def(ty.span, ty.id, id),
-            _ => (),
+    /// Checks the type `ty` of a parameter, return value or static, written
+    /// at `sp`.
+    fn check_type_at(&mut self, sp: Span, ty: Ty<'tcx>) {
+        self.seen.clear();
+        let cx = self.cx;
+        match self.check_type(ty) {
+            FfiSafe => {}
+            FfiRustInt(name, replacement) => {
+                cx.span_lint(self.lint, sp,
+                             format!("found rust type `{}` in foreign module, while {} \
+                                      should be used", name, replacement).as_slice());
+            }
+            FfiNoRepr(bad_ty, did) => {
+                let mut db = cx.struct_span_lint(self.lint, sp,
+                                                 "found type without foreign-function-safe \
+                                                  representation annotation in foreign \
+                                                  module, consider adding a #[repr(...)] \
+                                                  attribute to the type");
+                if ast_util::is_local(did) {
+                    db = db.span_note(cx.tcx.map.span(did.node),
+                                      format!("`{}` is defined here",
+                                              ty_to_string(cx.tcx, bad_ty)).as_slice());
+                }
+                db.emit();
+            }
+            FfiUnsafe(bad_ty, reason) => {
+                let msg = if bad_ty == ty {
+                    format!("found type `{}` in foreign module: {}",
+                            ty_to_string(cx.tcx, bad_ty), reason)
+                } else {
+                    format!("found type `{}` in foreign module, as part of `{}`: {}",
+                            ty_to_string(cx.tcx, bad_ty), ty_to_string(cx.tcx, ty), reason)
+                };
+                cx.span_lint(self.lint, sp, msg.as_slice());
+            }
+        }
+    }
+
+    /// Checks the signature of a function with the type `fn_ty` and the
+    /// declaration `decl`.
+    fn check_fn(&mut self, decl: &ast::FnDecl, fn_ty: Ty<'tcx>) {
+        let sig = match fn_ty.sty {
+            ty::ty_bare_fn(ref f) => f.sig.clone(),
+            _ => return,
+        };
+        for (input, &input_ty) in decl.inputs.iter().zip(sig.inputs.iter()) {
+            self.check_type_at(input.ty.span, input_ty);
+        }
+        if let (&ast::Return(ref ret_ty), ty::FnConverging(output)) = (&decl.output, sig.output) {
+            self.check_type_at(ret_ty.span, output);
         }
-        visit::walk_ty(self, ty);
     }
 }
 
@@ -419,30 +618,32 @@ pub struct ImproperCTypes;
 
 impl LintPass for ImproperCTypes {
     fn get_lints(&self) -> LintArray {
-        lint_array!(IMPROPER_CTYPES)
+        lint_array!(IMPROPER_CTYPES, IMPROPER_CTYPES_DEFINITIONS)
     }
 
     fn check_item(&mut self, cx: &Context, it: &ast::Item) {
-        fn check_ty(cx: &Context, ty: &ast::Ty) {
-            let mut vis = ImproperCTypesVisitor { cx: cx };
-            vis.visit_ty(ty);
-        }
-
-        fn check_foreign_fn(cx: &Context, decl: &ast::FnDecl) {
-            for input in decl.inputs.iter() {
-                check_ty(cx, &*input.ty);
-            }
-            if let ast::Return(ref ret_ty) = decl.output {
-                check_ty(cx, &**ret_ty);
-            }
-        }
-
+        let mut vis = ImproperCTypesVisitor {
+            cx: cx,
+            lint: IMPROPER_CTYPES,
+            seen: FnvHashSet::new(),
+        };
         match it.node {
             ast::ItemForeignMod(ref nmod) if nmod.abi != abi::RustIntrinsic => {
                 for ni in nmod.items.iter() {
+                    let ty = ty::node_id_to_type(cx.tcx, ni.id);
                     match ni.node {
-                        ast::ForeignItemFn(ref decl, _) => check_foreign_fn(cx, &**decl),
-                        ast::ForeignItemStatic(ref t, _) => check_ty(cx, &**t)
+                        ast::ForeignItemFn(ref decl, _) => vis.check_fn(&**decl, ty),
+                        ast::ForeignItemStatic(ref t, _) => vis.check_type_at(t.span, ty),
+                    }
+                }
+            }
+            // Functions defined in Rust to be called from C
+            ast::ItemFn(ref decl, _, abi, ref generics, _) if generics.ty_params.is_empty() => {
+                match abi {
+                    abi::Rust | abi::RustIntrinsic | abi::RustCall => {}
+                    _ => {
+                        vis.lint = IMPROPER_CTYPES_DEFINITIONS;
+                        vis.check_fn(&**decl, ty::node_id_to_type(cx.tcx, it.id))
                     }
                 }
             }
@@ -640,8 +841,18 @@ impl LintPass for UnusedAttributes {
             "no_main",
             "no_std",
             "no_builtins",
+            "parser_recursion_limit",
+            "accept_names",
         ];
 
+        // `accept_names` is only read from the crate root; anywhere else it's
+        // left unused, to be reported as a misplaced crate-level attribute.
+        if attr.name().equiv(&("accept_names")) &&
+           cx.krate.attrs.iter().any(|a| a.node.id == attr.node.id) {
+            attr::mark_used(attr);
+            check_accept_names(cx, attr);
+        }
+
         for &name in ATTRIBUTE_WHITELIST.iter() {
             if attr.check_name(name) {
                 break;
@@ -663,6 +874,39 @@ impl LintPass for UnusedAttributes {
     }
 }
 
+/// Reports malformed `#![accept_names(...)]` attributes, whose patterns are
+/// read by the naming lints.
+fn check_accept_names(cx: &Context, attr: &ast::Attribute) {
+    let metas = match attr::expect_list(attr.meta()) {
+        Ok(metas) => metas,
+        Err(_) => {
+            cx.sess().span_err(attr.span, "expected `#![accept_names(lint = \"pattern\", ...)]`");
+            return;
+        }
+    };
+    for meta in metas.iter() {
+        if !NAMING_LINTS.iter().any(|lint| meta.name().equiv(lint)) {
+            cx.sess().span_err(meta.span,
+                               format!("`accept_names` can't give patterns to `{}`, only to {}",
+                                       meta.name(), NAMING_LINTS.connect(", ")).as_slice());
+            continue;
+        }
+        match attr::expect_value_str(&**meta) {
+            Err(_) => {
+                cx.sess().span_err(meta.span,
+                                   format!("expected `{} = \"pattern\"`", meta.name()).as_slice());
+            }
+            Ok(pattern) => {
+                if let Some(error) = name_pattern_error(pattern.get()) {
+                    cx.sess().span_err(meta.span,
+                                       format!("invalid name pattern `{}`: {}",
+                                               pattern, error).as_slice());
+                }
+            }
+        }
+    }
+}
+
 declare_lint!(pub PATH_STATEMENTS, Warn,
               "path statements with no effect")
 
@@ -688,7 +932,7 @@ impl LintPass for PathStatements {
 }
 
 declare_lint!(pub UNUSED_MUST_USE, Warn,
-              "unused result of a type flagged as #[must_use]")
+              "unused result of a type or function flagged as #[must_use]")
 
 declare_lint!(pub UNUSED_RESULTS, Allow,
               "unused result of an expression in a statement")
@@ -710,20 +954,49 @@ impl LintPass for UnusedResults {
             return;
         }
 
-        let t = ty::expr_ty(cx.tcx, expr);
         let mut warned = false;
+
+        // Functions and methods can ask for their results to be used, like
+        // types can.
+        if let Some(callee) = callee_def_id(cx, expr) {
+            let msg = format!("unused return value of `{}` which must be used",
+                              ty::item_path_str(cx.tcx, callee));
+            // An implementation of a trait method has the attributes of the
+            // trait's declaration too.
+            let declared = ty::trait_item_of_item(cx.tcx, callee).map(|id| id.def_id());
+            for &did in Some(callee).iter().chain(declared.iter()) {
+                if warned { break }
+                if ast_util::is_local(did) {
+                    cx.tcx.map.with_attrs(did.node, |attrs| {
+                        if let Some(attrs) = attrs {
+                            warned |= check_must_use(cx, attrs, msg.as_slice(), s.span);
+                        }
+                    });
+                } else {
+                    csearch::get_item_attrs(&cx.sess().cstore, did, |attrs| {
+                        warned |= check_must_use(cx, attrs.as_slice(), msg.as_slice(), s.span);
+                    });
+                }
+            }
+        }
+        if warned {
+            return;
+        }
+
+        let t = ty::expr_ty(cx.tcx, expr);
         match t.sty {
             ty::ty_tup(ref tys) if tys.is_empty() => return,
             ty::ty_bool => return,
             ty::ty_struct(did, _) |
             ty::ty_enum(did, _) => {
+                let msg = "unused result which must be used";
                 if ast_util::is_local(did) {
                     if let ast_map::NodeItem(it) = cx.tcx.map.get(did.node) {
-                        warned |= check_must_use(cx, it.attrs.as_slice(), s.span);
+                        warned |= check_must_use(cx, it.attrs.as_slice(), msg, s.span);
                     }
                 } else {
                     csearch::get_item_attrs(&cx.sess().cstore, did, |attrs| {
-                        warned |= check_must_use(cx, attrs.as_slice(), s.span);
+                        warned |= check_must_use(cx, attrs.as_slice(), msg, s.span);
                     });
                 }
             }
@@ -733,10 +1006,10 @@ impl LintPass for UnusedResults {
             cx.span_lint(UNUSED_RESULTS, s.span, "unused result");
         }
 
-        fn check_must_use(cx: &Context, attrs: &[ast::Attribute], sp: Span) -> bool {
-            for attr in attrs.iter() {
-                if attr.check_name("must_use") {
-                    let mut msg = "unused result which must be used".to_string();
+        fn check_must_use(cx: &Context, attrs: &[ast::Attribute], msg: &str, sp: Span) -> bool {
+            match attr::find_by_name(attrs, "must_use") {
+                Some(attr) => {
+                    let mut msg = msg.to_string();
                     // check for #[must_use="..."]
                     match attr.value_str() {
                         None => {}
@@ -746,10 +1019,48 @@ impl LintPass for UnusedResults {
                         }
                     }
                     cx.span_lint(UNUSED_MUST_USE, sp, msg.as_slice());
-                    return true;
+                    true
+                }
+                None => false
+            }
+        }
+
+        /// The function or method called by `expr`, if it's a call of a
+        /// known one.
+        fn callee_def_id(cx: &Context, expr: &ast::Expr) -> Option<ast::DefId> {
+            match expr.node {
+                ast::ExprCall(ref callee, _) => {
+                    match cx.tcx.def_map.borrow().get(&callee.id) {
+                        Some(&def::DefFn(did, false)) |
+                        Some(&def::DefStaticMethod(did, _)) |
+                        Some(&def::DefMethod(did, _, _)) => Some(did),
+                        _ => None,
+                    }
+                }
+                ast::ExprMethodCall(..) => {
+                    let method_call = typeck::MethodCall::expr(expr.id);
+                    match cx.tcx.method_map.borrow().get(&method_call) {
+                        Some(method) => match method.origin {
+                            typeck::MethodStatic(did) => Some(did),
+                            typeck::MethodTypeParam(typeck::MethodParam {
+                                ref trait_ref,
+                                method_num: index,
+                                ..
+                            }) |
+                            typeck::MethodTraitObject(typeck::MethodObject {
+                                ref trait_ref,
+                                method_num: index,
+                                ..
+                            }) => {
+                                Some(ty::trait_item(cx.tcx, trait_ref.def_id, index).def_id())
+                            }
+                            typeck::MethodStaticUnboxedClosure(_) => None,
+                        },
+                        None => None,
+                    }
                 }
+                _ => None,
             }
-            false
         }
     }
 }
@@ -757,9 +1068,119 @@ impl LintPass for UnusedResults {
 declare_lint!(pub NON_CAMEL_CASE_TYPES, Warn,
               "types, variants, traits and type parameters should have camel case names")
 
-pub struct NonCamelCaseTypes;
+/// The lints whose names can be given patterns of names to accept, besides
+/// those of their case, by `#![accept_names(...)]`.
+static NAMING_LINTS: &'static [&'static str] = &[
+    "non_camel_case_types", "non_snake_case", "non_upper_case_globals",
+];
+
+/// Whether `name` matches `pattern`, in which `?` matches any character, `*`
+/// matches any sequence of characters, and `[...]` matches any character in
+/// the set, which can have ranges like `a-z`.
+fn matches_name_pattern(pattern: &[char], name: &[char]) -> bool {
+    match pattern.head() {
+        None => name.is_empty(),
+        Some(&'*') => {
+            range(0, name.len() + 1).any(|i| {
+                matches_name_pattern(pattern.tail(), name.slice_from(i))
+            })
+        }
+        Some(&'?') => !name.is_empty() && matches_name_pattern(pattern.tail(), name.tail()),
+        Some(&'[') => {
+            let end = match pattern.iter().position(|&c| c == ']') {
+                Some(end) => end,
+                None => return false,
+            };
+            match name.head() {
+                Some(&c) if set_contains(pattern.slice(1, end), c) => {
+                    matches_name_pattern(pattern.slice_from(end + 1), name.tail())
+                }
+                _ => false,
+            }
+        }
+        Some(&c) => name.head() == Some(&c) && matches_name_pattern(pattern.tail(), name.tail()),
+    }
+}
+
+fn set_contains(set: &[char], c: char) -> bool {
+    let mut i = 0;
+    while i < set.len() {
+        if i + 2 < set.len() && set[i + 1] == '-' {
+            if set[i] <= c && c <= set[i + 2] {
+                return true;
+            }
+            i += 3;
+        } else {
+            if set[i] == c {
+                return true;
+            }
+            i += 1;
+        }
+    }
+    false
+}
+
+/// Why `pattern` isn't a valid pattern of names, if it isn't.
+fn name_pattern_error(pattern: &str) -> Option<&'static str> {
+    let mut in_set = false;
+    for c in pattern.chars() {
+        match c {
+            '[' if in_set => return Some("`[` inside a set of characters"),
+            '[' => in_set = true,
+            ']' => in_set = false,
+            _ => {}
+        }
+    }
+    if in_set { Some("unclosed set of characters") } else { None }
+}
+
+/// The patterns of names the crate's `#![accept_names(...)]` attributes give
+/// `lint`. The attributes are checked by the `UnusedAttributes` pass.
+fn accepted_names(krate: &ast::Crate, lint: &'static Lint) -> Vec<Vec<char>> {
+    let lint_name = lint.name_lower();
+    let mut patterns = vec![];
+    for attr in krate.attrs.iter() {
+        if !attr.check_name("accept_names") {
+            continue;
+        }
+        attr::mark_used(attr);
+        let metas = match attr::expect_list(attr.meta()) {
+            Ok(metas) => metas,
+            Err(_) => continue,
+        };
+        for meta in metas.iter() {
+            if !meta.name().equiv(&lint_name.as_slice()) {
+                continue;
+            }
+            match attr::expect_value_str(&**meta) {
+                Ok(ref pattern) if name_pattern_error(pattern.get()).is_none() => {
+                    patterns.push(pattern.get().chars().collect());
+                }
+                _ => {}
+            }
+        }
+    }
+    patterns
+}
+
+fn is_accepted_name(patterns: &[Vec<char>], name: &str) -> bool {
+    if patterns.is_empty() {
+        return false;
+    }
+    let name: Vec<char> = name.chars().collect();
+    patterns.iter().any(|pattern| matches_name_pattern(pattern.as_slice(), name.as_slice()))
+}
+
+pub struct NonCamelCaseTypes {
+    /// Patterns of names to accept, from `#![accept_names(...)]`.
+    accepted: Vec<Vec<char>>,
+}
 
 impl NonCamelCaseTypes {
+    pub fn new() -> NonCamelCaseTypes {
+        NonCamelCaseTypes { accepted: vec![] }
+    }
+
     fn check_case(&self, cx: &Context, sort: &str, ident: ast::Ident, span: Span) {
         fn is_camel_case(ident: ast::Ident) -> bool {
             let ident = token::get_ident(ident);
@@ -778,9 +1199,11 @@ impl NonCamelCaseTypes {
             )).collect()
         }
 
+        if cx.sess().codemap().is_from_external_macro(span) { return }
+
         let s = token::get_ident(ident);
 
-        if !is_camel_case(ident) {
+        if !is_camel_case(ident) && !is_accepted_name(self.accepted.as_slice(), s.get()) {
             let c = to_camel_case(s.get());
             let m = if c.is_empty() {
                 format!("{} `{}` should have a camel case name such as `CamelCase`", sort, s)
@@ -797,12 +1220,13 @@ impl LintPass for NonCamelCaseTypes {
         lint_array!(NON_CAMEL_CASE_TYPES)
     }
 
+    fn check_crate(&mut self, _: &Context, krate: &ast::Crate) {
+        self.accepted = accepted_names(krate, NON_CAMEL_CASE_TYPES);
+    }
+
     fn check_item(&mut self, cx: &Context, it: &ast::Item) {
-        let has_extern_repr = it.attrs.iter().map(|attr| {
-            attr::find_repr_attrs(cx.tcx.sess.diagnostic(), attr).iter()
-                .any(|r| r == &attr::ReprExtern)
-        }).any(|x| x);
-        if has_extern_repr { return }
+        let reprs = attr::find_repr(cx.tcx.sess.diagnostic(), it.attrs.as_slice());
+        if reprs.contains(&attr::ReprExtern) { return }
 
         match it.node {
             ast::ItemTy(..) | ast::ItemStruct(..) => {
@@ -837,48 +1261,30 @@ enum MethodContext {
 }
 
 fn method_context(cx: &Context, m: &ast::Method) -> MethodContext {
-    let did = ast::DefId {
-        krate: ast::LOCAL_CRATE,
-        node: m.id
-    };
-
-    match cx.tcx.impl_or_trait_items.borrow().get(&did).cloned() {
-        None => cx.sess().span_bug(m.span, "missing method descriptor?!"),
-        Some(md) => {
-            match md {
-                ty::MethodTraitItem(md) => {
-                    match md.container {
-                        ty::TraitContainer(..) => TraitDefaultImpl,
-                        ty::ImplContainer(cid) => {
-                            match ty::impl_trait_ref(cx.tcx, cid) {
-                                Some(..) => TraitImpl,
-                                None => PlainImpl
-                            }
-                        }
-                    }
-                }
-                ty::TypeTraitItem(typedef) => {
-                    match typedef.container {
-                        ty::TraitContainer(..) => TraitDefaultImpl,
-                        ty::ImplContainer(cid) => {
-                            match ty::impl_trait_ref(cx.tcx, cid) {
-                                Some(..) => TraitImpl,
-                                None => PlainImpl
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    match cx.tcx.map.find(cx.tcx.map.get_parent(m.id)) {
+        Some(ast_map::NodeItem(item)) => match item.node {
+            ast::ItemTrait(..) => TraitDefaultImpl,
+            ast::ItemImpl(_, Some(_), _, _) => TraitImpl,
+            ast::ItemImpl(_, None, _, _) => PlainImpl,
+            _ => cx.sess().span_bug(m.span, "method not in an impl or trait?!"),
+        },
+        _ => cx.sess().span_bug(m.span, "method not in an impl or trait?!"),
     }
 }
 
 declare_lint!(pub NON_SNAKE_CASE, Warn,
               "methods, functions, lifetime parameters and modules should have snake case names")
 
-pub struct NonSnakeCase;
+pub struct NonSnakeCase {
+    /// Patterns of names to accept, from `#![accept_names(...)]`.
+    accepted: Vec<Vec<char>>,
+}
 
 impl NonSnakeCase {
+    pub fn new() -> NonSnakeCase {
+        NonSnakeCase { accepted: vec![] }
+    }
+
     fn check_snake_case(&self, cx: &Context, sort: &str, ident: ast::Ident, span: Span) {
         fn is_snake_case(ident: ast::Ident) -> bool {
             let ident = token::get_ident(ident);
@@ -918,9 +1324,11 @@ impl NonSnakeCase {
             words.connect("_")
         }
 
+        if cx.sess().codemap().is_from_external_macro(span) { return }
+
         let s = token::get_ident(ident);
 
-        if !is_snake_case(ident) {
+        if !is_snake_case(ident) && !is_accepted_name(self.accepted.as_slice(), s.get()) {
             cx.span_lint(NON_SNAKE_CASE, span,
                 format!("{} `{}` should have a snake case name such as `{}`",
                         sort, s, to_snake_case(s.get())).as_slice());
@@ -933,6 +1341,10 @@ impl LintPass for NonSnakeCase {
         lint_array!(NON_SNAKE_CASE)
     }
 
+    fn check_crate(&mut self, _: &Context, krate: &ast::Crate) {
+        self.accepted = accepted_names(krate, NON_SNAKE_CASE);
+    }
+
     fn check_fn(&mut self, cx: &Context,
                 fk: visit::FnKind, _: &ast::FnDecl,
                 _: &ast::Block, span: Span, _: ast::NodeId) {
@@ -985,23 +1397,40 @@ impl LintPass for NonSnakeCase {
 declare_lint!(pub NON_UPPER_CASE_GLOBALS, Warn,
               "static constants should have uppercase identifiers")
 
-pub struct NonUpperCaseGlobals;
+pub struct NonUpperCaseGlobals {
+    /// Patterns of names to accept, from `#![accept_names(...)]`.
+    accepted: Vec<Vec<char>>,
+}
+
+impl NonUpperCaseGlobals {
+    pub fn new() -> NonUpperCaseGlobals {
+        NonUpperCaseGlobals { accepted: vec![] }
+    }
+
+    fn is_upper_case(&self, s: &str) -> bool {
+        // check for lowercase letters rather than non-uppercase
+        // ones (some scripts don't have a concept of
+        // upper/lowercase)
+        !s.chars().any(|c| c.is_lowercase()) || is_accepted_name(self.accepted.as_slice(), s)
+    }
+}
 
 impl LintPass for NonUpperCaseGlobals {
     fn get_lints(&self) -> LintArray {
         lint_array!(NON_UPPER_CASE_GLOBALS)
     }
 
+    fn check_crate(&mut self, _: &Context, krate: &ast::Crate) {
+        self.accepted = accepted_names(krate, NON_UPPER_CASE_GLOBALS);
+    }
+
     fn check_item(&mut self, cx: &Context, it: &ast::Item) {
         match it.node {
             // only check static constants
             ast::ItemStatic(_, ast::MutImmutable, _) |
             ast::ItemConst(..) => {
                 let s = token::get_ident(it.ident);
-                // check for lowercase letters rather than non-uppercase
-                // ones (some scripts don't have a concept of
-                // upper/lowercase)
-                if s.get().chars().any(|c| c.is_lowercase()) {
+                if !self.is_upper_case(s.get()) {
                     cx.span_lint(NON_UPPER_CASE_GLOBALS, it.span,
                         format!("static constant `{}` should have an uppercase name \
                                  such as `{}`",
@@ -1018,7 +1447,7 @@ impl LintPass for NonUpperCaseGlobals {
         match (&p.node, cx.tcx.def_map.borrow().get(&p.id)) {
             (&ast::PatIdent(_, ref path1, _), Some(&def::DefConst(..))) => {
                 let s = token::get_ident(path1.node);
-                if s.get().chars().any(|c| c.is_lowercase()) {
+                if !self.is_upper_case(s.get()) {
                     cx.span_lint(NON_UPPER_CASE_GLOBALS, path1.span,
                         format!("static constant in pattern `{}` should have an uppercase \
                                  name such as `{}`",
@@ -1039,6 +1468,10 @@ pub struct UnusedParens;
 impl UnusedParens {
     fn check_unused_parens_core(&self, cx: &Context, value: &ast::Expr, msg: &str,
                                      struct_lit_needs_parens: bool) {
+        // Macros may parenthesize their arguments defensively; the user
+        // can't remove parentheses from a macro they didn't write.
+        if cx.sess().codemap().is_from_external_macro(value.span) { return }
+
         if let ast::ExprParen(ref inner) = value.node {
             let necessary = struct_lit_needs_parens && contains_exterior_struct_lit(&**inner);
             if !necessary {
@@ -1185,6 +1618,26 @@ impl LintPass for NonShorthandFieldPatterns {
     }
 }
 
+declare_lint!(pub CONTEXTUAL_KEYWORD_IDENTS, Warn,
+              "identifiers that are contextual keywords, which may become reserved")
+
+pub struct ContextualKeywordIdents;
+
+impl LintPass for ContextualKeywordIdents {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(CONTEXTUAL_KEYWORD_IDENTS)
+    }
+
+    fn check_ident(&mut self, cx: &Context, sp: Span, id: ast::Ident) {
+        if token::is_contextual_keyword_name(id.name) {
+            cx.span_lint(CONTEXTUAL_KEYWORD_IDENTS, sp,
+                         format!("`{}` is a contextual keyword and may become a reserved \
+                                  keyword in the future; consider renaming this identifier",
+                                 token::get_ident(id)).as_slice());
+        }
+    }
+}
+
 declare_lint!(pub UNUSED_UNSAFE, Warn,
               "unnecessary use of an `unsafe` block")
 
@@ -1325,9 +1778,64 @@ impl LintPass for UnusedAllocation {
     }
 }
 
+declare_lint!(ASM_BARRIERS, Warn,
+              "detects empty `asm!` blocks used as memory barriers")
+
+pub struct AsmBarriers;
+
+impl LintPass for AsmBarriers {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(ASM_BARRIERS)
+    }
+
+    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+        let asm = match e.node {
+            ast::ExprInlineAsm(ref asm) => asm,
+            _ => return
+        };
+
+        // An empty template without operands does nothing but constrain
+        // the optimizer, so it's only there as a barrier.
+        if !asm.asm.get().trim().is_empty() || !asm.outputs.is_empty() ||
+           !asm.inputs.is_empty() || asm.noreturn {
+            return;
+        }
+
+        // With a `"memory"` clobber it's the usual compiler barrier, which a
+        // fence expresses without asm.
+        if asm.clobbers.iter().any(|&(ref c, _)| c.equiv(&("memory"))) {
+            cx.struct_span_lint(ASM_BARRIERS, e.span, "empty `asm!` used as a memory barrier")
+              .span_help(e.span, "use `std::sync::atomic::fence` instead, which the optimizer \
+                                  understands (it also orders the accesses of other threads)")
+              .emit();
+            return;
+        }
+
+        // Clobbering only registers is a deliberate way to spill them, not
+        // an attempt at a barrier.
+        if !asm.clobbers.is_empty() {
+            return;
+        }
+
+        cx.struct_span_lint(ASM_BARRIERS, e.span,
+                            "empty `asm!` without a `\"memory\"` clobber doesn't order \
+                             memory accesses")
+          .span_help(e.span, "add `\"memory\"` to its clobbers to make it a compiler barrier, \
+                              or use `std::sync::atomic::fence`")
+          .emit();
+    }
+}
+
 declare_lint!(MISSING_DOCS, Allow,
               "detects missing documentation for public members")
 
+/// The kinds of items `missing_docs` can be limited to, as in
+/// `#![warn(missing_docs(pub_fn, pub_struct))]`.
+static MISSING_DOCS_CATEGORIES: &'static [&'static str] = &[
+    "crate", "pub_mod", "pub_fn", "pub_struct", "pub_enum", "pub_trait",
+    "pub_method", "pub_trait_method", "pub_field", "pub_variant",
+];
+
 pub struct MissingDoc {
     /// Stack of IDs of struct definitions.
     struct_def_stack: Vec<ast::NodeId>,
@@ -1338,6 +1846,10 @@ pub struct MissingDoc {
     /// Stack of whether #[doc(hidden)] is set
     /// at each level which has lint attributes.
     doc_hidden_stack: Vec<bool>,
+
+    /// Stack of the categories of items that need documentation at each
+    /// level which has lint attributes, or `None` when all of them do.
+    categories_stack: Vec<Option<Vec<&'static str>>>,
 }
 
 impl MissingDoc {
@@ -1346,6 +1858,7 @@ impl MissingDoc {
             struct_def_stack: vec!(),
             in_variant: false,
             doc_hidden_stack: vec!(false),
+            categories_stack: vec!(None),
         }
     }
 
@@ -1353,12 +1866,82 @@ impl MissingDoc {
         *self.doc_hidden_stack.last().expect("empty doc_hidden_stack")
     }
 
+    fn checks_category(&self, category: &'static str) -> bool {
+        match *self.categories_stack.last().expect("empty categories_stack") {
+            None => true,
+            Some(ref categories) => categories.contains(&category),
+        }
+    }
+
+    /// Finds the categories `missing_docs` checks under the lint attributes
+    /// in `attrs`, if they change them. `#[warn(missing_docs(pub_fn))]`
+    /// limits the lint to the categories it names, while
+    /// `#[allow(missing_docs(pub_fn))]` exempts them from those checked
+    /// outside, and naming the lint without categories checks all of them
+    /// again.
+    fn categories_in(&self, cx: &Context, attrs: &[ast::Attribute])
+                     -> Option<Option<Vec<&'static str>>> {
+        let mut found = None;
+        for attr in attrs.iter() {
+            let level = match Level::from_str(attr.name().get()) {
+                Some(level) => level,
+                None => continue,
+            };
+            let metas = match attr.meta_item_list() {
+                Some(metas) => metas,
+                None => continue,
+            };
+            for meta in metas.iter() {
+                if !meta.name().equiv(&("missing_docs")) {
+                    continue;
+                }
+                let items = match meta.meta_item_list() {
+                    None => {
+                        found = Some(None);
+                        continue;
+                    }
+                    Some(items) => items,
+                };
+                let mut categories = vec![];
+                for item in items.iter() {
+                    let name = item.name();
+                    match MISSING_DOCS_CATEGORIES.iter().find(|c| name.equiv(c)) {
+                        Some(&category) if item.meta_item_list().is_none() => {
+                            categories.push(category)
+                        }
+                        _ => {
+                            cx.sess().span_err(item.span,
+                                               format!("unknown missing_docs category `{}`, \
+                                                        expected one of {}",
+                                                       name,
+                                                       MISSING_DOCS_CATEGORIES.connect(", "))
+                                                   .as_slice());
+                        }
+                    }
+                }
+                if level == Allow {
+                    let checked = match found {
+                        Some(ref checked) => checked.clone(),
+                        None => self.categories_stack.last()
+                                    .expect("empty categories_stack").clone(),
+                    };
+                    let mut checked = checked.unwrap_or_else(|| MISSING_DOCS_CATEGORIES.to_vec());
+                    checked.retain(|category| !categories.contains(category));
+                    categories = checked;
+                }
+                found = Some(Some(categories));
+            }
+        }
+        found
+    }
+
     fn check_missing_docs_attrs(&self,
                                cx: &Context,
                                id: Option<ast::NodeId>,
                                attrs: &[ast::Attribute],
                                sp: Span,
-                               desc: &'static str) {
+                               desc: &'static str,
+                               category: &'static str) {
         // If we're building a test harness, then warning about
         // documentation is probably not really relevant right now.
         if cx.sess().opts.test { return }
@@ -1366,6 +1949,9 @@ impl MissingDoc {
         // `#[doc(hidden)]` disables missing_docs check.
         if self.doc_hidden() { return }
 
+        // The lint attributes may only ask for some kinds of items.
+        if !self.checks_category(category) { return }
+
         // Only check publicly-visible items, using the result from the privacy pass.
         // It's an option so the crate root can also use this function (it doesn't
         // have a NodeId).
@@ -1383,7 +1969,7 @@ impl MissingDoc {
         });
         if !has_doc {
             cx.span_lint(MISSING_DOCS, sp,
-                format!("missing documentation for {}", desc).as_slice());
+                format!("missing documentation for {} ({})", desc, category).as_slice());
         }
     }
 }
@@ -1393,18 +1979,25 @@ impl LintPass for MissingDoc {
         lint_array!(MISSING_DOCS)
     }
 
-    fn enter_lint_attrs(&mut self, _: &Context, attrs: &[ast::Attribute]) {
+    fn enter_lint_attrs(&mut self, cx: &Context, attrs: &[ast::Attribute]) {
         let doc_hidden = self.doc_hidden() || attrs.iter().any(|attr| {
-            attr.check_name("doc") && match attr.meta_item_list() {
-                None => false,
-                Some(l) => attr::contains_name(l.as_slice(), "hidden"),
+            attr.check_name("doc") && match attr::expect_list(attr.meta()) {
+                Ok(l) => attr::contains_name(l, "hidden"),
+                Err(_) => false,
             }
         });
         self.doc_hidden_stack.push(doc_hidden);
+
+        let categories = match self.categories_in(cx, attrs) {
+            Some(categories) => categories,
+            None => self.categories_stack.last().expect("empty categories_stack").clone(),
+        };
+        self.categories_stack.push(categories);
     }
 
     fn exit_lint_attrs(&mut self, _: &Context, _: &[ast::Attribute]) {
         self.doc_hidden_stack.pop().expect("empty doc_hidden_stack");
+        self.categories_stack.pop().expect("empty categories_stack");
     }
 
     fn check_struct_def(&mut self, _: &Context,
@@ -1420,20 +2013,20 @@ impl LintPass for MissingDoc {
 
     fn check_crate(&mut self, cx: &Context, krate: &ast::Crate) {
         self.check_missing_docs_attrs(cx, None, krate.attrs.as_slice(),
-                                     krate.span, "crate");
+                                     krate.span, "crate", "crate");
     }
 
     fn check_item(&mut self, cx: &Context, it: &ast::Item) {
-        let desc = match it.node {
-            ast::ItemFn(..) => "a function",
-            ast::ItemMod(..) => "a module",
-            ast::ItemEnum(..) => "an enum",
-            ast::ItemStruct(..) => "a struct",
-            ast::ItemTrait(..) => "a trait",
+        let (desc, category) = match it.node {
+            ast::ItemFn(..) => ("a function", "pub_fn"),
+            ast::ItemMod(..) => ("a module", "pub_mod"),
+            ast::ItemEnum(..) => ("an enum", "pub_enum"),
+            ast::ItemStruct(..) => ("a struct", "pub_struct"),
+            ast::ItemTrait(..) => ("a trait", "pub_trait"),
             _ => return
         };
         self.check_missing_docs_attrs(cx, Some(it.id), it.attrs.as_slice(),
-                                     it.span, desc);
+                                     it.span, desc, category);
     }
 
     fn check_fn(&mut self, cx: &Context,
@@ -1441,18 +2034,22 @@ impl LintPass for MissingDoc {
             _: &ast::Block, _: Span, _: ast::NodeId) {
         if let visit::FkMethod(_, _, m) = fk {
             // If the method is an impl for a trait, don't doc.
-            if method_context(cx, m) == TraitImpl { return; }
+            let category = match method_context(cx, m) {
+                TraitImpl => return,
+                TraitDefaultImpl => "pub_trait_method",
+                PlainImpl => "pub_method",
+            };
 
             // Otherwise, doc according to privacy. This will also check
             // doc for default methods defined on traits.
             self.check_missing_docs_attrs(cx, Some(m.id), m.attrs.as_slice(),
-                                          m.span, "a method");
+                                          m.span, "a method", category);
         }
     }
 
     fn check_ty_method(&mut self, cx: &Context, tm: &ast::TypeMethod) {
         self.check_missing_docs_attrs(cx, Some(tm.id), tm.attrs.as_slice(),
-                                     tm.span, "a type method");
+                                     tm.span, "a type method", "pub_trait_method");
     }
 
     fn check_struct_field(&mut self, cx: &Context, sf: &ast::StructField) {
@@ -1462,14 +2059,14 @@ impl LintPass for MissingDoc {
                     .expect("empty struct_def_stack");
                 self.check_missing_docs_attrs(cx, Some(cur_struct_def),
                                               sf.node.attrs.as_slice(), sf.span,
-                                              "a struct field")
+                                              "a struct field", "pub_field")
             }
         }
     }
 
     fn check_variant(&mut self, cx: &Context, v: &ast::Variant, _: &ast::Generics) {
         self.check_missing_docs_attrs(cx, Some(v.node.id), v.node.attrs.as_slice(),
-                                     v.span, "a variant");
+                                     v.span, "a variant", "pub_variant");
         assert!(!self.in_variant);
         self.in_variant = true;
     }
@@ -1480,14 +2077,14 @@ impl LintPass for MissingDoc {
     }
 }
 
-declare_lint!(DEPRECATED, Warn,
+declare_lint!(pub DEPRECATED, Warn,
               "detects use of #[deprecated] items")
 
 // FIXME #6875: Change to Warn after std library stabilization is complete
-declare_lint!(EXPERIMENTAL, Allow,
+declare_lint!(pub EXPERIMENTAL, Allow,
               "detects use of #[experimental] items")
 
-declare_lint!(UNSTABLE, Allow,
+declare_lint!(pub UNSTABLE, Allow,
               "detects use of #[unstable] items (incl. items with no stability attribute)")
 
 /// Checks for use of items with `#[deprecated]`, `#[experimental]` and
@@ -1495,7 +2092,9 @@ declare_lint!(UNSTABLE, Allow,
 pub struct Stability;
 
 impl Stability {
-    fn lint(&self, cx: &Context, id: ast::DefId, span: Span) {
+    /// Lints a use of `id` at `span`. When the item names its replacement,
+    /// a path at `span` is suggested to be replaced with it.
+    fn lint(&self, cx: &Context, id: ast::DefId, span: Span, is_path: bool) {
         let stability = stability::lookup(cx.tcx, id);
         let cross_crate = !ast_util::is_local(id);
 
@@ -1520,7 +2119,16 @@ impl Stability {
             _ => format!("use of {} item", label)
         };
 
-        cx.span_lint(lint, span, msg.as_slice());
+        let mut db = cx.struct_span_lint(lint, span, msg.as_slice());
+        match stability {
+            Some(attr::Stability { replacement: Some(ref replacement), .. }) if is_path => {
+                db = db.span_suggestion(span,
+                                        format!("replace it with `{}`", *replacement).as_slice(),
+                                        replacement.get().to_string());
+            }
+            _ => {}
+        }
+        db.emit();
     }
 
     fn is_internal(&self, cx: &Context, span: Span) -> bool {
@@ -1572,7 +2180,7 @@ impl LintPass for Stability {
             None => return,
         };
         let id = ast::DefId { krate: cnum, node: ast::CRATE_NODE_ID };
-        self.lint(cx, id, item.span);
+        self.lint(cx, id, item.span, false);
     }
 
     fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
@@ -1582,6 +2190,9 @@ impl LintPass for Stability {
 
         let id = match e.node {
             ast::ExprPath(..) | ast::ExprStruct(..) => {
+                if let ast::ExprStruct(ref path, _, _) = e.node {
+                    span = path.span;
+                }
                 match cx.tcx.def_map.borrow().get(&e.id) {
                     Some(&def) => def.def_id(),
                     None => return
@@ -1620,7 +2231,7 @@ impl LintPass for Stability {
             }
             _ => return
         };
-        self.lint(cx, id, span);
+        self.lint(cx, id, span, true);
     }
 
     fn check_item(&mut self, cx: &Context, item: &ast::Item) {
@@ -1631,13 +2242,13 @@ impl LintPass for Stability {
                 for t in supertraits.iter() {
                     if let ast::TraitTyParamBound(ref t) = *t {
                         let id = ty::trait_ref_to_def_id(cx.tcx, &t.trait_ref);
-                        self.lint(cx, id, t.trait_ref.path.span);
+                        self.lint(cx, id, t.trait_ref.path.span, true);
                     }
                 }
             }
             ast::ItemImpl(_, Some(ref t), _, _) => {
                 let id = ty::trait_ref_to_def_id(cx.tcx, t);
-                self.lint(cx, id, t.path.span);
+                self.lint(cx, id, t.path.span, true);
             }
             _ => (/* pass */)
         }
@@ -1683,6 +2294,21 @@ declare_lint!(pub VARIANT_SIZE_DIFFERENCES, Allow,
 declare_lint!(pub FAT_PTR_TRANSMUTES, Allow,
               "detects transmutes of fat pointers")
 
+declare_lint!(pub ASM_MISPLACED_OPTIONS, Warn,
+              "detects asm! options listed among the clobbers")
+
+declare_lint!(pub UNRECOGNIZED_ASM_OPTIONS, Warn,
+              "detects asm! options that aren't recognized")
+
+declare_lint!(pub UNUSED_MACROS, Allow,
+              "detects macros that are defined but never used")
+
+declare_lint!(pub INTEGER_FALLBACK, Allow,
+              "detects integer literals whose type is chosen by the `i32` fallback")
+
+declare_lint!(pub REDUNDANT_PATTERN_REST, Warn,
+              "detects `..` in struct patterns that name every field")
+
 /// Does nothing as a lint pass, but registers some `Lint`s
 /// which are used by other parts of the compiler.
 pub struct HardwiredLints;
@@ -1702,7 +2328,12 @@ impl LintPass for HardwiredLints {
             UNKNOWN_FEATURES,
             UNKNOWN_CRATE_TYPES,
             VARIANT_SIZE_DIFFERENCES,
-            FAT_PTR_TRANSMUTES
+            FAT_PTR_TRANSMUTES,
+            ASM_MISPLACED_OPTIONS,
+            UNRECOGNIZED_ASM_OPTIONS,
+            UNUSED_MACROS,
+            INTEGER_FALLBACK,
+            REDUNDANT_PATTERN_REST
         )
     }
 }
@@ -23,6 +23,10 @@
 //! previous lint state is pushed onto a stack and the ast is then recursed
 //! upon.  As the ast is traversed, this keeps track of the current lint level
 //! for all lint attributes.
+//!
+//! Lints that only look at the syntax of the crate are checked the same way
+//! by an earlier pass, which runs right after macro expansion with an
+//! `EarlyContext`.
 use self::TargetLint::*;
 
 use middle::privacy::ExportedItems;
@@ -30,8 +34,9 @@ use middle::subst;
 use middle::ty::{mod, Ty};
 use middle::typeck::astconv::AstConv;
 use middle::typeck::infer;
-use session::{early_error, Session};
-use lint::{Level, LevelSource, Lint, LintId, LintArray, LintPass, LintPassObject};
+use session::{early_error, config, Session};
+use lint::{Level, LevelSource, LintSource, Lint, LintId, LintArray, LintPass, LintPassObject};
+use lint::{EarlyLintPass, EarlyLintPassObject};
 use lint::{Default, CommandLine, Node, Allow, Warn, Deny, Forbid};
 use lint::builtin;
 use util::nodemap::FnvHashMap;
@@ -43,9 +48,13 @@ use std::mem;
 use syntax::ast_util::IdVisitingOperation;
 use syntax::attr::AttrMetaMethods;
 use syntax::attr;
-use syntax::codemap::Span;
+use syntax::codemap::{Span, Pos};
+use syntax::diagnostic;
+use syntax::diagnostic::DiagnosticBuilder;
 use syntax::visit::{Visitor, FnKind};
+use syntax::parse::BufferedLint;
 use syntax::parse::token::InternedString;
+use syntax::ptr::P;
 use syntax::{ast, ast_util, visit};
 
 /// Information about the registered lints.
@@ -62,15 +71,22 @@ pub struct LintStore {
     /// of run_lints.
     passes: Option<Vec<LintPassObject>>,
 
+    /// Trait objects for each early lint pass, likewise.
+    early_passes: Option<Vec<EarlyLintPassObject>>,
+
     /// Lints indexed by name.
     by_name: FnvHashMap<String, TargetLint>,
 
-    /// Current levels of each lint, and where they were set.
-    levels: FnvHashMap<LintId, LevelSource>,
+    /// Current levels of each lint, where they were set, and the reasons
+    /// given for them by the attributes that set them, with their spans.
+    levels: FnvHashMap<LintId, (Level, LintSource, Option<(InternedString, Span)>)>,
 
     /// Map of registered lint groups to what lints they expand to. The bool
     /// is true if the lint group was added by a plugin.
     lint_groups: FnvHashMap<&'static str, (Vec<LintId>, bool)>,
+
+    /// The highest level any lint can have, from `--cap-lints`.
+    lint_cap: Option<Level>,
 }
 
 /// The targed of the `by_name` map, which accounts for renaming/deprecation.
@@ -85,16 +101,28 @@ enum TargetLint {
 impl LintStore {
     fn get_level_source(&self, lint: LintId) -> LevelSource {
         match self.levels.get(&lint) {
-            Some(&s) => s,
+            Some(&(level, src, _)) => (level, src),
             None => (Allow, Default),
         }
     }
 
-    fn set_level(&mut self, lint: LintId, lvlsrc: LevelSource) {
-        if lvlsrc.val0() == Allow {
+    /// The reason given by the attribute that set the current level of
+    /// `lint`, if any, and where it was given.
+    fn get_reason(&self, lint: LintId) -> Option<(InternedString, Span)> {
+        self.levels.get(&lint).and_then(|&(_, _, ref reason)| reason.clone())
+    }
+
+    fn set_level(&mut self, lint: LintId, lvlsrc: LevelSource,
+                 reason: Option<(InternedString, Span)>) {
+        let (level, src) = lvlsrc;
+        let level = match self.lint_cap {
+            Some(cap) if level > cap => cap,
+            _ => level,
+        };
+        if level == Allow {
             self.levels.remove(&lint);
         } else {
-            self.levels.insert(lint, lvlsrc);
+            self.levels.insert(lint, (level, src, reason));
         }
     }
 
@@ -102,9 +130,11 @@ impl LintStore {
         LintStore {
             lints: vec!(),
             passes: Some(vec!()),
+            early_passes: Some(vec!()),
             by_name: FnvHashMap::new(),
             levels: FnvHashMap::new(),
             lint_groups: FnvHashMap::new(),
+            lint_cap: None,
         }
     }
 
@@ -120,7 +150,19 @@ impl LintStore {
 
     pub fn register_pass(&mut self, sess: Option<&Session>,
                          from_plugin: bool, pass: LintPassObject) {
-        for &lint in pass.get_lints().iter() {
+        self.register_lints(sess, from_plugin, pass.get_lints());
+        self.passes.as_mut().unwrap().push(pass);
+    }
+
+    pub fn register_early_pass(&mut self, sess: Option<&Session>,
+                               from_plugin: bool, pass: EarlyLintPassObject) {
+        self.register_lints(sess, from_plugin, pass.get_lints());
+        self.early_passes.as_mut().unwrap().push(pass);
+    }
+
+    fn register_lints(&mut self, sess: Option<&Session>,
+                      from_plugin: bool, lints: LintArray) {
+        for &lint in lints.iter() {
             self.lints.push((*lint, from_plugin));
 
             let id = LintId::of(*lint);
@@ -129,7 +171,7 @@ impl LintStore {
                 match (sess, from_plugin) {
                     // We load builtin lints first, so a duplicate is a compiler bug.
                     // Use early_error when handling -W help with no crate.
-                    (None, _) => early_error(msg.as_slice()),
+                    (None, _) => early_error(config::HumanOutput(diagnostic::Auto), msg.as_slice()),
                     (Some(sess), false) => sess.bug(msg.as_slice()),
 
                     // A duplicate name from a plugin is a user error.
@@ -138,23 +180,29 @@ impl LintStore {
             }
 
             if lint.default_level != Allow {
-                self.levels.insert(id, (lint.default_level, Default));
+                self.levels.insert(id, (lint.default_level, Default, None));
             }
         }
-        self.passes.as_mut().unwrap().push(pass);
     }
 
     pub fn register_group(&mut self, sess: Option<&Session>,
                           from_plugin: bool, name: &'static str,
                           to: Vec<LintId>) {
-        let new = self.lint_groups.insert(name, (to, from_plugin)).is_none();
+        // A group can't share its name with a lint, as attributes and
+        // command line flags name either.
+        let clashes = self.by_name.contains_key(name);
+        let new = !clashes && self.lint_groups.insert(name, (to, from_plugin)).is_none();
 
         if !new {
-            let msg = format!("duplicate specification of lint group {}", name);
+            let msg = if clashes {
+                format!("lint group {} has the same name as a lint", name)
+            } else {
+                format!("duplicate specification of lint group {}", name)
+            };
             match (sess, from_plugin) {
                 // We load builtin lints first, so a duplicate is a compiler bug.
                 // Use early_error when handling -W help with no crate.
-                (None, _) => early_error(msg.as_slice()),
+                (None, _) => early_error(config::HumanOutput(diagnostic::Auto), msg.as_slice()),
                 (Some(sess), false) => sess.bug(msg.as_slice()),
 
                 // A duplicate name from a plugin is a user error.
@@ -197,16 +245,15 @@ impl LintStore {
                      UnusedAttributes,
                      PathStatements,
                      UnusedResults,
-                     NonCamelCaseTypes,
-                     NonSnakeCase,
-                     NonUpperCaseGlobals,
                      UnusedParens,
                      UnusedImportBraces,
                      NonShorthandFieldPatterns,
+                     ContextualKeywordIdents,
                      UnusedUnsafe,
                      UnsafeBlocks,
                      UnusedMut,
                      UnusedAllocation,
+                     AsmBarriers,
                      Stability,
         )
 
@@ -214,6 +261,9 @@ impl LintStore {
                               TypeLimits,
                               RawPointerDeriving,
                               MissingDoc,
+                              NonCamelCaseTypes,
+                              NonSnakeCase,
+                              NonUpperCaseGlobals,
         )
 
         add_lint_group!(sess, "bad_style",
@@ -222,7 +272,14 @@ impl LintStore {
         add_lint_group!(sess, "unused",
                         UNUSED_IMPORTS, UNUSED_VARIABLES, UNUSED_ASSIGNMENTS, DEAD_CODE,
                         UNUSED_MUT, UNREACHABLE_CODE, UNUSED_MUST_USE,
-                        UNUSED_UNSAFE, PATH_STATEMENTS)
+                        UNUSED_UNSAFE, PATH_STATEMENTS, UNUSED_MACROS)
+
+        add_lint_group!(sess, "stability",
+                        DEPRECATED, EXPERIMENTAL, UNSTABLE)
+
+        // Code that compiles today but may stop compiling in a later version
+        add_lint_group!(sess, "future_incompatible",
+                        CONTEXTUAL_KEYWORD_IDENTS)
 
         // We have one lint pass defined in this module.
         self.register_pass(sess, false, box GatherNodeLevels as LintPassObject);
@@ -273,20 +330,65 @@ impl LintStore {
         }
     }
 
+    /// Like `find_lint`, but also looks up lint groups, and doesn't warn
+    /// about renamed lints.
+    fn find_lints_quietly(&self, name: &str) -> Option<Vec<LintId>> {
+        match self.by_name.get(name) {
+            Some(&Id(lint_id)) | Some(&Renamed(_, lint_id)) => Some(vec![lint_id]),
+            None => self.lint_groups.get(name).map(|&(ref v, _)| v.clone()),
+        }
+    }
+
+    /// The level `lint` is emitted at, which for warnings depends on the
+    /// level of the `warnings` lint.
+    fn lookup_level(&self, lint: &'static Lint) -> LevelSource {
+        match self.levels.get(&LintId::of(lint)) {
+            None => (Allow, Default),
+            Some(&(Warn, src, _)) => {
+                let lint_id = LintId::of(builtin::WARNINGS);
+                (self.get_level_source(lint_id).val0(), src)
+            }
+            Some(&(level, src, _)) => (level, src),
+        }
+    }
+
+    /// Starts building `lint` at the level it's emitted at, followed by the
+    /// reason given for that level, if any.
+    fn struct_lint<'a>(&self, sess: &'a Session, lint: &'static Lint, span: Option<Span>,
+                       msg: &str) -> DiagnosticBuilder<'a> {
+        let db = raw_struct_lint(sess, lint, self.lookup_level(lint), span, msg);
+        match self.get_reason(LintId::of(lint)) {
+            Some((reason, span)) => {
+                db.span_note(span, format!("reason given: {}", reason).as_slice())
+            }
+            None => db,
+        }
+    }
+
     pub fn process_command_line(&mut self, sess: &Session) {
+        // The cap also bounds the default levels of the lints registered so
+        // far, and every level set after this, from the command line or from
+        // attributes.
+        self.lint_cap = sess.opts.lint_cap;
+        if self.lint_cap.is_some() {
+            let levels: Vec<(LintId, LevelSource)> =
+                self.levels.iter().map(|(&id, &(level, src, _))| (id, (level, src))).collect();
+            for (id, lvlsrc) in levels.into_iter() {
+                self.set_level(id, lvlsrc, None);
+            }
+        }
+
         for &(ref lint_name, level) in sess.opts.lint_opts.iter() {
             match self.find_lint(lint_name.as_slice(), sess, None) {
-                Some(lint_id) => self.set_level(lint_id, (level, CommandLine)),
+                Some(lint_id) => self.set_level(lint_id, (level, CommandLine), None),
                 None => {
-                    match self.lint_groups.iter().map(|(&x, pair)| (x, pair.ref0().clone()))
-                                                 .collect::<FnvHashMap<&'static str,
-                                                                       Vec<LintId>>>()
-                                                 .get(lint_name.as_slice()) {
+                    let group = self.lint_groups.get(lint_name.as_slice())
+                                                .map(|&(ref v, _)| v.clone());
+                    match group {
                         Some(v) => {
-                            v.iter()
-                             .map(|lint_id: &LintId|
-                                     self.set_level(*lint_id, (level, CommandLine)))
-                             .collect::<Vec<()>>();
+                            for &lint_id in v.iter() {
+                                self.set_level(lint_id, (level, CommandLine), None);
+                            }
                         }
                         None => sess.err(format!("unknown {} flag: {}",
                                                  level.as_str(), lint_name).as_slice()),
@@ -314,11 +416,15 @@ pub struct Context<'a, 'tcx: 'a> {
     /// When recursing into an attributed node of the ast which modifies lint
     /// levels, this stack keeps track of the previous lint levels of whatever
     /// was modified.
-    level_stack: Vec<(LintId, LevelSource)>,
+    level_stack: Vec<(LintId, LevelSource, Option<(InternedString, Span)>)>,
 
     /// Level of lints for certain NodeIds, stored here because the body of
     /// the lint needs to run in trans.
     node_levels: RefCell<FnvHashMap<(ast::NodeId, LintId), LevelSource>>,
+
+    /// The lints allowed by attributes, with the spans of their names and
+    /// the reasons given for them, for `-Z lint-suppressions`.
+    suppressions: Vec<(InternedString, Span, Option<InternedString>)>,
 }
 
 /// Convenience macro for calling a `LintPass` method on every pass in the context.
@@ -332,10 +438,13 @@ macro_rules! run_lints ( ($cx:expr, $f:ident, $($args:expr),*) => ({
     $cx.lints.passes = Some(passes);
 }))
 
-/// Parse the lint attributes into a vector, with `Err`s for malformed lint
-/// attributes. Writing this as an iterator is an enormous mess.
+/// Parse the lint attributes into a list of lint names, the levels set for
+/// them, their spans and the reasons given in their attributes, like
+/// `#[allow(dead_code, reason = "...")]`, with the spans of the reasons and
+/// `Err`s for malformed lint attributes.
 pub fn gather_attrs(attrs: &[ast::Attribute])
-                    -> Vec<Result<(InternedString, Level, Span), Span>> {
+                    -> Vec<Result<(InternedString, Level, Span,
+                                   Option<(InternedString, Span)>), Span>> {
     let mut out = vec!();
     for attr in attrs.iter() {
         let level = match Level::from_str(attr.name().get()) {
@@ -354,9 +463,28 @@ pub fn gather_attrs(attrs: &[ast::Attribute])
             }
         };
 
+        let is_reason = |meta: &P<ast::MetaItem>| meta.name().equiv(&("reason"));
+        let reason = metas.iter().find(|meta| is_reason(*meta)).and_then(|meta| {
+            meta.value_str().map(|reason| (reason, meta.span))
+        });
         for meta in metas.iter() {
             out.push(match meta.node {
-                ast::MetaWord(ref lint_name) => Ok((lint_name.clone(), level, meta.span)),
+                ast::MetaWord(ref lint_name) => {
+                    Ok((lint_name.clone(), level, meta.span, reason.clone()))
+                }
+                // Only `missing_docs` takes arguments, like
+                // `missing_docs(pub_fn)`, which its lint pass reads from the
+                // attribute itself. Allowing some of its categories leaves
+                // the others checked at the level they already had.
+                ast::MetaList(ref lint_name, _) if lint_name.equiv(&("missing_docs")) => {
+                    if level == Allow {
+                        continue;
+                    }
+                    Ok((lint_name.clone(), level, meta.span, reason.clone()))
+                }
+                ast::MetaNameValue(..) if is_reason(meta) && meta.value_str().is_some() => {
+                    continue;
+                }
                 _ => Err(meta.span),
             });
         }
@@ -373,8 +501,21 @@ pub fn gather_attrs(attrs: &[ast::Attribute])
 /// `Session::add_lint()` instead.
 pub fn raw_emit_lint(sess: &Session, lint: &'static Lint,
                      lvlsrc: LevelSource, span: Option<Span>, msg: &str) {
+    raw_struct_lint(sess, lint, lvlsrc, span, msg).emit();
+}
+
+/// Like `raw_emit_lint`, returning the diagnostic for more notes and help
+/// messages to be attached before it's emitted. When the lint is allowed,
+/// the diagnostic is cancelled, so emitting it does nothing.
+pub fn raw_struct_lint<'a>(sess: &'a Session, lint: &'static Lint,
+                           lvlsrc: LevelSource, span: Option<Span>,
+                           msg: &str) -> DiagnosticBuilder<'a> {
     let (mut level, source) = lvlsrc;
-    if level == Allow { return }
+    if level == Allow {
+        let mut db = sess.diagnostic().struct_warn(msg);
+        db.cancel();
+        return db;
+    }
 
     let name = lint.name_lower();
     let mut note = None;
@@ -399,17 +540,19 @@ pub fn raw_emit_lint(sess: &Session, lint: &'static Lint,
     // For purposes of printing, we can treat forbid as deny.
     if level == Forbid { level = Deny; }
 
-    match (level, span) {
-        (Warn, Some(sp)) => sess.span_warn(sp, msg.as_slice()),
-        (Warn, None)     => sess.warn(msg.as_slice()),
-        (Deny, Some(sp)) => sess.span_err(sp, msg.as_slice()),
-        (Deny, None)     => sess.err(msg.as_slice()),
+    let handler = sess.diagnostic();
+    let mut db = match (level, span) {
+        (Warn, Some(sp)) => handler.struct_span_warn(sp, msg.as_slice()),
+        (Warn, None)     => handler.struct_warn(msg.as_slice()),
+        (Deny, Some(sp)) => handler.struct_span_err(sp, msg.as_slice()),
+        (Deny, None)     => handler.struct_err(msg.as_slice()),
         _ => sess.bug("impossible level in raw_emit_lint"),
-    }
+    };
 
     for span in note.into_iter() {
-        sess.span_note(span, "lint level defined here");
+        db = db.span_note(span, "lint level defined here");
     }
+    db
 }
 
 impl<'a, 'tcx> Context<'a, 'tcx> {
@@ -427,6 +570,7 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
             lints: lint_store,
             level_stack: vec![],
             node_levels: RefCell::new(FnvHashMap::new()),
+            suppressions: Vec::new(),
         }
     }
 
@@ -438,20 +582,16 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
     /// Get the level of `lint` at the current position of the lint
     /// traversal.
     pub fn current_level(&self, lint: &'static Lint) -> Level {
-        self.lints.levels.get(&LintId::of(lint)).map_or(Allow, |&(lvl, _)| lvl)
+        self.lints.levels.get(&LintId::of(lint)).map_or(Allow, |&(lvl, _, _)| lvl)
     }
 
     fn lookup_and_emit(&self, lint: &'static Lint, span: Option<Span>, msg: &str) {
-        let (level, src) = match self.lints.levels.get(&LintId::of(lint)) {
-            None => return,
-            Some(&(Warn, src)) => {
-                let lint_id = LintId::of(builtin::WARNINGS);
-                (self.lints.get_level_source(lint_id).val0(), src)
-            }
-            Some(&pair) => pair,
-        };
+        self.lookup(lint, span, msg).emit();
+    }
 
-        raw_emit_lint(&self.tcx.sess, lint, (level, src), span, msg);
+    fn lookup(&self, lint: &'static Lint, span: Option<Span>,
+              msg: &str) -> DiagnosticBuilder<'a> {
+        self.lints.struct_lint(&self.tcx.sess, lint, span, msg)
     }
 
     /// Emit a lint at the appropriate level, with no associated span.
@@ -464,6 +604,33 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
         self.lookup_and_emit(lint, Some(span), msg);
     }
 
+    /// Starts building a lint at the appropriate level, for a particular
+    /// span, so that notes and help messages can be attached to it before
+    /// it's emitted. If the lint is allowed, emitting it does nothing.
+    pub fn struct_span_lint(&self, lint: &'static Lint, span: Span,
+                            msg: &str) -> DiagnosticBuilder<'a> {
+        self.lookup(lint, Some(span), msg)
+    }
+
+    /// Lists the lints allowed by attributes in the crate, in the order of
+    /// the attributes, with the reasons given for them.
+    fn print_suppressions(&mut self) {
+        let mut suppressions = mem::replace(&mut self.suppressions, Vec::new());
+        suppressions.sort_by(|&(_, a, _), &(_, b, _)| a.lo.to_uint().cmp(&b.lo.to_uint()));
+        suppressions.dedup();
+        let cm = self.tcx.sess.codemap();
+        for &(ref lint_name, span, ref reason) in suppressions.iter() {
+            match *reason {
+                Some(ref reason) => {
+                    println!("{}: allow({}): {}", cm.span_to_string(span), lint_name, reason)
+                }
+                None => {
+                    println!("{}: allow({}) (no reason given)", cm.span_to_string(span), lint_name)
+                }
+            }
+        }
+    }
+
     /// Merge the lints specified by any lint attributes into the
     /// current lint context, call the provided function, then reset the
     /// lints in effect to their previous state.
@@ -477,13 +644,18 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
         let mut pushed = 0u;
 
         for result in gather_attrs(attrs).into_iter() {
-            let v = match result {
+            let (v, reason) = match result {
                 Err(span) => {
                     self.tcx.sess.span_err(span, "malformed lint attribute");
                     continue;
                 }
-                Ok((lint_name, level, span)) => {
-                    match self.lints.find_lint(lint_name.get(), &self.tcx.sess, Some(span)) {
+                Ok((lint_name, level, span, reason)) => {
+                    if level == Allow {
+                        let given = reason.as_ref().map(|&(ref reason, _)| reason.clone());
+                        self.suppressions.push((lint_name.clone(), span, given));
+                    }
+                    let sess = &self.tcx.sess;
+                    let v = match self.lints.find_lint(lint_name.get(), sess, Some(span)) {
                         Some(lint_id) => vec![(lint_id, level, span)],
                         None => {
                             match self.lints.lint_groups.get(lint_name.get()) {
@@ -499,7 +671,8 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
                                 }
                             }
                         }
-                    }
+                    };
+                    (v, reason)
                 }
             };
 
@@ -513,9 +686,10 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
                                                    lint_name).as_slice());
                 } else if now != level {
                     let src = self.lints.get_level_source(lint_id).val1();
-                    self.level_stack.push((lint_id, (now, src)));
+                    let old_reason = self.lints.get_reason(lint_id);
+                    self.level_stack.push((lint_id, (now, src), old_reason));
                     pushed += 1;
-                    self.lints.set_level(lint_id, (level, Node(span)));
+                    self.lints.set_level(lint_id, (level, Node(span)), reason.clone());
                 }
             }
         }
@@ -526,8 +700,8 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
 
         // rollback
         for _ in range(0, pushed) {
-            let (lint, lvlsrc) = self.level_stack.pop().unwrap();
-            self.lints.set_level(lint, lvlsrc);
+            let (lint, lvlsrc, reason) = self.level_stack.pop().unwrap();
+            self.lints.set_level(lint, lvlsrc, reason);
         }
     }
 
@@ -791,6 +965,290 @@ impl LintPass for GatherNodeLevels {
     }
 }
 
+/// Context for lint checking on the syntax of the crate, right after macro
+/// expansion.
+pub struct EarlyContext<'a> {
+    /// The compiler session.
+    pub sess: &'a Session,
+
+    /// The crate being checked.
+    pub krate: &'a ast::Crate,
+
+    /// The store of registered lints.
+    lints: LintStore,
+
+    /// Previous lint levels of whatever the lint attributes around the
+    /// current node modified, as for `Context`.
+    level_stack: Vec<(LintId, LevelSource, Option<(InternedString, Span)>)>,
+
+    /// Lints reported before lint checking, like those of macro expansion,
+    /// that haven't been emitted yet. They are all emitted by this pass, so
+    /// that an error that aborts compilation before the late lint pass, or
+    /// a flag that stops it after expansion, doesn't lose them.
+    buffered_lints: Vec<BufferedLint>,
+}
+
+/// Like `run_lints!`, for the early lint passes.
+macro_rules! run_early_lints ( ($cx:expr, $f:ident, $($args:expr),*) => ({
+    let mut passes = $cx.lints.early_passes.take().unwrap();
+    for obj in passes.iter_mut() {
+        obj.$f($cx, $($args),*);
+    }
+    $cx.lints.early_passes = Some(passes);
+}))
+
+impl<'a> EarlyContext<'a> {
+    fn new(sess: &'a Session, krate: &'a ast::Crate) -> EarlyContext<'a> {
+        // Borrow the lint store for the pass; `check_ast_crate` puts it back.
+        let lint_store = mem::replace(&mut *sess.lint_store.borrow_mut(),
+                                      LintStore::new());
+
+        EarlyContext {
+            sess: sess,
+            krate: krate,
+            lints: lint_store,
+            level_stack: vec![],
+            buffered_lints: mem::replace(&mut *sess.parse_sess.buffered_lints.borrow_mut(),
+                                         Vec::new()),
+        }
+    }
+
+    /// Get the level of `lint` at the current position of the lint
+    /// traversal.
+    pub fn current_level(&self, lint: &'static Lint) -> Level {
+        self.lints.levels.get(&LintId::of(lint)).map_or(Allow, |&(lvl, _, _)| lvl)
+    }
+
+    /// Emit a lint at the appropriate level, with no associated span.
+    pub fn lint(&self, lint: &'static Lint, msg: &str) {
+        self.lints.struct_lint(self.sess, lint, None, msg).emit();
+    }
+
+    /// Emit a lint at the appropriate level, for a particular span.
+    pub fn span_lint(&self, lint: &'static Lint, span: Span, msg: &str) {
+        self.lints.struct_lint(self.sess, lint, Some(span), msg).emit();
+    }
+
+    /// Starts building a lint at the appropriate level, for a particular
+    /// span, as `Context::struct_span_lint` does.
+    pub fn struct_span_lint(&self, lint: &'static Lint, span: Span,
+                            msg: &str) -> DiagnosticBuilder<'a> {
+        self.lints.struct_lint(self.sess, lint, Some(span), msg)
+    }
+
+    /// Emits the buffered lints that point into the node at `span` with
+    /// `attrs`, at the levels in effect there. This is called once the nodes
+    /// inside it have been visited, so that each lint is emitted by the
+    /// innermost node with lint attributes around it.
+    fn emit_buffered_lints(&mut self, span: Span, attrs: &[ast::Attribute]) {
+        if self.buffered_lints.is_empty() {
+            return;
+        }
+        // Spans of nodes don't cover their outer attributes
+        let lo = attrs.iter().map(|attr| attr.span.lo).fold(span.lo, |lo, attr_lo| {
+            if attr_lo < lo { attr_lo } else { lo }
+        });
+        let lints = mem::replace(&mut self.buffered_lints, Vec::new());
+        for lint in lints.into_iter() {
+            // A lint in code a macro expanded to belongs to the node holding
+            // the invocation, not to wherever the macro was defined
+            let lint_span = self.sess.codemap().outer_call_site(lint.span);
+            if lo <= lint_span.lo && lint_span.hi <= span.hi {
+                self.emit_buffered_lint(lint);
+            } else {
+                self.buffered_lints.push(lint);
+            }
+        }
+    }
+
+    fn emit_buffered_lint(&self, lint: BufferedLint) {
+        let lint_id = match self.lints.find_lint(lint.lint_name, self.sess, None) {
+            Some(lint_id) => lint_id,
+            None => self.sess.span_bug(lint.span,
+                                     format!("unknown lint `{}` reported by libsyntax",
+                                             lint.lint_name).as_slice()),
+        };
+        let mut db = self.struct_span_lint(lint_id.lint, lint.span, lint.msg.as_slice());
+        for help in lint.helps.iter() {
+            db = db.span_help(lint.span, help.as_slice());
+        }
+        if let Some((sp, msg, suggestion)) = lint.suggestion {
+            db = db.span_suggestion(sp, msg.as_slice(), suggestion);
+        }
+        db.emit();
+    }
+
+    /// Merge the lints specified by any lint attributes into the current
+    /// lint context, as `Context::with_lint_attrs` does. Malformed and
+    /// unknown lint attributes are left to the late pass to report, so they
+    /// aren't reported twice.
+    fn with_lint_attrs(&mut self,
+                       attrs: &[ast::Attribute],
+                       f: |&mut EarlyContext|) {
+        let mut pushed = 0u;
+
+        for result in gather_attrs(attrs).into_iter() {
+            let (lint_name, level, span, reason) = match result {
+                Ok(attr) => attr,
+                Err(_) => continue,
+            };
+            let lint_ids = match self.lints.find_lints_quietly(lint_name.get()) {
+                Some(lint_ids) => lint_ids,
+                None => continue,
+            };
+            for lint_id in lint_ids.into_iter() {
+                let (now, src) = self.lints.get_level_source(lint_id);
+                if now != Forbid && now != level {
+                    let old_reason = self.lints.get_reason(lint_id);
+                    self.level_stack.push((lint_id, (now, src), old_reason));
+                    pushed += 1;
+                    self.lints.set_level(lint_id, (level, Node(span)), reason.clone());
+                }
+            }
+        }
+
+        run_early_lints!(self, enter_lint_attrs, attrs);
+        f(self);
+        run_early_lints!(self, exit_lint_attrs, attrs);
+
+        // rollback
+        for _ in range(0, pushed) {
+            let (lint, lvlsrc, reason) = self.level_stack.pop().unwrap();
+            self.lints.set_level(lint, lvlsrc, reason);
+        }
+    }
+}
+
+impl<'a, 'v> Visitor<'v> for EarlyContext<'a> {
+    fn visit_item(&mut self, it: &ast::Item) {
+        self.with_lint_attrs(it.attrs.as_slice(), |cx| {
+            run_early_lints!(cx, check_item, it);
+            visit::walk_item(cx, it);
+            cx.emit_buffered_lints(it.span, it.attrs.as_slice());
+        })
+    }
+
+    fn visit_foreign_item(&mut self, it: &ast::ForeignItem) {
+        self.with_lint_attrs(it.attrs.as_slice(), |cx| {
+            run_early_lints!(cx, check_foreign_item, it);
+            visit::walk_foreign_item(cx, it);
+            cx.emit_buffered_lints(it.span, it.attrs.as_slice());
+        })
+    }
+
+    fn visit_view_item(&mut self, i: &ast::ViewItem) {
+        self.with_lint_attrs(i.attrs.as_slice(), |cx| {
+            run_early_lints!(cx, check_view_item, i);
+            visit::walk_view_item(cx, i);
+            cx.emit_buffered_lints(i.span, i.attrs.as_slice());
+        })
+    }
+
+    fn visit_pat(&mut self, p: &ast::Pat) {
+        run_early_lints!(self, check_pat, p);
+        visit::walk_pat(self, p);
+    }
+
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        run_early_lints!(self, check_expr, e);
+        visit::walk_expr(self, e);
+    }
+
+    fn visit_stmt(&mut self, s: &ast::Stmt) {
+        run_early_lints!(self, check_stmt, s);
+        visit::walk_stmt(self, s);
+    }
+
+    fn visit_fn(&mut self, fk: FnKind<'v>, decl: &'v ast::FnDecl,
+                body: &'v ast::Block, span: Span, _: ast::NodeId) {
+        match fk {
+            visit::FkMethod(_, _, m) => {
+                self.with_lint_attrs(m.attrs.as_slice(), |cx| {
+                    run_early_lints!(cx, check_fn, fk, decl, body, span);
+                    visit::walk_fn(cx, fk, decl, body, span);
+                    cx.emit_buffered_lints(span, m.attrs.as_slice());
+                })
+            },
+            _ => {
+                run_early_lints!(self, check_fn, fk, decl, body, span);
+                visit::walk_fn(self, fk, decl, body, span);
+            }
+        }
+    }
+
+    fn visit_ty_method(&mut self, t: &ast::TypeMethod) {
+        self.with_lint_attrs(t.attrs.as_slice(), |cx| {
+            run_early_lints!(cx, check_ty_method, t);
+            visit::walk_ty_method(cx, t);
+            cx.emit_buffered_lints(t.span, t.attrs.as_slice());
+        })
+    }
+
+    fn visit_struct_field(&mut self, s: &ast::StructField) {
+        self.with_lint_attrs(s.node.attrs.as_slice(), |cx| {
+            run_early_lints!(cx, check_struct_field, s);
+            visit::walk_struct_field(cx, s);
+            cx.emit_buffered_lints(s.span, s.node.attrs.as_slice());
+        })
+    }
+
+    fn visit_variant(&mut self, v: &ast::Variant, g: &ast::Generics) {
+        self.with_lint_attrs(v.node.attrs.as_slice(), |cx| {
+            run_early_lints!(cx, check_variant, v, g);
+            visit::walk_variant(cx, v, g);
+            cx.emit_buffered_lints(v.span, v.node.attrs.as_slice());
+        })
+    }
+
+    fn visit_ty(&mut self, t: &ast::Ty) {
+        run_early_lints!(self, check_ty, t);
+        visit::walk_ty(self, t);
+    }
+
+    fn visit_ident(&mut self, sp: Span, id: ast::Ident) {
+        run_early_lints!(self, check_ident, sp, id);
+    }
+
+    fn visit_mod(&mut self, m: &ast::Mod, s: Span, _: ast::NodeId) {
+        run_early_lints!(self, check_mod, m, s);
+        visit::walk_mod(self, m);
+    }
+
+    fn visit_local(&mut self, l: &ast::Local) {
+        run_early_lints!(self, check_local, l);
+        visit::walk_local(self, l);
+    }
+
+    fn visit_block(&mut self, b: &ast::Block) {
+        run_early_lints!(self, check_block, b);
+        visit::walk_block(self, b);
+    }
+
+    fn visit_arm(&mut self, a: &ast::Arm) {
+        run_early_lints!(self, check_arm, a);
+        visit::walk_arm(self, a);
+    }
+
+    fn visit_generics(&mut self, g: &ast::Generics) {
+        run_early_lints!(self, check_generics, g);
+        visit::walk_generics(self, g);
+    }
+
+    fn visit_trait_item(&mut self, m: &ast::TraitItem) {
+        run_early_lints!(self, check_trait_method, m);
+        visit::walk_trait_item(self, m);
+    }
+
+    fn visit_path(&mut self, p: &ast::Path, _: ast::NodeId) {
+        run_early_lints!(self, check_path, p);
+        visit::walk_path(self, p);
+    }
+
+    fn visit_attribute(&mut self, attr: &ast::Attribute) {
+        run_early_lints!(self, check_attribute, attr);
+    }
+}
+
 /// Perform lint checking on a crate.
 ///
 /// Consumes the `lint_store` field of the `Session`.
@@ -824,6 +1282,32 @@ pub fn check_crate(tcx: &ty::ctxt,
         }
     }
 
+    if tcx.sess.debugging_opt(config::LINT_SUPPRESSIONS) {
+        cx.print_suppressions();
+    }
+
     tcx.sess.abort_if_errors();
     *tcx.node_lint_levels.borrow_mut() = cx.node_levels.into_inner();
 }
+
+/// Perform the early lint checks on the expanded crate.
+///
+/// Borrows the `lint_store` field of the `Session`, which is put back for
+/// `check_crate`.
+pub fn check_ast_crate(sess: &Session, krate: &ast::Crate) {
+    let mut cx = EarlyContext::new(sess, krate);
+
+    cx.with_lint_attrs(krate.attrs.as_slice(), |cx| {
+        run_early_lints!(cx, check_crate, krate);
+        visit::walk_crate(cx, krate);
+
+        // Whatever isn't inside any node, e.g. because it points into the
+        // definition of a macro, gets the crate's levels
+        let rest = mem::replace(&mut cx.buffered_lints, Vec::new());
+        for lint in rest.into_iter() {
+            cx.emit_buffered_lint(lint);
+        }
+    });
+
+    *sess.lint_store.borrow_mut() = cx.lints;
+}
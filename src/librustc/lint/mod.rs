@@ -17,9 +17,13 @@
 //! to compile the program at all.
 //!
 //! Most lints can be written as `LintPass` instances. These run just before
-//! translation to LLVM bytecode. The `LintPass`es built into rustc are defined
-//! within `builtin.rs`, which has further comments on how to add such a lint.
-//! rustc can also load user-defined lint plugins via the plugin mechanism.
+//! translation to LLVM bytecode, after type checking, so they can look up the
+//! types of expressions and items. The `LintPass`es built into rustc are
+//! defined within `builtin.rs`, which has further comments on how to add such
+//! a lint. Lints that only need the syntax of the crate can instead be
+//! written as `EarlyLintPass` instances, which run right after macro
+//! expansion, even when the crate fails to type check. rustc can also load
+//! user-defined lint plugins of either kind via the plugin mechanism.
 //!
 //! Some of rustc's lints are defined elsewhere in the compiler and work by
 //! calling `add_lint()` on the overall `Session` object. This works when
@@ -39,7 +43,8 @@ use syntax::codemap::Span;
 use syntax::visit::FnKind;
 use syntax::ast;
 
-pub use lint::context::{Context, LintStore, raw_emit_lint, check_crate, gather_attrs};
+pub use lint::context::{Context, EarlyContext, LintStore, raw_emit_lint, raw_struct_lint};
+pub use lint::context::{check_crate, check_ast_crate, gather_attrs};
 
 /// Specification of a single lint.
 pub struct Lint {
@@ -109,7 +114,7 @@ macro_rules! lint_array ( ($( $lint:expr ),*) => (
 
 pub type LintArray = &'static [&'static &'static Lint];
 
-/// Trait for types providing lint checks.
+/// Trait for types providing lint checks, which run after type checking.
 ///
 /// Each `check` method checks a single syntax node, and should not
 /// invoke methods recursively (unlike `Visitor`). By default they
@@ -172,6 +177,49 @@ pub trait LintPass {
 /// A lint pass boxed up as a trait object.
 pub type LintPassObject = Box<LintPass + 'static>;
 
+/// Trait for types providing lint checks on the syntax of the crate alone,
+/// which run after macro expansion and before name resolution.
+///
+/// These are like the methods of `LintPass`, but the `EarlyContext` they
+/// get has no type information, and node ids aren't assigned yet.
+pub trait EarlyLintPass {
+    /// Get descriptions of the lints this `EarlyLintPass` object can emit.
+    fn get_lints(&self) -> LintArray;
+
+    fn check_crate(&mut self, _: &EarlyContext, _: &ast::Crate) { }
+    fn check_ident(&mut self, _: &EarlyContext, _: Span, _: ast::Ident) { }
+    fn check_mod(&mut self, _: &EarlyContext, _: &ast::Mod, _: Span) { }
+    fn check_view_item(&mut self, _: &EarlyContext, _: &ast::ViewItem) { }
+    fn check_foreign_item(&mut self, _: &EarlyContext, _: &ast::ForeignItem) { }
+    fn check_item(&mut self, _: &EarlyContext, _: &ast::Item) { }
+    fn check_local(&mut self, _: &EarlyContext, _: &ast::Local) { }
+    fn check_block(&mut self, _: &EarlyContext, _: &ast::Block) { }
+    fn check_stmt(&mut self, _: &EarlyContext, _: &ast::Stmt) { }
+    fn check_arm(&mut self, _: &EarlyContext, _: &ast::Arm) { }
+    fn check_pat(&mut self, _: &EarlyContext, _: &ast::Pat) { }
+    fn check_expr(&mut self, _: &EarlyContext, _: &ast::Expr) { }
+    fn check_ty(&mut self, _: &EarlyContext, _: &ast::Ty) { }
+    fn check_generics(&mut self, _: &EarlyContext, _: &ast::Generics) { }
+    fn check_fn(&mut self, _: &EarlyContext,
+        _: FnKind, _: &ast::FnDecl, _: &ast::Block, _: Span) { }
+    fn check_ty_method(&mut self, _: &EarlyContext, _: &ast::TypeMethod) { }
+    fn check_trait_method(&mut self, _: &EarlyContext, _: &ast::TraitItem) { }
+    fn check_struct_field(&mut self, _: &EarlyContext, _: &ast::StructField) { }
+    fn check_variant(&mut self, _: &EarlyContext, _: &ast::Variant, _: &ast::Generics) { }
+    fn check_path(&mut self, _: &EarlyContext, _: &ast::Path) { }
+    fn check_attribute(&mut self, _: &EarlyContext, _: &ast::Attribute) { }
+
+    /// Called when entering a syntax node that can have lint attributes such
+    /// as `#[allow(...)]`. Called with *all* the attributes of that node.
+    fn enter_lint_attrs(&mut self, _: &EarlyContext, _: &[ast::Attribute]) { }
+
+    /// Counterpart to `enter_lint_attrs`.
+    fn exit_lint_attrs(&mut self, _: &EarlyContext, _: &[ast::Attribute]) { }
+}
+
+/// An early lint pass boxed up as a trait object.
+pub type EarlyLintPassObject = Box<EarlyLintPass + 'static>;
+
 /// Identifies a lint known to the compiler.
 #[deriving(Clone)]
 pub struct LintId {
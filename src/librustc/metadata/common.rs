@@ -197,7 +197,9 @@ pub const tag_impls_impl: uint = 0x81;
 pub const tag_items_data_item_inherent_impl: uint = 0x82;
 pub const tag_items_data_item_extension_impl: uint = 0x83;
 
-// GAP 0x84, 0x85, 0x86
+pub const tag_macro_def_source: uint = 0x84;
+pub const tag_macro_def_filename: uint = 0x85;
+pub const tag_macro_def_line_col: uint = 0x86;
 
 pub const tag_native_libraries: uint = 0x87;
 pub const tag_native_libraries_lib: uint = 0x88;
@@ -218,6 +220,8 @@ pub const tag_items_data_item_stability: uint = 0x92;
 
 pub const tag_items_data_item_repr: uint = 0x93;
 
+pub const tag_macro_def_expansion: uint = 0x94;
+
 #[deriving(Clone, Show)]
 pub struct LinkMeta {
     pub crate_name: String,
@@ -39,6 +39,7 @@ pub struct MethodInfo {
     pub name: ast::Name,
     pub def_id: ast::DefId,
     pub vis: ast::Visibility,
+    pub is_associated_const: bool,
 }
 
 pub fn get_symbol(cstore: &cstore::CStore, def: ast::DefId) -> String {
@@ -26,7 +26,7 @@ use metadata::tydecode::{parse_ty_data, parse_region_data, parse_def_id,
                          parse_bare_fn_ty_data, parse_trait_ref_data};
 use middle::def;
 use middle::lang_items;
-use middle::resolve::{TraitItemKind, TypeTraitItemKind};
+use middle::resolve::{TraitItemKind, TypeTraitItemKind, ConstTraitItemKind};
 use middle::subst;
 use middle::ty::{ImplContainer, TraitContainer};
 use middle::ty::{mod, Ty};
@@ -50,6 +50,7 @@ use syntax::parse::token;
 use syntax::print::pprust;
 use syntax::ast;
 use syntax::codemap;
+use syntax::ext::expand::ExportedMacro;
 use syntax::ptr::P;
 
 pub type Cmd<'a> = &'a crate_metadata;
@@ -130,6 +131,7 @@ enum Family {
     PublicField,           // g
     InheritedField,        // N
     Constant,              // C
+    AssociatedConst,       // A
 }
 
 fn item_family(item: rbml::Doc) -> Family {
@@ -154,6 +156,7 @@ fn item_family(item: rbml::Doc) -> Family {
       'S' => Struct,
       'g' => PublicField,
       'N' => InheritedField,
+      'A' => AssociatedConst,
        c => panic!("unexpected family char: {}", c)
     }
 }
@@ -319,7 +322,7 @@ fn item_to_def_like(item: rbml::Doc, did: ast::DefId, cnum: ast::CrateNum)
         Struct    => DlDef(def::DefStruct(did)),
         Fn        => DlDef(def::DefFn(did, false)),
         CtorFn    => DlDef(def::DefFn(did, true)),
-        Method | StaticMethod => {
+        Method | StaticMethod | AssociatedConst => {
             // def_static_method carries an optional field of its enclosing
             // trait or enclosing impl (if this is an inherent static method).
             // So we need to detect whether this is in a trait or not, which
@@ -337,6 +340,7 @@ fn item_to_def_like(item: rbml::Doc, did: ast::DefId, cnum: ast::CrateNum)
                 // We don't bother to get encode/decode the trait id, we don't need it.
                 Method => DlDef(def::DefMethod(did, None, provenance)),
                 StaticMethod => DlDef(def::DefStaticMethod(did, provenance)),
+                AssociatedConst => DlDef(def::DefAssociatedConst(did, provenance)),
                 _ => panic!()
             }
         }
@@ -748,6 +752,7 @@ pub fn get_impl_items(cdata: Cmd, impl_id: ast::NodeId)
         match item_sort(doc) {
             'r' | 'p' => impl_items.push(ty::MethodTraitItemId(def_id)),
             't' => impl_items.push(ty::TypeTraitItemId(def_id)),
+            'c' => impl_items.push(ty::ConstTraitItemId(def_id)),
             _ => panic!("unknown impl item sort"),
         }
         true
@@ -768,6 +773,7 @@ pub fn get_trait_item_name_and_kind(intr: Rc<IdentInterner>,
             (name, TraitItemKind::from_explicit_self_category(explicit_self))
         }
         't' => (name, TypeTraitItemKind),
+        'c' => (name, ConstTraitItemKind),
         c => {
             panic!("get_trait_item_name_and_kind(): unknown trait item kind \
                    in metadata: `{}`", c)
@@ -820,6 +826,19 @@ pub fn get_impl_or_trait_item<'tcx>(intr: Rc<IdentInterner>,
                 container: container,
             }))
         }
+        'c' => {
+            let ty = doc_type(method_doc, tcx, cdata);
+            // Only constants with a value have their AST encoded.
+            let has_value = method_doc.opt_child(tag_ast).is_some();
+            ty::ConstTraitItem(Rc::new(ty::AssociatedConst {
+                name: name,
+                ty: ty,
+                vis: vis,
+                def_id: def_id,
+                container: container,
+                has_value: has_value,
+            }))
+        }
         _ => panic!("unknown impl/trait item sort"),
     }
 }
@@ -834,6 +853,7 @@ pub fn get_trait_item_def_ids(cdata: Cmd, id: ast::NodeId)
         match item_sort(mth) {
             'r' | 'p' => result.push(ty::MethodTraitItemId(def_id)),
             't' => result.push(ty::TypeTraitItemId(def_id)),
+            'c' => result.push(ty::ConstTraitItemId(def_id)),
             _ => panic!("unknown trait item sort"),
         }
         true
@@ -871,7 +891,7 @@ pub fn get_provided_trait_methods<'tcx>(intr: Rc<IdentInterner>,
                 ty::MethodTraitItem(ref method) => {
                     result.push((*method).clone())
                 }
-                ty::TypeTraitItem(_) => {}
+                ty::TypeTraitItem(_) | ty::ConstTraitItem(_) => {}
             }
         }
         true
@@ -941,11 +961,12 @@ pub fn get_methods_if_impl(intr: Rc<IdentInterner>,
         let impl_method_doc = lookup_item(impl_method_id.node, cdata.data());
         let family = item_family(impl_method_doc);
         match family {
-            StaticMethod | Method => {
+            StaticMethod | Method | AssociatedConst => {
                 impl_methods.push(MethodInfo {
                     name: item_name(&*intr, impl_method_doc),
                     def_id: item_def_id(impl_method_doc, cdata),
                     vis: item_visibility(impl_method_doc),
+                    is_associated_const: family == AssociatedConst,
                 });
             }
             _ => {}
@@ -1289,12 +1310,26 @@ pub fn get_plugin_registrar_fn(data: &[u8]) -> Option<ast::NodeId> {
         .map(|doc| FromPrimitive::from_u32(reader::doc_as_u32(doc)).unwrap())
 }
 
-pub fn get_exported_macros(data: &[u8]) -> Vec<String> {
+pub fn get_exported_macros(data: &[u8]) -> Vec<ExportedMacro> {
     let macros = reader::get_doc(rbml::Doc::new(data),
                                  tag_exported_macros);
     let mut result = Vec::new();
     reader::tagged_docs(macros, tag_macro_def, |macro_doc| {
-        result.push(macro_doc.as_str().to_string());
+        let source = reader::get_doc(macro_doc, tag_macro_def_source);
+        let filename = reader::get_doc(macro_doc, tag_macro_def_filename);
+        let line_col = reader::doc_as_u64(reader::get_doc(macro_doc, tag_macro_def_line_col));
+        let mut expansion = Vec::new();
+        reader::tagged_docs(macro_doc, tag_macro_def_expansion, |frame_doc| {
+            expansion.push(frame_doc.as_str().to_string());
+            true
+        });
+        result.push(ExportedMacro {
+            source: source.as_str().to_string(),
+            filename: filename.as_str().to_string(),
+            line: (line_col >> 32) as uint,
+            col: (line_col & 0xffff_ffff) as uint,
+            expansion: expansion,
+        });
         true
     });
     result
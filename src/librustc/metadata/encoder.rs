@@ -39,6 +39,7 @@ use syntax::ast_util::*;
 use syntax::ast_util;
 use syntax::attr;
 use syntax::attr::AttrMetaMethods;
+use syntax::codemap::Pos;
 use syntax::diagnostic::SpanHandler;
 use syntax::parse::token::special_idents;
 use syntax::parse::token;
@@ -415,7 +416,7 @@ fn encode_reexported_static_base_methods(ecx: &EncodeContext,
                                                             m.def_id,
                                                             m.name);
                         }
-                        ty::TypeTraitItem(_) => {}
+                        ty::TypeTraitItem(_) | ty::ConstTraitItem(_) => {}
                     }
                 }
             }
@@ -906,6 +907,47 @@ fn encode_info_for_associated_type(ecx: &EncodeContext,
     rbml_w.end_tag();
 }
 
+fn encode_info_for_associated_const(ecx: &EncodeContext,
+                                    rbml_w: &mut Encoder,
+                                    associated_const: &ty::AssociatedConst,
+                                    impl_path: PathElems,
+                                    parent_id: NodeId,
+                                    ast_item_opt: Option<&ast::ImplItem>) {
+    debug!("encode_info_for_associated_const({},{})",
+           associated_const.def_id,
+           token::get_name(associated_const.name));
+
+    rbml_w.start_tag(tag_items_data_item);
+
+    encode_def_id(rbml_w, associated_const.def_id);
+    encode_name(rbml_w, associated_const.name);
+    encode_visibility(rbml_w, associated_const.vis);
+    encode_family(rbml_w, ASSOCIATED_CONST_FAMILY);
+    encode_parent_item(rbml_w, local_def(parent_id));
+    encode_item_sort(rbml_w, 'c');
+
+    let stab = stability::lookup(ecx.tcx, associated_const.def_id);
+    encode_stability(rbml_w, stab);
+
+    let pty = lookup_item_type(ecx.tcx, associated_const.def_id);
+    encode_bounds_and_type(rbml_w, ecx, &pty);
+
+    let elem = ast_map::PathName(associated_const.name);
+    encode_path(rbml_w, impl_path.chain(Some(elem).into_iter()));
+
+    match ast_item_opt {
+        Some(&ast::ConstImplItem(ref ast_const)) => {
+            encode_attributes(rbml_w, ast_const.attrs.as_slice());
+            // Like other constants, the value is always inlined.
+            encode_inlined_item(ecx, rbml_w, IIImplItemRef(local_def(parent_id),
+                                                           ast_item_opt.unwrap()));
+        }
+        Some(_) | None => {}
+    }
+
+    rbml_w.end_tag();
+}
+
 fn encode_method_argument_names(rbml_w: &mut Encoder,
                                 decl: &ast::FnDecl) {
     rbml_w.start_tag(tag_method_argument_names);
@@ -923,11 +965,7 @@ fn encode_method_argument_names(rbml_w: &mut Encoder,
 fn encode_repr_attrs(rbml_w: &mut Encoder,
                      ecx: &EncodeContext,
                      attrs: &[ast::Attribute]) {
-    let mut repr_attrs = Vec::new();
-    for attr in attrs.iter() {
-        repr_attrs.extend(attr::find_repr_attrs(ecx.tcx.sess.diagnostic(),
-                                                attr).into_iter());
-    }
+    let repr_attrs = attr::find_repr(ecx.tcx.sess.diagnostic(), attrs);
     rbml_w.start_tag(tag_items_data_item_repr);
     repr_attrs.encode(rbml_w);
     rbml_w.end_tag();
@@ -944,6 +982,7 @@ fn encode_inlined_item(ecx: &EncodeContext,
 const FN_FAMILY: char = 'f';
 const STATIC_METHOD_FAMILY: char = 'F';
 const METHOD_FAMILY: char = 'h';
+const ASSOCIATED_CONST_FAMILY: char = 'A';
 
 fn should_inline(attrs: &[ast::Attribute]) -> bool {
     use syntax::attr::*;
@@ -1223,6 +1262,10 @@ fn encode_info_for_item(ecx: &EncodeContext,
                     encode_def_id(rbml_w, item_def_id);
                     encode_item_sort(rbml_w, 't');
                 }
+                ty::ConstTraitItemId(item_def_id) => {
+                    encode_def_id(rbml_w, item_def_id);
+                    encode_item_sort(rbml_w, 'c');
+                }
             }
             rbml_w.end_tag();
         }
@@ -1291,6 +1334,14 @@ fn encode_info_for_item(ecx: &EncodeContext,
                                                     item.id,
                                                     None)
                 }
+                (ty::ConstTraitItem(ref associated_const), _) => {
+                    encode_info_for_associated_const(ecx,
+                                                     rbml_w,
+                                                     &**associated_const,
+                                                     path.clone(),
+                                                     item.id,
+                                                     ast_item)
+                }
             }
         }
       }
@@ -1318,6 +1369,10 @@ fn encode_info_for_item(ecx: &EncodeContext,
                     encode_def_id(rbml_w, type_def_id);
                     encode_item_sort(rbml_w, 't');
                 }
+                ty::ConstTraitItemId(const_def_id) => {
+                    encode_def_id(rbml_w, const_def_id);
+                    encode_item_sort(rbml_w, 'c');
+                }
             }
             rbml_w.end_tag();
 
@@ -1388,6 +1443,22 @@ fn encode_info_for_item(ecx: &EncodeContext,
 
                     encode_family(rbml_w, 'y');
 
+                    is_nonstatic_method = false;
+                }
+                ty::ConstTraitItem(associated_const) => {
+                    encode_name(rbml_w, associated_const.name);
+                    encode_def_id(rbml_w, associated_const.def_id);
+                    encode_visibility(rbml_w, associated_const.vis);
+
+                    let elem = ast_map::PathName(associated_const.name);
+                    encode_path(rbml_w,
+                                path.clone().chain(Some(elem).into_iter()));
+
+                    encode_family(rbml_w, ASSOCIATED_CONST_FAMILY);
+                    let pty = ty::lookup_item_type(tcx,
+                                                   associated_const.def_id);
+                    encode_bounds_and_type(rbml_w, ecx, &pty);
+
                     is_nonstatic_method = false;
                 }
             }
@@ -1426,6 +1497,16 @@ fn encode_info_for_item(ecx: &EncodeContext,
                                       associated_type.attrs.as_slice());
                     encode_item_sort(rbml_w, 't');
                 }
+
+                &ast::ConstTraitItem(ref associated_const) => {
+                    encode_attributes(rbml_w,
+                                      associated_const.attrs.as_slice());
+                    encode_item_sort(rbml_w, 'c');
+                    if associated_const.expr.is_some() {
+                        encode_inlined_item(ecx, rbml_w,
+                                            IITraitItemRef(def_id, trait_item));
+                    }
+                }
             }
 
             rbml_w.end_tag();
@@ -1764,14 +1845,31 @@ fn encode_plugin_registrar_fn(ecx: &EncodeContext, rbml_w: &mut Encoder) {
 }
 
 /// Given a span, write the text of that span into the output stream
-/// as an exported macro
+/// as an exported macro, along with where it starts in its file
 fn encode_macro_def(ecx: &EncodeContext,
                     rbml_w: &mut Encoder,
                     span: &syntax::codemap::Span) {
-    let def = ecx.tcx.sess.codemap().span_to_snippet(*span)
+    let cm = ecx.tcx.sess.codemap();
+    let def = cm.span_to_snippet(*span)
         .expect("Unable to find source for macro");
+    let loc = cm.lookup_char_pos(span.lo);
     rbml_w.start_tag(tag_macro_def);
-    rbml_w.wr_str(def.as_slice());
+    rbml_w.wr_tagged_str(tag_macro_def_source, def.as_slice());
+    rbml_w.wr_tagged_str(tag_macro_def_filename, loc.file.name.as_slice());
+    // The 0-based line goes in the high half, the column in the low half.
+    let line_col = ((loc.line - 1) as u64 << 32) | loc.col.to_uint() as u64;
+    rbml_w.wr_tagged_u64(tag_macro_def_line_col, line_col);
+    // Macros defined by other macros can't be traced back to their
+    // definition otherwise, since downstream crates don't have our spans.
+    for frame in cm.macro_backtrace(*span).iter() {
+        let call_site = cm.lookup_char_pos(frame.call_site.lo);
+        let expansion = format!("{} at {}:{}:{}",
+                                frame.macro_decl_name,
+                                call_site.file.name,
+                                call_site.line,
+                                call_site.col.to_uint() + 1);
+        rbml_w.wr_tagged_str(tag_macro_def_expansion, expansion.as_slice());
+    }
     rbml_w.end_tag();
 }
 
@@ -80,8 +80,10 @@ pub fn encode_inlined_item(ecx: &e::EncodeContext,
         e::IITraitItemRef(_, &ast::ProvidedMethod(ref m)) => m.id,
         e::IITraitItemRef(_, &ast::RequiredMethod(ref m)) => m.id,
         e::IITraitItemRef(_, &ast::TypeTraitItem(ref ti)) => ti.ty_param.id,
+        e::IITraitItemRef(_, &ast::ConstTraitItem(ref ac)) => ac.id,
         e::IIImplItemRef(_, &ast::MethodImplItem(ref m)) => m.id,
         e::IIImplItemRef(_, &ast::TypeImplItem(ref ti)) => ti.id,
+        e::IIImplItemRef(_, &ast::ConstImplItem(ref ac)) => ac.id,
     };
     debug!("> Encoding inlined item: {} ({})",
            ecx.tcx.map.path_to_string(id),
@@ -155,12 +157,14 @@ pub fn decode_inlined_item<'tcx>(cdata: &cstore::crate_metadata,
                     ast::ProvidedMethod(ref m) => m.pe_ident(),
                     ast::RequiredMethod(ref ty_m) => ty_m.ident,
                     ast::TypeTraitItem(ref ti) => ti.ty_param.ident,
+                    ast::ConstTraitItem(ref ac) => ac.ident,
                 }
             },
             ast::IIImplItem(_, ref m) => {
                 match *m {
                     ast::MethodImplItem(ref m) => m.pe_ident(),
                     ast::TypeImplItem(ref ti) => ti.ident,
+                    ast::ConstImplItem(ref ac) => ac.ident,
                 }
             }
         };
@@ -383,6 +387,12 @@ fn simplify_ast(ii: e::InlinedItemRef) -> ast::InlinedItem {
                             (**associated_type).clone(),
                             &mut fld)))
                 }
+                ast::ConstTraitItem(ref associated_const) => {
+                    ast::ConstTraitItem(
+                        P(fold::noop_fold_associated_const(
+                            (**associated_const).clone(),
+                            &mut fld)))
+                }
             })
         }
         e::IIImplItemRef(d, m) => {
@@ -397,6 +407,11 @@ fn simplify_ast(ii: e::InlinedItemRef) -> ast::InlinedItem {
                     ast::TypeImplItem(
                         P(fold::noop_fold_typedef((**td).clone(), &mut fld)))
                 }
+                ast::ConstImplItem(ref ac) => {
+                    ast::ConstImplItem(
+                        P(fold::noop_fold_associated_const((**ac).clone(),
+                                                           &mut fld)))
+                }
             })
         }
         e::IIForeignRef(i) => {
@@ -437,6 +452,9 @@ impl tr for def::Def {
           def::DefForeignMod(did) => { def::DefForeignMod(did.tr(dcx)) }
           def::DefStatic(did, m) => { def::DefStatic(did.tr(dcx), m) }
           def::DefConst(did) => { def::DefConst(did.tr(dcx)) }
+          def::DefAssociatedConst(did, p) => {
+            def::DefAssociatedConst(did.tr(dcx), p.map(|did2| did2.tr(dcx)))
+          }
           def::DefLocal(nid) => { def::DefLocal(dcx.tr_id(nid)) }
           def::DefVariant(e_did, v_did, is_s) => {
             def::DefVariant(e_did.tr(dcx), v_did.tr(dcx), is_s)
@@ -26,7 +26,7 @@ use middle::mem_categorization as mc;
 use middle::region;
 use middle::ty;
 use syntax::ast;
-use syntax::codemap::Span;
+use syntax::codemap::{BytePos, MultiSpan, Span};
 use util::ppaux::Repr;
 
 use std::rc::Rc;
@@ -454,54 +454,36 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
                 format!("`{}`", ol)
             };
 
-            match (new_loan.kind, old_loan.kind) {
+            let msg = match (new_loan.kind, old_loan.kind) {
                 (ty::MutBorrow, ty::MutBorrow) => {
-                    self.bccx.span_err(
-                        new_loan.span,
-                        format!("cannot borrow `{}`{} as mutable \
-                                more than once at a time",
-                                nl, new_loan_msg).as_slice())
+                    format!("cannot borrow `{}`{} as mutable \
+                            more than once at a time",
+                            nl, new_loan_msg)
                 }
 
                 (ty::UniqueImmBorrow, _) => {
-                    self.bccx.span_err(
-                        new_loan.span,
-                        format!("closure requires unique access to `{}` \
-                                but {} is already borrowed{}",
-                                nl, ol_pronoun, old_loan_msg).as_slice());
+                    format!("closure requires unique access to `{}` \
+                            but {} is already borrowed{}",
+                            nl, ol_pronoun, old_loan_msg)
                 }
 
                 (_, ty::UniqueImmBorrow) => {
-                    self.bccx.span_err(
-                        new_loan.span,
-                        format!("cannot borrow `{}`{} as {} because \
-                                previous closure requires unique access",
-                                nl, new_loan_msg, new_loan.kind.to_user_str()).as_slice());
+                    format!("cannot borrow `{}`{} as {} because \
+                            previous closure requires unique access",
+                            nl, new_loan_msg, new_loan.kind.to_user_str())
                 }
 
                 (_, _) => {
-                    self.bccx.span_err(
-                        new_loan.span,
-                        format!("cannot borrow `{}`{} as {} because \
-                                {} is also borrowed as {}{}",
-                                nl,
-                                new_loan_msg,
-                                new_loan.kind.to_user_str(),
-                                ol_pronoun,
-                                old_loan.kind.to_user_str(),
-                                old_loan_msg).as_slice());
+                    format!("cannot borrow `{}`{} as {} because \
+                            {} is also borrowed as {}{}",
+                            nl,
+                            new_loan_msg,
+                            new_loan.kind.to_user_str(),
+                            ol_pronoun,
+                            old_loan.kind.to_user_str(),
+                            old_loan_msg)
                 }
-            }
-
-            match new_loan.cause {
-                euv::ClosureCapture(span) => {
-                    self.bccx.span_note(
-                        span,
-                        format!("borrow occurs due to use of `{}` in closure",
-                                nl).as_slice());
-                }
-                _ => { }
-            }
+            };
 
             let rule_summary = match old_loan.kind {
                 ty::MutBorrow => {
@@ -545,13 +527,31 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
                 }
             };
 
-            self.bccx.span_note(
-                old_loan.span,
-                format!("{}; {}", borrow_summary, rule_summary).as_slice());
-
+            // Both borrows and the end of the first one are labeled in the
+            // snippet of the error, which keeps them together when they're
+            // close, as they usually are.
             let old_loan_span = self.tcx().map.span(old_loan.kill_scope.node_id());
-            self.bccx.span_end_note(old_loan_span,
-                                    "previous borrow ends here");
+            let old_loan_end = Span {
+                lo: old_loan_span.hi - BytePos(1),
+                hi: old_loan_span.hi,
+                expn_id: old_loan_span.expn_id
+            };
+            let mut msp = MultiSpan::new(new_loan.span);
+            msp.push_label(old_loan.span, borrow_summary);
+            msp.push_label(old_loan_end, "previous borrow ends here".to_string());
+            self.bccx.span_err_multi(&msp, msg.as_slice());
+
+            match new_loan.cause {
+                euv::ClosureCapture(span) => {
+                    self.bccx.span_note(
+                        span,
+                        format!("borrow occurs due to use of `{}` in closure",
+                                nl).as_slice());
+                }
+                _ => { }
+            }
+
+            self.bccx.fileline_note(old_loan.span, rule_summary.as_slice());
 
             return false;
         }
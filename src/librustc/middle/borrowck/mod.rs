@@ -34,7 +34,7 @@ use syntax::ast;
 use syntax::ast_map;
 use syntax::ast_map::blocks::{FnLikeNode, FnParts};
 use syntax::ast_util;
-use syntax::codemap::Span;
+use syntax::codemap::{Span, MultiSpan};
 use syntax::parse::token;
 use syntax::visit;
 use syntax::visit::{Visitor, FnKind};
@@ -708,6 +708,10 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
         self.tcx.sess.span_err(s, m);
     }
 
+    pub fn span_err_multi(&self, msp: &MultiSpan, m: &str) {
+        self.tcx.sess.span_err_multi(msp, m);
+    }
+
     pub fn span_note(&self, s: Span, m: &str) {
         self.tcx.sess.span_note(s, m);
     }
@@ -716,6 +720,10 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
         self.tcx.sess.span_end_note(s, m);
     }
 
+    pub fn fileline_note(&self, s: Span, m: &str) {
+        self.tcx.sess.fileline_note(s, m);
+    }
+
     pub fn span_help(&self, s: Span, m: &str) {
         self.tcx.sess.span_help(s, m);
     }
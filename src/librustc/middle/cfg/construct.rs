@@ -259,6 +259,12 @@ impl<'a, 'tcx> CFGBuilder<'a, 'tcx> {
                 expr_exit
             }
 
+            ast::ExprTry(..) => {
+                self.tcx.sess.span_bug(expr.span, "non-desugared ExprTry");
+            }
+            ast::ExprInclusiveRange(..) => {
+                self.tcx.sess.span_bug(expr.span, "non-desugared ExprInclusiveRange");
+            }
             ast::ExprWhileLet(..) => {
                 self.tcx.sess.span_bug(expr.span, "non-desugared ExprWhileLet");
             }
@@ -399,9 +405,10 @@ impl<'a, 'tcx> CFGBuilder<'a, 'tcx> {
                 self.add_node(ast::DUMMY_NODE_ID, &[])
             }
 
-            ast::ExprBreak(label) => {
+            ast::ExprBreak(label, ref v) => {
                 let loop_scope = self.find_scope(expr, label);
-                let b = self.add_node(expr.id, &[pred]);
+                let v_exit = self.opt_expr(v, pred);
+                let b = self.add_node(expr.id, &[v_exit]);
                 self.add_exiting_edge(expr, b,
                                       loop_scope, loop_scope.break_index);
                 self.add_node(ast::DUMMY_NODE_ID, &[])
@@ -472,6 +479,7 @@ impl<'a, 'tcx> CFGBuilder<'a, 'tcx> {
 
             ast::ExprAddrOf(_, ref e) |
             ast::ExprCast(ref e, _) |
+            ast::ExprType(ref e, _) |
             ast::ExprUnary(_, ref e) |
             ast::ExprParen(ref e) |
             ast::ExprField(ref e, _) |
@@ -484,15 +492,22 @@ impl<'a, 'tcx> CFGBuilder<'a, 'tcx> {
                 let outputs = inline_asm.outputs.iter();
                 let post_inputs = self.exprs(inputs.map(|a| {
                     debug!("cfg::construct InlineAsm id:{} input:{}", expr.id, a);
-                    let &(_, ref expr) = a;
+                    let &(_, ref expr, _) = a;
                     &**expr
                 }), pred);
                 let post_outputs = self.exprs(outputs.map(|a| {
                     debug!("cfg::construct InlineAsm id:{} output:{}", expr.id, a);
-                    let &(_, ref expr, _) = a;
+                    let &(_, ref expr, _, _) = a;
                     &**expr
                 }), post_inputs);
-                self.add_node(expr.id, &[post_outputs])
+                let asm_exit = self.add_node(expr.id, &[post_outputs]);
+                if inline_asm.noreturn {
+                    // Like `return`, nothing after the asm is reached
+                    self.add_returning_edge(expr, asm_exit);
+                    self.add_node(ast::DUMMY_NODE_ID, &[])
+                } else {
+                    asm_exit
+                }
             }
 
             ast::ExprMac(..) |
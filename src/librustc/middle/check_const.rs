@@ -56,7 +56,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for CheckCrateVisitor<'a, 'tcx> {
 pub fn check_crate(tcx: &ty::ctxt) {
     visit::walk_crate(&mut CheckCrateVisitor { tcx: tcx, in_const: false },
                       tcx.map.krate());
-    tcx.sess.abort_if_errors();
+    tcx.sess.abort_if_errors_unless_keep_going();
 }
 
 fn check_item(v: &mut CheckCrateVisitor, it: &ast::Item) {
@@ -146,6 +146,7 @@ fn check_expr(v: &mut CheckCrateVisitor, e: &ast::Expr) -> bool {
             match v.tcx.def_map.borrow().get(&e.id) {
                 Some(&DefStatic(..)) |
                 Some(&DefConst(..)) |
+                Some(&DefAssociatedConst(..)) |
                 Some(&DefFn(..)) |
                 Some(&DefVariant(_, _, _)) |
                 Some(&DefStruct(_)) => { }
@@ -205,6 +206,7 @@ fn check_expr(v: &mut CheckCrateVisitor, e: &ast::Expr) -> bool {
         ast::ExprVec(_) |
         ast::ExprAddrOf(ast::MutImmutable, _) |
         ast::ExprParen(..) |
+        ast::ExprType(..) |
         ast::ExprField(..) |
         ast::ExprTupField(..) |
         ast::ExprIndex(..) |
@@ -52,7 +52,10 @@ impl<'a, 'v> Visitor<'v> for CheckLoopVisitor<'a> {
             ast::ExprProc(_, ref b) => {
                 self.with_context(Closure, |v| v.visit_block(&**b));
             }
-            ast::ExprBreak(_) => self.require_loop("break", e.span),
+            ast::ExprBreak(_, _) => {
+                self.require_loop("break", e.span);
+                visit::walk_expr(self, e)
+            }
             ast::ExprAgain(_) => self.require_loop("continue", e.span),
             _ => visit::walk_expr(self, e)
         }
@@ -146,7 +146,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for MatchCheckCtxt<'a, 'tcx> {
 
 pub fn check_crate(tcx: &ty::ctxt) {
     visit::walk_crate(&mut MatchCheckCtxt { tcx: tcx }, tcx.map.krate());
-    tcx.sess.abort_if_errors();
+    tcx.sess.abort_if_errors_unless_keep_going();
 }
 
 fn check_expr(cx: &mut MatchCheckCtxt, ex: &ast::Expr) {
@@ -13,10 +13,11 @@
 
 use session::Session;
 use middle::resolve;
-use middle::def::{DefStatic, DefConst};
+use middle::def::{DefStatic, DefConst, DefAssociatedConst, FromImpl};
 
 use syntax::ast;
 use syntax::{ast_util, ast_map};
+use syntax::codemap::Span;
 use syntax::visit::Visitor;
 use syntax::visit;
 
@@ -52,12 +53,21 @@ fn check_item(v: &mut CheckCrateVisitor, it: &ast::Item) {
             check_item_recursion(v.sess, v.ast_map, v.def_map, it);
             visit::walk_expr(v, &**ex)
         },
+        ast::ItemImpl(_, _, _, ref impl_items) => {
+            for impl_item in impl_items.iter() {
+                if let ast::ConstImplItem(ref associated_const) = *impl_item {
+                    check_associated_const_recursion(v.sess, v.ast_map, v.def_map,
+                                                     &**associated_const);
+                }
+            }
+            visit::walk_item(v, it)
+        },
         _ => visit::walk_item(v, it)
     }
 }
 
 struct CheckItemRecursionVisitor<'a, 'ast: 'a> {
-    root_it: &'a ast::Item,
+    root_span: Span,
     sess: &'a Session,
     ast_map: &'a ast_map::Map<'ast>,
     def_map: &'a resolve::DefMap,
@@ -72,7 +82,7 @@ pub fn check_item_recursion<'a>(sess: &'a Session,
                                 it: &'a ast::Item) {
 
     let mut visitor = CheckItemRecursionVisitor {
-        root_it: it,
+        root_span: it.span,
         sess: sess,
         ast_map: ast_map,
         def_map: def_map,
@@ -81,10 +91,38 @@ pub fn check_item_recursion<'a>(sess: &'a Session,
     visitor.visit_item(it);
 }
 
+// Same as `check_item_recursion`, for the value of an inherent associated
+// constant.
+fn check_associated_const_recursion<'a>(sess: &'a Session,
+                                        ast_map: &'a ast_map::Map,
+                                        def_map: &'a resolve::DefMap,
+                                        associated_const: &ast::AssociatedConst) {
+    let mut visitor = CheckItemRecursionVisitor {
+        root_span: associated_const.span,
+        sess: sess,
+        ast_map: ast_map,
+        def_map: def_map,
+        idstack: Vec::new()
+    };
+    visitor.visit_associated_const(associated_const);
+}
+
+impl<'a, 'ast> CheckItemRecursionVisitor<'a, 'ast> {
+    fn visit_associated_const(&mut self, associated_const: &ast::AssociatedConst) {
+        if self.idstack.iter().any(|x| x == &(associated_const.id)) {
+            self.sess.span_err(self.root_span, "recursive constant");
+            return;
+        }
+        self.idstack.push(associated_const.id);
+        visit::walk_associated_const(self, associated_const);
+        self.idstack.pop();
+    }
+}
+
 impl<'a, 'ast, 'v> Visitor<'v> for CheckItemRecursionVisitor<'a, 'ast> {
     fn visit_item(&mut self, it: &ast::Item) {
         if self.idstack.iter().any(|x| x == &(it.id)) {
-            self.sess.span_err(self.root_it.span, "recursive constant");
+            self.sess.span_err(self.root_span, "recursive constant");
             return;
         }
         self.idstack.push(it.id);
@@ -111,6 +149,15 @@ impl<'a, 'ast, 'v> Visitor<'v> for CheckItemRecursionVisitor<'a, 'ast> {
                           },
                         }
                     }
+                    Some(&DefAssociatedConst(def_id, FromImpl(_))) if
+                            ast_util::is_local(def_id) => {
+                        if let ast_map::NodeImplItem(impl_item) =
+                                self.ast_map.get(def_id.node) {
+                            if let ast::ConstImplItem(ref associated_const) = *impl_item {
+                                self.visit_associated_const(&**associated_const);
+                            }
+                        }
+                    }
                     _ => ()
                 }
             },
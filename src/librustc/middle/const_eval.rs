@@ -22,11 +22,15 @@ use middle::typeck::{astconv, check};
 use util::nodemap::DefIdMap;
 
 use syntax::ast::{mod, Expr};
+use syntax::ast_util::is_shift_binop;
+use syntax::codemap::{Span, DUMMY_SP};
 use syntax::parse::token::InternedString;
 use syntax::ptr::P;
 use syntax::visit::{mod, Visitor};
 use syntax::{ast_map, ast_util, codemap};
 
+use std::i64;
+use std::num::{Int, ToPrimitive};
 use std::rc::Rc;
 use std::collections::hash_map::Vacant;
 
@@ -90,6 +94,12 @@ fn lookup_const<'a>(tcx: &'a ty::ctxt, e: &Expr) -> Option<&'a Expr> {
         Some(def::DefConst(def_id)) => {
             lookup_const_by_id(tcx, def_id)
         }
+        // The value of a constant declared in a trait depends on the
+        // implementation selected for `Self`, so only inherent associated
+        // constants can be looked up here.
+        Some(def::DefAssociatedConst(def_id, def::FromImpl(_))) => {
+            lookup_const_by_id(tcx, def_id)
+        }
         Some(def::DefVariant(enum_def, variant_def, _)) => {
             lookup_variant_by_id(tcx, enum_def, variant_def)
         }
@@ -160,6 +170,12 @@ pub fn lookup_const_by_id<'a>(tcx: &'a ty::ctxt, def_id: ast::DefId)
                 }
                 _ => None
             },
+            Some(ast_map::NodeImplItem(ii)) => match *ii {
+                ast::ConstImplItem(ref associated_const) => {
+                    associated_const.expr.as_ref().map(|e| &**e)
+                }
+                _ => None
+            },
             Some(_) => None
         }
     } else {
@@ -176,6 +192,12 @@ pub fn lookup_const_by_id<'a>(tcx: &'a ty::ctxt, def_id: ast::DefId)
                 ast::ItemConst(_, ref const_expr) => Some(const_expr.id),
                 _ => None
             },
+            csearch::found(&ast::IIImplItem(_, ref ii)) => match *ii {
+                ast::ConstImplItem(ref associated_const) => {
+                    associated_const.expr.as_ref().map(|e| e.id)
+                }
+                _ => None
+            },
             _ => None
         };
         tcx.extern_const_statics.borrow_mut().insert(def_id,
@@ -204,7 +226,8 @@ impl<'a, 'tcx> ConstEvalVisitor<'a, 'tcx> {
                 }
             }
 
-            ast::ExprUnary(_, ref inner) | ast::ExprParen(ref inner) =>
+            ast::ExprUnary(_, ref inner) | ast::ExprParen(ref inner) |
+            ast::ExprType(ref inner, _) =>
                 self.classify(&**inner),
 
             ast::ExprBinary(_, ref a, ref b) =>
@@ -311,6 +334,22 @@ pub enum const_val {
     const_bool(bool)
 }
 
+/// An error raised while evaluating a constant expression. `span` points at
+/// the subexpression that could not be evaluated, which may be nested inside
+/// the expression that was passed in, or inside the definition of a constant
+/// it refers to.
+#[deriving(Clone, PartialEq)]
+pub struct ConstEvalErr {
+    pub span: Span,
+    pub msg: String,
+}
+
+macro_rules! signal(
+    ($e:expr, $msg:expr) => (
+        return Err(ConstEvalErr { span: $e.span, msg: $msg.to_string() })
+    )
+)
+
 pub fn const_expr_to_pat(tcx: &ty::ctxt, expr: &Expr) -> P<ast::Pat> {
     let pat = match expr.node {
         ast::ExprTup(ref exprs) =>
@@ -371,120 +410,193 @@ pub fn const_expr_to_pat(tcx: &ty::ctxt, expr: &Expr) -> P<ast::Pat> {
 pub fn eval_const_expr(tcx: &ty::ctxt, e: &Expr) -> const_val {
     match eval_const_expr_partial(tcx, e) {
         Ok(r) => r,
-        Err(s) => tcx.sess.span_fatal(e.span, s.as_slice())
+        Err(err) => tcx.sess.span_fatal(err.span, err.msg.as_slice())
+    }
+}
+
+// Whether `e` is an integer literal without a suffix, possibly wrapped in
+// parentheses. Such a literal takes its type from the other operand of a
+// binary operator, so it may be used where an unsigned value is expected.
+fn is_unsuffixed_int_lit(e: &Expr) -> bool {
+    match e.node {
+        ast::ExprParen(ref inner) => is_unsuffixed_int_lit(&**inner),
+        ast::ExprLit(ref lit) => match lit.node {
+            ast::LitInt(_, ast::UnsuffixedIntLit(_)) => true,
+            _ => false
+        },
+        _ => false
+    }
+}
+
+/// The width in bits of the integral type of `e`, or 64 if its type isn't
+/// known yet or isn't integral.
+fn expr_int_bits(tcx: &ty::ctxt, e: &Expr) -> uint {
+    let ty = match ty::expr_ty_opt(tcx, e) {
+        Some(ty) => ty,
+        None => return 64
+    };
+    match ty.sty {
+        ty::ty_int(ast::TyI) => int_ty_bits(tcx.sess.target.int_type),
+        ty::ty_int(t) => int_ty_bits(t),
+        ty::ty_uint(ast::TyU) => uint_ty_bits(tcx.sess.target.uint_type),
+        ty::ty_uint(t) => uint_ty_bits(t),
+        _ => 64
+    }
+}
+
+fn int_ty_bits(t: ast::IntTy) -> uint {
+    match t {
+        ast::TyI8 => 8,
+        ast::TyI16 => 16,
+        ast::TyI32 => 32,
+        ast::TyI | ast::TyI64 => 64
+    }
+}
+
+fn uint_ty_bits(t: ast::UintTy) -> uint {
+    match t {
+        ast::TyU8 => 8,
+        ast::TyU16 => 16,
+        ast::TyU32 => 32,
+        ast::TyU | ast::TyU64 => 64
     }
 }
 
-pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, String> {
+/// Truncates `v` to its low `bits` bits, sign- or zero-extending it back to
+/// 64 according to `T`. A value of a `bits` wide type is unchanged by it.
+fn truncate<T: Int>(v: T, bits: uint) -> T {
+    let unused = 64 - bits;
+    (v << unused) >> unused
+}
+
+/// Evaluates `a op b` for operands of a `bits` wide integral type, held in
+/// 64 bits as `T`.
+fn eval_int_binop<T: Int>(op: ast::BinOp, a: T, b: T, bits: uint, wrap: fn(T) -> const_val)
+                          -> Result<const_val, String> {
     fn fromb(b: bool) -> Result<const_val, String> { Ok(const_int(b as i64)) }
+    fn checked<T: Int>(v: Option<T>, bits: uint, wrap: fn(T) -> const_val, what: &str)
+                       -> Result<const_val, String> {
+        match v {
+            Some(v) if truncate(v, bits) == v => Ok(wrap(v)),
+            _ => Err(format!("attempted to {} with overflow", what))
+        }
+    }
+    // Shifting by the full width of the value or more is an overflow, and
+    // so is shifting by a negative amount. The bits shifted out are lost.
+    let shift = |what: &str| {
+        match b.to_u64() {
+            Some(n) if n < bits as u64 => Ok(n as uint),
+            _ => Err(format!("attempted to shift {} with overflow", what))
+        }
+    };
+    match op {
+        ast::BiAdd => checked(a.checked_add(b), bits, wrap, "add"),
+        ast::BiSub => checked(a.checked_sub(b), bits, wrap, "subtract"),
+        ast::BiMul => checked(a.checked_mul(b), bits, wrap, "multiply"),
+        ast::BiDiv if b == Int::zero() => {
+            Err("attempted to divide by zero".to_string())
+        }
+        ast::BiDiv => checked(a.checked_div(b), bits, wrap, "divide"),
+        ast::BiRem if b == Int::zero() => {
+            Err("attempted remainder with a divisor of \
+                 zero".to_string())
+        }
+        ast::BiRem => match a.checked_div(b) {
+            Some(q) if truncate(q, bits) == q => Ok(wrap(a % b)),
+            _ => Err("attempted remainder with overflow".to_string())
+        },
+        ast::BiAnd | ast::BiBitAnd => Ok(wrap(a & b)),
+        ast::BiOr | ast::BiBitOr => Ok(wrap(a | b)),
+        ast::BiBitXor => Ok(wrap(a ^ b)),
+        ast::BiShl => shift("left").map(|n| wrap(truncate(a << n, bits))),
+        ast::BiShr => shift("right").map(|n| wrap(a >> n)),
+        ast::BiEq => fromb(a == b),
+        ast::BiLt => fromb(a < b),
+        ast::BiLe => fromb(a <= b),
+        ast::BiNe => fromb(a != b),
+        ast::BiGe => fromb(a >= b),
+        ast::BiGt => fromb(a > b)
+    }
+}
+
+pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, ConstEvalErr> {
+    fn fromb(b: bool) -> Result<const_val, ConstEvalErr> { Ok(const_int(b as i64)) }
     match e.node {
       ast::ExprUnary(ast::UnNeg, ref inner) => {
-        match eval_const_expr_partial(tcx, &**inner) {
-          Ok(const_float(f)) => Ok(const_float(-f)),
-          Ok(const_int(i)) => Ok(const_int(-i)),
-          Ok(const_uint(i)) => Ok(const_uint(-i)),
-          Ok(const_str(_)) => Err("negate on string".to_string()),
-          Ok(const_bool(_)) => Err("negate on boolean".to_string()),
-          ref err => ((*err).clone())
+        match try!(eval_const_expr_partial(tcx, &**inner)) {
+          const_float(f) => Ok(const_float(-f)),
+          const_int(i) if i == i64::MIN || truncate(-i, expr_int_bits(tcx, e)) != -i => {
+            signal!(e, "attempted to negate with overflow")
+          }
+          const_int(i) => Ok(const_int(-i)),
+          const_uint(i) => Ok(const_uint(-i)),
+          const_str(_) => signal!(e, "negate on string"),
+          const_bool(_) => signal!(e, "negate on boolean"),
+          const_binary(_) => signal!(e, "negate on binary literal")
         }
       }
       ast::ExprUnary(ast::UnNot, ref inner) => {
-        match eval_const_expr_partial(tcx, &**inner) {
-          Ok(const_int(i)) => Ok(const_int(!i)),
-          Ok(const_uint(i)) => Ok(const_uint(!i)),
-          Ok(const_bool(b)) => Ok(const_bool(!b)),
-          _ => Err("not on float or string".to_string())
+        match try!(eval_const_expr_partial(tcx, &**inner)) {
+          const_int(i) => Ok(const_int(!i)),
+          // The bits above the width of the type are set by `!` too
+          const_uint(i) => Ok(const_uint(truncate(!i, expr_int_bits(tcx, e)))),
+          const_bool(b) => Ok(const_bool(!b)),
+          _ => signal!(e, "not on float or string")
         }
       }
       ast::ExprBinary(op, ref a, ref b) => {
-        match (eval_const_expr_partial(tcx, &**a),
-               eval_const_expr_partial(tcx, &**b)) {
-          (Ok(const_float(a)), Ok(const_float(b))) => {
+        let a_val = try!(eval_const_expr_partial(tcx, &**a));
+        let b_val = try!(eval_const_expr_partial(tcx, &**b));
+        // Unsuffixed literals evaluate to signed values; let them take on
+        // the signedness of the other operand, as they would in typeck.
+        let (a_val, b_val) = match (a_val, b_val) {
+          (const_int(x), const_uint(y))
+                  if !is_shift_binop(op) && x >= 0 && is_unsuffixed_int_lit(&**a) => {
+            (const_uint(x as u64), const_uint(y))
+          }
+          (const_uint(x), const_int(y))
+                  if !is_shift_binop(op) && y >= 0 && is_unsuffixed_int_lit(&**b) => {
+            (const_uint(x), const_uint(y as u64))
+          }
+          vals => vals
+        };
+        // Overflow is checked at the width of the operands' type, which for
+        // shifts is that of the left-hand side.
+        let bits = expr_int_bits(tcx, &**a);
+        let result = match (a_val, b_val) {
+          (const_float(a), const_float(b)) => {
             match op {
               ast::BiAdd => Ok(const_float(a + b)),
               ast::BiSub => Ok(const_float(a - b)),
               ast::BiMul => Ok(const_float(a * b)),
               ast::BiDiv => Ok(const_float(a / b)),
               ast::BiRem => Ok(const_float(a % b)),
-              ast::BiEq => fromb(a == b),
-              ast::BiLt => fromb(a < b),
-              ast::BiLe => fromb(a <= b),
-              ast::BiNe => fromb(a != b),
-              ast::BiGe => fromb(a >= b),
-              ast::BiGt => fromb(a > b),
+              ast::BiEq => return fromb(a == b),
+              ast::BiLt => return fromb(a < b),
+              ast::BiLe => return fromb(a <= b),
+              ast::BiNe => return fromb(a != b),
+              ast::BiGe => return fromb(a >= b),
+              ast::BiGt => return fromb(a > b),
               _ => Err("can't do this op on floats".to_string())
             }
           }
-          (Ok(const_int(a)), Ok(const_int(b))) => {
-            match op {
-              ast::BiAdd => Ok(const_int(a + b)),
-              ast::BiSub => Ok(const_int(a - b)),
-              ast::BiMul => Ok(const_int(a * b)),
-              ast::BiDiv if b == 0 => {
-                  Err("attempted to divide by zero".to_string())
-              }
-              ast::BiDiv => Ok(const_int(a / b)),
-              ast::BiRem if b == 0 => {
-                  Err("attempted remainder with a divisor of \
-                       zero".to_string())
-              }
-              ast::BiRem => Ok(const_int(a % b)),
-              ast::BiAnd | ast::BiBitAnd => Ok(const_int(a & b)),
-              ast::BiOr | ast::BiBitOr => Ok(const_int(a | b)),
-              ast::BiBitXor => Ok(const_int(a ^ b)),
-              ast::BiShl => Ok(const_int(a << b as uint)),
-              ast::BiShr => Ok(const_int(a >> b as uint)),
-              ast::BiEq => fromb(a == b),
-              ast::BiLt => fromb(a < b),
-              ast::BiLe => fromb(a <= b),
-              ast::BiNe => fromb(a != b),
-              ast::BiGe => fromb(a >= b),
-              ast::BiGt => fromb(a > b)
-            }
+          (const_int(a), const_int(b)) => eval_int_binop(op, a, b, bits, const_int),
+          (const_uint(a), const_uint(b)) => eval_int_binop(op, a, b, bits, const_uint),
+          // shifts can have any integral type as their rhs; a value that
+          // doesn't fit the lhs type is out of range for the shift anyway
+          (const_int(a), const_uint(b)) if is_shift_binop(op) => {
+            eval_int_binop(op, a, b as i64, bits, const_int)
           }
-          (Ok(const_uint(a)), Ok(const_uint(b))) => {
-            match op {
-              ast::BiAdd => Ok(const_uint(a + b)),
-              ast::BiSub => Ok(const_uint(a - b)),
-              ast::BiMul => Ok(const_uint(a * b)),
-              ast::BiDiv if b == 0 => {
-                  Err("attempted to divide by zero".to_string())
-              }
-              ast::BiDiv => Ok(const_uint(a / b)),
-              ast::BiRem if b == 0 => {
-                  Err("attempted remainder with a divisor of \
-                       zero".to_string())
-              }
-              ast::BiRem => Ok(const_uint(a % b)),
-              ast::BiAnd | ast::BiBitAnd => Ok(const_uint(a & b)),
-              ast::BiOr | ast::BiBitOr => Ok(const_uint(a | b)),
-              ast::BiBitXor => Ok(const_uint(a ^ b)),
-              ast::BiShl => Ok(const_uint(a << b as uint)),
-              ast::BiShr => Ok(const_uint(a >> b as uint)),
-              ast::BiEq => fromb(a == b),
-              ast::BiLt => fromb(a < b),
-              ast::BiLe => fromb(a <= b),
-              ast::BiNe => fromb(a != b),
-              ast::BiGe => fromb(a >= b),
-              ast::BiGt => fromb(a > b),
-            }
+          (const_uint(a), const_int(b)) if is_shift_binop(op) => {
+            eval_int_binop(op, a, b as u64, bits, const_uint)
           }
-          // shifts can have any integral type as their rhs
-          (Ok(const_int(a)), Ok(const_uint(b))) => {
-            match op {
-              ast::BiShl => Ok(const_int(a << b as uint)),
-              ast::BiShr => Ok(const_int(a >> b as uint)),
-              _ => Err("can't do this op on an int and uint".to_string())
-            }
+          (const_int(_), const_uint(_)) => {
+            Err("can't do this op on an int and uint".to_string())
           }
-          (Ok(const_uint(a)), Ok(const_int(b))) => {
-            match op {
-              ast::BiShl => Ok(const_uint(a << b as uint)),
-              ast::BiShr => Ok(const_uint(a >> b as uint)),
-              _ => Err("can't do this op on a uint and int".to_string())
-            }
+          (const_uint(_), const_int(_)) => {
+            Err("can't do this op on a uint and int".to_string())
           }
-          (Ok(const_bool(a)), Ok(const_bool(b))) => {
+          (const_bool(a), const_bool(b)) => {
             Ok(const_bool(match op {
               ast::BiAnd => a && b,
               ast::BiOr => a || b,
@@ -493,11 +605,12 @@ pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, St
               ast::BiBitOr => a | b,
               ast::BiEq => a == b,
               ast::BiNe => a != b,
-              _ => return Err("can't do this op on bools".to_string())
+              _ => signal!(e, "can't do this op on bools")
              }))
           }
           _ => Err("bad operands for binary".to_string())
-        }
+        };
+        result.map_err(|msg| ConstEvalErr { span: e.span, msg: msg })
       }
       ast::ExprCast(ref base, ref target_ty) => {
         // This tends to get called w/o the type actually having been
@@ -533,8 +646,8 @@ pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, St
             })
         )
 
-        eval_const_expr_partial(tcx, &**base)
-            .and_then(|val| define_casts!(val, {
+        let val = try!(eval_const_expr_partial(tcx, &**base));
+        define_casts!(val, {
                 ty::ty_int(ast::TyI) => (int, const_int, i64),
                 ty::ty_int(ast::TyI8) => (i8, const_int, i64),
                 ty::ty_int(ast::TyI16) => (i16, const_int, i64),
@@ -547,16 +660,25 @@ pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, St
                 ty::ty_uint(ast::TyU64) => (u64, const_uint, u64),
                 ty::ty_float(ast::TyF32) => (f32, const_float, f64),
                 ty::ty_float(ast::TyF64) => (f64, const_float, f64)
-            }))
+        }).map_err(|msg| ConstEvalErr { span: e.span, msg: msg })
       }
       ast::ExprPath(_) => {
           match lookup_const(tcx, e) {
-              Some(actual_e) => eval_const_expr_partial(tcx, &*actual_e),
-              None => Err("non-constant path in constant expr".to_string())
+              // Constants inlined from other crates have no useful spans, so
+              // errors inside them are reported at the path that used them.
+              Some(actual_e) => eval_const_expr_partial(tcx, &*actual_e).map_err(|err| {
+                  if err.span == DUMMY_SP {
+                      ConstEvalErr { span: e.span, msg: err.msg }
+                  } else {
+                      err
+                  }
+              }),
+              None => signal!(e, "non-constant path in constant expr")
           }
       }
       ast::ExprLit(ref lit) => Ok(lit_to_const(&**lit)),
       ast::ExprParen(ref e)     => eval_const_expr_partial(tcx, &**e),
+      ast::ExprType(ref e, _)   => eval_const_expr_partial(tcx, &**e),
       ast::ExprBlock(ref block) => {
         match block.expr {
             Some(ref expr) => eval_const_expr_partial(tcx, &**expr),
@@ -570,11 +692,11 @@ pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, St
             if fields.len() > index.node {
                 return eval_const_expr_partial(tcx, &*fields[index.node])
             } else {
-                return Err("tuple index out of bounds".to_string())
+                signal!(e, "tuple index out of bounds")
             }
         }
 
-        Err("non-constant struct in constant expr".to_string())
+        signal!(e, "non-constant struct in constant expr")
       }
       ast::ExprField(ref base, field_name) => {
         // Get the base expression if it is a struct and it is constant
@@ -585,13 +707,13 @@ pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, St
                                            f.ident.node.as_str() == field_name.node.as_str()) {
                 return eval_const_expr_partial(tcx, &*f.expr)
             } else {
-                return Err("nonexistent struct field".to_string())
+                signal!(e, "nonexistent struct field")
             }
         }
 
-        Err("non-constant struct in constant expr".to_string())
+        signal!(e, "non-constant struct in constant expr")
       }
-      _ => Err("unsupported constant expr".to_string())
+      _ => signal!(e, "unsupported constant expr")
     }
 }
 
@@ -19,6 +19,7 @@ pub use self::EntryOrExit::*;
 use middle::cfg;
 use middle::cfg::CFGIndex;
 use middle::ty;
+use std::default::Default;
 use std::io;
 use std::uint;
 use syntax::ast;
@@ -460,8 +461,8 @@ impl<'a, 'tcx, O:DataFlowOperator+Clone+'static> DataFlowContext<'a, 'tcx, O> {
 
     fn pretty_print_to(&self, wr: Box<io::Writer+'static>,
                        blk: &ast::Block) -> io::IoResult<()> {
-        let mut ps = pprust::rust_printer_annotated(wr, self);
-        try!(ps.cbox(pprust::indent_unit));
+        let mut ps = pprust::rust_printer_annotated(wr, self, Default::default());
+        try!(ps.cbox_indent());
         try!(ps.ibox(0u));
         try!(ps.print_block(blk));
         pp::eof(&mut ps.s)
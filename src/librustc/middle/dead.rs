@@ -72,7 +72,7 @@ impl<'a, 'tcx> MarkSymbolVisitor<'a, 'tcx> {
     fn lookup_and_handle_definition(&mut self, id: &ast::NodeId) {
         self.tcx.def_map.borrow().get(id).map(|def| {
             match def {
-                &def::DefConst(_) => {
+                &def::DefConst(_) | &def::DefAssociatedConst(..) => {
                     self.check_def_id(def.def_id())
                 }
                 _ if self.ignore_non_const_paths => (),
@@ -121,6 +121,9 @@ impl<'a, 'tcx> MarkSymbolVisitor<'a, 'tcx> {
                             ty::TypeTraitItem(typedef) => {
                                 self.check_def_id(typedef.def_id);
                             }
+                            ty::ConstTraitItem(associated_const) => {
+                                self.check_def_id(associated_const.def_id);
+                            }
                         }
                     }
                 }
@@ -237,6 +240,9 @@ impl<'a, 'tcx> MarkSymbolVisitor<'a, 'tcx> {
                         visit::walk_block(self, method.pe_body());
                     }
                     ast::TypeImplItem(_) => {}
+                    ast::ConstImplItem(ref associated_const) => {
+                        visit::walk_associated_const(self, &**associated_const);
+                    }
                 }
             }
             ast_map::NodeForeignItem(foreign_item) => {
@@ -320,7 +326,7 @@ fn has_allow_dead_code_or_lang_attr(attrs: &[ast::Attribute]) -> bool {
     let dead_code = lint::builtin::DEAD_CODE.name_lower();
     for attr in lint::gather_attrs(attrs).into_iter() {
         match attr {
-            Ok((ref name, lint::Allow, _))
+            Ok((ref name, lint::Allow, _, _))
                 if name.get() == dead_code.as_slice() => return true,
             _ => (),
         }
@@ -362,6 +368,9 @@ impl<'v> Visitor<'v> for LifeSeeder {
                             self.worklist.push(method.id);
                         }
                         ast::TypeImplItem(_) => {}
+                        ast::ConstImplItem(ref associated_const) => {
+                            self.worklist.push(associated_const.id);
+                        }
                     }
                 }
             }
@@ -588,6 +597,9 @@ impl<'a, 'tcx, 'v> Visitor<'v> for DeadVisitor<'a, 'tcx> {
             }
             ast::RequiredMethod(_) => {}
             ast::TypeTraitItem(_) => {}
+            ast::ConstTraitItem(ref associated_const) => {
+                visit::walk_expr_opt(self, &associated_const.expr)
+            }
         }
     }
 }
@@ -128,7 +128,15 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
                 // external blocks (e.g. `unsafe { println("") }`,
                 // expands to `unsafe { ... unsafe { ... } }` where
                 // the inner one is compiler generated).
-                if self.unsafe_context == SafeContext || source == ast::CompilerGenerated {
+                //
+                // Blocks written in a macro's definition are treated the
+                // same way: the macro needs its block wherever it's invoked,
+                // so it's the one used by the operations inside, and an
+                // `unsafe` block around the invocation is the unnecessary
+                // one. Blocks the user passed to a macro are their own.
+                let from_macro_def = self.tcx.sess.codemap().is_from_macro_def(block.span);
+                if self.unsafe_context == SafeContext || source == ast::CompilerGenerated ||
+                   from_macro_def {
                     self.unsafe_context = UnsafeBlock(block.id)
                 }
             }
@@ -231,7 +231,7 @@ impl OverloadedCallType {
             ty::MethodTraitItem(ref method_descriptor) => {
                 (*method_descriptor).clone()
             }
-            ty::TypeTraitItem(_) => {
+            ty::TypeTraitItem(_) | ty::ConstTraitItem(_) => {
                 tcx.sess.bug("overloaded call method wasn't in method map")
             }
         };
@@ -500,17 +500,22 @@ impl<'d,'t,'tcx,TYPER:mc::Typer<'tcx>> ExprUseVisitor<'d,'t,'tcx,TYPER> {
             }
 
             ast::ExprInlineAsm(ref ia) => {
-                for &(_, ref input) in ia.inputs.iter() {
+                for &(_, ref input, _) in ia.inputs.iter() {
                     self.consume_expr(&**input);
                 }
 
-                for &(_, ref output, is_rw) in ia.outputs.iter() {
+                for &(_, ref output, is_rw, _) in ia.outputs.iter() {
                     self.mutate_expr(expr, &**output,
                                            if is_rw { WriteAndRead } else { JustWrite });
                 }
             }
 
-            ast::ExprBreak(..) |
+            ast::ExprBreak(_, ref opt_value) => {
+                for value in opt_value.iter() {
+                    self.consume_expr(&**value);
+                }
+            }
+
             ast::ExprAgain(..) |
             ast::ExprLit(..) => {}
 
@@ -523,6 +528,12 @@ impl<'d,'t,'tcx,TYPER:mc::Typer<'tcx>> ExprUseVisitor<'d,'t,'tcx,TYPER> {
                 self.walk_block(&**blk);
             }
 
+            ast::ExprTry(..) => {
+                self.tcx().sess.span_bug(expr.span, "non-desugared ExprTry");
+            }
+            ast::ExprInclusiveRange(..) => {
+                self.tcx().sess.span_bug(expr.span, "non-desugared ExprInclusiveRange");
+            }
             ast::ExprWhileLet(..) => {
                 self.tcx().sess.span_bug(expr.span, "non-desugared ExprWhileLet");
             }
@@ -573,7 +584,8 @@ impl<'d,'t,'tcx,TYPER:mc::Typer<'tcx>> ExprUseVisitor<'d,'t,'tcx,TYPER> {
                 self.consume_expr(&**rhs);
             }
 
-            ast::ExprCast(ref base, _) => {
+            ast::ExprCast(ref base, _) |
+            ast::ExprType(ref base, _) => {
                 self.consume_expr(&**base);
             }
 
@@ -9,6 +9,7 @@
 // except according to those terms.
 
 use metadata::csearch;
+use middle::const_eval;
 use middle::def::DefFn;
 use middle::subst::Subst;
 use middle::ty::{TransmuteRestriction, ctxt, ty_bare_fn};
@@ -73,6 +74,17 @@ struct IntrinsicCheckingVisitor<'a, 'tcx: 'a> {
 
 impl<'a, 'tcx> IntrinsicCheckingVisitor<'a, 'tcx> {
     fn def_id_is_transmute(&self, def_id: DefId) -> bool {
+        self.def_id_is_intrinsic(def_id, "transmute")
+    }
+
+    fn def_id_is_prefetch(&self, def_id: DefId) -> bool {
+        ["prefetch_read_data", "prefetch_write_data",
+         "prefetch_read_instruction", "prefetch_write_instruction"].iter().any(|name| {
+            self.def_id_is_intrinsic(def_id, *name)
+        })
+    }
+
+    fn def_id_is_intrinsic(&self, def_id: DefId, name: &str) -> bool {
         let intrinsic = match ty::lookup_item_type(self.tcx, def_id).ty.sty {
             ty::ty_bare_fn(ref bfty) => bfty.abi == RustIntrinsic,
             _ => return false
@@ -80,16 +92,14 @@ impl<'a, 'tcx> IntrinsicCheckingVisitor<'a, 'tcx> {
         if def_id.krate == ast::LOCAL_CRATE {
             match self.tcx.map.get(def_id.node) {
                 NodeForeignItem(ref item) if intrinsic => {
-                    token::get_ident(item.ident) ==
-                        token::intern_and_get_ident("transmute")
+                    token::get_ident(item.ident) == token::intern_and_get_ident(name)
                 }
                 _ => false,
             }
         } else {
             match csearch::get_item_path(self.tcx, def_id).last() {
                 Some(ref last) if intrinsic => {
-                    token::get_name(last.name()) ==
-                        token::intern_and_get_ident("transmute")
+                    token::get_name(last.name()) == token::intern_and_get_ident(name)
                 }
                 _ => false,
             }
@@ -114,6 +124,18 @@ impl<'a, 'tcx> IntrinsicCheckingVisitor<'a, 'tcx> {
         };
         self.tcx.transmute_restrictions.borrow_mut().push(restriction);
     }
+
+    /// LLVM only accepts an immediate prefetch locality, so it has to be a
+    /// constant expression, which trans evaluates again.
+    fn check_prefetch_locality(&self, locality: &ast::Expr) {
+        match const_eval::eval_const_expr_partial(self.tcx, locality) {
+            Ok(const_eval::const_int(0...3)) | Ok(const_eval::const_uint(0...3)) => {}
+            _ => {
+                self.tcx.sess.span_err(locality.span,
+                                       "prefetch locality must be a constant between 0 and 3");
+            }
+        }
+    }
 }
 
 impl<'a, 'tcx, 'v> Visitor<'v> for IntrinsicCheckingVisitor<'a, 'tcx> {
@@ -140,6 +162,17 @@ impl<'a, 'tcx, 'v> Visitor<'v> for IntrinsicCheckingVisitor<'a, 'tcx> {
             }
         }
 
+        if let ast::ExprCall(ref callee, ref args) = expr.node {
+            if let ast::ExprPath(..) = callee.node {
+                match ty::resolve_expr(self.tcx, &**callee) {
+                    DefFn(did, _) if self.def_id_is_prefetch(did) => {
+                        self.check_prefetch_locality(&*args[1]);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
         visit::walk_expr(self, expr);
     }
 }
@@ -190,7 +190,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for IrMaps<'a, 'tcx> {
 
 pub fn check_crate(tcx: &ty::ctxt) {
     visit::walk_crate(&mut IrMaps::new(tcx), tcx.map.krate());
-    tcx.sess.abort_if_errors();
+    tcx.sess.abort_if_errors_unless_keep_going();
 }
 
 impl fmt::Show for LiveNode {
@@ -482,6 +482,12 @@ fn visit_expr(ir: &mut IrMaps, expr: &Expr) {
       ast::ExprIfLet(..) => {
           ir.tcx.sess.span_bug(expr.span, "non-desugared ExprIfLet");
       }
+      ast::ExprTry(..) => {
+          ir.tcx.sess.span_bug(expr.span, "non-desugared ExprTry");
+      }
+      ast::ExprInclusiveRange(..) => {
+          ir.tcx.sess.span_bug(expr.span, "non-desugared ExprInclusiveRange");
+      }
       ast::ExprWhileLet(..) => {
           ir.tcx.sess.span_bug(expr.span, "non-desugared ExprWhileLet");
       }
@@ -508,7 +514,7 @@ fn visit_expr(ir: &mut IrMaps, expr: &Expr) {
       ast::ExprIndex(..) | ast::ExprField(..) | ast::ExprTupField(..) |
       ast::ExprVec(..) | ast::ExprCall(..) | ast::ExprMethodCall(..) |
       ast::ExprTup(..) | ast::ExprBinary(..) | ast::ExprAddrOf(..) |
-      ast::ExprCast(..) | ast::ExprUnary(..) | ast::ExprBreak(_) |
+      ast::ExprCast(..) | ast::ExprType(..) | ast::ExprUnary(..) | ast::ExprBreak(..) |
       ast::ExprAgain(_) | ast::ExprLit(_) | ast::ExprRet(..) |
       ast::ExprBlock(..) | ast::ExprAssign(..) | ast::ExprAssignOp(..) |
       ast::ExprMac(..) | ast::ExprStruct(..) | ast::ExprRepeat(..) |
@@ -1023,6 +1029,12 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
             self.propagate_through_loop(expr, WhileLoop(&**cond), &**blk, succ)
           }
 
+          ast::ExprTry(..) => {
+              self.ir.tcx.sess.span_bug(expr.span, "non-desugared ExprTry");
+          }
+          ast::ExprInclusiveRange(..) => {
+              self.ir.tcx.sess.span_bug(expr.span, "non-desugared ExprInclusiveRange");
+          }
           ast::ExprWhileLet(..) => {
               self.ir.tcx.sess.span_bug(expr.span, "non-desugared ExprWhileLet");
           }
@@ -1079,18 +1091,21 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
             self.propagate_through_opt_expr(o_e.as_ref().map(|e| &**e), exit_ln)
           }
 
-          ast::ExprBreak(opt_label) => {
+          ast::ExprBreak(opt_label, ref opt_value) => {
               // Find which label this break jumps to
               let sc = self.find_loop_scope(opt_label, expr.id, expr.span);
 
               // Now that we know the label we're going to,
               // look it up in the break loop nodes table
 
-              match self.break_ln.get(&sc) {
+              let b = match self.break_ln.get(&sc) {
                   Some(&b) => b,
                   None => self.ir.tcx.sess.span_bug(expr.span,
                                                     "break to unknown label")
-              }
+              };
+
+              // The value, if any, is evaluated before jumping
+              self.propagate_through_opt_expr(opt_value.as_ref().map(|e| &**e), b)
           }
 
           ast::ExprAgain(opt_label) => {
@@ -1196,21 +1211,24 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
 
           ast::ExprAddrOf(_, ref e) |
           ast::ExprCast(ref e, _) |
+          ast::ExprType(ref e, _) |
           ast::ExprUnary(_, ref e) |
           ast::ExprParen(ref e) => {
             self.propagate_through_expr(&**e, succ)
           }
 
           ast::ExprInlineAsm(ref ia) => {
+            // A `noreturn` asm doesn't go on to `succ`, like `return`
+            let succ = if ia.noreturn { self.s.exit_ln } else { succ };
 
-            let succ = ia.outputs.iter().rev().fold(succ, |succ, &(_, ref expr, _)| {
+            let succ = ia.outputs.iter().rev().fold(succ, |succ, &(_, ref expr, _, _)| {
                 // see comment on lvalues
                 // in propagate_through_lvalue_components()
                 let succ = self.write_lvalue(&**expr, succ, ACC_WRITE);
                 self.propagate_through_lvalue_components(&**expr, succ)
             });
             // Inputs are executed first. Propagate last because of rev order
-            ia.inputs.iter().rev().fold(succ, |succ, &(_, ref expr)| {
+            ia.inputs.iter().rev().fold(succ, |succ, &(_, ref expr, _)| {
                 self.propagate_through_expr(&**expr, succ)
             })
           }
@@ -1453,12 +1471,12 @@ fn check_expr(this: &mut Liveness, expr: &Expr) {
       }
 
       ast::ExprInlineAsm(ref ia) => {
-        for &(_, ref input) in ia.inputs.iter() {
+        for &(_, ref input, _) in ia.inputs.iter() {
           this.visit_expr(&**input);
         }
 
         // Output operands must be lvalues
-        for &(_, ref out, _) in ia.outputs.iter() {
+        for &(_, ref out, _, _) in ia.outputs.iter() {
           this.check_lvalue(&**out);
           this.visit_expr(&**out);
         }
@@ -1479,7 +1497,7 @@ fn check_expr(this: &mut Liveness, expr: &Expr) {
       ast::ExprMatch(..) | ast::ExprWhile(..) | ast::ExprLoop(..) |
       ast::ExprIndex(..) | ast::ExprField(..) | ast::ExprTupField(..) |
       ast::ExprVec(..) | ast::ExprTup(..) | ast::ExprBinary(..) |
-      ast::ExprCast(..) | ast::ExprUnary(..) | ast::ExprRet(..) |
+      ast::ExprCast(..) | ast::ExprType(..) | ast::ExprUnary(..) | ast::ExprRet(..) |
       ast::ExprBreak(..) | ast::ExprAgain(..) | ast::ExprLit(_) |
       ast::ExprBlock(..) | ast::ExprMac(..) | ast::ExprAddrOf(..) |
       ast::ExprStruct(..) | ast::ExprRepeat(..) | ast::ExprParen(..) |
@@ -1490,6 +1508,12 @@ fn check_expr(this: &mut Liveness, expr: &Expr) {
       ast::ExprIfLet(..) => {
         this.ir.tcx.sess.span_bug(expr.span, "non-desugared ExprIfLet");
       }
+      ast::ExprTry(..) => {
+        this.ir.tcx.sess.span_bug(expr.span, "non-desugared ExprTry");
+      }
+      ast::ExprInclusiveRange(..) => {
+        this.ir.tcx.sess.span_bug(expr.span, "non-desugared ExprInclusiveRange");
+      }
       ast::ExprWhileLet(..) => {
         this.ir.tcx.sess.span_bug(expr.span, "non-desugared ExprWhileLet");
       }
@@ -541,7 +541,7 @@ impl<'t,'tcx,TYPER:Typer<'tcx>> MemCategorizationContext<'t,TYPER> {
           ast::ExprClosure(..) | ast::ExprProc(..) |
           ast::ExprRet(..) |
           ast::ExprUnary(..) | ast::ExprSlice(..) |
-          ast::ExprMethodCall(..) | ast::ExprCast(..) |
+          ast::ExprMethodCall(..) | ast::ExprCast(..) | ast::ExprType(..) |
           ast::ExprVec(..) | ast::ExprTup(..) | ast::ExprIf(..) |
           ast::ExprBinary(..) | ast::ExprWhile(..) |
           ast::ExprBlock(..) | ast::ExprLoop(..) | ast::ExprMatch(..) |
@@ -555,6 +555,12 @@ impl<'t,'tcx,TYPER:Typer<'tcx>> MemCategorizationContext<'t,TYPER> {
           ast::ExprIfLet(..) => {
             self.tcx().sess.span_bug(expr.span, "non-desugared ExprIfLet");
           }
+          ast::ExprTry(..) => {
+            self.tcx().sess.span_bug(expr.span, "non-desugared ExprTry");
+          }
+          ast::ExprInclusiveRange(..) => {
+            self.tcx().sess.span_bug(expr.span, "non-desugared ExprInclusiveRange");
+          }
           ast::ExprWhileLet(..) => {
             self.tcx().sess.span_bug(expr.span, "non-desugared ExprWhileLet");
           }
@@ -572,7 +578,7 @@ impl<'t,'tcx,TYPER:Typer<'tcx>> MemCategorizationContext<'t,TYPER> {
 
         match def {
           def::DefStruct(..) | def::DefVariant(..) | def::DefFn(..) |
-          def::DefStaticMethod(..) | def::DefConst(..) => {
+          def::DefStaticMethod(..) | def::DefConst(..) | def::DefAssociatedConst(..) => {
                 Ok(self.cat_rvalue_node(id, span, expr_ty))
           }
           def::DefMod(_) | def::DefForeignMod(_) | def::DefUse(_) |
@@ -45,86 +45,22 @@ pub type PublicItems = NodeSet;
 type CheckResult = Option<(Span, String, Option<(Span, String)>)>;
 
 ////////////////////////////////////////////////////////////////////////////////
-/// The parent visitor, used to determine what's the parent of what (node-wise)
+/// The field parent visitor, used to find the module of each struct field
 ////////////////////////////////////////////////////////////////////////////////
 
-struct ParentVisitor {
+/// Struct fields aren't in the AST map, so their parents are recorded here:
+/// the module containing the struct (or enum, for struct variants).
+struct FieldParentVisitor<'a, 'ast: 'a> {
+    map: &'a ast_map::Map<'ast>,
     parents: NodeMap<ast::NodeId>,
-    curparent: ast::NodeId,
 }
 
-impl<'v> Visitor<'v> for ParentVisitor {
-    fn visit_item(&mut self, item: &ast::Item) {
-        self.parents.insert(item.id, self.curparent);
-
-        let prev = self.curparent;
-        match item.node {
-            ast::ItemMod(..) => { self.curparent = item.id; }
-            // Enum variants are parented to the enum definition itself because
-            // they inherit privacy
-            ast::ItemEnum(ref def, _) => {
-                for variant in def.variants.iter() {
-                    // The parent is considered the enclosing enum because the
-                    // enum will dictate the privacy visibility of this variant
-                    // instead.
-                    self.parents.insert(variant.node.id, item.id);
-                }
-            }
-
-            // Trait methods are always considered "public", but if the trait is
-            // private then we need some private item in the chain from the
-            // method to the root. In this case, if the trait is private, then
-            // parent all the methods to the trait to indicate that they're
-            // private.
-            ast::ItemTrait(_, _, _, ref methods) if item.vis != ast::Public => {
-                for m in methods.iter() {
-                    match *m {
-                        ast::ProvidedMethod(ref m) => {
-                            self.parents.insert(m.id, item.id);
-                        }
-                        ast::RequiredMethod(ref m) => {
-                            self.parents.insert(m.id, item.id);
-                        }
-                        ast::TypeTraitItem(_) => {}
-                    };
-                }
-            }
-
-            _ => {}
-        }
-        visit::walk_item(self, item);
-        self.curparent = prev;
-    }
-
-    fn visit_foreign_item(&mut self, a: &ast::ForeignItem) {
-        self.parents.insert(a.id, self.curparent);
-        visit::walk_foreign_item(self, a);
-    }
-
-    fn visit_fn(&mut self, a: visit::FnKind<'v>, b: &'v ast::FnDecl,
-                c: &'v ast::Block, d: Span, id: ast::NodeId) {
-        // We already took care of some trait methods above, otherwise things
-        // like impl methods and pub trait methods are parented to the
-        // containing module, not the containing trait.
-        if !self.parents.contains_key(&id) {
-            self.parents.insert(id, self.curparent);
-        }
-        visit::walk_fn(self, a, b, c, d);
-    }
-
+impl<'a, 'ast, 'v> Visitor<'v> for FieldParentVisitor<'a, 'ast> {
     fn visit_struct_def(&mut self, s: &ast::StructDef, _: ast::Ident,
                         _: &'v ast::Generics, n: ast::NodeId) {
-        // Struct constructors are parented to their struct definitions because
-        // they essentially are the struct definitions.
-        match s.ctor_id {
-            Some(id) => { self.parents.insert(id, n); }
-            None => {}
-        }
-
-        // While we have the id of the struct definition, go ahead and parent
-        // all the fields.
+        let module = self.map.get_module_parent(n);
         for field in s.fields.iter() {
-            self.parents.insert(field.node.id, self.curparent);
+            self.parents.insert(field.node.id, module);
         }
         visit::walk_struct_def(self, s)
     }
@@ -275,6 +211,11 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EmbargoVisitor<'a, 'tcx> {
                                 }
                             }
                             ast::TypeImplItem(_) => {}
+                            ast::ConstImplItem(ref c) => {
+                                if (public_ty && c.vis == ast::Public) || tr.is_some() {
+                                    self.exported_items.insert(c.id);
+                                }
+                            }
                         }
                     }
                 }
@@ -297,6 +238,10 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EmbargoVisitor<'a, 'tcx> {
                             debug!("typedef {}", t.ty_param.id);
                             self.exported_items.insert(t.ty_param.id);
                         }
+                        ast::ConstTraitItem(ref c) => {
+                            debug!("const {}", c.id);
+                            self.exported_items.insert(c.id);
+                        }
                     }
                 }
             }
@@ -361,7 +306,7 @@ struct PrivacyVisitor<'a, 'tcx: 'a> {
     tcx: &'a ty::ctxt<'tcx>,
     curitem: ast::NodeId,
     in_foreign: bool,
-    parents: NodeMap<ast::NodeId>,
+    field_parents: NodeMap<ast::NodeId>,
     external_exports: resolve::ExternalExports,
     last_private_map: resolve::LastPrivateMap,
 }
@@ -384,6 +329,50 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
         self.tcx.map.node_to_string(id).to_string()
     }
 
+    /// Returns the node whose privacy scopes `id`: usually the enclosing
+    /// module, or `DUMMY_NODE_ID` above the crate root.
+    fn parent(&self, id: ast::NodeId) -> ast::NodeId {
+        let map = &self.tcx.map;
+        if id == ast::CRATE_NODE_ID {
+            return ast::DUMMY_NODE_ID;
+        }
+        match map.find(id) {
+            // Enum variants are parented to the enum definition itself
+            // because they inherit privacy, and struct constructors to their
+            // struct definitions because they essentially are the struct
+            // definitions.
+            Some(ast_map::NodeVariant(..)) | Some(ast_map::NodeStructCtor(..)) => {
+                map.get_parent(id)
+            }
+
+            // Trait methods are always considered "public", but if the trait
+            // is private then we need some private item in the chain from the
+            // method to the root. In this case, if the trait is private, then
+            // parent all the methods to the trait to indicate that they're
+            // private.
+            Some(ast_map::NodeTraitItem(&ast::ProvidedMethod(..))) |
+            Some(ast_map::NodeTraitItem(&ast::RequiredMethod(..))) |
+            Some(ast_map::NodeTraitItem(&ast::ConstTraitItem(..))) => {
+                let trait_id = map.get_parent(id);
+                if map.expect_item(trait_id).vis != ast::Public {
+                    trait_id
+                } else {
+                    map.get_module_parent(id)
+                }
+            }
+
+            // Struct fields aren't in the AST map.
+            None => match self.field_parents.get(&id) {
+                Some(&module) => module,
+                None => map.get_module_parent(id)
+            },
+
+            // Everything else, including impl methods, is parented to the
+            // containing module.
+            _ => map.get_module_parent(id)
+        }
+    }
+
     // Determines whether the given definition is public from the point of view
     // of the current item.
     fn def_privacy(&self, did: ast::DefId) -> PrivacyResult {
@@ -447,6 +436,31 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                         }
                     }
                 }
+                Some(&ty::ConstTraitItem(ref associated_const)) => {
+                    match associated_const.container {
+                        ty::TraitContainer(id) => {
+                            debug!("privacy - recursing on trait {}", id);
+                            self.def_privacy(id)
+                        }
+                        ty::ImplContainer(id) => {
+                            match ty::impl_trait_ref(self.tcx, id) {
+                                Some(t) => {
+                                    debug!("privacy - impl of trait {}", id);
+                                    self.def_privacy(t.def_id)
+                                }
+                                None => {
+                                    debug!("privacy - found a const {}",
+                                            associated_const.vis);
+                                    if associated_const.vis == ast::Public {
+                                        Allowable
+                                    } else {
+                                        ExternallyDenied
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
                 None => {
                     debug!("privacy - nope, not even a method");
                     ExternallyDenied
@@ -457,7 +471,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
         debug!("privacy - local {} not public all the way down",
                self.tcx.map.node_to_string(did.node));
         // return quickly for things in the same module
-        if self.parents.get(&did.node) == self.parents.get(&self.curitem) {
+        if self.parent(did.node) == self.parent(self.curitem) {
             debug!("privacy - same parent, we're done here");
             return Allowable;
         }
@@ -498,6 +512,15 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                             }
                         }
                         ast::TypeImplItem(_) => return Allowable,
+                        ast::ConstImplItem(ref c) => {
+                            let imp = self.tcx.map
+                                          .get_parent_did(closest_private_id);
+                            match ty::impl_trait_ref(self.tcx, imp) {
+                                Some(..) => return Allowable,
+                                _ if c.vis == ast::Public => return Allowable,
+                                _ => c.vis
+                            }
+                        }
                     }
                 }
                 Some(ast_map::NodeTraitItem(_)) => {
@@ -519,7 +542,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             // if we've reached the root, then everything was allowable and this
             // access is public.
             if closest_private_id == ast::CRATE_NODE_ID { return Allowable }
-            closest_private_id = self.parents[closest_private_id];
+            closest_private_id = self.parent(closest_private_id);
 
             // If we reached the top, then we were public all the way down and
             // we can allow this access.
@@ -537,7 +560,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
     /// whether the node is accessible by the current module that iteration is
     /// inside.
     fn private_accessible(&self, id: ast::NodeId) -> bool {
-        let parent = self.parents[id];
+        let parent = self.parent(id);
         debug!("privacy - accessible parent {}", self.nodestr(parent));
 
         // After finding `did`'s closest private member, we roll ourselves back
@@ -561,7 +584,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                 _ => {}
             }
 
-            cur = self.parents[cur];
+            cur = self.parent(cur);
         }
     }
 
@@ -688,7 +711,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             ty::MethodTraitItem(method_type) => {
                 method_type.provided_source.unwrap_or(method_id)
             }
-            ty::TypeTraitItem(_) => method_id,
+            ty::TypeTraitItem(_) | ty::ConstTraitItem(_) => method_id,
         };
 
         let string = token::get_ident(name);
@@ -787,6 +810,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             def::DefFn(..) => ck("function"),
             def::DefStatic(..) => ck("static"),
             def::DefConst(..) => ck("const"),
+            def::DefAssociatedConst(..) => ck("associated const"),
             def::DefVariant(..) => ck("variant"),
             def::DefTy(_, false) => ck("type"),
             def::DefTy(_, true) => ck("enum"),
@@ -1106,6 +1130,9 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
                             check_inherited(m.span, m.pe_vis(), "");
                         }
                         ast::TypeImplItem(_) => {}
+                        ast::ConstImplItem(ref c) => {
+                            check_inherited(c.span, c.vis, "");
+                        }
                     }
                 }
             }
@@ -1145,7 +1172,7 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
                             check_inherited(m.span, m.vis,
                                             "unnecessary visibility");
                         }
-                        ast::TypeTraitItem(_) => {}
+                        ast::TypeTraitItem(_) | ast::ConstTraitItem(_) => {}
                     }
                 }
             }
@@ -1182,6 +1209,9 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
                             check_inherited(tcx, m.span, m.pe_vis());
                         }
                         ast::TypeImplItem(_) => {}
+                        ast::ConstImplItem(ref c) => {
+                            check_inherited(tcx, c.span, c.vis);
+                        }
                     }
                 }
             }
@@ -1204,7 +1234,7 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
                         ast::RequiredMethod(..) => {}
                         ast::ProvidedMethod(ref m) => check_inherited(tcx, m.span,
                                                                 m.pe_vis()),
-                        ast::TypeTraitItem(_) => {}
+                        ast::TypeTraitItem(_) | ast::ConstTraitItem(_) => {}
                     }
                 }
             }
@@ -1368,6 +1398,9 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                                           self.exported_items.contains(&m.id)
                                       }
                                       ast::TypeImplItem(_) => false,
+                                      ast::ConstImplItem(ref c) => {
+                                          self.exported_items.contains(&c.id)
+                                      }
                                   }
                               });
 
@@ -1385,6 +1418,11 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                                         visit::walk_method_helper(self, &**method)
                                     }
                                     ast::TypeImplItem(_) => {}
+                                    ast::ConstImplItem(ref c) => {
+                                        if self.exported_items.contains(&c.id) {
+                                            self.visit_ty(&*c.ty)
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -1420,6 +1458,12 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                                 }
                             }
                             ast::TypeImplItem(_) => {}
+                            ast::ConstImplItem(ref c) => {
+                                if self.exported_items.contains(&c.id) {
+                                    found_pub_static = true;
+                                    self.visit_ty(&*c.ty);
+                                }
+                            }
                         }
                     }
                     if found_pub_static {
@@ -1453,8 +1497,16 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
             }
         }
         for predicate in generics.where_clause.predicates.iter() {
-            for bound in predicate.bounds.iter() {
-                self.check_ty_param_bound(predicate.span, bound)
+            match *predicate {
+                ast::BoundPredicate(ref bound_pred) => {
+                    for bound in bound_pred.bounds.iter() {
+                        self.check_ty_param_bound(bound_pred.span, bound)
+                    }
+                }
+                ast::EqPredicate(ref eq_pred) => {
+                    self.visit_ty(&*eq_pred.lhs_ty);
+                    self.visit_ty(&*eq_pred.rhs_ty);
+                }
             }
         }
     }
@@ -1519,19 +1571,19 @@ pub fn check_crate(tcx: &ty::ctxt,
                    -> (ExportedItems, PublicItems) {
     let krate = tcx.map.krate();
 
-    // Figure out who everyone's parent is
-    let mut visitor = ParentVisitor {
+    // Figure out the parents of struct fields, which the AST map lacks
+    let mut visitor = FieldParentVisitor {
+        map: &tcx.map,
         parents: NodeMap::new(),
-        curparent: ast::DUMMY_NODE_ID,
     };
     visit::walk_crate(&mut visitor, krate);
 
-    // Use the parent map to check the privacy of everything
+    // Use the parents in the AST map to check the privacy of everything
     let mut visitor = PrivacyVisitor {
         curitem: ast::DUMMY_NODE_ID,
         in_foreign: false,
+        field_parents: visitor.parents,
         tcx: tcx,
-        parents: visitor.parents,
         external_exports: external_exports,
         last_private_map: last_private_map,
     };
@@ -1545,7 +1597,7 @@ pub fn check_crate(tcx: &ty::ctxt,
     };
     visit::walk_crate(&mut visitor, krate);
 
-    tcx.sess.abort_if_errors();
+    tcx.sess.abort_if_errors_unless_keep_going();
 
     // Build up a set of all exported items in the AST. This is a set of all
     // items which are reachable from external crates based on visibility.
@@ -123,7 +123,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for ReachableContext<'a, 'tcx> {
                             // If this path leads to a constant, then we need to
                             // recurse into the constant to continue finding
                             // items that are reachable.
-                            def::DefConst(..) => {
+                            def::DefConst(..) | def::DefAssociatedConst(..) => {
                                 self.worklist.push(def_id.node);
                             }
 
@@ -196,6 +196,7 @@ impl<'a, 'tcx> ReachableContext<'a, 'tcx> {
                     ast::RequiredMethod(_) => false,
                     ast::ProvidedMethod(_) => true,
                     ast::TypeTraitItem(_) => false,
+                    ast::ConstTraitItem(_) => false,
                 }
             }
             Some(ast_map::NodeImplItem(impl_item)) => {
@@ -225,6 +226,7 @@ impl<'a, 'tcx> ReachableContext<'a, 'tcx> {
                         }
                     }
                     ast::TypeImplItem(_) => false,
+                    ast::ConstImplItem(_) => false,
                 }
             }
             Some(_) => false,
@@ -319,6 +321,14 @@ impl<'a, 'tcx> ReachableContext<'a, 'tcx> {
                         visit::walk_block(self, &*method.pe_body());
                     }
                     ast::TypeTraitItem(_) => {}
+                    // Like other constants, associated constants with a
+                    // value are inlined into other crates.
+                    ast::ConstTraitItem(ref associated_const) => {
+                        match associated_const.expr {
+                            Some(ref expr) => self.visit_expr(&**expr),
+                            None => {}
+                        }
+                    }
                 }
             }
             ast_map::NodeImplItem(impl_item) => {
@@ -330,6 +340,12 @@ impl<'a, 'tcx> ReachableContext<'a, 'tcx> {
                         }
                     }
                     ast::TypeImplItem(_) => {}
+                    ast::ConstImplItem(ref associated_const) => {
+                        match associated_const.expr {
+                            Some(ref expr) => self.visit_expr(&**expr),
+                            None => {}
+                        }
+                    }
                 }
             }
             // Nothing to recurse on for these
@@ -66,7 +66,7 @@ use syntax::ast::{TupleVariantKind, Ty, TyBool, TyChar, TyClosure, TyF32};
 use syntax::ast::{TyF64, TyFloat, TyI, TyI8, TyI16, TyI32, TyI64, TyInt, TyObjectSum};
 use syntax::ast::{TyParam, TyParamBound, TyPath, TyPtr, TyPolyTraitRef, TyProc, TyQPath};
 use syntax::ast::{TyRptr, TyStr, TyU, TyU8, TyU16, TyU32, TyU64, TyUint};
-use syntax::ast::{TypeImplItem, UnnamedField};
+use syntax::ast::{TypeImplItem, UnnamedField, ConstImplItem};
 use syntax::ast::{Variant, ViewItem, ViewItemExternCrate};
 use syntax::ast::{ViewItemUse, ViewPathGlob, ViewPathList, ViewPathSimple};
 use syntax::ast::{Visibility};
@@ -75,7 +75,7 @@ use syntax::ast_util::{mod, PostExpansionMethod, local_def, walk_pat};
 use syntax::attr::AttrMetaMethods;
 use syntax::ext::mtwt;
 use syntax::parse::token::{mod, special_names, special_idents};
-use syntax::codemap::{Span, DUMMY_SP, Pos};
+use syntax::codemap::{Span, MultiSpan, DUMMY_SP, Pos};
 use syntax::owned_slice::OwnedSlice;
 use syntax::visit::{mod, Visitor};
 
@@ -275,6 +275,7 @@ enum FallbackSuggestion {
     TraitItem,
     StaticMethod(String),
     TraitMethod(String),
+    AssociatedConst(String),
 }
 
 enum TypeParameters<'a> {
@@ -340,6 +341,7 @@ pub enum TraitItemKind {
     NonstaticMethodTraitItemKind,
     StaticMethodTraitItemKind,
     TypeTraitItemKind,
+    ConstTraitItemKind,
 }
 
 impl TraitItemKind {
@@ -1195,18 +1197,18 @@ impl<'a> Resolver<'a> {
                     // Return an error here by looking up the namespace that
                     // had the duplicate.
                     let ns = ns.unwrap();
-                    self.resolve_error(sp,
-                        format!("duplicate definition of {} `{}`",
-                             namespace_error_to_string(duplicate_type),
-                             token::get_name(name)).as_slice());
-                    {
-                        let r = child.span_for_namespace(ns);
-                        for sp in r.iter() {
-                            self.session.span_note(*sp,
-                                 format!("first definition of {} `{}` here",
-                                      namespace_error_to_string(duplicate_type),
-                                      token::get_name(name)).as_slice());
-                        }
+                    let mut msp = MultiSpan::new(sp);
+                    for first in child.span_for_namespace(ns).iter() {
+                        msp.push_label(*first,
+                             format!("first definition of {} `{}` here",
+                                  namespace_error_to_string(duplicate_type),
+                                  token::get_name(name)));
+                    }
+                    if self.emit_errors {
+                        self.session.span_err_multi(&msp,
+                            format!("duplicate definition of {} `{}`",
+                                 namespace_error_to_string(duplicate_type),
+                                 token::get_name(name)).as_slice());
                     }
                 }
                 child
@@ -1528,6 +1530,28 @@ impl<'a> Resolver<'a> {
                                         typedef.span,
                                         modifiers);
                                 }
+                                ConstImplItem(ref associated_const) => {
+                                    // Add the constant to the module.
+                                    let name = associated_const.ident.name;
+                                    let const_name_bindings =
+                                        self.add_child(name,
+                                                       new_parent.clone(),
+                                                       ForbidDuplicateValues,
+                                                       associated_const.span);
+                                    let def = DefAssociatedConst(
+                                        local_def(associated_const.id),
+                                        FromImpl(local_def(item.id)));
+                                    // NB: not IMPORTABLE
+                                    let modifiers = if associated_const.vis == ast::Public {
+                                        PUBLIC
+                                    } else {
+                                        DefModifiers::empty()
+                                    };
+                                    const_name_bindings.define_value(
+                                        def,
+                                        associated_const.span,
+                                        modifiers);
+                                }
                             }
                         }
                     }
@@ -1615,6 +1639,23 @@ impl<'a> Resolver<'a> {
 
                             (associated_type.ty_param.ident.name, TypeTraitItemKind)
                         }
+                        ast::ConstTraitItem(ref associated_const) => {
+                            let def = DefAssociatedConst(
+                                local_def(associated_const.id),
+                                FromTrait(local_def(item.id)));
+
+                            let name_bindings =
+                                self.add_child(associated_const.ident.name,
+                                               module_parent.clone(),
+                                               ForbidDuplicateTypesAndValues,
+                                               associated_const.span);
+                            // NB: not IMPORTABLE
+                            name_bindings.define_value(def,
+                                                       associated_const.span,
+                                                       PUBLIC);
+
+                            (associated_const.ident.name, ConstTraitItemKind)
+                        }
                     };
 
                     self.trait_item_map.insert((name, def_id), kind);
@@ -1932,7 +1973,8 @@ impl<'a> Resolver<'a> {
                 csearch::get_tuple_struct_definition_if_ctor(&self.session.cstore, ctor_id)
                     .map_or(def, |_| DefStruct(ctor_id)), DUMMY_SP, modifiers);
           }
-          DefFn(..) | DefStaticMethod(..) | DefStatic(..) | DefConst(..) | DefMethod(..) => {
+          DefFn(..) | DefStaticMethod(..) | DefStatic(..) | DefConst(..) |
+          DefAssociatedConst(..) | DefMethod(..) => {
             debug!("(building reduced graph for external \
                     crate) building value (fn/static) {}", final_ident);
             // impl methods have already been defined with the correct importability modifier
@@ -2124,7 +2166,11 @@ impl<'a> Resolver<'a> {
                                                        new_parent.clone(),
                                                        OverwriteDuplicates,
                                                        DUMMY_SP);
-                                    let def = DefFn(method_info.def_id, false);
+                                    let def = if method_info.is_associated_const {
+                                        DefAssociatedConst(method_info.def_id, FromImpl(def))
+                                    } else {
+                                        DefFn(method_info.def_id, false)
+                                    };
 
                                     // NB: not IMPORTABLE
                                     let modifiers = if visibility == ast::Public {
@@ -4153,6 +4199,7 @@ impl<'a> Resolver<'a> {
                                                                ItemRibKind),
                                              |this| {
                     this.resolve_type_parameters(&generics.ty_params);
+                    this.resolve_where_clause(&generics.where_clause);
                     visit::walk_item(this, item);
                 });
             }
@@ -4240,6 +4287,9 @@ impl<'a> Resolver<'a> {
                               this.resolve_type_parameter(&data.ty_param);
                               visit::walk_trait_item(this, trait_item);
                           }
+                          ast::ConstTraitItem(_) => {
+                              visit::walk_trait_item(this, trait_item);
+                          }
                         }
                     }
                 });
@@ -4269,8 +4319,10 @@ impl<'a> Resolver<'a> {
                                     HasTypeParameters(
                                         generics, FnSpace, foreign_item.id,
                                         ItemRibKind),
-                                    |this| visit::walk_foreign_item(this,
-                                                                    &**foreign_item));
+                                    |this| {
+                                        this.resolve_where_clause(&generics.where_clause);
+                                        visit::walk_foreign_item(this, &**foreign_item)
+                                    });
                             }
                             ForeignItemStatic(..) => {
                                 visit::walk_foreign_item(this,
@@ -4525,26 +4577,34 @@ impl<'a> Resolver<'a> {
 
     fn resolve_where_clause(&mut self, where_clause: &ast::WhereClause) {
         for predicate in where_clause.predicates.iter() {
-            match self.resolve_identifier(predicate.ident,
-                                          TypeNS,
-                                          true,
-                                          predicate.span) {
-                Some((def @ DefTyParam(_, _, _), last_private)) => {
-                    self.record_def(predicate.id, (def, last_private));
+            match *predicate {
+                ast::BoundPredicate(ref bound_pred) => {
+                    match self.resolve_identifier(bound_pred.ident,
+                                                  TypeNS,
+                                                  true,
+                                                  bound_pred.span) {
+                        Some((def @ DefTyParam(_, _, _), last_private)) => {
+                            self.record_def(bound_pred.id, (def, last_private));
+                        }
+                        _ => {
+                            self.resolve_error(
+                                bound_pred.span,
+                                format!("undeclared type parameter `{}`",
+                                        token::get_ident(
+                                            bound_pred.ident)).as_slice());
+                        }
+                    }
+
+                    for bound in bound_pred.bounds.iter() {
+                        self.resolve_type_parameter_bound(bound_pred.id, bound,
+                                                          TraitBoundingTypeParameter);
+                    }
                 }
-                _ => {
-                    self.resolve_error(
-                        predicate.span,
-                        format!("undeclared type parameter `{}`",
-                                token::get_ident(
-                                    predicate.ident)).as_slice());
+                ast::EqPredicate(ref eq_pred) => {
+                    self.resolve_type(&*eq_pred.lhs_ty);
+                    self.resolve_type(&*eq_pred.rhs_ty);
                 }
             }
-
-            for bound in predicate.bounds.iter() {
-                self.resolve_type_parameter_bound(predicate.id, bound,
-                                                  TraitBoundingTypeParameter);
-            }
         }
     }
 
@@ -4665,6 +4725,15 @@ impl<'a> Resolver<'a> {
 
                                 this.resolve_type(&*typedef.typ);
                             }
+                            ConstImplItem(ref associated_const) => {
+                                // If this is a trait impl, ensure the
+                                // constant exists in trait
+                                this.check_trait_item(associated_const.ident.name,
+                                                      associated_const.span);
+
+                                visit::walk_associated_const(this,
+                                                             &**associated_const);
+                            }
                         }
                     }
                 });
@@ -5624,6 +5693,9 @@ impl<'a> Resolver<'a> {
                                 FromTrait(_) => unreachable!()
                             }
                         }
+                        Some(DefAssociatedConst(_, FromImpl(_))) => {
+                            return AssociatedConst(p_str)
+                        }
                         Some(DefMethod(_, None, _)) if allowed == Everything => return Method,
                         Some(DefMethod(_, Some(_), _)) => return TraitItem,
                         _ => ()
@@ -5643,6 +5715,9 @@ impl<'a> Resolver<'a> {
                     Some(&StaticMethodTraitItemKind) => {
                         return TraitMethod(path_str)
                     }
+                    Some(&ConstTraitItemKind) => {
+                        return AssociatedConst(path_str)
+                    }
                     Some(_) => return TraitItem,
                     None => {}
                 }
@@ -5771,7 +5846,9 @@ impl<'a> Resolver<'a> {
                                             format!("to call `self.{}`", wrong_name),
                                         TraitMethod(path_str)
                                         | StaticMethod(path_str) =>
-                                            format!("to call `{}::{}`", path_str, wrong_name)
+                                            format!("to call `{}::{}`", path_str, wrong_name),
+                                        AssociatedConst(path_str) =>
+                                            format!("`{}::{}`", path_str, wrong_name)
                                     };
 
                                     if msg.len() > 0 {
@@ -5871,7 +5948,7 @@ impl<'a> Resolver<'a> {
                 self.value_ribs.pop();
             }
 
-            ExprBreak(Some(label)) | ExprAgain(Some(label)) => {
+            ExprBreak(Some(label), _) | ExprAgain(Some(label)) => {
                 let renamed = mtwt::resolve(label);
                 match self.search_ribs(self.label_ribs.as_slice(),
                                        renamed, expr.span) {
@@ -5891,6 +5968,7 @@ impl<'a> Resolver<'a> {
                                                label def!")
                     }
                 }
+                visit::walk_expr(self, expr);
             }
 
             _ => {
@@ -208,8 +208,16 @@ impl<'a, 'v> Visitor<'v> for LifetimeContext<'a> {
             }
         }
         for predicate in generics.where_clause.predicates.iter() {
-            self.visit_ident(predicate.span, predicate.ident);
-            visit::walk_ty_param_bounds_helper(self, &predicate.bounds);
+            match *predicate {
+                ast::BoundPredicate(ref bound_pred) => {
+                    self.visit_ident(bound_pred.span, bound_pred.ident);
+                    visit::walk_ty_param_bounds_helper(self, &bound_pred.bounds);
+                }
+                ast::EqPredicate(ref eq_pred) => {
+                    self.visit_ty(&*eq_pred.lhs_ty);
+                    self.visit_ty(&*eq_pred.rhs_ty);
+                }
+            }
         }
     }
 
@@ -484,7 +492,15 @@ fn early_bound_lifetime_names(generics: &ast::Generics) -> Vec<ast::Name> {
             visit::walk_ty_param_bounds_helper(&mut collector, &ty_param.bounds);
         }
         for predicate in generics.where_clause.predicates.iter() {
-            visit::walk_ty_param_bounds_helper(&mut collector, &predicate.bounds);
+            match *predicate {
+                ast::BoundPredicate(ref bound_pred) => {
+                    visit::walk_ty_param_bounds_helper(&mut collector, &bound_pred.bounds);
+                }
+                ast::EqPredicate(ref eq_pred) => {
+                    collector.visit_ty(&*eq_pred.lhs_ty);
+                    collector.visit_ty(&*eq_pred.rhs_ty);
+                }
+            }
         }
     }
 
@@ -11,13 +11,16 @@
 //! A pass that annotates every item and method with its stability level,
 //! propagating default levels lexically from parent to children ast nodes.
 
+use session::Session;
 use util::nodemap::{NodeMap, DefIdMap};
 use syntax::codemap::Span;
+use syntax::diagnostic::SpanHandler;
 use syntax::{attr, visit};
 use syntax::ast;
 use syntax::ast::{Attribute, Block, Crate, DefId, FnDecl, NodeId, Variant};
 use syntax::ast::{Item, RequiredMethod, ProvidedMethod, TraitItem};
 use syntax::ast::{TypeMethod, Method, Generics, StructField, TypeTraitItem};
+use syntax::ast::ConstTraitItem;
 use syntax::ast_util::is_local;
 use syntax::attr::Stability;
 use syntax::visit::{FnKind, FkMethod, Visitor};
@@ -35,16 +38,17 @@ pub struct Index {
 }
 
 // A private tree-walker for producing an Index.
-struct Annotator {
+struct Annotator<'a> {
+    diagnostic: &'a SpanHandler,
     index: Index,
     parent: Option<Stability>
 }
 
-impl Annotator {
+impl<'a> Annotator<'a> {
     // Determine the stability for a node based on its attributes and inherited
     // stability. The stability is recorded in the index and used as the parent.
-    fn annotate(&mut self, id: NodeId, attrs: &Vec<Attribute>, f: |&mut Annotator|) {
-        match attr::find_stability(attrs.as_slice()) {
+    fn annotate(&mut self, id: NodeId, attrs: &Vec<Attribute>, f: |&mut Annotator<'a>|) {
+        match attr::find_stability_checked(self.diagnostic, attrs.as_slice()) {
             Some(stab) => {
                 self.index.local.insert(id, stab.clone());
 
@@ -65,7 +69,7 @@ impl Annotator {
     }
 }
 
-impl<'v> Visitor<'v> for Annotator {
+impl<'a, 'v> Visitor<'v> for Annotator<'a> {
     fn visit_item(&mut self, i: &Item) {
         self.annotate(i.id, &i.attrs, |v| visit::walk_item(v, i));
 
@@ -98,6 +102,9 @@ impl<'v> Visitor<'v> for Annotator {
             }
 
             TypeTraitItem(ref typedef) => (typedef.ty_param.id, &typedef.attrs),
+            ConstTraitItem(ref associated_const) => {
+                (associated_const.id, &associated_const.attrs)
+            }
         };
         self.annotate(id, attrs, |v| visit::walk_trait_item(v, t));
     }
@@ -113,8 +120,9 @@ impl<'v> Visitor<'v> for Annotator {
 
 impl Index {
     /// Construct the stability index for a crate being compiled.
-    pub fn build(krate: &Crate) -> Index {
+    pub fn build(sess: &Session, krate: &Crate) -> Index {
         let mut annotator = Annotator {
+            diagnostic: sess.diagnostic(),
             index: Index {
                 local: NodeMap::new(),
                 extern_cache: DefIdMap::new()
@@ -18,6 +18,7 @@ use middle::subst;
 use middle::subst::Subst;
 use middle::ty::{mod, Ty};
 use middle::typeck::infer::{mod, InferCtxt};
+use std::rc::Rc;
 use syntax::ast;
 use syntax::codemap::DUMMY_SP;
 use util::ppaux::Repr;
@@ -53,6 +54,43 @@ pub fn impl_can_satisfy(infcx: &InferCtxt,
     selcx.evaluate_impl(impl2_def_id, &obligation)
 }
 
+/// Given two impls that are known to overlap, computes the self type that
+/// both of them apply to, with `_` left for any part that either impl
+/// leaves unconstrained. For example, `impl<T> Foo for Vec<T>` and
+/// `impl<U:Clone> Foo for U` overlap at `Vec<_>`.
+pub fn overlapping_self_ty<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
+                                     impl1_def_id: ast::DefId,
+                                     impl2_def_id: ast::DefId)
+                                     -> Ty<'tcx>
+{
+    let impl1_trait_ref = fresh_trait_ref_for_impl(infcx, impl1_def_id);
+    let impl2_trait_ref = fresh_trait_ref_for_impl(infcx, impl2_def_id);
+
+    // The impls are known to overlap, so unifying the trait references
+    // should succeed; if it somehow doesn't, we just report the self type
+    // of the first impl.
+    let _ = infer::mk_sub_trait_refs(infcx,
+                                     true,
+                                     infer::Misc(DUMMY_SP),
+                                     impl1_trait_ref.clone(),
+                                     impl2_trait_ref);
+
+    infcx.resolve_type_vars_if_possible(impl1_trait_ref.self_ty())
+}
+
+fn fresh_trait_ref_for_impl<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
+                                      impl_def_id: ast::DefId)
+                                      -> Rc<ty::TraitRef<'tcx>>
+{
+    let impl_substs = util::fresh_substs_for_impl(infcx, DUMMY_SP, impl_def_id);
+    let impl_trait_ref =
+        ty::impl_trait_ref(infcx.tcx, impl_def_id).unwrap()
+                                                  .subst(infcx.tcx, &impl_substs);
+    infcx.replace_late_bound_regions_with_fresh_var(DUMMY_SP,
+                                                    infer::FnCall,
+                                                    &impl_trait_ref).0
+}
+
 pub fn impl_is_local(tcx: &ty::ctxt,
                      impl_def_id: ast::DefId)
                      -> bool
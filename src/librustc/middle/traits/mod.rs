@@ -258,6 +258,16 @@ pub fn overlapping_impls(infcx: &InferCtxt,
     coherence::impl_can_satisfy(infcx, impl2_def_id, impl1_def_id)
 }
 
+/// The self type at which two overlapping impls both apply. See
+/// `coherence::overlapping_self_ty`.
+pub fn overlapping_self_ty<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
+                                     impl1_def_id: ast::DefId,
+                                     impl2_def_id: ast::DefId)
+                                     -> Ty<'tcx>
+{
+    coherence::overlapping_self_ty(infcx, impl1_def_id, impl2_def_id)
+}
+
 /// Given generic bounds from an impl like:
 ///
 ///    impl<A:Foo, B:Bar+Qux> ...
@@ -56,7 +56,7 @@ use middle::ty;
 use middle::typeck;
 use middle::ty_fold::{mod, TypeFoldable, TypeFolder, HigherRankedFoldable};
 use middle;
-use util::ppaux::{note_and_explain_region, bound_region_ptr_to_string};
+use util::ppaux::{note_and_explain_region, bound_region_description};
 use util::ppaux::{trait_store_to_string, ty_to_string};
 use util::ppaux::{Repr, UserString};
 use util::common::{indenter, memoized};
@@ -115,6 +115,7 @@ impl ImplOrTraitItemContainer {
 pub enum ImplOrTraitItem<'tcx> {
     MethodTraitItem(Rc<Method<'tcx>>),
     TypeTraitItem(Rc<AssociatedType>),
+    ConstTraitItem(Rc<AssociatedConst<'tcx>>),
 }
 
 impl<'tcx> ImplOrTraitItem<'tcx> {
@@ -124,6 +125,9 @@ impl<'tcx> ImplOrTraitItem<'tcx> {
             TypeTraitItem(ref associated_type) => {
                 TypeTraitItemId(associated_type.def_id)
             }
+            ConstTraitItem(ref associated_const) => {
+                ConstTraitItemId(associated_const.def_id)
+            }
         }
     }
 
@@ -131,6 +135,7 @@ impl<'tcx> ImplOrTraitItem<'tcx> {
         match *self {
             MethodTraitItem(ref method) => method.def_id,
             TypeTraitItem(ref associated_type) => associated_type.def_id,
+            ConstTraitItem(ref associated_const) => associated_const.def_id,
         }
     }
 
@@ -138,6 +143,7 @@ impl<'tcx> ImplOrTraitItem<'tcx> {
         match *self {
             MethodTraitItem(ref method) => method.name,
             TypeTraitItem(ref associated_type) => associated_type.name,
+            ConstTraitItem(ref associated_const) => associated_const.name,
         }
     }
 
@@ -145,13 +151,14 @@ impl<'tcx> ImplOrTraitItem<'tcx> {
         match *self {
             MethodTraitItem(ref method) => method.container,
             TypeTraitItem(ref associated_type) => associated_type.container,
+            ConstTraitItem(ref associated_const) => associated_const.container,
         }
     }
 
     pub fn as_opt_method(&self) -> Option<Rc<Method<'tcx>>> {
         match *self {
             MethodTraitItem(ref m) => Some((*m).clone()),
-            TypeTraitItem(_) => None
+            TypeTraitItem(_) | ConstTraitItem(_) => None
         }
     }
 }
@@ -160,6 +167,7 @@ impl<'tcx> ImplOrTraitItem<'tcx> {
 pub enum ImplOrTraitItemId {
     MethodTraitItemId(ast::DefId),
     TypeTraitItemId(ast::DefId),
+    ConstTraitItemId(ast::DefId),
 }
 
 impl ImplOrTraitItemId {
@@ -167,6 +175,7 @@ impl ImplOrTraitItemId {
         match *self {
             MethodTraitItemId(def_id) => def_id,
             TypeTraitItemId(def_id) => def_id,
+            ConstTraitItemId(def_id) => def_id,
         }
     }
 }
@@ -223,6 +232,18 @@ pub struct AssociatedType {
     pub container: ImplOrTraitItemContainer,
 }
 
+#[deriving(Clone, Show)]
+pub struct AssociatedConst<'tcx> {
+    pub name: ast::Name,
+    pub ty: Ty<'tcx>,
+    pub vis: ast::Visibility,
+    pub def_id: ast::DefId,
+    pub container: ImplOrTraitItemContainer,
+
+    // Whether the const has a value, which is only optional in traits.
+    pub has_value: bool,
+}
+
 #[deriving(Clone, PartialEq, Eq, Hash, Show)]
 pub struct mt<'tcx> {
     pub ty: Ty<'tcx>,
@@ -486,6 +507,11 @@ pub struct ctxt<'tcx> {
     pub ast_ty_to_ty_cache: RefCell<NodeMap<ast_ty_to_ty_cache_entry<'tcx>>>,
     pub enum_var_cache: RefCell<DefIdMap<Rc<Vec<Rc<VariantInfo<'tcx>>>>>>,
     pub ty_param_defs: RefCell<NodeMap<TypeParameterDef<'tcx>>>,
+    /// The types that equality constraints in `where` clauses bind
+    /// associated types to, keyed by the def-ids of the type parameter and
+    /// of the associated type, along with the id of the constraint.
+    pub associated_type_equalities: RefCell<FnvHashMap<(ast::DefId, ast::DefId),
+                                                       (ast::NodeId, Ty<'tcx>)>>,
     pub adjustments: RefCell<NodeMap<AutoAdjustment<'tcx>>>,
     pub normalized_cache: RefCell<FnvHashMap<Ty<'tcx>, Ty<'tcx>>>,
     pub lang_items: middle::lang_items::LanguageItems,
@@ -1521,11 +1547,11 @@ impl<'tcx> ParameterEnvironment<'tcx> {
                                     method_generics,
                                     method.pe_body().id)
                             }
-                            TypeTraitItem(_) => {
+                            TypeTraitItem(_) | ConstTraitItem(_) => {
                                 cx.sess
                                   .bug("ParameterEnvironment::from_item(): \
                                         can't create a parameter environment \
-                                        for type trait items")
+                                        for type or const trait items")
                             }
                         }
                     }
@@ -1534,6 +1560,14 @@ impl<'tcx> ParameterEnvironment<'tcx> {
                                      can't create a parameter environment \
                                      for type impl items")
                     }
+                    ast::ConstImplItem(ref associated_const) => {
+                        let const_generics = lookup_item_type(cx, ast_util::local_def(id)).generics;
+                        construct_parameter_environment(
+                            cx,
+                            associated_const.span,
+                            &const_generics,
+                            associated_const.expr.as_ref().unwrap().id)
+                    }
                 }
             }
             Some(ast_map::NodeTraitItem(trait_method)) => {
@@ -1556,11 +1590,11 @@ impl<'tcx> ParameterEnvironment<'tcx> {
                                     method_generics,
                                     method.pe_body().id)
                             }
-                            TypeTraitItem(_) => {
+                            TypeTraitItem(_) | ConstTraitItem(_) => {
                                 cx.sess
                                   .bug("ParameterEnvironment::from_item(): \
                                         can't create a parameter environment \
-                                        for type trait items")
+                                        for type or const trait items")
                             }
                         }
                     }
@@ -1569,6 +1603,18 @@ impl<'tcx> ParameterEnvironment<'tcx> {
                                      can't create a parameter environment \
                                      for type trait items")
                     }
+                    ast::ConstTraitItem(ref associated_const) => {
+                        let const_generics = lookup_item_type(cx, ast_util::local_def(id)).generics;
+                        let body_id = match associated_const.expr {
+                            Some(ref expr) => expr.id,
+                            None => id,
+                        };
+                        construct_parameter_environment(
+                            cx,
+                            associated_const.span,
+                            &const_generics,
+                            body_id)
+                    }
                 }
             }
             Some(ast_map::NodeItem(item)) => {
@@ -1715,6 +1761,7 @@ pub fn mk_ctxt<'tcx>(s: Session,
         trait_items_cache: RefCell::new(DefIdMap::new()),
         impl_trait_cache: RefCell::new(DefIdMap::new()),
         ty_param_defs: RefCell::new(NodeMap::new()),
+        associated_type_equalities: RefCell::new(FnvHashMap::new()),
         adjustments: RefCell::new(NodeMap::new()),
         normalized_cache: RefCell::new(FnvHashMap::new()),
         lang_items: lang_items,
@@ -3891,7 +3938,7 @@ pub fn expr_kind(tcx: &ctxt, expr: &ast::Expr) -> ExprKind {
                 def::DefUpvar(..) |
                 def::DefLocal(..) => LvalueExpr,
 
-                def::DefConst(..) => RvalueDatumExpr,
+                def::DefConst(..) | def::DefAssociatedConst(..) => RvalueDatumExpr,
 
                 def => {
                     tcx.sess.span_bug(
@@ -3928,6 +3975,12 @@ pub fn expr_kind(tcx: &ctxt, expr: &ast::Expr) -> ExprKind {
         ast::ExprIfLet(..) => {
             tcx.sess.span_bug(expr.span, "non-desugared ExprIfLet");
         }
+        ast::ExprTry(..) => {
+            tcx.sess.span_bug(expr.span, "non-desugared ExprTry");
+        }
+        ast::ExprInclusiveRange(..) => {
+            tcx.sess.span_bug(expr.span, "non-desugared ExprInclusiveRange");
+        }
         ast::ExprWhileLet(..) => {
             tcx.sess.span_bug(expr.span, "non-desugared ExprWhileLet");
         }
@@ -3966,7 +4019,6 @@ pub fn expr_kind(tcx: &ctxt, expr: &ast::Expr) -> ExprKind {
         ast::ExprAgain(..) |
         ast::ExprRet(..) |
         ast::ExprWhile(..) |
-        ast::ExprLoop(..) |
         ast::ExprAssign(..) |
         ast::ExprInlineAsm(..) |
         ast::ExprAssignOp(..) |
@@ -3974,7 +4026,13 @@ pub fn expr_kind(tcx: &ctxt, expr: &ast::Expr) -> ExprKind {
             RvalueStmtExpr
         }
 
+        // A `loop` may produce a value through `break`.
+        ast::ExprLoop(..) => {
+            RvalueDpsExpr
+        }
+
         ast::ExprLit(_) | // Note: LitStr is carved out above
+        ast::ExprType(..) |
         ast::ExprUnary(..) |
         ast::ExprAddrOf(..) |
         ast::ExprBinary(..) => {
@@ -4145,14 +4203,12 @@ pub fn type_err_to_str<'tcx>(cx: &ctxt<'tcx>, err: &type_err<'tcx>) -> String {
             "lifetimes do not intersect".to_string()
         }
         terr_regions_insufficiently_polymorphic(br, _) => {
-            format!("expected bound lifetime parameter {}, \
-                     found concrete lifetime",
-                    bound_region_ptr_to_string(cx, br))
+            format!("expected {}, found concrete lifetime",
+                    bound_region_description(br))
         }
         terr_regions_overly_polymorphic(br, _) => {
-            format!("expected concrete lifetime, \
-                     found bound lifetime parameter {}",
-                    bound_region_ptr_to_string(cx, br))
+            format!("expected concrete lifetime, found {}",
+                    bound_region_description(br))
         }
         terr_trait_stores_differ(_, ref values) => {
             format!("trait storage differs: expected `{}`, found `{}`",
@@ -4264,7 +4320,7 @@ pub fn provided_trait_methods<'tcx>(cx: &ctxt<'tcx>, id: ast::DefId)
                                     cx,
                                     ast_util::local_def(m.id)) {
                                 MethodTraitItem(m) => m,
-                                TypeTraitItem(_) => {
+                                TypeTraitItem(_) | ConstTraitItem(_) => {
                                     cx.sess.bug("provided_trait_methods(): \
                                                  split_trait_methods() put \
                                                  associated types in the \
@@ -4357,7 +4413,7 @@ pub fn is_associated_type(cx: &ctxt, id: ast::DefId) -> bool {
                 Some(ref item) => {
                     match **item {
                         TypeTraitItem(_) => true,
-                        MethodTraitItem(_) => false,
+                        MethodTraitItem(_) | ConstTraitItem(_) => false,
                     }
                 }
                 None => false,
@@ -4662,9 +4718,9 @@ pub fn enum_variants<'tcx>(cx: &ctxt<'tcx>, id: ast::DefId)
                                             }
                                             Err(ref err) => {
                                                 cx.sess
-                                                  .span_err(e.span,
+                                                  .span_err(err.span,
                                                             format!("expected constant: {}",
-                                                                    *err).as_slice());
+                                                                    err.msg).as_slice());
                                             }
                                         },
                                     None => {}
@@ -5286,7 +5342,7 @@ pub fn populate_implementations_for_type_if_necessary(tcx: &ctxt,
                            .insert(method_def_id, source);
                     }
                 }
-                TypeTraitItem(_) => {}
+                TypeTraitItem(_) | ConstTraitItem(_) => {}
             }
         }
 
@@ -5334,7 +5390,7 @@ pub fn populate_implementations_for_trait_if_necessary(
                            .insert(method_def_id, source);
                     }
                 }
-                TypeTraitItem(_) => {}
+                TypeTraitItem(_) | ConstTraitItem(_) => {}
             }
         }
 
@@ -5565,6 +5621,16 @@ impl Variance {
             Bivariant => "*",
         }
     }
+
+    /// The name of the variance, for use in diagnostics.
+    pub fn description(self) -> &'static str {
+        match self {
+            Covariant => "covariant",
+            Contravariant => "contravariant",
+            Invariant => "invariant",
+            Bivariant => "bivariant",
+        }
+    }
 }
 
 /// Construct a parameter environment suitable for static contexts or other contexts where there
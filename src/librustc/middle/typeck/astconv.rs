@@ -995,10 +995,10 @@ pub fn ast_ty_to_ty<'tcx, AC: AstConv<'tcx>, RS: RegionScope>(
                     }
                     Err(ref r) => {
                         tcx.sess.span_fatal(
-                            ast_ty.span,
+                            r.span,
                             format!("expected constant expr for array \
                                      length: {}",
-                                    *r).as_slice());
+                                    r.msg).as_slice());
                     }
                 }
             }
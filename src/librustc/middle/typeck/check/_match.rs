@@ -8,6 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use lint;
 use middle::def;
 use middle::pat_util::{PatIdMap, pat_id_map, pat_is_binding, pat_is_const};
 use middle::subst::{Subst, Substs};
@@ -55,7 +56,7 @@ pub fn check_pat<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>,
                 match valid_range_bounds(fcx.ccx, &**begin, &**end) {
                     Some(false) => {
                         span_err!(tcx.sess, begin.span, E0030,
-                            "lower range bound must be less than upper");
+                            "lower range bound must be less than or equal to upper");
                     },
                     None => {
                         span_err!(tcx.sess, begin.span, E0031,
@@ -347,7 +348,7 @@ pub fn check_pat_struct<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>, pat: &ast::Pat,
         .unwrap_or_else(|| Substs::empty());
 
     let struct_fields = ty::struct_fields(tcx, variant_def_id, &item_substs);
-    check_struct_pat_fields(pcx, pat.span, fields, struct_fields.as_slice(),
+    check_struct_pat_fields(pcx, pat.id, pat.span, fields, struct_fields.as_slice(),
                             variant_def_id, etc);
 }
 
@@ -432,12 +433,13 @@ pub fn check_pat_enum<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>, pat: &ast::Pat,
     }
 }
 
-/// `path` is the AST path item naming the type of this struct.
+/// `pat_id` and `span` are the ID and span of the struct pattern.
 /// `fields` is the field patterns of the struct pattern.
 /// `struct_fields` describes the type of each field of the struct.
 /// `struct_id` is the ID of the struct.
 /// `etc` is true if the pattern said '...' and false otherwise.
 pub fn check_struct_pat_fields<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>,
+                                         pat_id: ast::NodeId,
                                          span: Span,
                                          fields: &[Spanned<ast::FieldPat>],
                                          struct_fields: &[ty::field<'tcx>],
@@ -484,12 +486,25 @@ pub fn check_struct_pat_fields<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>,
 
     // Report an error if not all the fields were specified.
     if !etc {
+        let mut missing_any = false;
         for field in struct_fields
             .iter()
             .filter(|field| !used_fields.contains_key(&field.name)) {
             span_err!(tcx.sess, span, E0027,
                 "pattern does not mention field `{}`",
                 token::get_name(field.name));
+            missing_any = true;
         }
+        if missing_any {
+            tcx.sess.span_help(span, "add `..` after the named fields to ignore the \
+                                      remaining fields");
+        }
+    } else if !struct_fields.is_empty() &&
+              struct_fields.iter().all(|field| used_fields.contains_key(&field.name)) {
+        // Macros may add `..` whatever fields they name, so this is only a lint
+        tcx.sess.add_lint(lint::builtin::REDUNDANT_PATTERN_REST, pat_id, span,
+                          format!("every field of `{}` is named in the pattern, so `..` \
+                                   has nothing to ignore",
+                                  ty::item_path_str(tcx, struct_id)));
     }
 }
@@ -29,6 +29,7 @@ use std::rc::Rc;
 use syntax::ast::{DefId};
 use syntax::ast;
 use syntax::codemap::Span;
+use syntax::ptr::P;
 
 pub use self::MethodError::*;
 pub use self::CandidateSource::*;
@@ -48,7 +49,7 @@ pub enum MethodError {
 
 // A pared down enum describing just the places from which a method
 // candidate can arise. Used for error reporting only.
-#[deriving(PartialOrd, Ord, PartialEq, Eq)]
+#[deriving(Clone, PartialOrd, Ord, PartialEq, Eq)]
 pub enum CandidateSource {
     ImplSource(ast::DefId),
     TraitSource(/* trait id */ ast::DefId),
@@ -296,10 +297,13 @@ pub fn lookup_in_trait_adjusted<'a, 'tcx>(fcx: &'a FnCtxt<'a, 'tcx>,
     Some(callee)
 }
 
+/// Reports a failed method lookup. `args` are the arguments of the method
+/// call, starting with the receiver.
 pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                               span: Span,
                               rcvr_ty: Ty<'tcx>,
                               method_name: ast::Name,
+                              args: &[P<ast::Expr>],
                               error: MethodError)
 {
     match error {
@@ -348,7 +352,8 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
             span_err!(fcx.sess(), span, E0034,
                       "multiple applicable methods in scope");
 
-            report_candidates(fcx, span, method_name, sources);
+            report_candidates(fcx, span, method_name, sources.clone());
+            suggest_disambiguation(fcx, span, rcvr_ty, method_name, args, sources);
         }
     }
 
@@ -394,6 +399,63 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
             }
         }
     }
+
+    // Suggests calling each candidate that comes from a trait through the
+    // path of that trait, passing the receiver explicitly.
+    fn suggest_disambiguation<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                        span: Span,
+                                        rcvr_ty: Ty<'tcx>,
+                                        method_name: ast::Name,
+                                        args: &[P<ast::Expr>],
+                                        mut sources: Vec<CandidateSource>) {
+        sources.sort();
+        sources.dedup();
+
+        let tcx = fcx.tcx();
+        let snippets: Option<Vec<String>> = args.iter().map(|arg| {
+            tcx.sess.codemap().span_to_snippet(arg.span)
+        }).collect();
+        let snippets = match snippets {
+            Some(snippets) => snippets,
+            None => return
+        };
+
+        for (idx, source) in sources.iter().enumerate() {
+            let trait_did = match *source {
+                ImplSource(impl_did) => match impl_trait_ref(tcx, impl_did) {
+                    Some(trait_ref) => trait_ref.def_id,
+                    None => continue
+                },
+                TraitSource(trait_did) => trait_did
+            };
+            let method = match trait_method(tcx, trait_did, method_name) {
+                Some((_, method)) => method,
+                None => continue
+            };
+
+            // The receiver is not auto-referenced when the method is called
+            // through a path, so borrow it as the method expects.
+            let rcvr_is_ref = match rcvr_ty.sty {
+                ty_rptr(..) => true,
+                _ => false
+            };
+            let borrow = match method.explicit_self {
+                ByReferenceExplicitSelfCategory(_, ast::MutImmutable) if !rcvr_is_ref => "&",
+                ByReferenceExplicitSelfCategory(_, ast::MutMutable) if !rcvr_is_ref => "&mut ",
+                _ => ""
+            };
+            let mut call_args = vec![format!("{}{}", borrow, snippets[0])];
+            call_args.push_all(snippets[1..]);
+
+            fcx.sess().span_help(
+                span,
+                format!("to use candidate #{}, write `{}::{}({})` instead",
+                        idx + 1u,
+                        ty::item_path_str(tcx, trait_did),
+                        method_name.user_string(tcx),
+                        call_args.connect(", ")).as_slice());
+        }
+    }
 }
 
 /// Find method with name `method_name` defined in `trait_def_id` and return it, along with its
@@ -741,7 +741,7 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
         }
 
         if applicable_candidates.len() > 1 {
-            let sources = probes.iter().map(|p| p.to_source()).collect();
+            let sources = applicable_candidates.iter().map(|p| p.to_source()).collect();
             return Some(Err(Ambiguity(sources)));
         }
 
@@ -1019,7 +1019,7 @@ fn get_method_index<'tcx>(tcx: &ty::ctxt<'tcx>,
             for trait_item in trait_items.iter() {
                 match *trait_item {
                     ty::MethodTraitItem(_) => method_count += 1,
-                    ty::TypeTraitItem(_) => {}
+                    ty::TypeTraitItem(_) | ty::ConstTraitItem(_) => {}
                 }
             }
             true
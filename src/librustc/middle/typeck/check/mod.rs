@@ -110,7 +110,8 @@ use std::collections::hash_map::{Occupied, Vacant};
 use std::mem::replace;
 use std::rc::Rc;
 use syntax::{mod, abi, attr};
-use syntax::ast::{mod, ProvidedMethod, RequiredMethod, TypeTraitItem};
+use syntax::ast::{mod, ProvidedMethod, RequiredMethod, TypeTraitItem, ConstTraitItem};
+use syntax::ast_map;
 use syntax::ast_util::{mod, local_def, PostExpansionMethod};
 use syntax::codemap::{mod, Span};
 use syntax::owned_slice::OwnedSlice;
@@ -267,6 +268,12 @@ pub struct FnCtxt<'a, 'tcx: 'a> {
 
     ps: RefCell<FnStyleState>,
 
+    // The loops enclosing the expression being checked, innermost last,
+    // paired with the type of the values their `break`s carry. `while`,
+    // `while let` and `for` loops cannot break with a value and have `None`
+    // here.
+    loop_scopes: RefCell<Vec<(ast::NodeId, Option<Ty<'tcx>>)>>,
+
     inh: &'a Inherited<'a, 'tcx>,
 
     ccx: &'a CrateCtxt<'a, 'tcx>,
@@ -339,6 +346,7 @@ pub fn blank_fn_ctxt<'a, 'tcx>(ccx: &'a CrateCtxt<'a, 'tcx>,
         err_count_on_creation: ccx.tcx.sess.err_count(),
         ret_ty: rty,
         ps: RefCell::new(FnStyleState::function(ast::NormalFn, 0)),
+        loop_scopes: RefCell::new(Vec::new()),
         inh: inh,
         ccx: ccx
     }
@@ -392,6 +400,8 @@ fn check_bare_fn<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
             let fcx = check_fn(ccx, fn_ty.fn_style, id, &fn_ty.sig,
                                decl, id, body, &inh);
 
+            vtable::select_fcx_obligations_where_possible(&fcx);
+            fcx.default_integer_type_variables();
             vtable::select_all_fcx_obligations_or_error(&fcx);
             regionck::regionck_fn(&fcx, id, body);
             fcx.default_diverging_type_variables_to_nil();
@@ -522,6 +532,7 @@ fn check_fn<'a, 'tcx>(ccx: &'a CrateCtxt<'a, 'tcx>,
         err_count_on_creation: err_count_on_creation,
         ret_ty: ret_ty,
         ps: RefCell::new(FnStyleState::function(fn_style, fn_style_id)),
+        loop_scopes: RefCell::new(Vec::new()),
         inh: inherited,
         ccx: ccx
     };
@@ -638,6 +649,10 @@ pub fn check_item(ccx: &CrateCtxt, it: &ast::Item) {
                 ast::TypeImplItem(_) => {
                     // Nothing to do here.
                 }
+                ast::ConstImplItem(ref associated_const) => {
+                    let expr = associated_const.expr.as_ref().unwrap();
+                    check_const(ccx, associated_const.span, &**expr, associated_const.id);
+                }
             }
         }
 
@@ -656,6 +671,11 @@ pub fn check_item(ccx: &CrateCtxt, it: &ast::Item) {
                 TypeTraitItem(_) => {
                     // Nothing to do.
                 }
+                ConstTraitItem(ref associated_const) => {
+                    for expr in associated_const.expr.iter() {
+                        check_const(ccx, associated_const.span, &**expr, associated_const.id);
+                    }
+                }
             }
         }
       }
@@ -837,6 +857,58 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                     }
                 }
             }
+            ast::ConstImplItem(ref associated_const) => {
+                let impl_const_ty = ty::impl_or_trait_item(ccx.tcx,
+                                                           local_def(associated_const.id));
+
+                // If this is an impl of an associated constant, find the
+                // corresponding constant in the trait.
+                let opt_trait_const =
+                    trait_items.iter()
+                               .find(|ti| {
+                                   ti.name() == impl_const_ty.name()
+                               });
+                match opt_trait_const {
+                    Some(trait_const) => {
+                        match (trait_const, &impl_const_ty) {
+                            (&ty::ConstTraitItem(ref trait_const),
+                             &ty::ConstTraitItem(ref impl_const)) => {
+                                compare_impl_const(ccx.tcx,
+                                                   &**impl_const,
+                                                   associated_const.span,
+                                                   &**trait_const,
+                                                   impl_trait_ref);
+                            }
+                            _ => {
+                                // This is `span_bug` as it should have
+                                // already been caught in resolve.
+                                tcx.sess
+                                   .span_bug(associated_const.span,
+                                             format!("item `{}` is of a \
+                                                      different kind from \
+                                                      its trait `{}`",
+                                                     token::get_name(
+                                                        impl_const_ty.name()),
+                                                     pprust::path_to_string(
+                                                        &ast_trait_ref.path))
+                                             .as_slice());
+                            }
+                        }
+                    }
+                    None => {
+                        // This is `span_bug` as it should have already been
+                        // caught in resolve.
+                        tcx.sess.span_bug(
+                            associated_const.span,
+                            format!(
+                                "associated constant `{}` is not a member of \
+                                 trait `{}`",
+                                token::get_name(impl_const_ty.name()),
+                                pprust::path_to_string(
+                                    &ast_trait_ref.path)).as_slice());
+                    }
+                }
+            }
         }
     }
 
@@ -853,7 +925,7 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                             ast::MethodImplItem(ref m) => {
                                 m.pe_ident().name == trait_method.name
                             }
-                            ast::TypeImplItem(_) => false,
+                            ast::TypeImplItem(_) | ast::ConstImplItem(_) => false,
                         }
                     });
                 let is_provided =
@@ -869,13 +941,26 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                         ast::TypeImplItem(ref typedef) => {
                             typedef.ident.name == associated_type.name
                         }
-                        ast::MethodImplItem(_) => false,
+                        ast::MethodImplItem(_) | ast::ConstImplItem(_) => false,
                     }
                 });
                 if !is_implemented {
                     missing_methods.push(format!("`{}`", token::get_name(associated_type.name)));
                 }
             }
+            ty::ConstTraitItem(ref associated_const) => {
+                let is_implemented = impl_items.iter().any(|ii| {
+                    match *ii {
+                        ast::ConstImplItem(ref c) => {
+                            c.ident.name == associated_const.name
+                        }
+                        ast::MethodImplItem(_) | ast::TypeImplItem(_) => false,
+                    }
+                });
+                if !is_implemented && !associated_const.has_value {
+                    missing_methods.push(format!("`{}`", token::get_name(associated_const.name)));
+                }
+            }
         }
     }
 
@@ -886,6 +971,34 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
     }
 }
 
+/// Checks that the type of an associated constant in an impl matches the
+/// type declared in the trait, after substituting the impl's trait
+/// parameters and `Self`.
+fn compare_impl_const<'tcx>(tcx: &ty::ctxt<'tcx>,
+                            impl_c: &ty::AssociatedConst<'tcx>,
+                            impl_c_span: Span,
+                            trait_c: &ty::AssociatedConst<'tcx>,
+                            impl_trait_ref: &ty::TraitRef<'tcx>) {
+    debug!("compare_impl_const(impl_trait_ref={})",
+           impl_trait_ref.repr(tcx));
+
+    let infcx = infer::new_infer_ctxt(tcx);
+    let trait_ty = trait_c.ty.subst(tcx, &impl_trait_ref.substs);
+    match infer::mk_eqty(&infcx, false, infer::Misc(impl_c_span),
+                         impl_c.ty, trait_ty) {
+        Ok(()) => {}
+        Err(ref terr) => {
+            tcx.sess.span_err(
+                impl_c_span,
+                format!("associated constant `{}` has an incompatible type \
+                         for trait: {}",
+                        token::get_name(trait_c.name),
+                        ty::type_err_to_str(tcx, terr)).as_slice());
+            ty::note_and_explain_type_err(tcx, terr);
+        }
+    }
+}
+
 /// Checks that a method from an impl conforms to the signature of
 /// the same method as declared in the trait.
 ///
@@ -1619,6 +1732,49 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         }
     }
 
+    /// Gives the integer literals whose type is still unconstrained the
+    /// fallback type `i32`. Literals are visited in source order, and each
+    /// one whose type is decided this way is reported by the
+    /// `integer_fallback` lint. Literals that share a type variable with an
+    /// earlier one are already resolved by then, so each variable is
+    /// reported once.
+    pub fn default_integer_type_variables(&self) {
+        // Integer type variables are only ever created for unsuffixed
+        // literals, so defaulting the literals' types covers all of them.
+        let mut literals: Vec<(ast::NodeId, Span, Ty<'tcx>)> =
+            self.inh.node_types.borrow().iter().filter_map(|(&id, &ty)| {
+                match ty.sty {
+                    ty::ty_infer(ty::IntVar(_)) => {}
+                    _ => return None,
+                }
+                match self.tcx().map.find(id) {
+                    Some(ast_map::NodeExpr(expr)) => match expr.node {
+                        ast::ExprLit(ref lit) => Some((id, lit.span, ty)),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }).collect();
+        literals.sort_by(|&(a, _, _), &(b, _, _)| a.cmp(&b));
+
+        let fallback_ty = ty::mk_mach_int(ast::TyI32);
+        for &(id, span, ty) in literals.iter() {
+            match self.infcx().resolve_type_vars_if_possible(ty).sty {
+                ty::ty_infer(ty::IntVar(_)) => {}
+                _ => continue,
+            }
+
+            demand::eqtype(self, span, ty, fallback_ty);
+            self.tcx().sess.add_lint(lint::builtin::INTEGER_FALLBACK,
+                                     id,
+                                     span,
+                                     format!("the type of this integer literal is not \
+                                              constrained, so it defaults to `{}`",
+                                             ppaux::ty_to_string(self.tcx(),
+                                                                 fallback_ty)));
+        }
+    }
+
     pub fn default_diverging_type_variables_to_nil(&self) {
         for (_, &ref ty) in self.inh.node_types.borrow_mut().iter_mut() {
             if self.infcx().type_var_diverges(self.infcx().resolve_type_vars_if_possible(*ty)) {
@@ -3090,6 +3246,54 @@ fn check_expr_with_unifier<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         write_call(fcx, call_expr, fn_sig.output);
     }
 
+    // Checks that the type of an inline asm operand is one that can be
+    // passed for its constraint: scalars and thin pointers can go in
+    // registers, `#[repr(C)]` aggregates only in memory, and immediates
+    // must be integers. `span` covers the constraint and the operand.
+    fn check_asm_operand<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                   constraint: &str,
+                                   operand: &ast::Expr,
+                                   span: Span) {
+        let tcx = fcx.tcx();
+        let ty = fcx.infcx().resolve_type_vars_if_possible(fcx.expr_ty(operand));
+        let kind = constraint.trim_left_chars(|c: char| {
+            c == '=' || c == '+' || c == '&' || c == '*'
+        });
+        let in_memory = !kind.starts_with("{") && kind.contains_char('m');
+        let immediate = kind == "i" || kind == "n";
+
+        let reason = match ty.sty {
+            ty::ty_err | ty::ty_infer(ty::TyVar(_)) => return,
+            _ if ty::type_is_nil(ty) => "`()` has no value to pass",
+            ty::ty_str | ty::ty_vec(_, None) | ty::ty_trait(..) => {
+                "values of unsized types cannot be passed to asm"
+            }
+            ty::ty_param(..) => "the layout of a type parameter is not known",
+            _ if immediate => {
+                if ty::type_is_integral(ty) { return }
+                "immediate constraints take an integer"
+            }
+            _ if ty::type_is_fat_ptr(tcx, ty) => {
+                "pointers to unsized values are two words wide and do not fit in a register"
+            }
+            _ if ty::type_is_scalar(ty) || ty::type_is_c_like_enum(tcx, ty) => return,
+            ty::ty_rptr(..) | ty::ty_uniq(_) => return,
+            ty::ty_struct(did, _) if !ty::lookup_repr_hints(tcx, did)
+                                            .contains(&attr::ReprExtern) => {
+                "only `#[repr(C)]` structs have a layout that asm can rely on"
+            }
+            ty::ty_struct(..) | ty::ty_vec(_, Some(_)) => {
+                if in_memory { return }
+                "aggregates must be passed in memory, with an `m` constraint"
+            }
+            _ => "only scalars, pointers and `#[repr(C)]` aggregates can be passed to asm"
+        };
+
+        span_err!(tcx.sess, span, E0178,
+                  "cannot use a value of type `{}` for the asm constraint `{}`: {}",
+                  fcx.infcx().ty_to_string(ty), constraint, reason);
+    }
+
     // Checks a method call.
     fn check_method_call(fcx: &FnCtxt,
                          expr: &ast::Expr,
@@ -3120,7 +3324,8 @@ fn check_expr_with_unifier<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                 method_ty
             }
             Err(error) => {
-                method::report_error(fcx, method_name.span, expr_t, method_name.node.name, error);
+                method::report_error(fcx, method_name.span, expr_t,
+                                     method_name.node.name, args, error);
                 fcx.write_error(expr.id);
                 ty::mk_err()
             }
@@ -3964,16 +4169,25 @@ fn check_expr_with_unifier<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
           constrain_path_type_parameters(fcx, expr);
       }
       ast::ExprInlineAsm(ref ia) => {
-          for &(_, ref input) in ia.inputs.iter() {
+          for &(ref constraint, ref input, sp) in ia.inputs.iter() {
               check_expr(fcx, &**input);
+              check_asm_operand(fcx, constraint.get(), &**input, sp);
           }
-          for &(_, ref out, _) in ia.outputs.iter() {
+          for &(ref constraint, ref out, _, sp) in ia.outputs.iter() {
               check_expr(fcx, &**out);
+              check_asm_operand(fcx, constraint.get(), &**out, sp);
+          }
+          if ia.noreturn {
+              fcx.write_ty(id, fcx.infcx().next_diverging_ty_var());
+          } else {
+              fcx.write_nil(id);
           }
-          fcx.write_nil(id);
       }
       ast::ExprMac(_) => tcx.sess.bug("unexpanded macro"),
-      ast::ExprBreak(_) => { fcx.write_ty(id, fcx.infcx().next_diverging_ty_var()); }
+      ast::ExprBreak(opt_label, ref opt_value) => {
+        check_break_value(fcx, expr, opt_label, opt_value.as_ref().map(|e| &**e));
+        fcx.write_ty(id, fcx.infcx().next_diverging_ty_var());
+      }
       ast::ExprAgain(_) => { fcx.write_ty(id, fcx.infcx().next_diverging_ty_var()); }
       ast::ExprRet(ref expr_opt) => {
         match fcx.ret_ty {
@@ -4037,7 +4251,9 @@ fn check_expr_with_unifier<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
       }
       ast::ExprWhile(ref cond, ref body, _) => {
         check_expr_has_type(fcx, &**cond, ty::mk_bool());
+        fcx.loop_scopes.borrow_mut().push((id, None));
         check_block_no_value(fcx, &**body);
+        fcx.loop_scopes.borrow_mut().pop();
         let cond_ty = fcx.expr_ty(&**cond);
         let body_ty = fcx.node_ty(body.id);
         if ty::type_is_error(cond_ty) || ty::type_is_error(body_ty) {
@@ -4047,6 +4263,12 @@ fn check_expr_with_unifier<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
             fcx.write_nil(id);
         }
       }
+      ast::ExprTry(..) => {
+        tcx.sess.span_bug(expr.span, "non-desugared ExprTry");
+      }
+      ast::ExprInclusiveRange(..) => {
+        tcx.sess.span_bug(expr.span, "non-desugared ExprInclusiveRange");
+      }
       ast::ExprWhileLet(..) => {
         tcx.sess.span_bug(expr.span, "non-desugared ExprWhileLet");
       }
@@ -4061,15 +4283,28 @@ fn check_expr_with_unifier<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         };
         _match::check_pat(&pcx, &**pat, typ);
 
+        fcx.loop_scopes.borrow_mut().push((id, None));
         check_block_no_value(fcx, &**block);
+        fcx.loop_scopes.borrow_mut().pop();
+        fcx.write_nil(id);
+      }
+      ast::ExprLoop(ref body, _) if is_desugared_while_let(&**body) => {
+        fcx.loop_scopes.borrow_mut().push((id, None));
+        check_block_no_value(fcx, &**body);
+        fcx.loop_scopes.borrow_mut().pop();
         fcx.write_nil(id);
       }
       ast::ExprLoop(ref body, _) => {
+        let break_ty = fcx.infcx().next_ty_var();
+        fcx.loop_scopes.borrow_mut().push((id, Some(break_ty)));
         check_block_no_value(fcx, &**body);
+        fcx.loop_scopes.borrow_mut().pop();
         if !may_break(tcx, expr.id, &**body) {
             fcx.write_ty(id, fcx.infcx().next_diverging_ty_var());
         } else {
-            fcx.write_nil(id);
+            // Every `break` out of this loop has unified its value (or `()`)
+            // with `break_ty`.
+            fcx.write_ty(id, break_ty);
         }
       }
       ast::ExprMatch(ref discrim, ref arms, _) => {
@@ -4125,6 +4360,15 @@ fn check_expr_with_unifier<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         }
         check_cast(fcx, expr, &**e, &**t);
       }
+      ast::ExprType(ref e, ref t) => {
+        let t_ty = fcx.to_ty(&**t);
+        check_expr_coercable_to_type(fcx, &**e, t_ty);
+        if ty::type_is_error(fcx.expr_ty(&**e)) {
+            fcx.write_error(id);
+        } else {
+            fcx.write_ty(id, t_ty);
+        }
+      }
       ast::ExprVec(ref args) => {
         let uty = match expected {
             ExpectHasType(uty) => {
@@ -4708,6 +4952,8 @@ pub fn check_const_with_ty<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
 
     check_expr_with_hint(fcx, e, declty);
     demand::coerce(fcx, e.span, declty, e);
+    vtable::select_fcx_obligations_where_possible(fcx);
+    fcx.default_integer_type_variables();
     vtable::select_all_fcx_obligations_or_error(fcx);
     regionck::regionck_expr(fcx, e);
     writeback::resolve_type_vars_in_expr(fcx, e);
@@ -4879,8 +5125,8 @@ pub fn check_enum_variants(ccx: &CrateCtxt,
                                 "expected signed integer constant");
                         }
                         Err(ref err) => {
-                            span_err!(ccx.tcx.sess, e.span, E0080,
-                                "expected constant: {}", *err);
+                            span_err!(ccx.tcx.sess, err.span, E0080,
+                                "expected constant: {}", err.msg);
                         }
                     }
                 },
@@ -4967,7 +5213,7 @@ pub fn polytype_for_def<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
       }
       def::DefFn(id, _) | def::DefStaticMethod(id, _) | def::DefMethod(id, _, _) |
       def::DefStatic(id, _) | def::DefVariant(_, id, _) |
-      def::DefStruct(id) | def::DefConst(id) => {
+      def::DefStruct(id) | def::DefConst(id) | def::DefAssociatedConst(id, _) => {
         return ty::lookup_item_type(fcx.ccx.tcx, id);
       }
       def::DefTrait(_) |
@@ -5069,6 +5315,9 @@ pub fn instantiate_path<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     //    `SomeStruct::<A>`, contains parameters in TypeSpace, and the
     //    final segment, `foo::<B>` contains parameters in fn space.
     //
+    //    Associated constants are handled the same way, except that
+    //    they never declare parameters of their own.
+    //
     // The first step then is to categorize the segments appropriately.
 
     assert!(path.segments.len() >= 1);
@@ -5098,8 +5347,9 @@ pub fn instantiate_path<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
             segment_spaces.push(Some(subst::FnSpace));
         }
 
-        // Case 3. Reference to a method.
-        def::DefStaticMethod(..) => {
+        // Case 3. Reference to a method or associated constant.
+        def::DefStaticMethod(..) |
+        def::DefAssociatedConst(..) => {
             assert!(path.segments.len() >= 2);
             segment_spaces = Vec::from_elem(path.segments.len() - 2, None);
             segment_spaces.push(Some(subst::TypeSpace));
@@ -5476,13 +5726,68 @@ pub fn structure_of<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>, sp: Span, typ: Ty<'tcx>)
     &structurally_resolved_type(fcx, sp, typ).sty
 }
 
+/// Checks the value carried by a `break` against the type of the loop it
+/// exits. A `break` without a value out of a `loop` gives it type `()`.
+fn check_break_value<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                               expr: &ast::Expr,
+                               opt_label: Option<ast::Ident>,
+                               opt_value: Option<&ast::Expr>) {
+    let target = {
+        let loop_scopes = fcx.loop_scopes.borrow();
+        match opt_label {
+            None => loop_scopes.last().map(|&(_, ty)| ty),
+            Some(_) => {
+                match fcx.tcx().def_map.borrow().get(&expr.id) {
+                    Some(&def::DefLabel(loop_id)) => {
+                        loop_scopes.iter()
+                                   .find(|&&(id, _)| id == loop_id)
+                                   .map(|&(_, ty)| ty)
+                    }
+                    _ => None
+                }
+            }
+        }
+    };
+
+    match (target, opt_value) {
+        (Some(Some(break_ty)), Some(value)) => {
+            check_expr_coercable_to_type(fcx, value, break_ty);
+        }
+        (Some(Some(break_ty)), None) => {
+            demand::suptype(fcx, expr.span, break_ty, ty::mk_nil(fcx.tcx()));
+        }
+        (Some(None), Some(value)) => {
+            check_expr(fcx, value);
+            span_err!(fcx.tcx().sess, value.span, E0174,
+                      "`break` with a value is only allowed inside a `loop`");
+        }
+        (None, Some(value)) => {
+            // Not inside a loop at all; check_loop reports that.
+            check_expr(fcx, value);
+        }
+        (_, None) => {}
+    }
+}
+
+// Returns true if `body` is the body of the `loop` that a `while let` is
+// desugared to.
+fn is_desugared_while_let(body: &ast::Block) -> bool {
+    match body.expr {
+        Some(ref e) if body.stmts.is_empty() => match e.node {
+            ast::ExprMatch(_, _, ast::MatchWhileLetDesugar) => true,
+            _ => false
+        },
+        _ => false
+    }
+}
+
 // Returns true if b contains a break that can exit from b
 pub fn may_break(cx: &ty::ctxt, id: ast::NodeId, b: &ast::Block) -> bool {
     // First: is there an unlabeled break immediately
     // inside the loop?
     (loop_query(&*b, |e| {
         match *e {
-            ast::ExprBreak(_) => true,
+            ast::ExprBreak(..) => true,
             _ => false
         }
     })) ||
@@ -5490,7 +5795,7 @@ pub fn may_break(cx: &ty::ctxt, id: ast::NodeId, b: &ast::Block) -> bool {
    // <id> nested anywhere inside the loop?
     (block_query(b, |e| {
         match e.node {
-            ast::ExprBreak(Some(_)) => {
+            ast::ExprBreak(Some(_), _) => {
                 match cx.def_map.borrow().get(&e.id) {
                     Some(&def::DefLabel(loop_id)) if id == loop_id => true,
                     _ => false,
@@ -5773,6 +6078,10 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
             "return_address" => (0, vec![], ty::mk_imm_ptr(tcx, ty::mk_u8())),
 
             "assume" => (0, vec![ty::mk_bool()], ty::mk_nil(tcx)),
+            "likely" | "unlikely" => (0, vec![ty::mk_bool()], ty::mk_bool()),
+            "prefetch_read_data" | "prefetch_write_data" |
+            "prefetch_read_instruction" | "prefetch_write_instruction" =>
+                (1, vec![ty::mk_imm_ptr(tcx, param(ccx, 0)), ty::mk_i32()], ty::mk_nil(tcx)),
 
             ref other => {
                 span_err!(tcx.sess, it.span, E0093,
@@ -21,8 +21,9 @@ use middle::typeck::infer;
 use std::rc::Rc;
 use syntax::ast;
 use syntax::codemap::Span;
+use syntax::parse::token;
 use util::common::ErrorReported;
-use util::ppaux::{UserString, Repr, ty_to_string};
+use util::ppaux::{UserString, Repr};
 
 pub fn check_object_cast<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                                    cast_expr: &ast::Expr,
@@ -150,81 +151,90 @@ pub fn check_object_safety<'tcx>(tcx: &ty::ctxt<'tcx>,
     for item in trait_items.iter() {
         match *item {
             ty::MethodTraitItem(ref m) => {
-                errors.push(check_object_safety_of_method(tcx, &**m))
+                let reasons = object_safety_violations_of_method(tcx, &**m);
+                if !reasons.is_empty() {
+                    errors.push((m.clone(), reasons));
+                }
             }
-            ty::TypeTraitItem(_) => {}
+            ty::TypeTraitItem(_) | ty::ConstTraitItem(_) => {}
         }
     }
 
-    let mut errors = errors.iter().flat_map(|x| x.iter()).peekable();
-    if errors.peek().is_some() {
+    if !errors.is_empty() {
         let trait_name = ty::item_path_str(tcx, object_trait.principal.def_id);
         span_err!(tcx.sess, span, E0038,
             "cannot convert to a trait object because trait `{}` is not object-safe",
             trait_name);
 
-        for msg in errors {
-            tcx.sess.note(msg.as_slice());
+        // One note per offending method, pointing at its declaration when
+        // it's in this crate.
+        for &(ref method, ref reasons) in errors.iter() {
+            let msg = format!("method `{}` cannot be called through a trait object \
+                               because it {}",
+                              token::get_name(method.name),
+                              join_reasons(reasons.as_slice()));
+            if method.def_id.krate == ast::LOCAL_CRATE {
+                tcx.sess.span_note(tcx.map.span(method.def_id.node), msg.as_slice());
+            } else {
+                tcx.sess.note(msg.as_slice());
+            }
         }
     }
 
-    /// Returns a vec of error messages. If hte vec is empty - no errors!
+    /// Returns the reasons a method can't be called through a trait object,
+    /// as phrases like "has type parameters". If the vec is empty, the
+    /// method is object-safe.
     ///
     /// There are some limitations to calling functions through an object, because (a) the self
     /// type is not known (that's the whole point of a trait instance, after all, to obscure the
     /// self type) and (b) the call must go through a vtable and hence cannot be monomorphized.
-    fn check_object_safety_of_method<'tcx>(tcx: &ty::ctxt<'tcx>,
-                                           method: &ty::Method<'tcx>)
-                                           -> Vec<String> {
-        let mut msgs = Vec::new();
-
-        let method_name = method.name.repr(tcx);
+    fn object_safety_violations_of_method<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                                method: &ty::Method<'tcx>)
+                                                -> Vec<String> {
+        let mut reasons = Vec::new();
 
         match method.explicit_self {
             ty::ByValueExplicitSelfCategory => { // reason (a) above
-                msgs.push(format!("cannot call a method (`{}`) with a by-value \
-                                   receiver through a trait object", method_name))
+                reasons.push(format!("takes `self` by value"))
             }
 
             ty::StaticExplicitSelfCategory => {
                 // Static methods are always object-safe since they
                 // can't be called through a trait object
-                return msgs
+                return reasons
             }
             ty::ByReferenceExplicitSelfCategory(..) |
             ty::ByBoxExplicitSelfCategory => {}
         }
 
         // reason (a) above
-        let check_for_self_ty = |ty| {
-            if ty::type_has_self(ty) {
-                Some(format!(
-                    "cannot call a method (`{}`) whose type contains \
-                     a self-type (`{}`) through a trait object",
-                    method_name, ty_to_string(tcx, ty)))
-            } else {
-                None
-            }
-        };
         let ref sig = method.fty.sig;
-        for &input_ty in sig.inputs[1..].iter() {
-            if let Some(msg) = check_for_self_ty(input_ty) {
-                msgs.push(msg);
-            }
+        if sig.inputs[1..].iter().any(|&input_ty| ty::type_has_self(input_ty)) {
+            reasons.push(format!("references the `Self` type in its arguments"));
         }
         if let ty::FnConverging(result_type) = sig.output {
-            if let Some(msg) = check_for_self_ty(result_type) {
-                msgs.push(msg);
+            if ty::type_has_self(result_type) {
+                reasons.push(format!("references the `Self` type in its return type"));
             }
         }
 
         if method.generics.has_type_params(FnSpace) {
             // reason (b) above
-            msgs.push(format!("cannot call a generic method (`{}`) through a trait object",
-                              method_name));
+            reasons.push(format!("has type parameters"));
         }
 
-        msgs
+        reasons
+    }
+
+    /// Joins reasons into "a", "a and b" or "a, b and c".
+    fn join_reasons(reasons: &[String]) -> String {
+        match reasons.len() {
+            0 => String::new(),
+            1 => reasons[0].clone(),
+            _ => format!("{} and {}",
+                         reasons.init().connect(", "),
+                         reasons.last().unwrap()),
+        }
     }
 }
 
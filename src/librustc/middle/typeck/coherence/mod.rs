@@ -21,7 +21,7 @@ use metadata::csearch;
 use middle::subst;
 use middle::subst::{Substs};
 use middle::ty::{ImplContainer, ImplOrTraitItemId, MethodTraitItemId};
-use middle::ty::{TypeTraitItemId, lookup_item_type};
+use middle::ty::{TypeTraitItemId, ConstTraitItemId, lookup_item_type};
 use middle::ty::{Ty, ty_bool, ty_char, ty_enum, ty_err};
 use middle::ty::{ty_str, ty_vec, ty_float, ty_infer, ty_int, ty_open};
 use middle::ty::{ty_param, Polytype, ty_ptr};
@@ -334,6 +334,9 @@ impl<'a, 'tcx> CoherenceChecker<'a, 'tcx> {
                                 ast::TypeImplItem(ref typedef) => {
                                     TypeTraitItemId(local_def(typedef.id))
                                 }
+                                ast::ConstImplItem(ref associated_const) => {
+                                    ConstTraitItemId(local_def(associated_const.id))
+                                }
                             }
                         }).collect();
 
@@ -395,7 +398,7 @@ impl<'a, 'tcx> CoherenceChecker<'a, 'tcx> {
                            .insert(item_def_id.def_id(), source);
                     }
                 }
-                ty::TypeTraitItem(_) => {}
+                ty::TypeTraitItem(_) | ty::ConstTraitItem(_) => {}
             }
         }
 
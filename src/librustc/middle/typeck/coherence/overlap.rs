@@ -18,8 +18,9 @@ use middle::typeck::infer;
 use syntax::ast::{DefId};
 use syntax::ast::{LOCAL_CRATE};
 use syntax::ast;
+use syntax::ast_map;
 use syntax::codemap::{Span};
-use util::ppaux::Repr;
+use util::ppaux::{Repr, UserString};
 
 pub fn check(tcx: &ty::ctxt) {
     let overlap = OverlapChecker { tcx: tcx };
@@ -104,9 +105,37 @@ impl<'cx, 'tcx> OverlapChecker<'cx, 'tcx> {
             let crate_store = &self.tcx.sess.cstore;
             let cdata = crate_store.get_crate_data(impl2_def_id.krate);
             span_note!(self.tcx.sess, self.span_of_impl(impl1_def_id),
-                       "conflicting implementation in crate `{}`",
-                       cdata.name);
+                       "conflicting implementation in crate `{}`: `{}` in `{}`",
+                       cdata.name,
+                       self.impl_to_string(impl2_def_id),
+                       self.module_path_of_impl(impl2_def_id));
         }
+
+        let infcx = infer::new_infer_ctxt(self.tcx);
+        let self_ty = traits::overlapping_self_ty(&infcx, impl1_def_id, impl2_def_id);
+        span_note!(self.tcx.sess, self.span_of_impl(impl1_def_id),
+                   "both implementations apply to the type `{}`",
+                   self_ty.user_string(self.tcx));
+    }
+
+    /// Describes an impl as `impl Trait for Type`, for impls whose source
+    /// isn't available to point at.
+    fn impl_to_string(&self, impl_did: ast::DefId) -> String {
+        let trait_ref = ty::impl_trait_ref(self.tcx, impl_did).unwrap();
+        format!("impl {} for {}",
+                trait_ref.user_string(self.tcx),
+                trait_ref.self_ty().user_string(self.tcx))
+    }
+
+    /// The path of the module containing an impl, starting with the name of
+    /// its crate.
+    fn module_path_of_impl(&self, impl_did: ast::DefId) -> String {
+        ty::with_path(self.tcx, impl_did, |path| {
+            let mut path: Vec<ast_map::PathElem> = path.collect();
+            // The last element is the impl itself.
+            path.pop();
+            ast_map::path_to_string(path.into_iter())
+        })
     }
 
     fn push_impls_of_trait(&self,
@@ -63,6 +63,7 @@ use syntax::ast_util::{local_def, PostExpansionMethod};
 use syntax::codemap::Span;
 use syntax::parse::token::{special_idents};
 use syntax::parse::token;
+use syntax::print::pprust;
 use syntax::ptr::P;
 use syntax::visit;
 
@@ -299,6 +300,10 @@ fn collect_trait_methods<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                 tcx.sess.span_bug(at.ty_param.span,
                                                   "there shouldn't be a type trait item here")
                             }
+                            ast::ConstTraitItem(ref ac) => {
+                                tcx.sess.span_bug(ac.span,
+                                                  "there shouldn't be a const trait item here")
+                            }
                         });
 
                         debug!("ty_method_of_trait_method yielded {} for method {} of trait {}",
@@ -326,6 +331,9 @@ fn collect_trait_methods<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                             .borrow_mut()
                             .insert(associated_type.def_id, trait_item);
                     }
+                    // Associated constants were already converted along
+                    // with the rest of the trait in `convert`.
+                    ast::ConstTraitItem(_) => {}
                 }
             }
 
@@ -342,6 +350,9 @@ fn collect_trait_methods<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                         ast::TypeTraitItem(ref typedef) => {
                             ty::TypeTraitItemId(local_def(typedef.ty_param.id))
                         }
+                        ast::ConstTraitItem(ref associated_const) => {
+                            ty::ConstTraitItemId(local_def(associated_const.id))
+                        }
                     }
                 }).collect());
 
@@ -492,6 +503,52 @@ fn convert_associated_type<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
     }
 }
 
+fn convert_associated_const<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
+                                      container: ImplOrTraitItemContainer,
+                                      generics: &ty::Generics<'tcx>,
+                                      associated_const: &ast::AssociatedConst,
+                                      ty: Ty<'tcx>,
+                                      vis: ast::Visibility) {
+    // Constants are translated once rather than per instantiation, so a
+    // value can only be given when its type doesn't depend on `Self` or on
+    // the type parameters in scope.
+    if associated_const.expr.is_some() &&
+       (ty::type_has_params(ty) || ty::type_has_self(ty)) {
+        let msg = match container {
+            TraitContainer(_) => {
+                "default values of associated constants whose type refers to \
+                 `Self` or to type parameters are not supported"
+            }
+            ImplContainer(_) => {
+                "associated constants in generic impls cannot have a type \
+                 that refers to type parameters"
+            }
+        };
+        ccx.tcx.sess.span_err(associated_const.ty.span, msg);
+    }
+
+    ccx.tcx.tcache.borrow_mut().insert(local_def(associated_const.id),
+                                       Polytype {
+                                        generics: generics.clone(),
+                                        ty: ty,
+                                       });
+    write_ty_to_tcx(ccx.tcx, associated_const.id, ty);
+
+    let associated_const = Rc::new(ty::AssociatedConst {
+        name: associated_const.ident.name,
+        ty: ty,
+        vis: vis,
+        def_id: local_def(associated_const.id),
+        container: container,
+        has_value: associated_const.expr.is_some(),
+    });
+    ccx.tcx
+       .impl_or_trait_items
+       .borrow_mut()
+       .insert(associated_const.def_id,
+               ty::ConstTraitItem(associated_const));
+}
+
 enum ConvertMethodContext<'a> {
     /// Used when converting implementation methods.
     ImplConvertMethodContext,
@@ -632,6 +689,22 @@ pub fn ensure_no_ty_param_bounds(ccx: &CrateCtxt,
         }
     }
 
+    for predicate in generics.where_clause.predicates.iter() {
+        match *predicate {
+            ast::BoundPredicate(ref bound_pred) => {
+                for bound in bound_pred.bounds.iter() {
+                    match *bound {
+                        ast::TraitTyParamBound(..) => {
+                            warn = true;
+                        }
+                        ast::RegionTyParamBound(..) => { }
+                    }
+                }
+            }
+            ast::EqPredicate(..) => { }
+        }
+    }
+
     if warn {
         // According to accepted RFC #XXX, we should
         // eventually accept these, but it will not be
@@ -685,6 +758,10 @@ fn find_associated_type_in_generics<'tcx>(tcx: &ty::ctxt<'tcx>,
                                                     param_ty.idx);
             let param_id = type_parameter.def_id;*/
             let param_id = param_ty.def_id;
+            match tcx.associated_type_equalities.borrow().get(&(param_id, associated_type_id)) {
+                Some(&(_, ty)) => return ty,
+                None => {}
+            }
             for type_parameter in generics.types.iter() {
                 if type_parameter.def_id == associated_type_id
                     && type_parameter.associated_with == Some(param_id) {
@@ -778,7 +855,7 @@ impl<'a,'tcx> AstConv<'tcx> for ImplCtxt<'a,'tcx> {
                 let associated_type = ty::impl_or_trait_item(self.ccx.tcx, associated_type_id);
                 for impl_item in self.impl_items.iter() {
                     match *impl_item {
-                        ast::MethodImplItem(_) => {}
+                        ast::MethodImplItem(_) | ast::ConstImplItem(_) => {}
                         ast::TypeImplItem(ref typedef) => {
                             if associated_type.name() == typedef.ident.name {
                                 return self.ccx.to_ty(&ExplicitRscope, &*typedef.typ)
@@ -953,7 +1030,9 @@ impl<'a,'tcx> AstConv<'tcx> for TraitMethodCtxt<'a,'tcx> {
             let mut index = 0;
             for item in self.trait_items.iter() {
                 match *item {
-                    ast::RequiredMethod(_) | ast::ProvidedMethod(_) => {}
+                    ast::RequiredMethod(_) |
+                    ast::ProvidedMethod(_) |
+                    ast::ConstTraitItem(_) => {}
                     ast::TypeTraitItem(ref item) => {
                         if local_def(item.ty_param.id) == associated_type_id {
                             return ty::mk_param(self.tcx(),
@@ -1129,6 +1208,20 @@ pub fn convert(ccx: &CrateCtxt, it: &ast::Item) {
                            .insert(local_def(typedef.id),
                                    ty::TypeTraitItem(associated_type));
                     }
+                    ast::ConstImplItem(ref associated_const) => {
+                        let ty = icx.to_ty(&ExplicitRscope, &*associated_const.ty);
+                        let vis = if opt_trait_ref.is_some() {
+                            ast::Public
+                        } else {
+                            associated_const.vis
+                        };
+                        convert_associated_const(ccx,
+                                                 ImplContainer(local_def(it.id)),
+                                                 &ty_generics,
+                                                 &**associated_const,
+                                                 ty,
+                                                 vis);
+                    }
                 }
             }
 
@@ -1178,6 +1271,21 @@ pub fn convert(ccx: &CrateCtxt, it: &ast::Item) {
                                                 &*trait_def,
                                                 &**associated_type);
                     }
+                    ast::ConstTraitItem(ref associated_const) => {
+                        let tmcx = TraitMethodCtxt {
+                            ccx: ccx,
+                            trait_id: local_def(it.id),
+                            trait_items: trait_methods.as_slice(),
+                            method_generics: &trait_def.generics,
+                        };
+                        let ty = tmcx.to_ty(&ExplicitRscope, &*associated_const.ty);
+                        convert_associated_const(ccx,
+                                                 TraitContainer(local_def(it.id)),
+                                                 &trait_def.generics,
+                                                 &**associated_const,
+                                                 ty,
+                                                 ast::Public);
+                    }
                 }
             }
 
@@ -1193,7 +1301,8 @@ pub fn convert(ccx: &CrateCtxt, it: &ast::Item) {
                             trait_methods.iter().filter_map(|m| match *m {
                                 ast::RequiredMethod(_) => None,
                                 ast::ProvidedMethod(ref m) => Some(&**m),
-                                ast::TypeTraitItem(_) => None,
+                                ast::TypeTraitItem(_) |
+                                ast::ConstTraitItem(_) => None,
                             }),
                             untransformed_rcvr_ty,
                             &trait_def.generics,
@@ -1414,7 +1523,9 @@ pub fn trait_def_of_item<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                       index,
                                       local_def(trait_item.ty_param.id))).into_iter()
                 }
-                ast::RequiredMethod(_) | ast::ProvidedMethod(_) => {
+                ast::RequiredMethod(_) |
+                ast::ProvidedMethod(_) |
+                ast::ConstTraitItem(_) => {
                     None.into_iter()
                 }
             })
@@ -1571,6 +1682,10 @@ fn ty_of_trait_item<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
             };
             convert_associated_type(ccx, &*trait_def, &**associated_type)
         }
+        ast::ConstTraitItem(ref associated_const) => {
+            ccx.tcx.sess.span_bug(associated_const.span,
+                                  "ty_of_trait_item() on associated const")
+        }
     }
 }
 
@@ -1619,7 +1734,9 @@ fn ty_generics_for_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                                           def.clone());
                 generics.types.push(subst::AssocSpace, def);
             }
-            ast::ProvidedMethod(_) | ast::RequiredMethod(_) => {}
+            ast::ProvidedMethod(_) |
+            ast::RequiredMethod(_) |
+            ast::ConstTraitItem(_) => {}
         }
     }
 
@@ -1744,6 +1861,13 @@ fn ty_generics<'tcx,AC>(this: &AC,
 
     assert!(result.types.is_empty_in(space));
 
+    // Equality constraints fix some associated types, which then need no
+    // virtual type parameter of their own.
+    bind_associated_type_equalities(this,
+                                    space,
+                                    where_clause,
+                                    create_type_parameters_for_associated_types_flag);
+
     // First, create the virtual type parameters for associated types if
     // necessary.
     let mut associated_types_generics = ty::Generics::empty();
@@ -1751,6 +1875,7 @@ fn ty_generics<'tcx,AC>(this: &AC,
         DontCreateTypeParametersForAssociatedTypes => {}
         CreateTypeParametersForAssociatedTypes => {
             create_type_parameters_for_associated_types(this, space, types,
+                                                        where_clause,
                                                         &mut associated_types_generics);
         }
     }
@@ -1791,6 +1916,7 @@ fn ty_generics<'tcx,AC>(this: &AC,
         this: &AC,
         space: subst::ParamSpace,
         types: &[ast::TyParam],
+        where_clause: &ast::WhereClause,
         associated_types_generics: &mut ty::Generics<'tcx>)
         where AC: AstConv<'tcx>
     {
@@ -1813,9 +1939,14 @@ fn ty_generics<'tcx,AC>(this: &AC,
         let mut index = 0;
 
         // Iterate over the each type parameter `T` (from the example)
-        for param in types.iter() {
-            // Iterate over the bound `Iterator`
-            for bound in param.bounds.iter() {
+        for (i, param) in types.iter().enumerate() {
+            // Iterate over the bound `Iterator`, whether it was written
+            // inline or in the `where` clause.
+            let param_ty = ty::ParamTy::new(space, i, local_def(param.id));
+            for bound in merge_param_bounds(this.tcx(),
+                                            param_ty,
+                                            param.bounds.as_slice(),
+                                            where_clause).into_iter() {
                 // In the above example, `ast_trait_ref` is `Iterator`.
                 let ast_trait_ref = match *bound {
                     ast::TraitTyParamBound(ref r) => r,
@@ -1839,6 +1970,13 @@ fn ty_generics<'tcx,AC>(this: &AC,
 
                 // Iterate over each associated type `Elem`
                 for associated_type_def in associated_type_defs.iter() {
+                    // `where <T as Iterator>::Elem == uint` uses `uint` in
+                    // place of `A`.
+                    let key = (local_def(param.id), associated_type_def.def_id);
+                    if this.tcx().associated_type_equalities.borrow().contains_key(&key) {
+                        continue;
+                    }
+
                     // Create the fresh type parameter `A`
                     let def = ty::TypeParameterDef {
                         name: associated_type_def.name,
@@ -2028,17 +2166,23 @@ fn merge_param_bounds<'a>(tcx: &ty::ctxt,
     }
 
     for predicate in where_clause.predicates.iter() {
+        let bound_pred = match *predicate {
+            ast::BoundPredicate(ref bound_pred) => bound_pred,
+            // Equality constraints don't bound a type parameter; they are
+            // checked separately by `check_where_eq_predicates`.
+            ast::EqPredicate(..) => continue
+        };
         let predicate_param_id =
             tcx.def_map
                .borrow()
-               .get(&predicate.id)
+               .get(&bound_pred.id)
                .expect("compute_bounds(): resolve didn't resolve the type \
                         parameter identifier in a `where` clause")
                .def_id();
         if param_ty.def_id != predicate_param_id {
             continue
         }
-        for bound in predicate.bounds.iter() {
+        for bound in bound_pred.bounds.iter() {
             result.push(bound);
         }
     }
@@ -2046,6 +2190,87 @@ fn merge_param_bounds<'a>(tcx: &ty::ctxt,
     result
 }
 
+/// Records the types that equality constraints such as
+/// `where <T as Iterator>::Elem == uint` bind associated types of the type
+/// parameters in `space` to. Wherever the associated type is used, including
+/// in the bound `T: Iterator` itself, it is replaced by the bound type, so
+/// callers meet the constraint by meeting the bound.
+fn bind_associated_type_equalities<'tcx,AC>(
+        this: &AC,
+        space: subst::ParamSpace,
+        where_clause: &ast::WhereClause,
+        create_type_parameters_for_associated_types_flag:
+        CreateTypeParametersForAssociatedTypesFlag)
+        where AC: AstConv<'tcx> {
+    let tcx = this.tcx();
+    for predicate in where_clause.predicates.iter() {
+        let eq_pred = match *predicate {
+            ast::EqPredicate(ref eq_pred) => eq_pred,
+            ast::BoundPredicate(..) => continue
+        };
+        if create_type_parameters_for_associated_types_flag ==
+                DontCreateTypeParametersForAssociatedTypes {
+            span_err!(tcx.sess, eq_pred.span, E0173,
+                      "equality constraints are only supported on functions, \
+                       impls and the methods of impls");
+            continue;
+        }
+
+        let key = match associated_type_of_param(tcx, space, &*eq_pred.lhs_ty) {
+            Some(key) => key,
+            None => {
+                span_err!(tcx.sess, eq_pred.lhs_ty.span, E0179,
+                          "only an associated type of a type parameter, such as \
+                           `<T as Trait>::Name`, can be constrained to equal a type");
+                continue;
+            }
+        };
+        match tcx.associated_type_equalities.borrow().get(&key) {
+            // The generics of an item can be computed more than once.
+            Some(&(id, _)) if id == eq_pred.id => continue,
+            Some(_) => {
+                span_err!(tcx.sess, eq_pred.span, E0180,
+                          "`{}` is already constrained to equal another type",
+                          pprust::ty_to_string(&*eq_pred.lhs_ty));
+                continue;
+            }
+            None => {}
+        }
+
+        let ty = ast_ty_to_ty(this, &ExplicitRscope, &*eq_pred.rhs_ty);
+        tcx.associated_type_equalities.borrow_mut().insert(key, (eq_pred.id, ty));
+    }
+}
+
+/// Returns the def-ids of the type parameter and of the associated type that
+/// `ty` names if it's of the form `<T as Trait>::Name`, where `T` is a type
+/// parameter in `space`.
+fn associated_type_of_param(tcx: &ty::ctxt,
+                            space: subst::ParamSpace,
+                            ty: &ast::Ty)
+                            -> Option<(ast::DefId, ast::DefId)> {
+    let qpath = match ty.node {
+        ast::TyQPath(ref qpath) => qpath,
+        _ => return None
+    };
+    let param_id = match qpath.self_type.node {
+        ast::TyPath(_, id) => match tcx.def_map.borrow().get(&id) {
+            Some(&def::DefTyParam(param_space, param_id, _)) if param_space == space => param_id,
+            _ => return None
+        },
+        _ => return None
+    };
+    let trait_def_id = match lookup_def_tcx(tcx, qpath.trait_ref.path.span,
+                                            qpath.trait_ref.ref_id) {
+        def::DefTrait(trait_def_id) => trait_def_id,
+        _ => return None
+    };
+    let trait_def = ty::lookup_trait_def(tcx, trait_def_id);
+    trait_def.generics.types.get_slice(subst::AssocSpace).iter()
+             .find(|def| def.name == qpath.item_name.name)
+             .map(|def| (param_id, def.def_id))
+}
+
 pub fn ty_of_foreign_fn_decl<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                        decl: &ast::FnDecl,
                                        def_id: ast::DefId,
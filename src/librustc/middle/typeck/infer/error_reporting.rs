@@ -87,6 +87,7 @@ use syntax::codemap;
 use syntax::parse::token;
 use syntax::print::pprust;
 use syntax::ptr::P;
+use util::ppaux;
 use util::ppaux::bound_region_to_string;
 use util::ppaux::note_and_explain_region;
 
@@ -109,6 +110,8 @@ pub trait ErrorReporting<'tcx> {
 
     fn values_str(&self, values: &ValuePairs<'tcx>) -> Option<String>;
 
+    fn elided_types_str(&self, exp_found: &ty::expected_found<Ty<'tcx>>) -> Option<String>;
+
     fn expected_found_str<T: UserString<'tcx> + Resolvable<'tcx>>(
         &self,
         exp_found: &ty::expected_found<T>)
@@ -154,6 +157,8 @@ trait ErrorReportingHelpers<'tcx> {
     fn note_region_origin(&self,
                           origin: &SubregionOrigin<'tcx>);
 
+    fn note_variance(&self, values: &ValuePairs<'tcx>);
+
     fn give_expl_lifetime_param(&self,
                                 decl: &ast::FnDecl,
                                 fn_style: ast::FnStyle,
@@ -367,13 +372,24 @@ impl<'a, 'tcx> ErrorReporting<'tcx> for InferCtxt<'a, 'tcx> {
             infer::IfExpressionWithNoElse(_) => "if may be missing an else clause",
         };
 
+        let elided_types_str = match trace.values {
+            infer::Types(ref exp_found) => self.elided_types_str(exp_found),
+            infer::TraitRefs(_) => None
+        };
+
         self.tcx.sess.span_err(
             trace.origin.span(),
             format!("{}: {} ({})",
                  message_root_str,
-                 expected_found_str,
+                 elided_types_str.as_ref().unwrap_or(&expected_found_str),
                  ty::type_err_to_str(self.tcx, terr)).as_slice());
 
+        if elided_types_str.is_some() {
+            self.tcx.sess.span_note(
+                trace.origin.span(),
+                format!("in full, {}", expected_found_str).as_slice());
+        }
+
         match trace.origin {
             infer::MatchExpressionArm(_, arm_span) =>
                 self.tcx.sess.span_note(arm_span, "match arm with an incompatible type"),
@@ -397,6 +413,16 @@ impl<'a, 'tcx> ErrorReporting<'tcx> for InferCtxt<'a, 'tcx> {
         }
     }
 
+    /// Returns a string of the form "expected `{}`, found `{}`" in which the components the
+    /// types have in common are replaced by `_`, or None if there are none to replace.
+    fn elided_types_str(&self, exp_found: &ty::expected_found<Ty<'tcx>>) -> Option<String> {
+        let expected = exp_found.expected.resolve(self);
+        let found = exp_found.found.resolve(self);
+        ppaux::ty_diff_to_strings(self.tcx, expected, found).map(|(expected, found)| {
+            format!("expected `{}`, found `{}`", expected, found)
+        })
+    }
+
     fn expected_found_str<T: UserString<'tcx> + Resolvable<'tcx>>(
         &self,
         exp_found: &ty::expected_found<T>)
@@ -489,7 +515,9 @@ impl<'a, 'tcx> ErrorReporting<'tcx> for InferCtxt<'a, 'tcx> {
         match origin {
             infer::Subtype(trace) => {
                 let terr = ty::terr_regions_does_not_outlive(sup, sub);
+                let values = trace.values.clone();
                 self.report_and_explain_type_error(trace, &terr);
+                self.note_variance(&values);
             }
             infer::Reborrow(span) => {
                 self.tcx.sess.span_err(
@@ -866,7 +894,7 @@ impl<'a, 'tcx> ErrorReporting<'tcx> for InferCtxt<'a, 'tcx> {
                                   Some(&m.pe_explicit_self().node),
                                   m.span))
                         }
-                        ast::TypeImplItem(_) => None,
+                        ast::TypeImplItem(_) | ast::ConstImplItem(_) => None,
                     }
                 },
                 ast_map::NodeTraitItem(ref item) => {
@@ -1426,6 +1454,20 @@ impl<'a, 'tcx> Rebuilder<'a, 'tcx> {
 }
 
 impl<'a, 'tcx> ErrorReportingHelpers<'tcx> for InferCtxt<'a, 'tcx> {
+    /// Explains how the variance of the parameters of the types being
+    /// related forced a lifetime in one of them to be equal to, or to be
+    /// outlived by, the corresponding lifetime in the other.
+    fn note_variance(&self, values: &ValuePairs<'tcx>) {
+        match *values {
+            infer::Types(ref exp_found) => {
+                note_variance_of_tys(self.tcx,
+                                     self.resolve_type_vars_if_possible(exp_found.expected),
+                                     self.resolve_type_vars_if_possible(exp_found.found));
+            }
+            infer::TraitRefs(_) => {}
+        }
+    }
+
     fn give_expl_lifetime_param(&self,
                                 decl: &ast::FnDecl,
                                 fn_style: ast::FnStyle,
@@ -1529,6 +1571,7 @@ impl<'a, 'tcx> ErrorReportingHelpers<'tcx> for InferCtxt<'a, 'tcx> {
                             format!("...so that {}", desc).as_slice());
                     }
                 }
+                self.note_variance(&trace.values);
             }
             infer::Reborrow(span) => {
                 self.tcx.sess.span_note(
@@ -1669,6 +1712,76 @@ impl<'a, 'tcx> ErrorReportingHelpers<'tcx> for InferCtxt<'a, 'tcx> {
     }
 }
 
+fn note_variance_of_tys<'tcx>(tcx: &ty::ctxt<'tcx>, expected: Ty<'tcx>, found: Ty<'tcx>) {
+    match (&expected.sty, &found.sty) {
+        (&ty::ty_enum(def_a, ref substs_a), &ty::ty_enum(def_b, ref substs_b)) |
+        (&ty::ty_struct(def_a, ref substs_a), &ty::ty_struct(def_b, ref substs_b))
+                if def_a == def_b => {
+            note_variance_of_substs(tcx, def_a, substs_a, substs_b);
+        }
+        (&ty::ty_rptr(_, ref mt_a), &ty::ty_rptr(_, ref mt_b)) |
+        (&ty::ty_ptr(ref mt_a), &ty::ty_ptr(ref mt_b)) => {
+            note_variance_of_tys(tcx, mt_a.ty, mt_b.ty);
+        }
+        _ => {}
+    }
+}
+
+fn note_variance_of_substs<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                 def_id: ast::DefId,
+                                 substs_a: &subst::Substs<'tcx>,
+                                 substs_b: &subst::Substs<'tcx>) {
+    let variances = ty::item_variances(tcx, def_id);
+    let generics = ty::lookup_item_type(tcx, def_id).generics;
+    let span = if def_id.krate == ast::LOCAL_CRATE {
+        Some(tcx.map.span(def_id.node))
+    } else {
+        None
+    };
+    let note = |kind: &str, name: ast::Name, variance: ty::Variance| {
+        let name = token::get_name(name);
+        let consequence = match variance {
+            ty::Invariant => {
+                format!("so `{}` must be the same in both types", name)
+            }
+            ty::Contravariant => {
+                format!("so the relationship required between the two `{}` is reversed", name)
+            }
+            ty::Covariant | ty::Bivariant => return
+        };
+        let msg = format!("`{}` is {} over its {} parameter `{}`, {}",
+                          ty::item_path_str(tcx, def_id),
+                          variance.description(),
+                          kind,
+                          name,
+                          consequence);
+        match span {
+            Some(span) => tcx.sess.span_note(span, msg.as_slice()),
+            None => tcx.sess.note(msg.as_slice())
+        }
+    };
+
+    for def in generics.regions.get_slice(subst::TypeSpace).iter() {
+        let region_a = *substs_a.regions().get(subst::TypeSpace, def.index);
+        let region_b = *substs_b.regions().get(subst::TypeSpace, def.index);
+        if region_a != region_b {
+            note("lifetime", def.name, *variances.regions.get(subst::TypeSpace, def.index));
+        }
+    }
+
+    for def in generics.types.get_slice(subst::TypeSpace).iter() {
+        let ty_a = *substs_a.types.get(subst::TypeSpace, def.index);
+        let ty_b = *substs_b.types.get(subst::TypeSpace, def.index);
+        if ty_a == ty_b {
+            continue;
+        }
+        match *variances.types.get(subst::TypeSpace, def.index) {
+            ty::Covariant | ty::Contravariant => note_variance_of_tys(tcx, ty_a, ty_b),
+            variance => note("type", def.name, variance)
+        }
+    }
+}
+
 pub trait Resolvable<'tcx> {
     fn resolve<'a>(&self, infcx: &InferCtxt<'a, 'tcx>) -> Self;
     fn contains_error(&self) -> bool;
@@ -1713,7 +1826,7 @@ fn lifetimes_in_scope(tcx: &ty::ctxt,
                         taken.push_all(m.pe_generics().lifetimes.as_slice());
                         Some(m.id)
                     }
-                    ast::TypeImplItem(_) => None,
+                    ast::TypeImplItem(_) | ast::ConstImplItem(_) => None,
                 }
             }
             _ => None
@@ -197,11 +197,13 @@ use middle::resolve_lifetime as rl;
 use middle::subst;
 use middle::subst::{ParamSpace, FnSpace, TypeSpace, SelfSpace, VecPerParamSpace};
 use middle::ty::{mod, Ty};
+use session::config;
 use std::fmt;
 use std::rc::Rc;
 use syntax::ast;
 use syntax::ast_map;
 use syntax::ast_util;
+use syntax::parse::token;
 use syntax::visit;
 use syntax::visit::Visitor;
 use util::nodemap::NodeMap;
@@ -517,6 +519,10 @@ impl<'a, 'tcx, 'v> Visitor<'v> for ConstraintContext<'a, 'tcx> {
                             self.add_constraints_from_sig(&method.fty.sig,
                                                           self.covariant);
                         }
+                        ty::ConstTraitItem(ref associated_const) => {
+                            self.add_constraints_from_ty(associated_const.ty,
+                                                         self.covariant);
+                        }
                         ty::TypeTraitItem(_) => {}
                     }
                 }
@@ -536,6 +542,28 @@ impl<'a, 'tcx, 'v> Visitor<'v> for ConstraintContext<'a, 'tcx> {
     }
 }
 
+/// The name of the parameter described by `info`, as written in the
+/// definition of its item.
+fn param_name(tcx: &ty::ctxt, info: &InferredInfo) -> String {
+    if info.space == SelfSpace {
+        return "Self".to_string();
+    }
+    let generics = match tcx.map.expect_item(info.item_id).node {
+        ast::ItemEnum(_, ref generics) |
+        ast::ItemStruct(_, ref generics) |
+        ast::ItemTrait(ref generics, _, _, _) => generics,
+        _ => tcx.sess.bug("variance inferred for an item without generics")
+    };
+    match info.kind {
+        RegionParam => {
+            token::get_name(generics.lifetimes[info.index].lifetime.name).get().to_string()
+        }
+        TypeParam => {
+            token::get_ident(generics.ty_params[info.index].ident).get().to_string()
+        }
+    }
+}
+
 /// Is `param_id` a lifetime according to `map`?
 fn is_lifetime(map: &ast_map::Map, param_id: ast::NodeId) -> bool {
     match map.find(param_id) {
@@ -1006,12 +1034,14 @@ impl<'a, 'tcx> SolveContext<'a, 'tcx> {
         let tcx = self.terms_cx.tcx;
         let solutions = &self.solutions;
         let inferred_infos = &self.terms_cx.inferred_infos;
+        let print = tcx.sess.debugging_opt(config::PRINT_VARIANCE);
         let mut index = 0;
         let num_inferred = self.terms_cx.num_inferred();
         while index < num_inferred {
             let item_id = inferred_infos[index].item_id;
             let mut types = VecPerParamSpace::empty();
             let mut regions = VecPerParamSpace::empty();
+            let mut described = Vec::new();
 
             while index < num_inferred &&
                   inferred_infos[index].item_id == item_id {
@@ -1019,6 +1049,11 @@ impl<'a, 'tcx> SolveContext<'a, 'tcx> {
                 let variance = solutions[index];
                 debug!("Index {} Info {} / {} / {} Variance {}",
                        index, info.index, info.kind, info.space, variance);
+                if print {
+                    described.push(format!("{} {}",
+                                           param_name(tcx, &info),
+                                           variance.description()));
+                }
                 match info.kind {
                     TypeParam => {
                         types.push(info.space, variance);
@@ -1040,6 +1075,12 @@ impl<'a, 'tcx> SolveContext<'a, 'tcx> {
 
             let item_def_id = ast_util::local_def(item_id);
 
+            if print {
+                println!("variance of `{}`: {}",
+                         ty::item_path_str(tcx, item_def_id),
+                         described.connect(", "));
+            }
+
             // For unit testing: check for a special "rustc_variance"
             // attribute and report an error with various results if found.
             if ty::has_attr(tcx, item_def_id, "rustc_variance") {
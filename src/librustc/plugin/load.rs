@@ -21,13 +21,13 @@ use syntax::ast;
 use syntax::attr;
 use syntax::visit;
 use syntax::visit::Visitor;
-use syntax::ext::expand::ExportedMacros;
+use syntax::ext::expand::{ExportedMacro, ExportedMacros};
 use syntax::attr::AttrMetaMethods;
 
 /// Plugin-related crate metadata.
 pub struct PluginMetadata {
-    /// Source code of macros exported by the crate.
-    pub macros: Vec<String>,
+    /// Macros exported by the crate.
+    pub macros: Vec<ExportedMacro>,
     /// Path to the shared library file.
     pub lib: Option<Path>,
     /// Symbol name of the plugin registrar function.
@@ -10,7 +10,7 @@
 
 //! Used by plugin crates to tell `rustc` about the plugins they provide.
 
-use lint::{LintPassObject, LintId, Lint};
+use lint::{LintPassObject, EarlyLintPassObject, LintId, Lint};
 
 use syntax::ext::base::{SyntaxExtension, NamedSyntaxExtension, NormalTT};
 use syntax::ext::base::{IdentTT, LetSyntaxTT, Decorator, Modifier};
@@ -39,6 +39,9 @@ pub struct Registry {
     #[doc(hidden)]
     pub lint_passes: Vec<LintPassObject>,
 
+    #[doc(hidden)]
+    pub early_lint_passes: Vec<EarlyLintPassObject>,
+
     #[doc(hidden)]
     pub lint_groups: HashMap<&'static str, Vec<LintId>>,
 }
@@ -50,6 +53,7 @@ impl Registry {
             krate_span: krate.span,
             syntax_exts: vec!(),
             lint_passes: vec!(),
+            early_lint_passes: vec!(),
             lint_groups: HashMap::new(),
         }
     }
@@ -77,11 +81,25 @@ impl Registry {
         self.register_syntax_extension(token::intern(name), NormalTT(box expander, None));
     }
 
-    /// Register a compiler lint pass.
+    /// Register a compiler lint pass, which runs after type checking.
+    ///
+    /// This is the same as `register_late_lint_pass`.
     pub fn register_lint_pass(&mut self, lint_pass: LintPassObject) {
+        self.register_late_lint_pass(lint_pass);
+    }
+
+    /// Register a lint pass that runs after type checking, and can look up
+    /// the types of the crate through `Context::tcx`.
+    pub fn register_late_lint_pass(&mut self, lint_pass: LintPassObject) {
         self.lint_passes.push(lint_pass);
     }
 
+    /// Register a lint pass that runs on the syntax of the crate right after
+    /// macro expansion, before the crate is type checked.
+    pub fn register_early_lint_pass(&mut self, lint_pass: EarlyLintPassObject) {
+        self.early_lint_passes.push(lint_pass);
+    }
+
     /// Register a lint group.
     pub fn register_lint_group(&mut self, name: &'static str, to: Vec<&'static Lint>) {
         self.lint_groups.insert(name, to.into_iter().map(|x| LintId::of(x)).collect());
@@ -14,9 +14,12 @@
 pub use self::EntryFnType::*;
 pub use self::CrateType::*;
 pub use self::Passes::*;
+pub use self::StackProtector::*;
 pub use self::OptLevel::*;
 pub use self::OutputType::*;
 pub use self::DebugInfoLevel::*;
+pub use self::ErrorFormat::*;
+pub use self::ErrorOutput::*;
 
 use session::{early_error, early_warn, Session};
 
@@ -29,6 +32,7 @@ use syntax::ast::{IntTy, UintTy};
 use syntax::attr;
 use syntax::attr::AttrMetaMethods;
 use syntax::diagnostic::{ColorConfig, Auto, Always, Never, SpanHandler};
+use syntax::diagnostic::{MacroBacktraceMode, NoBacktrace, CollapsedBacktrace, FullBacktrace};
 use syntax::parse;
 use syntax::parse::token::InternedString;
 
@@ -52,7 +56,9 @@ pub enum OptLevel {
     No, // -O0
     Less, // -O1
     Default, // -O2
-    Aggressive // -O3
+    Aggressive, // -O3
+    Size, // -Os
+    SizeMin, // -Oz
 }
 
 #[deriving(Clone, PartialEq)]
@@ -71,6 +77,21 @@ pub enum OutputType {
     OutputTypeExe,
 }
 
+/// How diagnostics are printed, chosen with `--error-format`.
+#[deriving(Clone, PartialEq)]
+pub enum ErrorFormat {
+    HumanErrors,
+    JsonErrors,
+}
+
+/// Where `early_error` and `early_warn` print, before there is a session to
+/// report diagnostics: as text with the `--color` setting, or as JSON.
+#[deriving(Clone)]
+pub enum ErrorOutput {
+    HumanOutput(ColorConfig),
+    JsonOutput,
+}
+
 #[deriving(Clone)]
 pub struct Options {
     // The crate config requested for the session, which may be combined
@@ -81,6 +102,8 @@ pub struct Options {
     pub optimize: OptLevel,
     pub debuginfo: DebugInfoLevel,
     pub lint_opts: Vec<(String, lint::Level)>,
+    /// The highest level any lint is reported at, from `--cap-lints`.
+    pub lint_cap: Option<lint::Level>,
     pub describe_lints: bool,
     pub output_types: Vec<OutputType> ,
     // This was mutable for rustpkg, which updates search paths based on the
@@ -100,13 +123,28 @@ pub struct Options {
     pub no_trans: bool,
     pub no_analysis: bool,
     pub debugging_opts: u64,
+    /// How much of the macro backtrace diagnostics print, from
+    /// `-Z macro-backtrace`.
+    pub macro_backtrace: MacroBacktraceMode,
+    /// The error that is reported as a compiler bug, panicking, from
+    /// `-Z treat-err-as-bug`, or 0 for none.
+    pub treat_err_as_bug: uint,
+    /// Globs over the paths of the functions kept in `--emit=ir,asm` output,
+    /// from `-Z emit-filter`, or empty to keep every function.
+    pub emit_filter: Vec<String>,
     /// Whether to write dependency files. It's (enabled, optional filename).
     pub write_dependency_info: (bool, Option<Path>),
     /// Crate id-related things to maybe print. It's (crate_name, crate_file_name).
     pub print_metas: (bool, bool),
     pub cg: CodegenOptions,
     pub color: ColorConfig,
+    pub error_format: ErrorFormat,
+    /// The number of errors after which compilation stops, or 0 for no limit.
+    pub error_limit: uint,
     pub externs: HashMap<String, Vec<String>>,
+    /// `(from, to)` pairs from `--remap-path-prefix`, applied to the names
+    /// of source files in diagnostics, `file!()` and debuginfo.
+    pub remap_path_prefix: Vec<(String, String)>,
     pub crate_name: Option<String>,
     /// An optional name to use as the crate for std during std injection,
     /// written `extern crate std = "name"`. Default to "std". Used by
@@ -135,6 +173,7 @@ pub fn basic_options() -> Options {
         optimize: No,
         debuginfo: NoDebugInfo,
         lint_opts: Vec::new(),
+        lint_cap: None,
         describe_lints: false,
         output_types: Vec::new(),
         addl_lib_search_paths: RefCell::new(Vec::new()),
@@ -146,11 +185,17 @@ pub fn basic_options() -> Options {
         no_trans: false,
         no_analysis: false,
         debugging_opts: 0,
+        macro_backtrace: CollapsedBacktrace,
+        treat_err_as_bug: 0,
+        emit_filter: Vec::new(),
         write_dependency_info: (false, None),
         print_metas: (false, false),
         cg: basic_codegen_options(),
         color: Auto,
+        error_format: HumanErrors,
+        error_limit: 0,
         externs: HashMap::new(),
+        remap_path_prefix: Vec::new(),
         crate_name: None,
         alt_std_name: None,
         libs: Vec::new(),
@@ -213,7 +258,15 @@ debugging_opts!(
         FLOWGRAPH_PRINT_MOVES,
         FLOWGRAPH_PRINT_ASSIGNS,
         FLOWGRAPH_PRINT_ALL,
-        PRINT_SYSROOT
+        PRINT_SYSROOT,
+        DROP_SOURCES,
+        AST_STATS,
+        KEEP_GOING,
+        SHOW_DUPLICATE_DIAGNOSTICS,
+        DIAGNOSTIC_SUMMARY,
+        TIME_MACROS,
+        LINT_SUPPRESSIONS,
+        PRINT_VARIANCE
     ]
     0
 )
@@ -259,7 +312,24 @@ pub fn debugging_opts_map() -> Vec<(&'static str, &'static str, u64)> {
      ("flowgraph-print-all", "Include all dataflow analysis data in \
                        --pretty flowgraph output", FLOWGRAPH_PRINT_ALL),
      ("print-sysroot", "Print the sysroot as used by this rustc invocation",
-      PRINT_SYSROOT)]
+      PRINT_SYSROOT),
+     ("drop-sources", "Free source files after expansion, re-reading them \
+                       from disk when needed for diagnostics", DROP_SOURCES),
+     ("ast-stats", "Print node counts and sizes of the expanded AST by kind, \
+                    macro and module", AST_STATS),
+     ("keep-going", "Run the checking passes after type checking even if one of \
+                     them reports errors", KEEP_GOING),
+     ("show-duplicate-diagnostics", "Report the diagnostics repeated by several \
+                                     expansions of a macro each time", SHOW_DUPLICATE_DIAGNOSTICS),
+     ("diagnostic-summary", "Print the number of errors and warnings emitted, and of those \
+                             suppressed as duplicates, when compilation ends",
+      DIAGNOSTIC_SUMMARY),
+     ("time-macros", "measure the time spent expanding each macro, and count \
+                      its invocations", TIME_MACROS),
+     ("lint-suppressions", "list the lints allowed by attributes, with the reasons \
+                            given for them", LINT_SUPPRESSIONS),
+     ("variance", "print the inferred variance of the type and lifetime \
+                   parameters of each type and trait", PRINT_VARIANCE)]
 }
 
 #[deriving(Clone)]
@@ -277,6 +347,17 @@ impl Passes {
     }
 }
 
+/// Which functions get stack canaries, from `-C stack-protector`.
+#[deriving(Clone, PartialEq)]
+pub enum StackProtector {
+    StackProtectorNone,
+    /// Functions with character arrays or large buffers on the stack.
+    StackProtectorBasic,
+    /// Functions with any array, or any local whose address is taken.
+    StackProtectorStrong,
+    StackProtectorAll,
+}
+
 /// Declare a macro that will define all CodegenOptions fields and parsers all
 /// at once. The goal of this macro is to define an interface that can be
 /// programmatically used by the option parser in order to initialize the struct
@@ -311,12 +392,18 @@ macro_rules! cgoptions(
         pub const parse_list: Option<&'static str> = Some("a space-separated list of strings");
         pub const parse_opt_list: Option<&'static str> = Some("a space-separated list of strings");
         pub const parse_uint: Option<&'static str> = Some("a number");
+        pub const parse_opt_uint: Option<&'static str> = Some("a number");
+        pub const parse_yes_no: Option<&'static str> = Some("`yes` or `no`");
         pub const parse_passes: Option<&'static str> =
             Some("a space-separated list of passes, or `all`");
+        pub const parse_stack_protector: Option<&'static str> =
+            Some("one of `none`, `basic`, `strong` or `all`");
     }
 
     mod cgsetters {
         use super::{CodegenOptions, Passes, SomePasses, AllPasses};
+        use super::{StackProtector, StackProtectorNone, StackProtectorBasic};
+        use super::{StackProtectorStrong, StackProtectorAll};
 
         $(
             pub fn $opt(cg: &mut CodegenOptions, v: Option<&str>) -> bool {
@@ -338,6 +425,14 @@ macro_rules! cgoptions(
             }
         }
 
+        fn parse_yes_no(slot: &mut Option<bool>, v: Option<&str>) -> bool {
+            match v {
+                Some("yes") => { *slot = Some(true); true },
+                Some("no") => { *slot = Some(false); true },
+                _ => false,
+            }
+        }
+
         fn parse_opt_string(slot: &mut Option<String>, v: Option<&str>) -> bool {
             match v {
                 Some(s) => { *slot = Some(s.to_string()); true },
@@ -384,6 +479,24 @@ macro_rules! cgoptions(
             }
         }
 
+        fn parse_opt_uint(slot: &mut Option<uint>, v: Option<&str>) -> bool {
+            match v.and_then(from_str) {
+                Some(i) => { *slot = Some(i); true },
+                None => false
+            }
+        }
+
+        fn parse_stack_protector(slot: &mut StackProtector, v: Option<&str>) -> bool {
+            *slot = match v {
+                Some("none") => StackProtectorNone,
+                Some("basic") => StackProtectorBasic,
+                Some("strong") => StackProtectorStrong,
+                Some("all") => StackProtectorAll,
+                _ => return false,
+            };
+            true
+        }
+
         fn parse_passes(slot: &mut Passes, v: Option<&str>) -> bool {
             match v {
                 Some("all") => {
@@ -416,7 +529,8 @@ cgoptions!(
     target_cpu: Option<String> = (None, parse_opt_string,
         "select target processor (llc -mcpu=help for details)"),
     target_feature: String = ("".to_string(), parse_string,
-        "target specific attributes (llc -mattr=help for details)"),
+        "target specific attributes (llc -mattr=help for details), \
+         comma separated; each enabled one is set as `cfg(target_feature)`"),
     passes: Vec<String> = (Vec::new(), parse_list,
         "a list of extra LLVM passes to run (space separated)"),
     llvm_args: Vec<String> = (Vec::new(), parse_list,
@@ -431,8 +545,12 @@ cgoptions!(
         "don't run the loop vectorization optimization passes"),
     no_vectorize_slp: bool = (false, parse_bool,
         "don't run LLVM's SLP vectorization pass"),
+    inline_threshold: Option<uint> = (None, parse_opt_uint,
+        "set the threshold for LLVM's inliner, overriding the default for \
+         the optimization level"),
     soft_float: bool = (false, parse_bool,
-        "generate software floating point library calls"),
+        "generate software floating point library calls and use the soft-float \
+         ABI, setting `cfg(target_feature = \"soft-float\")`"),
     prefer_dynamic: bool = (false, parse_bool,
         "prefer dynamic linking to static linking"),
     no_integrated_as: bool = (false, parse_bool,
@@ -440,7 +558,8 @@ cgoptions!(
     no_redzone: Option<bool> = (None, parse_opt_bool,
         "disable the use of the redzone"),
     relocation_model: Option<String> = (None, parse_opt_string,
-         "choose the relocation model to use (llc -relocation-model for details)"),
+         "choose the relocation model to use: pic, static, default or dynamic-no-pic \
+          (llc -relocation-model for details)"),
     code_model: Option<String> = (None, parse_opt_string,
          "choose the code model to use (llc -code-model for details)"),
     metadata: Vec<String> = (Vec::new(), parse_list,
@@ -448,14 +567,21 @@ cgoptions!(
     extra_filename: String = ("".to_string(), parse_string,
          "extra data to put in each output filename"),
     codegen_units: uint = (1, parse_uint,
-        "divide crate into N units to optimize in parallel"),
+        "divide crate into N units to optimize in parallel, on as many \
+         threads as there are CPUs"),
     remark: Passes = (SomePasses(Vec::new()), parse_passes,
         "print remarks for these optimization passes (space separated, or \"all\")"),
     no_stack_check: bool = (false, parse_bool,
         "disable checks for stack exhaustion (a memory-safety hazard!)"),
+    stack_protector: StackProtector = (StackProtectorNone, parse_stack_protector,
+        "add stack canaries to functions with buffers (`basic`), with arrays or \
+         address-taken locals (`strong`), or to all of them (`all`)"),
+    force_frame_pointers: Option<bool> = (None, parse_yes_no,
+        "keep (`yes`) or allow omitting (`no`) frame pointers in every function, \
+         instead of the target's default"),
 )
 
-pub fn build_codegen_options(matches: &getopts::Matches) -> CodegenOptions
+pub fn build_codegen_options(matches: &getopts::Matches, output: ErrorOutput) -> CodegenOptions
 {
     let mut cg = basic_codegen_options();
     for option in matches.opt_strs("C").into_iter() {
@@ -469,18 +595,18 @@ pub fn build_codegen_options(matches: &getopts::Matches) -> CodegenOptions
             if !setter(&mut cg, value) {
                 match (value, opt_type_desc) {
                     (Some(..), None) => {
-                        early_error(format!("codegen option `{}` takes no \
-                                             value", key).as_slice())
+                        early_error(output, format!("codegen option `{}` takes no \
+                                                     value", key).as_slice())
                     }
                     (None, Some(type_desc)) => {
-                        early_error(format!("codegen option `{0}` requires \
-                                             {1} (-C {0}=<value>)",
-                                            key, type_desc).as_slice())
+                        early_error(output, format!("codegen option `{0}` requires \
+                                                     {1} (-C {0}=<value>)",
+                                                    key, type_desc).as_slice())
                     }
                     (Some(value), Some(type_desc)) => {
-                        early_error(format!("incorrect value `{}` for codegen \
-                                             option `{}` - {} was expected",
-                                             value, key, type_desc).as_slice())
+                        early_error(output, format!("incorrect value `{}` for codegen \
+                                                     option `{}` - {} was expected",
+                                                     value, key, type_desc).as_slice())
                     }
                     (None, None) => unreachable!()
                 }
@@ -489,8 +615,8 @@ pub fn build_codegen_options(matches: &getopts::Matches) -> CodegenOptions
             break;
         }
         if !found {
-            early_error(format!("unknown codegen option: `{}`",
-                                key).as_slice());
+            early_error(output, format!("unknown codegen option: `{}`",
+                                        key).as_slice());
         }
     }
     return cg;
@@ -514,7 +640,7 @@ pub fn default_configuration(sess: &Session) -> ast::CrateConfig {
     };
 
     let mk = attr::mk_name_value_item_str;
-    return vec!(// Target bindings.
+    let mut cfg = vec!(// Target bindings.
          attr::mk_word_item(fam.clone()),
          mk(InternedString::new("target_os"), intern(os)),
          mk(InternedString::new("target_family"), fam),
@@ -523,6 +649,106 @@ pub fn default_configuration(sess: &Session) -> ast::CrateConfig {
          mk(InternedString::new("target_word_size"),
             intern(wordsz))
     );
+    for feature in enabled_target_features(sess).iter() {
+        cfg.push(mk(InternedString::new("target_feature"),
+                    intern(feature.as_slice())));
+    }
+    cfg
+}
+
+// Features which are always enabled when the feature on the left is. This is
+// only used to work out `cfg(target_feature)` and the registers asm! may use;
+// LLVM works these out by itself.
+static IMPLIED_TARGET_FEATURES: &'static [(&'static str, &'static str)] = &[
+    ("sse2", "sse"),
+    ("sse3", "sse2"),
+    ("ssse3", "sse3"),
+    ("sse4.1", "ssse3"),
+    ("sse4.2", "sse4.1"),
+    ("avx", "sse4.2"),
+    ("avx2", "avx"),
+    ("fma", "avx"),
+];
+
+fn implies_target_feature(feature: &str, other: &str) -> bool {
+    feature == other || IMPLIED_TARGET_FEATURES.iter().any(|&(f, implied)| {
+        f == feature && implies_target_feature(implied, other)
+    })
+}
+
+/// Computes the set of target features enabled for this session, from the
+/// target's default features, `-C soft-float` (as the `soft-float` feature)
+/// and then `-C target-feature`. Each entry is
+/// `+name`, `-name` or a bare `name` (which enables it), and later entries
+/// override earlier ones, with a disabled feature also disabling those that
+/// imply it. Features implied by an enabled feature are included as well, as
+/// is SSE2 on x86_64, which every such CPU has.
+pub fn enabled_target_features(sess: &Session) -> Vec<String> {
+    let baseline = match sess.target.target.arch.as_slice() {
+        "x86_64" => "+sse2",
+        _ => "",
+    };
+    let soft_float = if sess.opts.cg.soft_float { "+soft-float" } else { "" };
+    let all = format!("{},{},{},{}", baseline, sess.target.target.options.features,
+                      soft_float, sess.opts.cg.target_feature);
+    let mut features: Vec<String> = Vec::new();
+    for (enable, name) in target_feature_entries(all.as_slice()) {
+        let name = name.as_slice();
+        if enable {
+            features.retain(|f| f.as_slice() != name);
+            features.push(name.to_string());
+        } else {
+            // Disabling a feature disables everything that implies it too.
+            features.retain(|f| !implies_target_feature(f.as_slice(), name));
+        }
+    }
+
+    let mut i = 0;
+    while i < features.len() {
+        for &(feature, implied) in IMPLIED_TARGET_FEATURES.iter() {
+            if features[i].as_slice() == feature &&
+               !features.iter().any(|f| f.as_slice() == implied) {
+                features.push(implied.to_string());
+            }
+        }
+        i += 1;
+    }
+    features
+}
+
+/// The target features that the target's default features or
+/// `-C target-feature` explicitly disable, and which aren't enabled again
+/// later on. Features that imply one of these are disabled too, unlike
+/// those merely missing from `enabled_target_features`, which the target
+/// CPU may still have.
+pub fn disabled_target_features(sess: &Session) -> Vec<String> {
+    let all = format!("{},{}", sess.target.target.options.features,
+                      sess.opts.cg.target_feature);
+    let mut disabled: Vec<String> = Vec::new();
+    for (enable, name) in target_feature_entries(all.as_slice()) {
+        disabled.retain(|f| f.as_slice() != name.as_slice());
+        if !enable {
+            disabled.push(name);
+        }
+    }
+    disabled
+}
+
+/// Whether `feature` is one of, or implies one of, `disabled`.
+pub fn is_target_feature_disabled(disabled: &[String], feature: &str) -> bool {
+    disabled.iter().any(|f| implies_target_feature(feature, f.as_slice()))
+}
+
+/// Splits a comma separated list of `+name`, `-name` and bare `name`
+/// feature entries into whether each enables its feature, and its name.
+fn target_feature_entries(list: &str) -> Vec<(bool, String)> {
+    list.split(',').map(|f| f.trim()).filter(|f| !f.is_empty()).map(|feature| {
+        match feature.char_at(0) {
+            '+' => (true, feature.slice_from(1).to_string()),
+            '-' => (false, feature.slice_from(1).to_string()),
+            _ => (true, feature.to_string()),
+        }
+    }).collect()
 }
 
 pub fn append_configuration(cfg: &mut ast::CrateConfig,
@@ -599,7 +825,8 @@ pub fn optgroups() -> Vec<getopts::OptGroup> {
               "Parse and expand the source, but run no analysis and produce no output"),
         optflag("O", "", "Equivalent to --opt-level=2"),
         optopt("o", "", "Write output to <filename>", "FILENAME"),
-        optopt("", "opt-level", "Optimize with possible levels 0-3", "LEVEL"),
+        optopt("", "opt-level", "Optimize with possible levels 0-3, s (for size) \
+                                 or z (for size, at the expense of speed)", "LEVEL"),
         optopt( "",  "out-dir", "Write output to compiler-chosen filename in <dir>", "DIR"),
         optflag("", "parse-only", "Parse only; do not compile, assemble, or link"),
         optopt("", "explain", "Provide a detailed explanation of an error message", "OPT"),
@@ -608,7 +835,9 @@ pub fn optgroups() -> Vec<getopts::OptGroup> {
                    valid types are: `normal` (un-annotated source),
                    `expanded` (crates expanded),
                    `typed` (crates expanded, with type annotations),
-                   `expanded,identified` (fully parenthesized, AST nodes with IDs), or
+                   `expanded,identified` (fully parenthesized, AST nodes with IDs),
+                   `preserving` or `expanded,preserving` (unmodified nodes copied
+                   verbatim from the source), or
                    `flowgraph=<nodeid>` (graphviz formatted flowgraph for node)",
                  "TYPE"),
         optflagopt("", "dep-info",
@@ -623,6 +852,8 @@ pub fn optgroups() -> Vec<getopts::OptGroup> {
         optmulti("A", "allow", "Set lint allowed", "OPT"),
         optmulti("D", "deny", "Set lint denied", "OPT"),
         optmulti("F", "forbid", "Set lint forbidden", "OPT"),
+        optopt("", "cap-lints", "Set the most any lint is reported at, overriding \
+                                 attributes and the other lint flags", "LEVEL"),
         optmulti("C", "codegen", "Set a codegen option", "OPT[=VALUE]"),
         optmulti("Z", "", "Set internal debugging options", "FLAG"),
         optflagopt("v", "version", "Print version info and exit", "verbose"),
@@ -630,8 +861,15 @@ pub fn optgroups() -> Vec<getopts::OptGroup> {
             auto   = colorize, if output goes to a tty (default);
             always = always colorize output;
             never  = never colorize output", "auto|always|never"),
+        optopt("", "error-format", "How errors and other diagnostics are printed:
+            human = readable text (default);
+            json  = one JSON object per diagnostic, for tools", "human|json"),
+        optopt("", "error-limit", "Stop compiling after N errors (0 = no limit, the default)",
+               "N"),
         optmulti("", "extern", "Specify where an external rust library is located",
                  "NAME=PATH"),
+        optmulti("", "remap-path-prefix", "Remap source paths starting with FROM to TO
+                 in all output, including diagnostics and debuginfo", "FROM=TO"),
     )
 }
 
@@ -646,11 +884,57 @@ pub fn parse_cfgspecs(cfgspecs: Vec<String> ) -> ast::CrateConfig {
     }).collect::<ast::CrateConfig>()
 }
 
+/// Parses `--color`, which is needed before the other options to report
+/// errors in them.
+pub fn parse_color(matches: &getopts::Matches) -> ColorConfig {
+    match matches.opt_str("color").as_ref().map(|s| s.as_slice()) {
+        Some("auto")   => Auto,
+        Some("always") => Always,
+        Some("never")  => Never,
+
+        None => Auto,
+
+        Some(arg) => {
+            early_error(HumanOutput(Auto), format!("argument for --color must be auto, \
+                                                    always or never (instead was `{}`)",
+                                                   arg).as_slice())
+        }
+    }
+}
+
+/// Parses `--error-format`, which like `--color` is needed before the other
+/// options to report errors in them.
+pub fn parse_error_format(matches: &getopts::Matches, color: ColorConfig) -> ErrorFormat {
+    match matches.opt_str("error-format").as_ref().map(|s| s.as_slice()) {
+        Some("human") | None => HumanErrors,
+        Some("json") => JsonErrors,
+
+        Some(arg) => {
+            early_error(HumanOutput(color), format!("argument for --error-format must be \
+                                                     human or json (instead was `{}`)",
+                                                    arg).as_slice())
+        }
+    }
+}
+
+/// Where errors in the command line are reported, from `--color` and
+/// `--error-format`.
+pub fn parse_error_output(matches: &getopts::Matches) -> ErrorOutput {
+    let color = parse_color(matches);
+    match parse_error_format(matches, color) {
+        HumanErrors => HumanOutput(color),
+        JsonErrors => JsonOutput,
+    }
+}
+
 pub fn build_session_options(matches: &getopts::Matches) -> Options {
+    let color = parse_color(matches);
+    let error_format = parse_error_format(matches, color);
+    let output = parse_error_output(matches);
 
     let unparsed_crate_types = matches.opt_strs("crate-type");
     let crate_types = parse_crate_types_from_list(unparsed_crate_types)
-        .unwrap_or_else(|e| early_error(e.as_slice()));
+        .unwrap_or_else(|e| early_error(output, e.as_slice()));
 
     let parse_only = matches.opt_present("parse-only");
     let no_trans = matches.opt_present("no-trans");
@@ -670,9 +954,48 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     }
 
     let mut debugging_opts = 0;
+    let mut macro_backtrace = CollapsedBacktrace;
+    let mut treat_err_as_bug = 0;
+    let mut emit_filter = Vec::new();
     let debug_flags = matches.opt_strs("Z");
     let debug_map = debugging_opts_map();
     for debug_flag in debug_flags.iter() {
+        // The debug options that take a value rather than being a flag
+        let flag = debug_flag.as_slice();
+        if flag.starts_with("macro-backtrace=") {
+            macro_backtrace = match flag.slice_from("macro-backtrace=".len()) {
+                "none" => NoBacktrace,
+                "collapsed" => CollapsedBacktrace,
+                "full" => FullBacktrace,
+                arg => early_error(output, format!("argument for -Z macro-backtrace must be none, \
+                                                    collapsed or full (instead was `{}`)",
+                                                   arg).as_slice())
+            };
+            continue;
+        }
+        if flag == "treat-err-as-bug" || flag.starts_with("treat-err-as-bug=") {
+            treat_err_as_bug = match flag.slice_from("treat-err-as-bug".len()) {
+                "" => 1,
+                arg => match from_str::<uint>(arg.slice_from(1)) {
+                    Some(n) if n > 0 => n,
+                    _ => early_error(output, format!("argument for -Z treat-err-as-bug must be \
+                                                      a positive integer (instead was `{}`)",
+                                                     arg.slice_from(1)).as_slice())
+                }
+            };
+            continue;
+        }
+        if flag.starts_with("emit-filter=") {
+            for glob in flag.slice_from("emit-filter=".len()).split(',') {
+                if glob.is_empty() {
+                    early_error(output, "argument for -Z emit-filter must be a comma \
+                                         separated list of function paths");
+                }
+                emit_filter.push(glob.to_string());
+            }
+            continue;
+        }
+
         let mut this_bit = 0;
         for tuple in debug_map.iter() {
             let (name, bit) = match *tuple { (ref a, _, b) => (a, b) };
@@ -682,8 +1005,8 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
             }
         }
         if this_bit == 0 {
-            early_error(format!("unknown debug flag: {}",
-                                *debug_flag).as_slice())
+            early_error(output, format!("unknown debug flag: {}",
+                                        *debug_flag).as_slice())
         }
         debugging_opts |= this_bit;
     }
@@ -704,8 +1027,8 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
                     "obj"  => OutputTypeObject,
                     "link" => OutputTypeExe,
                     _ => {
-                        early_error(format!("unknown emission type: `{}`",
-                                            part).as_slice())
+                        early_error(output, format!("unknown emission type: `{}`",
+                                                    part).as_slice())
                     }
                 };
                 output_types.push(output_type)
@@ -724,7 +1047,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     let opt_level = {
         if matches.opt_present("O") {
             if matches.opt_present("opt-level") {
-                early_error("-O and --opt-level both provided");
+                early_error(output, "-O and --opt-level both provided");
             }
             Default
         } else if matches.opt_present("opt-level") {
@@ -734,10 +1057,12 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
                 Some("1") => Less,
                 Some("2") => Default,
                 Some("3") => Aggressive,
+                Some("s") => Size,
+                Some("z") => SizeMin,
                 Some(arg) => {
-                    early_error(format!("optimization level needs to be \
-                                         between 0-3 (instead was `{}`)",
-                                        arg).as_slice());
+                    early_error(output, format!("optimization level needs to be \
+                                                 between 0-3, s or z (instead was `{}`)",
+                                                arg).as_slice());
                 }
             }
         } else {
@@ -747,7 +1072,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     let gc = debugging_opts & GC != 0;
     let debuginfo = if matches.opt_present("g") {
         if matches.opt_present("debuginfo") {
-            early_error("-g and --debuginfo both provided");
+            early_error(output, "-g and --debuginfo both provided");
         }
         FullDebugInfo
     } else if matches.opt_present("debuginfo") {
@@ -757,9 +1082,9 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
             None      |
             Some("2") => FullDebugInfo,
             Some(arg) => {
-                early_error(format!("debug info level needs to be between \
-                                     0-2 (instead was `{}`)",
-                                    arg).as_slice());
+                early_error(output, format!("debug info level needs to be between \
+                                             0-2 (instead was `{}`)",
+                                            arg).as_slice());
             }
         }
     } else {
@@ -779,9 +1104,9 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
             (Some(name), "framework") => (name, cstore::NativeFramework),
             (Some(name), "static") => (name, cstore::NativeStatic),
             (_, s) => {
-                early_error(format!("unknown library kind `{}`, expected \
-                                     one of dylib, framework, or static",
-                                    s).as_slice());
+                early_error(output, format!("unknown library kind `{}`, expected \
+                                             one of dylib, framework, or static",
+                                            s).as_slice());
             }
         };
         (name.to_string(), kind)
@@ -797,27 +1122,31 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
                        matches.opt_present("print-file-name") ||
                        matches.opt_present("crate-file-name"));
     if matches.opt_present("crate-file-name") {
-        early_warn("the --crate-file-name argument has been renamed to \
-                    --print-file-name");
+        early_warn(output, "the --crate-file-name argument has been renamed to \
+                            --print-file-name");
     }
-    let cg = build_codegen_options(matches);
+    let lint_cap = matches.opt_str("cap-lints").map(|cap| {
+        lint::Level::from_str(cap.as_slice()).unwrap_or_else(|| {
+            early_error(output, format!("argument for --cap-lints must be allow, warn, \
+                                         deny or forbid (instead was `{}`)",
+                                        cap).as_slice())
+        })
+    });
+
+    let cg = build_codegen_options(matches, output);
 
     if !cg.remark.is_empty() && debuginfo == NoDebugInfo {
-        early_warn("-C remark will not show source locations without --debuginfo");
+        early_warn(output, "-C remark will not show source locations without --debuginfo");
     }
 
-    let color = match matches.opt_str("color").as_ref().map(|s| s.as_slice()) {
-        Some("auto")   => Auto,
-        Some("always") => Always,
-        Some("never")  => Never,
-
-        None => Auto,
-
-        Some(arg) => {
-            early_error(format!("argument for --color must be auto, always \
-                                 or never (instead was `{}`)",
-                                arg).as_slice())
-        }
+    let error_limit = match matches.opt_str("error-limit") {
+        Some(arg) => match from_str(arg.as_slice()) {
+            Some(limit) => limit,
+            None => early_error(output, format!("argument for --error-limit must be a \
+                                                 number (instead was `{}`)",
+                                                arg).as_slice()),
+        },
+        None => 0,
     };
 
     let mut externs = HashMap::new();
@@ -825,11 +1154,11 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         let mut parts = arg.as_slice().splitn(1, '=');
         let name = match parts.next() {
             Some(s) => s,
-            None => early_error("--extern value must not be empty"),
+            None => early_error(output, "--extern value must not be empty"),
         };
         let location = match parts.next() {
             Some(s) => s,
-            None => early_error("--extern value must be of the format `foo=bar`"),
+            None => early_error(output, "--extern value must be of the format `foo=bar`"),
         };
 
         match externs.entry(name.to_string()) {
@@ -838,6 +1167,14 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         }
     }
 
+    let remap_path_prefix = matches.opt_strs("remap-path-prefix").into_iter().map(|arg| {
+        let mut parts = arg.as_slice().splitn(1, '=');
+        match (parts.next(), parts.next()) {
+            (Some(from), Some(to)) => (from.to_string(), to.to_string()),
+            _ => early_error(output, "--remap-path-prefix value must be of the format `from=to`"),
+        }
+    }).collect();
+
     let crate_name = matches.opt_str("crate-name");
 
     Options {
@@ -846,6 +1183,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         optimize: opt_level,
         debuginfo: debuginfo,
         lint_opts: lint_opts,
+        lint_cap: lint_cap,
         describe_lints: describe_lints,
         output_types: output_types,
         addl_lib_search_paths: RefCell::new(addl_lib_search_paths),
@@ -857,11 +1195,17 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         no_trans: no_trans,
         no_analysis: no_analysis,
         debugging_opts: debugging_opts,
+        macro_backtrace: macro_backtrace,
+        treat_err_as_bug: treat_err_as_bug,
+        emit_filter: emit_filter,
         write_dependency_info: write_dependency_info,
         print_metas: print_metas,
         cg: cg,
         color: color,
+        error_format: error_format,
+        error_limit: error_limit,
         externs: externs,
+        remap_path_prefix: remap_path_prefix,
         crate_name: crate_name,
         alt_std_name: None,
         libs: libs,
@@ -15,10 +15,11 @@ use lint;
 use util::nodemap::NodeMap;
 
 use syntax::ast::NodeId;
-use syntax::codemap::Span;
+use syntax::codemap::{Span, MultiSpan};
 use syntax::diagnostic::{mod, Emitter};
 use syntax::diagnostics;
 use syntax::feature_gate;
+use syntax::json;
 use syntax::parse;
 use syntax::parse::token;
 use syntax::parse::ParseSess;
@@ -71,6 +72,9 @@ impl Session {
     pub fn span_err_with_code(&self, sp: Span, msg: &str, code: &str) {
         self.diagnostic().span_err_with_code(sp, msg, code)
     }
+    pub fn span_err_multi(&self, msp: &MultiSpan, msg: &str) {
+        self.diagnostic().span_err_multi(msp, msg)
+    }
     pub fn err(&self, msg: &str) {
         self.diagnostic().handler().err(msg)
     }
@@ -83,6 +87,24 @@ impl Session {
     pub fn abort_if_errors(&self) {
         self.diagnostic().handler().abort_if_errors()
     }
+    /// Like `abort_if_errors`, for the end of a checking pass that the
+    /// passes after it don't depend on. With `-Z keep-going` compilation
+    /// continues, so that the errors of those passes are reported too;
+    /// the driver still stops before translation.
+    pub fn abort_if_errors_unless_keep_going(&self) {
+        if !self.debugging_opt(config::KEEP_GOING) {
+            self.abort_if_errors()
+        }
+    }
+    /// Runs a pass, emitting the diagnostics it reports sorted by the spans
+    /// they point at once it's done, so that their order doesn't depend on
+    /// the order the pass visits things in.
+    pub fn sorting_diagnostics<T>(&self, f: || -> T) -> T {
+        self.diagnostic().handler().buffer_diagnostics();
+        let result = f();
+        self.diagnostic().handler().flush_diagnostics();
+        result
+    }
     pub fn span_warn(&self, sp: Span, msg: &str) {
         if self.can_print_warnings {
             self.diagnostic().span_warn(sp, msg)
@@ -113,6 +135,9 @@ impl Session {
     pub fn span_help(&self, sp: Span, msg: &str) {
         self.diagnostic().span_help(sp, msg)
     }
+    pub fn span_suggestion(&self, sp: Span, msg: &str, suggestion: String) {
+        self.diagnostic().span_suggestion(sp, msg, suggestion)
+    }
     pub fn fileline_note(&self, sp: Span, msg: &str) {
         self.diagnostic().fileline_note(sp, msg)
     }
@@ -202,6 +227,36 @@ impl Session {
     pub fn show_span(&self) -> bool {
         self.debugging_opt(config::SHOW_SPAN)
     }
+    /// The relocation model to generate code for, from `-C relocation-model`
+    /// or else the target's default.
+    pub fn relocation_model<'a>(&'a self) -> &'a str {
+        match self.opts.cg.relocation_model {
+            Some(ref s) => s.as_slice(),
+            None => self.target.target.options.relocation_model.as_slice()
+        }
+    }
+    /// Whether floating point is done in software, with the soft-float ABI,
+    /// from `-C soft-float` or a target with the `soft-float` feature.
+    pub fn soft_float(&self) -> bool {
+        config::enabled_target_features(self).iter().any(|f| f.as_slice() == "soft-float")
+    }
+    /// Whether every function must keep its frame pointer, as set by
+    /// `-C force-frame-pointers` or else by the target and debuginfo level.
+    pub fn keep_frame_pointers(&self) -> bool {
+        // FIXME: #11906: Omitting frame pointers breaks retrieving the value of a
+        // parameter.
+        self.opts.cg.force_frame_pointers.unwrap_or(
+            self.opts.debuginfo != config::NoDebugInfo ||
+            !self.target.target.options.eliminate_frame_pointer)
+    }
+    /// Whether the function at `path` is kept in the `--emit=ir,asm` output
+    /// by `-Z emit-filter`.
+    pub fn emit_filter_matches(&self, path: &str) -> bool {
+        self.opts.emit_filter.is_empty() ||
+            self.opts.emit_filter.iter().any(|glob| {
+                glob_matches(glob.as_bytes(), path.as_bytes())
+            })
+    }
     pub fn sysroot<'a>(&'a self) -> &'a Path {
         match self.opts.maybe_sysroot {
             Some (ref sysroot) => sysroot,
@@ -226,9 +281,22 @@ pub fn build_session(sopts: config::Options,
                      local_crate_source_file: Option<Path>,
                      registry: diagnostics::registry::Registry)
                      -> Session {
-    let codemap = codemap::CodeMap::new();
-    let diagnostic_handler =
-        diagnostic::default_handler(sopts.color, Some(registry));
+    let path_mapping = codemap::FilePathMapping::new(sopts.remap_path_prefix.clone());
+    let codemap = codemap::CodeMap::with_file_path_mapping(path_mapping);
+    let emitter: Box<Emitter + Send> = match sopts.error_format {
+        config::HumanErrors => {
+            let mut emitter = diagnostic::EmitterWriter::stderr(sopts.color, Some(registry));
+            emitter.set_macro_backtrace(sopts.macro_backtrace);
+            box emitter
+        }
+        config::JsonErrors => box json::JsonEmitter::stderr(Some(registry)),
+    };
+    let diagnostic_handler = diagnostic::mk_handler(emitter);
+    diagnostic_handler.set_error_limit(sopts.error_limit);
+    diagnostic_handler.set_treat_err_as_bug(sopts.treat_err_as_bug);
+    diagnostic_handler.set_dedup_expansions(
+        sopts.debugging_opts & config::SHOW_DUPLICATE_DIAGNOSTICS == 0);
+    diagnostic_handler.set_summary(sopts.debugging_opts & config::DIAGNOSTIC_SUMMARY != 0);
     let span_diagnostic_handler =
         diagnostic::mk_span_handler(diagnostic_handler, codemap);
 
@@ -292,14 +360,35 @@ pub fn expect<T>(sess: &Session, opt: Option<T>, msg: || -> String) -> T {
     diagnostic::expect(sess.diagnostic(), opt, msg)
 }
 
-pub fn early_error(msg: &str) -> ! {
-    let mut emitter = diagnostic::EmitterWriter::stderr(diagnostic::Auto, None);
+fn early_emitter(output: config::ErrorOutput) -> Box<Emitter + Send> {
+    match output {
+        config::HumanOutput(color) => box diagnostic::EmitterWriter::stderr(color, None),
+        config::JsonOutput => box json::JsonEmitter::stderr(None),
+    }
+}
+
+pub fn early_error(output: config::ErrorOutput, msg: &str) -> ! {
+    let mut emitter = early_emitter(output);
     emitter.emit(None, msg, None, diagnostic::Fatal);
     panic!(diagnostic::FatalError);
 }
 
-pub fn early_warn(msg: &str) {
-    let mut emitter = diagnostic::EmitterWriter::stderr(diagnostic::Auto, None);
+pub fn early_warn(output: config::ErrorOutput, msg: &str) {
+    let mut emitter = early_emitter(output);
     emitter.emit(None, msg, None, diagnostic::Warning);
 }
 
+// Matches `s` against `glob`, where `*` stands for any sequence of characters
+// (including `::`) and `?` for any single one.
+fn glob_matches(glob: &[u8], s: &[u8]) -> bool {
+    match glob.head() {
+        None => s.is_empty(),
+        Some(&b'*') => {
+            range(0, s.len() + 1).any(|i| glob_matches(glob[1..], s[i..]))
+        }
+        Some(&c) => match s.head() {
+            Some(&d) if c == b'?' || c == d => glob_matches(glob[1..], s[1..]),
+            _ => false,
+        },
+    }
+}
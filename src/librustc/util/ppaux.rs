@@ -26,6 +26,7 @@ use middle::ty;
 use middle::typeck;
 use middle::typeck::check::regionmanip;
 
+use std::cmp;
 use std::rc::Rc;
 use syntax::abi;
 use syntax::ast_map;
@@ -151,9 +152,27 @@ pub fn explain_region_and_span(cx: &ctxt, region: ty::Region)
         (format!("{}", token::get_name(name)), None)
       }
 
-      // I believe these cases should not occur (except when debugging,
+      // Regions bound by a `for<'a>` binder are described by name, pointing
+      // at their declaration when it is in this crate.
+      ty::ReInfer(ReSkolemized(_, br)) | ty::ReLateBound(_, br) => {
+        match br {
+          BrNamed(def_id, name) => {
+            let span = if def_id.krate == ast::LOCAL_CRATE {
+                cx.map.opt_span(def_id.node)
+            } else {
+                None
+            };
+            (format!("the higher-ranked lifetime `{}`", token::get_name(name)), span)
+          }
+          BrAnon(_) | BrFresh(_) | BrEnv => {
+            ("an anonymous higher-ranked lifetime".to_string(), None)
+          }
+        }
+      }
+
+      // I believe this case should not occur (except when debugging,
       // perhaps)
-      ty::ReInfer(_) | ty::ReLateBound(..) => {
+      ty::ReInfer(ReVar(_)) => {
         (format!("lifetime {}", region), None)
       }
     };
@@ -166,6 +185,19 @@ pub fn explain_region_and_span(cx: &ctxt, region: ty::Region)
     }
 }
 
+/// Describes a bound lifetime parameter in an error message, by name when it
+/// has one.
+pub fn bound_region_description(br: BoundRegion) -> String {
+    match br {
+        BrNamed(_, name) => {
+            format!("bound lifetime parameter `{}`", token::get_name(name))
+        }
+        BrAnon(_) | BrFresh(_) | BrEnv => {
+            "an anonymous bound lifetime parameter".to_string()
+        }
+    }
+}
+
 pub fn bound_region_ptr_to_string(cx: &ctxt, br: BoundRegion) -> String {
     bound_region_to_string(cx, "", false, br)
 }
@@ -491,6 +523,23 @@ pub fn parameterized<'tcx>(cx: &ctxt<'tcx>,
         }
     }
 
+    let mut strs = region_params_to_strings(cx, substs);
+
+    let tps = substs.types.get_slice(subst::TypeSpace);
+    let num_defaults = num_defaulted_params(cx, substs, generics);
+
+    for t in tps[..tps.len() - num_defaults].iter() {
+        strs.push(ty_to_string(cx, *t))
+    }
+
+    if strs.len() > 0u {
+        format!("{}<{}>", base, strs.connect(", "))
+    } else {
+        format!("{}", base)
+    }
+}
+
+fn region_params_to_strings<'tcx>(cx: &ctxt<'tcx>, substs: &subst::Substs<'tcx>) -> Vec<String> {
     let mut strs = Vec::new();
 
     match substs.regions {
@@ -512,10 +561,19 @@ pub fn parameterized<'tcx>(cx: &ctxt<'tcx>,
         }
     }
 
+    strs
+}
+
+/// The number of trailing type parameters in `substs` that are the same as
+/// their defaults, which are left out when printing.
+fn num_defaulted_params<'tcx>(cx: &ctxt<'tcx>,
+                              substs: &subst::Substs<'tcx>,
+                              generics: &ty::Generics<'tcx>)
+                              -> uint {
     let tps = substs.types.get_slice(subst::TypeSpace);
     let ty_params = generics.types.get_slice(subst::TypeSpace);
     let has_defaults = ty_params.last().map_or(false, |def| def.default.is_some());
-    let num_defaults = if has_defaults {
+    if has_defaults {
         ty_params.iter().zip(tps.iter()).rev().take_while(|&(def, &actual)| {
             match def.default {
                 Some(default) => default.subst(cx, substs) == actual,
@@ -524,17 +582,117 @@ pub fn parameterized<'tcx>(cx: &ctxt<'tcx>,
         }).count()
     } else {
         0
-    };
+    }
+}
 
-    for t in tps[..tps.len() - num_defaults].iter() {
-        strs.push(ty_to_string(cx, *t))
+/// Prints the mismatched types `expected` and `found` with the components
+/// they have in common replaced by `_`, so that the parts that differ stand
+/// out in long types. Returns `None` if no component could be left out.
+pub fn ty_diff_to_strings<'tcx>(cx: &ctxt<'tcx>, expected: Ty<'tcx>, found: Ty<'tcx>)
+                                -> Option<(String, String)> {
+    if cx.sess.verbose() || expected == found {
+        return None;
     }
 
-    if strs.len() > 0u {
-        format!("{}<{}>", base, strs.connect(", "))
-    } else {
-        format!("{}", base)
+    let mut elided = false;
+    let strs = diff_to_strings(cx, expected, found, &mut elided);
+    if elided { Some(strs) } else { None }
+}
+
+fn diff_to_strings<'tcx>(cx: &ctxt<'tcx>, a: Ty<'tcx>, b: Ty<'tcx>, elided: &mut bool)
+                         -> (String, String) {
+    if a == b {
+        // These are about as short as the `_` that would replace them.
+        match a.sty {
+            ty_bool | ty_char | ty_int(_) | ty_uint(_) | ty_float(_) | ty_str |
+            ty_param(_) | ty_infer(_) | ty_err => {}
+            ty_tup(ref elems) if elems.is_empty() => {}
+            _ => {
+                *elided = true;
+                return ("_".to_string(), "_".to_string());
+            }
+        }
+    }
+
+    // Only types built the same way can be compared component by
+    // component; anything else is printed in full.
+    match (&a.sty, &b.sty) {
+        (&ty_enum(a_did, ref a_substs), &ty_enum(b_did, ref b_substs)) |
+        (&ty_struct(a_did, ref a_substs), &ty_struct(b_did, ref b_substs))
+                if a_did == b_did && a_substs.regions == b_substs.regions => {
+            let base = ty::item_path_str(cx, a_did);
+            let generics = ty::lookup_item_type(cx, a_did).generics;
+            let a_tps = a_substs.types.get_slice(subst::TypeSpace);
+            let b_tps = b_substs.types.get_slice(subst::TypeSpace);
+            let len = a_tps.len() - cmp::min(num_defaulted_params(cx, a_substs, &generics),
+                                             num_defaulted_params(cx, b_substs, &generics));
+            let (a_strs, b_strs) = diff_all_to_strings(cx, a_tps.slice_to(len),
+                                                       b_tps.slice_to(len), elided);
+            let with_params = |strs: Vec<String>| {
+                let mut params = region_params_to_strings(cx, a_substs);
+                params.extend(strs.into_iter());
+                if params.is_empty() {
+                    base.clone()
+                } else {
+                    format!("{}<{}>", base, params.connect(", "))
+                }
+            };
+            (with_params(a_strs), with_params(b_strs))
+        }
+        (&ty_tup(ref a_elems), &ty_tup(ref b_elems)) if a_elems.len() == b_elems.len() => {
+            let (a_strs, b_strs) = diff_all_to_strings(cx, a_elems.as_slice(),
+                                                       b_elems.as_slice(), elided);
+            let tuple = |strs: Vec<String>| {
+                match strs.as_slice() {
+                    [ref string] => format!("({},)", string),
+                    strs => format!("({})", strs.connect(", "))
+                }
+            };
+            (tuple(a_strs), tuple(b_strs))
+        }
+        (&ty_uniq(a_ty), &ty_uniq(b_ty)) => {
+            let (a_str, b_str) = diff_to_strings(cx, a_ty, b_ty, elided);
+            (format!("Box<{}>", a_str), format!("Box<{}>", b_str))
+        }
+        (&ty_ptr(ref a_mt), &ty_ptr(ref b_mt)) if a_mt.mutbl == b_mt.mutbl => {
+            let prefix = match a_mt.mutbl {
+                ast::MutMutable => "*mut ",
+                ast::MutImmutable => "*const ",
+            };
+            let (a_str, b_str) = diff_to_strings(cx, a_mt.ty, b_mt.ty, elided);
+            (format!("{}{}", prefix, a_str), format!("{}{}", prefix, b_str))
+        }
+        (&ty_rptr(a_r, ref a_mt), &ty_rptr(b_r, ref b_mt))
+                if a_r == b_r && a_mt.mutbl == b_mt.mutbl => {
+            let prefix = format!("{}{}", region_ptr_to_string(cx, a_r),
+                                 mutability_to_string(a_mt.mutbl));
+            let (a_str, b_str) = diff_to_strings(cx, a_mt.ty, b_mt.ty, elided);
+            (format!("{}{}", prefix, a_str), format!("{}{}", prefix, b_str))
+        }
+        (&ty_vec(a_ty, a_sz), &ty_vec(b_ty, b_sz)) if a_sz == b_sz => {
+            let (a_str, b_str) = diff_to_strings(cx, a_ty, b_ty, elided);
+            match a_sz {
+                Some(n) => (format!("[{}, ..{}]", a_str, n), format!("[{}, ..{}]", b_str, n)),
+                None => (format!("[{}]", a_str), format!("[{}]", b_str)),
+            }
+        }
+        _ => (ty_to_string(cx, a), ty_to_string(cx, b))
+    }
+}
+
+fn diff_all_to_strings<'tcx>(cx: &ctxt<'tcx>,
+                             a_tys: &[Ty<'tcx>],
+                             b_tys: &[Ty<'tcx>],
+                             elided: &mut bool)
+                             -> (Vec<String>, Vec<String>) {
+    let mut a_strs = Vec::new();
+    let mut b_strs = Vec::new();
+    for (&a, &b) in a_tys.iter().zip(b_tys.iter()) {
+        let (a_str, b_str) = diff_to_strings(cx, a, b, elided);
+        a_strs.push(a_str);
+        b_strs.push(b_str);
     }
+    (a_strs, b_strs)
 }
 
 pub fn ty_to_short_str<'tcx>(cx: &ctxt<'tcx>, typ: Ty<'tcx>) -> String {
@@ -758,6 +916,8 @@ impl<'tcx> Repr<'tcx> for ast::TraitItem {
                                                      data.id),
             ast::TypeTraitItem(ref data) => format!("TypeTraitItem({}, id={})",
                                                      data.ty_param.ident, data.ty_param.id),
+            ast::ConstTraitItem(ref data) => format!("ConstTraitItem({}, id={})",
+                                                      data.ident, data.id),
         }
     }
 }
@@ -908,13 +908,10 @@ fn link_args(cmd: &mut Command,
 
     if t.options.position_independent_executables {
         let empty_vec = Vec::new();
-        let empty_str = String::new();
         let args = sess.opts.cg.link_args.as_ref().unwrap_or(&empty_vec);
         let mut args = args.iter().chain(used_link_args.iter());
         if !dylib
-            && (t.options.relocation_model.as_slice() == "pic"
-                || sess.opts.cg.relocation_model.as_ref()
-                   .unwrap_or(&empty_str).as_slice() == "pic")
+            && sess.relocation_model() == "pic"
             && !args.any(|x| x.as_slice() == "-static") {
             cmd.arg("-pie");
         }
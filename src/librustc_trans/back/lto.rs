@@ -36,9 +36,10 @@ pub fn run(sess: &session::Session, llmod: ModuleRef,
     for crate_type in sess.crate_types.borrow().iter() {
         match *crate_type {
             config::CrateTypeExecutable | config::CrateTypeStaticlib => {}
-            _ => {
-                sess.fatal("lto can only be run for executables and \
-                            static library outputs");
+            ty => {
+                sess.fatal(format!("lto can only be run for executables and \
+                                    static library outputs, not for a `{}`",
+                                   ty).as_slice());
             }
         }
     }
@@ -52,8 +53,12 @@ pub fn run(sess: &session::Session, llmod: ModuleRef,
         let path = match path {
             Some(p) => p,
             None => {
-                sess.fatal(format!("could not find rlib for: `{}`",
-                                   name).as_slice());
+                sess.err(format!("could not find rlib for: `{}`",
+                                 name).as_slice());
+                sess.note("LTO needs the bitcode of every dependency, which is only \
+                           available when it is linked in from an rlib");
+                sess.abort_if_errors();
+                unreachable!();
             }
         };
 
@@ -74,8 +79,12 @@ pub fn run(sess: &session::Session, llmod: ModuleRef,
                 None => {
                     if i == 0 {
                         // No bitcode was found at all.
-                        sess.fatal(format!("missing compressed bytecode in {}",
-                                           path.display()).as_slice());
+                        sess.err(format!("missing compressed bytecode in {}",
+                                         path.display()).as_slice());
+                        sess.note(format!("`{}` was built without bitcode; rebuild it with \
+                                           a compiler that embeds it in rlibs",
+                                          name).as_slice());
+                        sess.abort_if_errors();
                     }
                     // No more bitcode files to read.
                     break;
@@ -11,7 +11,7 @@
 use back::lto;
 use back::link::{get_cc_prog, remove};
 use driver::driver::{CrateTranslation, ModuleTranslation, OutputFilenames};
-use session::config::{NoDebugInfo, Passes, SomePasses, AllPasses};
+use session::config::{Passes, SomePasses, AllPasses};
 use session::Session;
 use session::config;
 use llvm;
@@ -23,15 +23,17 @@ use syntax::diagnostic;
 use syntax::diagnostic::{Emitter, Handler, Level, mk_handler};
 
 use std::c_str::{ToCStr, CString};
+use std::cmp;
 use std::io::Command;
 use std::io::fs;
 use std::iter::Unfold;
 use std::ptr;
 use std::str;
 use std::mem;
+use std::os;
 use std::sync::{Arc, Mutex};
 use std::task::TaskBuilder;
-use libc::{c_uint, c_int, c_void};
+use libc::{c_uint, c_int, c_void, c_char, size_t};
 
 pub fn llvm_err(handler: &diagnostic::Handler, msg: String) -> ! {
     unsafe {
@@ -150,35 +152,38 @@ fn get_llvm_opt_level(optimize: config::OptLevel) -> llvm::CodeGenOptLevel {
       config::Less => llvm::CodeGenLevelLess,
       config::Default => llvm::CodeGenLevelDefault,
       config::Aggressive => llvm::CodeGenLevelAggressive,
+      config::Size | config::SizeMin => llvm::CodeGenLevelDefault,
+    }
+}
+
+fn get_llvm_opt_size(optimize: config::OptLevel) -> llvm::CodeGenOptSize {
+    match optimize {
+      config::Size => llvm::CodeGenOptSizeDefault,
+      config::SizeMin => llvm::CodeGenOptSizeAggressive,
+      _ => llvm::CodeGenOptSizeNone,
     }
 }
 
 fn create_target_machine(sess: &Session) -> TargetMachineRef {
-    let reloc_model_arg = match sess.opts.cg.relocation_model {
-        Some(ref s) => s.as_slice(),
-        None => sess.target.target.options.relocation_model.as_slice()
-    };
+    let reloc_model_arg = sess.relocation_model();
     let reloc_model = match reloc_model_arg {
         "pic" => llvm::RelocPIC,
         "static" => llvm::RelocStatic,
         "default" => llvm::RelocDefault,
         "dynamic-no-pic" => llvm::RelocDynamicNoPic,
         _ => {
-            sess.err(format!("{} is not a valid relocation mode",
-                             sess.opts
-                                 .cg
-                                 .relocation_model).as_slice());
+            sess.err(format!("`{}` is not a valid relocation model, expected \
+                              one of `pic`, `static`, `default` or `dynamic-no-pic`",
+                             reloc_model_arg).as_slice());
             sess.abort_if_errors();
             unreachable!();
         }
     };
 
     let opt_level = get_llvm_opt_level(sess.opts.optimize);
-    let use_softfp = sess.opts.cg.soft_float;
+    let use_softfp = sess.soft_float();
 
-    // FIXME: #11906: Omitting frame pointers breaks retrieving the value of a parameter.
-    let no_fp_elim = (sess.opts.debuginfo != NoDebugInfo) ||
-                     !sess.target.target.options.eliminate_frame_pointer;
+    let no_fp_elim = sess.keep_frame_pointers();
 
     let any_library = sess.crate_types.borrow().iter().any(|ty| {
         *ty != config::CrateTypeExecutable
@@ -255,6 +260,11 @@ struct ModuleConfig {
     /// Some(level) to optimize at a certain level, or None to run
     /// absolutely no optimizations (used for the metadata module).
     opt_level: Option<llvm::CodeGenOptLevel>,
+    /// How much to favour code size over speed when optimizing.
+    opt_size: llvm::CodeGenOptSize,
+    /// The threshold for the inliner, from `-C inline-threshold`, or None
+    /// for the default for `opt_level` and `opt_size`.
+    inline_threshold: Option<uint>,
 
     // Flags indicating which outputs to produce.
     emit_no_opt_bc: bool,
@@ -270,6 +280,9 @@ struct ModuleConfig {
     no_prepopulate_passes: bool,
     no_builtins: bool,
     time_passes: bool,
+    /// The symbols of the functions to keep in the IR and assembly output,
+    /// or None to keep all of them.
+    emit_filter: Option<Vec<String>>,
 }
 
 impl ModuleConfig {
@@ -278,6 +291,8 @@ impl ModuleConfig {
             tm: tm,
             passes: passes,
             opt_level: None,
+            opt_size: llvm::CodeGenOptSizeNone,
+            inline_threshold: None,
 
             emit_no_opt_bc: false,
             emit_bc: false,
@@ -290,6 +305,7 @@ impl ModuleConfig {
             no_prepopulate_passes: false,
             no_builtins: false,
             time_passes: false,
+            emit_filter: None,
         }
     }
 
@@ -298,6 +314,11 @@ impl ModuleConfig {
         self.no_prepopulate_passes = sess.opts.cg.no_prepopulate_passes;
         self.no_builtins = trans.no_builtins;
         self.time_passes = sess.time_passes();
+        self.inline_threshold = sess.opts.cg.inline_threshold;
+        if !sess.opts.emit_filter.is_empty() {
+            self.emit_filter = Some(trans.emit_filtered_fns.iter()
+                                         .map(|s| s.clone()).collect());
+        }
     }
 }
 
@@ -428,8 +449,7 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
             if !config.no_prepopulate_passes {
                 llvm::LLVMRustAddAnalysisPasses(tm, fpm, llmod);
                 llvm::LLVMRustAddAnalysisPasses(tm, mpm, llmod);
-                populate_llvm_passes(fpm, mpm, llmod, opt_level,
-                                     config.no_builtins);
+                populate_llvm_passes(fpm, mpm, llmod, opt_level, &config);
             }
 
             for pass in config.passes.iter() {
@@ -493,20 +513,32 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         })
     }
 
+    // With `-Z emit-filter`, the IR and assembly are written from a copy of
+    // the module in which only the matched functions keep their bodies.
+    let llmod_filtered = match config.emit_filter {
+        Some(ref fns) if config.emit_ir || config.emit_asm => {
+            let cstrs: Vec<CString> = fns.iter().map(|s| s.as_slice().to_c_str()).collect();
+            let arr: Vec<*const c_char> = cstrs.iter().map(|c| c.as_ptr()).collect();
+            llvm::LLVMRustCloneModuleKeeping(llmod, arr.as_ptr(), arr.len() as size_t)
+        }
+        _ => llmod,
+    };
+
     time(config.time_passes, "codegen passes", (), |()| {
         if config.emit_ir {
             let ext = format!("{}.ll", name_extra);
             output_names.with_extension(ext.as_slice()).with_c_str(|output| {
-                with_codegen(tm, llmod, config.no_builtins, |cpm| {
-                    llvm::LLVMRustPrintModule(cpm, llmod, output);
+                with_codegen(tm, llmod_filtered, config.no_builtins, |cpm| {
+                    llvm::LLVMRustPrintModule(cpm, llmod_filtered, output);
                 })
             })
         }
 
         if config.emit_asm {
             let path = output_names.with_extension(format!("{}.s", name_extra).as_slice());
-            with_codegen(tm, llmod, config.no_builtins, |cpm| {
-                write_output_file(cgcx.handler, tm, cpm, llmod, &path, llvm::AssemblyFileType);
+            with_codegen(tm, llmod_filtered, config.no_builtins, |cpm| {
+                write_output_file(cgcx.handler, tm, cpm, llmod_filtered, &path,
+                                  llvm::AssemblyFileType);
             });
         }
 
@@ -518,6 +550,9 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         }
     });
 
+    if llmod_filtered != llmod {
+        llvm::LLVMDisposeModule(llmod_filtered);
+    }
     llvm::LLVMDisposeModule(llmod);
     llvm::LLVMContextDispose(llcx);
     llvm::LLVMRustDisposeTargetMachine(tm);
@@ -555,6 +590,7 @@ pub fn run_passes(sess: &Session,
     let mut metadata_config = ModuleConfig::new(tm, vec!());
 
     modules_config.opt_level = Some(get_llvm_opt_level(sess.opts.optimize));
+    modules_config.opt_size = get_llvm_opt_size(sess.opts.optimize);
 
     // Save all versions of the bytecode if we're saving our temporaries.
     if sess.opts.cg.save_temps {
@@ -622,11 +658,14 @@ pub fn run_passes(sess: &Session,
         work_items.push(work);
     }
 
-    // Process the work items, optionally using worker threads.
+    // Process the work items, optionally using worker threads.  There's no
+    // point in running more workers than there are CPUs to run them on, as
+    // each one just takes the next item off the queue when it's done.
     if sess.opts.cg.codegen_units == 1 {
         run_work_singlethreaded(sess, trans.reachable.as_slice(), work_items);
     } else {
-        run_work_multithreaded(sess, work_items, sess.opts.cg.codegen_units);
+        let num_workers = cmp::min(sess.opts.cg.codegen_units, os::num_cpus());
+        run_work_multithreaded(sess, work_items, num_workers);
     }
 
     // All codegen is finished.
@@ -948,11 +987,13 @@ unsafe fn configure_llvm(sess: &Session) {
     static INIT: Once = ONCE_INIT;
 
     // Copy what clang does by turning on loop vectorization at O2 and
-    // slp vectorization at O3
-    let vectorize_loop = !sess.opts.cg.no_vectorize_loops &&
+    // slp vectorization at O3. Neither is any use without an FPU, whose
+    // registers the vectorized code would need.
+    let soft_float = sess.soft_float();
+    let vectorize_loop = !sess.opts.cg.no_vectorize_loops && !soft_float &&
                          (sess.opts.optimize == config::Default ||
                           sess.opts.optimize == config::Aggressive);
-    let vectorize_slp = !sess.opts.cg.no_vectorize_slp &&
+    let vectorize_slp = !sess.opts.cg.no_vectorize_slp && !soft_float &&
                         sess.opts.optimize == config::Aggressive;
 
     let mut llvm_c_strs = Vec::new();
@@ -1008,31 +1049,44 @@ unsafe fn populate_llvm_passes(fpm: llvm::PassManagerRef,
                                mpm: llvm::PassManagerRef,
                                llmod: ModuleRef,
                                opt: llvm::CodeGenOptLevel,
-                               no_builtins: bool) {
+                               config: &ModuleConfig) {
     // Create the PassManagerBuilder for LLVM. We configure it with
     // reasonable defaults and prepare it to actually populate the pass
     // manager.
     let builder = llvm::LLVMPassManagerBuilderCreate();
-    match opt {
-        llvm::CodeGenLevelNone => {
+    match (opt, config.opt_size, config.inline_threshold) {
+        (_, _, Some(threshold)) => {
+            llvm::LLVMPassManagerBuilderUseInlinerWithThreshold(builder,
+                                                                threshold as c_uint);
+        }
+        (llvm::CodeGenLevelNone, _, None) => {
             // Don't add lifetime intrinsics at O0
             llvm::LLVMRustAddAlwaysInlinePass(builder, false);
         }
-        llvm::CodeGenLevelLess => {
+        (llvm::CodeGenLevelLess, _, None) => {
             llvm::LLVMRustAddAlwaysInlinePass(builder, true);
         }
         // numeric values copied from clang
-        llvm::CodeGenLevelDefault => {
+        (_, llvm::CodeGenOptSizeDefault, None) => {
+            llvm::LLVMPassManagerBuilderUseInlinerWithThreshold(builder,
+                                                                75);
+        }
+        (_, llvm::CodeGenOptSizeAggressive, None) => {
+            llvm::LLVMPassManagerBuilderUseInlinerWithThreshold(builder,
+                                                                25);
+        }
+        (llvm::CodeGenLevelDefault, llvm::CodeGenOptSizeNone, None) => {
             llvm::LLVMPassManagerBuilderUseInlinerWithThreshold(builder,
                                                                 225);
         }
-        llvm::CodeGenLevelAggressive => {
+        (llvm::CodeGenLevelAggressive, llvm::CodeGenOptSizeNone, None) => {
             llvm::LLVMPassManagerBuilderUseInlinerWithThreshold(builder,
                                                                 275);
         }
     }
     llvm::LLVMPassManagerBuilderSetOptLevel(builder, opt as c_uint);
-    llvm::LLVMRustAddBuilderLibraryInfo(builder, llmod, no_builtins);
+    llvm::LLVMPassManagerBuilderSetSizeLevel(builder, config.opt_size as c_uint);
+    llvm::LLVMRustAddBuilderLibraryInfo(builder, llmod, config.no_builtins);
 
     // Use the builder to populate the function/module pass managers.
     llvm::LLVMPassManagerBuilderPopulateFunctionPassManager(builder, fpm);
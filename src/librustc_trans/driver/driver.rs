@@ -27,7 +27,7 @@ use plugin;
 use trans;
 
 use util::common::time;
-use util::nodemap::{NodeSet};
+use util::nodemap::{FnvHashSet, NodeSet};
 
 use serialize::{json, Encodable};
 
@@ -40,6 +40,7 @@ use syntax::ast;
 use syntax::ast_map;
 use syntax::attr;
 use syntax::attr::{AttrMetaMethods};
+use syntax::codemap;
 use syntax::diagnostics;
 use syntax::parse;
 use syntax::parse::token;
@@ -155,7 +156,9 @@ pub fn phase_1_parse_input(sess: &Session, cfg: ast::CrateConfig, input: &Input)
         let mut stdout = io::BufferedWriter::new(io::stdout());
         let mut json = json::PrettyEncoder::new(&mut stdout);
         // unwrapping so IoError isn't ignored
-        krate.encode(&mut json).unwrap();
+        codemap::with_span_locations(sess.codemap(), || {
+            krate.encode(&mut json).unwrap();
+        });
     }
 
     if sess.show_span() {
@@ -239,10 +242,14 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         }
     });
 
-    let Registry { syntax_exts, lint_passes, lint_groups, .. } = registry;
+    let Registry { syntax_exts, lint_passes, early_lint_passes, lint_groups, .. } = registry;
 
     {
         let mut ls = sess.lint_store.borrow_mut();
+        for pass in early_lint_passes.into_iter() {
+            ls.register_early_pass(Some(sess), true, pass);
+        }
+
         for pass in lint_passes.into_iter() {
             ls.register_pass(Some(sess), true, pass);
         }
@@ -281,6 +288,7 @@ pub fn phase_2_configure_and_expand(sess: &Session,
                 deriving_hash_type_parameter: sess.features.borrow().default_type_params,
                 enable_quotes: sess.features.borrow().quote,
                 recursion_limit: sess.recursion_limit.get(),
+                time_macros: sess.debugging_opt(config::TIME_MACROS),
             };
             let ret = syntax::ext::expand::expand_crate(&sess.parse_sess,
                                               cfg,
@@ -312,6 +320,23 @@ pub fn phase_2_configure_and_expand(sess: &Session,
     time(time_passes, "checking that all macro invocations are gone", &krate, |krate|
          syntax::ext::expand::check_for_macros(&sess.parse_sess, krate));
 
+    time(time_passes, "early lint checks", &krate, |krate|
+         lint::check_ast_crate(sess, krate));
+
+    // Expansion errors only stop compilation here, once the lints buffered
+    // during expansion have been emitted.
+    sess.abort_if_errors();
+
+    if sess.opts.debugging_opts & config::AST_STATS != 0 {
+        syntax::ast_stats::print_ast_stats(sess.codemap(), &krate, crate_name);
+    }
+
+    // Nothing is lexed from files after expansion, so their sources are only
+    // needed again for diagnostics, which re-read them on demand.
+    if sess.opts.debugging_opts & config::DROP_SOURCES != 0 {
+        sess.codemap().drop_sources();
+    }
+
     Some(krate)
 }
 
@@ -324,8 +349,13 @@ pub fn assign_node_ids_and_map<'ast>(sess: &Session,
 
     impl<'a> ast_map::FoldOps for NodeIdAssigner<'a> {
         fn new_id(&self, old_id: ast::NodeId) -> ast::NodeId {
-            assert_eq!(old_id, ast::DUMMY_NODE_ID);
-            self.sess.next_node_id()
+            if old_id == ast::DUMMY_NODE_ID {
+                return self.sess.next_node_id();
+            }
+            // Syntax extensions may have handed out ids they reserved.
+            assert!(self.sess.parse_sess.is_reserved_node_id(old_id),
+                    "node id {} was neither dummy nor reserved", old_id);
+            old_id
         }
     }
 
@@ -336,7 +366,9 @@ pub fn assign_node_ids_and_map<'ast>(sess: &Session,
         let mut stdout = io::BufferedWriter::new(io::stdout());
         let mut json = json::PrettyEncoder::new(&mut stdout);
         // unwrapping so IoError isn't ignored
-        map.krate().encode(&mut json).unwrap();
+        codemap::with_span_locations(sess.codemap(), || {
+            map.krate().encode(&mut json).unwrap();
+        });
     }
 
     map
@@ -401,7 +433,7 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
          middle::check_loop::check_crate(&sess, krate));
 
     let stability_index = time(time_passes, "stability index", (), |_|
-                               stability::Index::build(krate));
+                               stability::Index::build(&sess, krate));
 
     time(time_passes, "static item recursion checking", (), |_|
          middle::check_static_recursion::check_crate(&sess, krate, &def_map, &ast_map));
@@ -436,22 +468,22 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
                  middle::privacy::check_crate(&ty_cx, &exp_map2, a, b));
 
     time(time_passes, "intrinsic checking", (), |_|
-         middle::intrinsicck::check_crate(&ty_cx));
+         ty_cx.sess.sorting_diagnostics(|| middle::intrinsicck::check_crate(&ty_cx)));
 
     time(time_passes, "effect checking", (), |_|
-         middle::effect::check_crate(&ty_cx));
+         ty_cx.sess.sorting_diagnostics(|| middle::effect::check_crate(&ty_cx)));
 
     time(time_passes, "match checking", (), |_|
-         middle::check_match::check_crate(&ty_cx));
+         ty_cx.sess.sorting_diagnostics(|| middle::check_match::check_crate(&ty_cx)));
 
     time(time_passes, "liveness checking", (), |_|
-         middle::liveness::check_crate(&ty_cx));
+         ty_cx.sess.sorting_diagnostics(|| middle::liveness::check_crate(&ty_cx)));
 
     time(time_passes, "borrow checking", (), |_|
-         middle::borrowck::check_crate(&ty_cx));
+         ty_cx.sess.sorting_diagnostics(|| middle::borrowck::check_crate(&ty_cx)));
 
     time(time_passes, "rvalue checking", (), |_|
-         middle::check_rvalues::check_crate(&ty_cx, krate));
+         ty_cx.sess.sorting_diagnostics(|| middle::check_rvalues::check_crate(&ty_cx, krate)));
 
     // Avoid overwhelming user with errors if type checking failed.
     // I'm not sure how helpful this is, to be honest, but it avoids a
@@ -465,13 +497,15 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
              reachable::find_reachable(&ty_cx, &exported_items));
 
     time(time_passes, "death checking", (), |_| {
-        middle::dead::check_crate(&ty_cx,
-                                  &exported_items,
-                                  &reachable_map)
+        ty_cx.sess.sorting_diagnostics(|| {
+            middle::dead::check_crate(&ty_cx,
+                                      &exported_items,
+                                      &reachable_map)
+        })
     });
 
     time(time_passes, "lint checking", (), |_|
-         lint::check_crate(&ty_cx, &exported_items));
+         ty_cx.sess.sorting_diagnostics(|| lint::check_crate(&ty_cx, &exported_items)));
 
     CrateAnalysis {
         exp_map2: exp_map2,
@@ -507,6 +541,8 @@ pub struct CrateTranslation {
     pub reachable: Vec<String>,
     pub crate_formats: dependency_format::Dependencies,
     pub no_builtins: bool,
+    /// Symbols of the functions kept in filtered `--emit=ir,asm` output.
+    pub emit_filtered_fns: FnvHashSet<String>,
 }
 
 /// Run the translation phase to LLVM, after which the AST and analysis can
@@ -519,8 +555,13 @@ pub fn phase_4_translate_to_llvm<'tcx>(analysis: CrateAnalysis<'tcx>)
          dependency_format::calculate(&analysis.ty_cx));
 
     // Option dance to work around the lack of stack once closures.
-    time(time_passes, "translation", analysis, |analysis|
-         trans::base::trans_crate(analysis))
+    let (tcx, trans) = time(time_passes, "translation", analysis, |analysis|
+                            trans::base::trans_crate(analysis));
+
+    // Don't hand LLVM a module that translation already found to be broken,
+    // such as one using asm! registers the target can't provide.
+    tcx.sess.abort_if_errors();
+    (tcx, trans)
 }
 
 /// Run LLVM itself, producing a bitcode file, assembly file or object file
@@ -654,7 +695,11 @@ fn write_out_deps(sess: &Session,
         // write Makefile-compatible dependency rules
         let files: Vec<String> = sess.codemap().files.borrow()
                                    .iter().filter(|fmap| fmap.is_real_file())
-                                   .map(|fmap| escape_dep_filename(fmap.name.as_slice()))
+                                   .map(|fmap| {
+                                       // Make reads the files on disk, so
+                                       // they're not remapped
+                                       escape_dep_filename(fmap.unmapped_name.as_slice())
+                                   })
                                    .collect();
         let mut file = try!(io::File::create(&deps_filename));
         for path in out_filenames.iter() {
@@ -26,6 +26,7 @@ use std::task::TaskBuilder;
 
 use session::early_error;
 
+use syntax;
 use syntax::ast;
 use syntax::parse;
 use syntax::diagnostic::Emitter;
@@ -50,7 +51,9 @@ fn run_compiler(args: &[String]) {
         None => return
     };
 
-    let descriptions = diagnostics::registry::Registry::new(&DIAGNOSTICS);
+    let all_diagnostics: Vec<(&'static str, &'static str)> =
+        DIAGNOSTICS.iter().chain(syntax::DIAGNOSTICS.iter()).map(|&d| d).collect();
+    let descriptions = diagnostics::registry::Registry::new(all_diagnostics.as_slice());
     match matches.opt_str("explain") {
         Some(ref code) => {
             match descriptions.find_description(code.as_slice()) {
@@ -58,7 +61,8 @@ fn run_compiler(args: &[String]) {
                     println!("{}", description);
                 }
                 None => {
-                    early_error(format!("no extended information for {}", code).as_slice());
+                    early_error(config::parse_error_output(&matches),
+                                format!("no extended information for {}", code).as_slice());
                 }
             }
             return;
@@ -67,6 +71,7 @@ fn run_compiler(args: &[String]) {
     }
 
     let sopts = config::build_session_options(&matches);
+    let output = config::parse_error_output(&matches);
     let (input, input_file_path) = match matches.free.len() {
         0u => {
             if sopts.describe_lints {
@@ -82,7 +87,7 @@ fn run_compiler(args: &[String]) {
                 return;
             }
 
-            early_error("no input filename given");
+            early_error(output, "no input filename given");
         }
         1u => {
             let ifile = matches.free[0].as_slice();
@@ -94,7 +99,7 @@ fn run_compiler(args: &[String]) {
                 (FileInput(Path::new(ifile)), Some(Path::new(ifile)))
             }
         }
-        _ => early_error("multiple input filenames provided")
+        _ => early_error(output, "multiple input filenames provided")
     };
 
     let sess = build_session(sopts, input_file_path, descriptions);
@@ -121,7 +126,7 @@ fn run_compiler(args: &[String]) {
                 list_metadata(&sess, &(*ifile), &mut stdout).unwrap();
             }
             StrInput(_) => {
-                early_error("can not list metadata for stdin");
+                early_error(output, "can not list metadata for stdin");
             }
         }
         return;
@@ -304,6 +309,15 @@ fn describe_debug_flags() {
             }
         }
     }
+    println!("    -Z {:>20} -- {}", "macro-backtrace=val",
+             "How much of the macro backtrace to print after errors: \
+              none, collapsed (default) or full");
+    println!("    -Z {:>20} -- {}", "treat-err-as-bug[=n]",
+             "Report the nth error (the first by default) as a compiler \
+              bug, panicking where it is emitted");
+    println!("    -Z {:>20} -- {}", "emit-filter=paths",
+             "Only write the functions whose paths match one of these \
+              comma separated globs (such as `foo::*`) with --emit=ir,asm");
 }
 
 fn describe_codegen_flags() {
@@ -334,7 +348,8 @@ pub fn handle_options(mut args: Vec<String>) -> Option<getopts::Matches> {
         match getopts::getopts(args.as_slice(), config::optgroups().as_slice()) {
             Ok(m) => m,
             Err(f) => {
-                early_error(f.to_string().as_slice());
+                // The arguments couldn't be parsed, so neither could `--color`
+                early_error(config::HumanOutput(diagnostic::Auto), f.to_string().as_slice());
             }
         };
 
@@ -364,7 +379,7 @@ pub fn handle_options(mut args: Vec<String>) -> Option<getopts::Matches> {
 
     if matches.opt_present("version") {
         match version("rustc", &matches) {
-            Some(err) => early_error(err.as_slice()),
+            Some(err) => early_error(config::parse_error_output(&matches), err.as_slice()),
             None => return None
         }
     }
@@ -30,11 +30,15 @@ use util::ppaux;
 
 use syntax::ast;
 use syntax::ast_map::{mod, blocks, NodePrinter};
+use syntax::codemap::{Span, ExpnId, NO_EXPANSION, MacroAttribute, MacroBang};
+use syntax::ext::mtwt;
 use syntax::print::{pp, pprust};
 
 use graphviz as dot;
 
 use std::io::{mod, MemReader};
+use std::cell::RefCell;
+use std::default::Default;
 use std::option;
 use std::str::FromStr;
 use arena::TypedArena;
@@ -47,6 +51,8 @@ pub enum PpSourceMode {
     PpmIdentified,
     PpmExpandedIdentified,
     PpmExpandedHygiene,
+    PpmPreserving,
+    PpmExpandedPreserving,
 }
 
 #[deriving(PartialEq, Show)]
@@ -66,12 +72,15 @@ pub fn parse_pretty(sess: &Session, name: &str) -> (PpMode, Option<UserIdentifie
         "expanded,identified" => PpmSource(PpmExpandedIdentified),
         "expanded,hygiene" => PpmSource(PpmExpandedHygiene),
         "identified"   => PpmSource(PpmIdentified),
+        "preserving"   => PpmSource(PpmPreserving),
+        "expanded,preserving" => PpmSource(PpmExpandedPreserving),
         "flowgraph"    => PpmFlowGraph,
         _ => {
             sess.fatal(format!(
                 "argument to `pretty` must be one of `normal`, \
                  `expanded`, `flowgraph=<nodeid>`, `typed`, `identified`, \
-                 or `expanded,identified`; got {}", name).as_slice());
+                 `expanded,identified`, `expanded,hygiene`, `preserving`, or \
+                 `expanded,preserving`; got {}", name).as_slice());
         }
     };
     let opt_second = opt_second.and_then::<UserIdentifiedItem>(from_str);
@@ -103,11 +112,20 @@ impl PpSourceMode {
                                         payload: B,
                                         f: |&PrinterSupport, B| -> A) -> A {
         match *self {
-            PpmNormal | PpmExpanded => {
+            PpmNormal | PpmPreserving | PpmExpandedPreserving => {
                 let annotation = NoAnn { sess: sess, ast_map: ast_map };
                 f(&annotation, payload)
             }
 
+            PpmExpanded => {
+                let annotation = ExpansionAnnotation {
+                    sess: sess,
+                    ast_map: ast_map,
+                    expansions: RefCell::new(Vec::new()),
+                };
+                f(&annotation, payload)
+            }
+
             PpmIdentified | PpmExpandedIdentified => {
                 let annotation = IdentifiedAnnotation { sess: sess, ast_map: ast_map };
                 f(&annotation, payload)
@@ -207,6 +225,98 @@ impl<'ast> pprust::PpAnn for IdentifiedAnnotation<'ast> {
     }
 }
 
+/// Marks the regions of the expanded output that came from a macro with
+/// comments naming the macro and where it was invoked.
+struct ExpansionAnnotation<'ast> {
+    sess: Session,
+    ast_map: Option<ast_map::Map<'ast>>,
+    /// The expansions of the nodes currently being printed, innermost last.
+    expansions: RefCell<Vec<ExpnId>>,
+}
+
+impl<'ast> PrinterSupport<'ast> for ExpansionAnnotation<'ast> {
+    fn sess<'a>(&'a self) -> &'a Session { &self.sess }
+
+    fn ast_map<'a>(&'a self) -> Option<&'a ast_map::Map<'ast>> {
+        self.ast_map.as_ref()
+    }
+}
+
+impl<'ast> ExpansionAnnotation<'ast> {
+    /// Describes the chain of macro invocations that produced `id`,
+    /// innermost first, e.g. `bar! at a.rs:3, from foo! at a.rs:10`.
+    fn describe_expansion(&self, id: ExpnId) -> String {
+        let cm = self.sess.codemap();
+        let mut origins = Vec::new();
+        let mut id = id;
+        while id != NO_EXPANSION {
+            id = cm.with_expn_info(id, |info| match info {
+                Some(info) => {
+                    let name = match info.callee.format {
+                        MacroBang => format!("{}!", info.callee.name),
+                        MacroAttribute => format!("#[{}]", info.callee.name),
+                    };
+                    let loc = cm.lookup_char_pos(info.call_site.lo);
+                    origins.push(format!("{} at {}:{}", name, loc.file.name, loc.line));
+                    info.call_site.expn_id
+                }
+                None => NO_EXPANSION
+            });
+        }
+        origins.connect(", from ")
+    }
+
+    fn enclosing_expansion(&self) -> ExpnId {
+        self.expansions.borrow().last().map_or(NO_EXPANSION, |&id| id)
+    }
+}
+
+fn ann_node_span(node: &pprust::AnnNode) -> Option<Span> {
+    match *node {
+        pprust::NodeItem(item) => Some(item.span),
+        pprust::NodeBlock(blk) => Some(blk.span),
+        pprust::NodeExpr(expr) => Some(expr.span),
+        pprust::NodePat(pat) => Some(pat.span),
+        pprust::NodeIdent(_) | pprust::NodeName(_) => None,
+    }
+}
+
+impl<'ast> pprust::PpAnn for ExpansionAnnotation<'ast> {
+    fn pre(&self,
+           s: &mut pprust::State,
+           node: pprust::AnnNode) -> io::IoResult<()> {
+        let id = match ann_node_span(&node) {
+            Some(sp) => sp.expn_id,
+            None => return Ok(())
+        };
+        let enclosing = self.enclosing_expansion();
+        self.expansions.borrow_mut().push(id);
+        if id != NO_EXPANSION && id != enclosing {
+            let callee = self.sess.codemap().with_expn_info(id, |info| {
+                info.map(|info| info.callee.name.clone())
+            });
+            if let Some(callee) = callee {
+                try!(s.synth_comment(format!("-> {}", callee)));
+                try!(pp::space(&mut s.s));
+            }
+        }
+        Ok(())
+    }
+    fn post(&self,
+            s: &mut pprust::State,
+            node: pprust::AnnNode) -> io::IoResult<()> {
+        if ann_node_span(&node).is_none() {
+            return Ok(());
+        }
+        let id = self.expansions.borrow_mut().pop().unwrap_or(NO_EXPANSION);
+        if id != NO_EXPANSION && id != self.enclosing_expansion() {
+            try!(pp::space(&mut s.s));
+            try!(s.synth_comment(format!("<- from {}", self.describe_expansion(id))));
+        }
+        Ok(())
+    }
+}
+
 struct HygieneAnnotation<'ast> {
     sess: Session,
     ast_map: Option<ast_map::Map<'ast>>,
@@ -225,11 +335,9 @@ impl<'ast> pprust::PpAnn for HygieneAnnotation<'ast> {
             s: &mut pprust::State,
             node: pprust::AnnNode) -> io::IoResult<()> {
         match node {
-            pprust::NodeIdent(&ast::Ident { name: ast::Name(nm), ctxt }) => {
+            pprust::NodeIdent(&id) => {
                 try!(pp::space(&mut s.s));
-                // FIXME #16420: this doesn't display the connections
-                // between syntax contexts
-                s.synth_comment(format!("{}#{}", nm, ctxt))
+                s.synth_comment(mtwt::describe_ident(id))
             }
             pprust::NodeName(&ast::Name(nm)) => {
                 try!(pp::space(&mut s.s));
@@ -384,9 +492,11 @@ impl UserIdentifiedItem {
 fn needs_ast_map(ppm: &PpMode, opt_uii: &Option<UserIdentifiedItem>) -> bool {
     match *ppm {
         PpmSource(PpmNormal) |
-        PpmSource(PpmIdentified) => opt_uii.is_some(),
+        PpmSource(PpmIdentified) |
+        PpmSource(PpmPreserving) => opt_uii.is_some(),
 
         PpmSource(PpmExpanded) |
+        PpmSource(PpmExpandedPreserving) |
         PpmSource(PpmExpandedIdentified) |
         PpmSource(PpmExpandedHygiene) |
         PpmSource(PpmTyped) |
@@ -397,9 +507,11 @@ fn needs_ast_map(ppm: &PpMode, opt_uii: &Option<UserIdentifiedItem>) -> bool {
 fn needs_expansion(ppm: &PpMode) -> bool {
     match *ppm {
         PpmSource(PpmNormal) |
-        PpmSource(PpmIdentified) => false,
+        PpmSource(PpmIdentified) |
+        PpmSource(PpmPreserving) => false,
 
         PpmSource(PpmExpanded) |
+        PpmSource(PpmExpandedPreserving) |
         PpmSource(PpmExpandedIdentified) |
         PpmSource(PpmExpandedHygiene) |
         PpmSource(PpmTyped) |
@@ -439,7 +551,8 @@ pub fn pretty_print_input(sess: Session,
 
     let src_name = driver::source_name(input);
     let src = sess.codemap().get_filemap(src_name.as_slice())
-                            .src.as_bytes().to_vec();
+                            .src().expect("source of the crate root changed while printing")
+                            .as_bytes().to_vec();
     let mut rdr = MemReader::new(src);
 
     let out = match ofile {
@@ -460,14 +573,19 @@ pub fn pretty_print_input(sess: Session,
                 sess, ast_map, &type_arena, id, out, |annotation, out| {
                     debug!("pretty printing source code {}", s);
                     let sess = annotation.sess();
-                    pprust::print_crate(sess.codemap(),
-                                        sess.diagnostic(),
-                                        krate,
-                                        src_name.to_string(),
-                                        &mut rdr,
-                                        out,
-                                        annotation.pp_ann(),
-                                        is_expanded)
+                    let print_crate = match s {
+                        PpmPreserving | PpmExpandedPreserving => pprust::print_crate_preserving,
+                        _ => pprust::print_crate,
+                    };
+                    print_crate(sess.codemap(),
+                                sess.diagnostic(),
+                                krate,
+                                src_name.to_string(),
+                                &mut rdr,
+                                out,
+                                annotation.pp_ann(),
+                                is_expanded,
+                                Default::default())
                 }),
 
         (PpmSource(s), Some(uii)) =>
@@ -484,7 +602,8 @@ pub fn pretty_print_input(sess: Session,
                                                       &mut rdr,
                                                       out,
                                                       annotation.pp_ann(),
-                                                      is_expanded);
+                                                      is_expanded,
+                                                      Default::default());
                     for node_id in uii.all_matching_node_ids(ast_map) {
                         let node = ast_map.get(node_id);
                         try!(pp_state.print_node(&node));
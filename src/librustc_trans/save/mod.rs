@@ -223,6 +223,7 @@ impl <'l, 'tcx> DxrVisitor<'l, 'tcx> {
             def::DefTrait(_) => Some(recorder::TypeRef),
             def::DefStatic(_, _) |
             def::DefConst(_) |
+            def::DefAssociatedConst(..) |
             def::DefLocal(_) |
             def::DefVariant(_, _, _) |
             def::DefUpvar(..) => Some(recorder::VarRef),
@@ -349,7 +350,7 @@ impl <'l, 'tcx> DxrVisitor<'l, 'tcx> {
                     ty::MethodTraitItemId(def_id) => {
                         method.id != 0 && def_id.node == 0
                     }
-                    ty::TypeTraitItemId(_) => false,
+                    ty::TypeTraitItemId(_) | ty::ConstTraitItemId(_) => false,
                 } {
                     Some(def_id)
                 } else {
@@ -686,6 +687,9 @@ impl <'l, 'tcx> DxrVisitor<'l, 'tcx> {
                 ast::TypeImplItem(ref typedef) => {
                     visit::walk_ty(self, &*typedef.typ)
                 }
+                ast::ConstImplItem(ref associated_const) => {
+                    visit::walk_associated_const(self, &**associated_const)
+                }
             }
         }
     }
@@ -780,6 +784,7 @@ impl <'l, 'tcx> DxrVisitor<'l, 'tcx> {
             def::DefLocal(..) |
             def::DefStatic(..) |
             def::DefConst(..) |
+            def::DefAssociatedConst(..) |
             def::DefVariant(..) => self.fmt.ref_str(recorder::VarRef,
                                                     ex.span,
                                                     sub_span,
@@ -850,6 +855,7 @@ impl <'l, 'tcx> DxrVisitor<'l, 'tcx> {
             def::DefConst(..) |
             def::DefStruct(_) |
             def::DefFn(..) => self.write_sub_paths_truncated(path),
+            def::DefAssociatedConst(..) => self.write_sub_path_trait_truncated(path),
             _ => {},
         }
 
@@ -932,7 +938,7 @@ impl <'l, 'tcx> DxrVisitor<'l, 'tcx> {
                     ty::MethodTraitItem(method) => {
                         method.provided_source.unwrap_or(def_id)
                     }
-                    ty::TypeTraitItem(_) => def_id,
+                    ty::TypeTraitItem(_) | ty::ConstTraitItem(_) => def_id,
                 };
                 (Some(def_id), decl_id)
             }
@@ -1150,6 +1156,9 @@ impl<'l, 'tcx, 'v> Visitor<'v> for DxrVisitor<'l, 'tcx> {
             }
             ast::ProvidedMethod(ref method) => self.process_method(&**method),
             ast::TypeTraitItem(_) => {}
+            ast::ConstTraitItem(ref associated_const) => {
+                visit::walk_associated_const(self, &**associated_const)
+            }
         }
     }
 
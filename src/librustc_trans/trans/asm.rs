@@ -20,9 +20,12 @@ use trans::expr;
 use trans::type_of;
 use trans::type_::Type;
 
+use session::config;
+
 use std::c_str::ToCStr;
 use std::string::String;
 use syntax::ast;
+use syntax::codemap::Span;
 use libc::{c_uint, c_char};
 
 // Take an inline assembly expression and splat it out via LLVM
@@ -33,13 +36,15 @@ pub fn trans_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm)
     let mut constraints = Vec::new();
     let mut output_types = Vec::new();
 
+    check_register_features(bcx, ia);
+
     let temp_scope = fcx.push_custom_cleanup_scope();
 
     let mut ext_inputs = Vec::new();
     let mut ext_constraints = Vec::new();
 
     // Prepare the output operands
-    let outputs = ia.outputs.iter().enumerate().map(|(i, &(ref c, ref out, is_rw))| {
+    let outputs = ia.outputs.iter().enumerate().map(|(i, &(ref c, ref out, is_rw, _))| {
         constraints.push((*c).clone());
 
         let out_datum = unpack_datum!(bcx, expr::trans(bcx, &**out));
@@ -60,7 +65,7 @@ pub fn trans_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm)
     }).collect::<Vec<_>>();
 
     // Now the input operands
-    let mut inputs = ia.inputs.iter().map(|&(ref c, ref input)| {
+    let mut inputs = ia.inputs.iter().map(|&(ref c, ref input, _)| {
         constraints.push((*c).clone());
 
         let in_datum = unpack_datum!(bcx, expr::trans(bcx, &**input));
@@ -84,7 +89,7 @@ pub fn trans_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm)
                                      .connect(",");
 
     let mut clobbers = ia.clobbers.iter()
-                                  .map(|s| format!("~{{{}}}", s.get()))
+                                  .map(|&(ref s, _)| format!("~{{{}}}", s.get()))
                                   .collect::<Vec<String>>()
                                   .connect(",");
     let more_clobbers = get_clobbers();
@@ -128,7 +133,7 @@ pub fn trans_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm)
                           c,
                           inputs.as_slice(),
                           output_type,
-                          ia.volatile,
+                          ia.volatile || ia.noreturn,
                           ia.alignstack,
                           dialect)
         })
@@ -157,10 +162,92 @@ pub fn trans_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm)
             llvm::LLVMMDNodeInContext(bcx.ccx().llcx(), &val, 1));
     }
 
+    if ia.noreturn {
+        Unreachable(bcx);
+    }
+
     return bcx;
 
 }
 
+// Check that the registers named by the constraints and clobbers of `ia`
+// don't need target features that are disabled; LLVM's own errors for these
+// don't say what's wrong.
+fn check_register_features(bcx: Block, ia: &ast::InlineAsm) {
+    let sess = bcx.sess();
+    let arch = sess.target.target.arch.as_slice();
+    let features = config::enabled_target_features(sess);
+    let disabled = config::disabled_target_features(sess);
+    let soft_float = features.iter().any(|f| f.as_slice() == "soft-float");
+
+    let check = |what: &str, required: Option<&'static str>, sp: Span| {
+        let feature = match required {
+            Some(feature) => feature,
+            None => return,
+        };
+        // Every register that needs a feature is a floating point or vector
+        // one, and those are off limits when floating point is done in
+        // software.
+        if soft_float {
+            sess.span_err(sp, format!("{} needs floating point hardware, which \
+                                       soft-float code must not use", what).as_slice());
+            return;
+        }
+        // Features the target CPU may have aren't all known here, so only
+        // those explicitly disabled are errors; LLVM judges the rest.
+        if features.iter().any(|f| f.as_slice() == feature) ||
+           !config::is_target_feature_disabled(disabled.as_slice(), feature) {
+            return;
+        }
+        sess.span_err(sp, format!("{} requires the `{}` target feature, which is \
+                                   disabled", what, feature).as_slice());
+        sess.span_help(sp, format!("enable it with `-C target-feature=+{}`",
+                                   feature).as_slice());
+    };
+
+    let constraints = ia.outputs.iter().map(|&(ref c, _, _, sp)| (c, sp))
+                        .chain(ia.inputs.iter().map(|&(ref c, _, sp)| (c, sp)));
+    for (constraint, sp) in constraints {
+        // Skip the `=`, `+` and `&` modifiers in front of the constraint.
+        let c = constraint.get().trim_left_chars(['=', '+', '&'].as_slice());
+        if c.starts_with("{") && c.ends_with("}") {
+            let reg = c.slice(1, c.len() - 1);
+            check(format!("the register `{}`", reg).as_slice(),
+                  register_feature(arch, reg), sp);
+        } else {
+            check(format!("the asm constraint `{}`", c).as_slice(),
+                  constraint_feature(arch, c), sp);
+        }
+    }
+    for &(ref reg, sp) in ia.clobbers.iter() {
+        check(format!("clobbering the register `{}`", reg.get()).as_slice(),
+              register_feature(arch, reg.get()), sp);
+    }
+}
+
+// The target feature needed to use the register `reg`, if any.
+fn register_feature(arch: &str, reg: &str) -> Option<&'static str> {
+    let is_numbered = |prefix: &str| {
+        reg.starts_with(prefix) && reg.len() > prefix.len() &&
+            reg.slice_from(prefix.len()).chars().all(|c| c.is_digit(10))
+    };
+    match arch {
+        "x86" | "x86_64" if is_numbered("xmm") => Some("sse"),
+        "x86" | "x86_64" if is_numbered("ymm") => Some("avx"),
+        "arm" if is_numbered("q") => Some("neon"),
+        _ => None,
+    }
+}
+
+// The target feature needed for a register class constraint, if any.
+fn constraint_feature(arch: &str, constraint: &str) -> Option<&'static str> {
+    match (arch, constraint) {
+        ("x86", "x") | ("x86_64", "x") => Some("sse"),
+        ("arm", "w") => Some("neon"),
+        _ => None,
+    }
+}
+
 // Default per-arch clobbers
 // Basically what clang does
 
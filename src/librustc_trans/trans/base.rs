@@ -85,7 +85,7 @@ use util::nodemap::NodeMap;
 
 use arena::TypedArena;
 use libc::{c_uint, uint64_t};
-use std::c_str::ToCStr;
+use std::c_str::{CString, ToCStr};
 use std::cell::{Cell, RefCell};
 use std::collections::HashSet;
 use std::rc::Rc;
@@ -195,6 +195,44 @@ pub fn decl_fn(ccx: &CrateContext, name: &str, cc: llvm::CallConv,
         llvm::SetFunctionAttribute(llfn, llvm::NoRedZoneAttribute)
     }
 
+    match ccx.sess().opts.cg.stack_protector {
+        config::StackProtectorNone => {}
+        config::StackProtectorBasic => {
+            llvm::SetFunctionAttribute(llfn, llvm::StackProtectAttribute)
+        }
+        config::StackProtectorStrong => unsafe {
+            llvm::LLVMAddFunctionAttribute(llfn,
+                                           llvm::FunctionIndex as c_uint,
+                                           llvm::StackProtectStrongAttribute as uint64_t)
+        },
+        config::StackProtectorAll => {
+            llvm::SetFunctionAttribute(llfn, llvm::StackProtectReqAttribute)
+        }
+    }
+
+    match ccx.sess().opts.optimize {
+        config::Size => {
+            llvm::SetFunctionAttribute(llfn, llvm::OptimizeForSizeAttribute)
+        }
+        config::SizeMin => unsafe {
+            llvm::SetFunctionAttribute(llfn, llvm::OptimizeForSizeAttribute);
+            llvm::LLVMAddFunctionAttribute(llfn,
+                                           llvm::FunctionIndex as c_uint,
+                                           llvm::MinSizeAttribute as uint64_t)
+        },
+        _ => {}
+    }
+
+    // Record the frame pointer choice on the function itself as well as on the
+    // target machine, so that it survives LTO and inlining into other crates.
+    let keep_frame_pointers = if ccx.sess().keep_frame_pointers() { "true" } else { "false" };
+    "no-frame-pointer-elim".with_c_str(|name| {
+        keep_frame_pointers.with_c_str(|value| unsafe {
+            llvm::LLVMAddFunctionAttrStringValue(llfn, llvm::FunctionIndex as c_uint,
+                                                 name, value)
+        })
+    });
+
     llvm::SetFunctionCallConv(llfn, cc);
     // Function addresses in Rust are never significant, allowing functions to be merged.
     llvm::SetUnnamedAddr(llfn, true);
@@ -313,6 +351,27 @@ pub fn decl_internal_rust_fn<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
     llfn
 }
 
+// Thread-local statics can use a cheaper access sequence when the code isn't
+// position independent and is only going to be linked into an executable.
+// Libraries built with a non-PIC model may still end up in a shared object, so
+// they keep the general model.
+fn set_tls_model(ccx: &CrateContext, global: ValueRef, defined_here: bool) {
+    let only_executables = ccx.sess().crate_types.borrow().iter().all(|ty| {
+        *ty == config::CrateTypeExecutable
+    });
+    if !only_executables {
+        return;
+    }
+    let mode = match ccx.sess().relocation_model() {
+        "static" | "dynamic-no-pic" if defined_here => llvm::LocalExecTLSModel,
+        "static" | "dynamic-no-pic" => llvm::InitialExecTLSModel,
+        _ => return,
+    };
+    unsafe {
+        llvm::LLVMSetThreadLocalMode(global, mode);
+    }
+}
+
 pub fn get_extern_const<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>, did: ast::DefId,
                                   t: Ty<'tcx>) -> ValueRef {
     let name = csearch::get_symbol(&ccx.sess().cstore, did);
@@ -334,6 +393,7 @@ pub fn get_extern_const<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>, did: ast::DefId,
         ty::each_attr(ccx.tcx(), did, |attr| {
             if attr.check_name("thread_local") {
                 llvm::set_thread_local(c, true);
+                set_tls_model(ccx, c, false);
             }
             true
         });
@@ -1363,6 +1423,10 @@ fn has_nested_returns(tcx: &ty::ctxt, id: ast::NodeId) -> bool {
                     tcx.sess.bug("unexpected variant: type trait item in \
                                   has_nested_returns")
                 }
+                ast::ConstTraitItem(_) => {
+                    tcx.sess.bug("unexpected variant: const trait item in \
+                                  has_nested_returns")
+                }
             }
         }
         Some(ast_map::NodeImplItem(ii)) => {
@@ -1383,6 +1447,10 @@ fn has_nested_returns(tcx: &ty::ctxt, id: ast::NodeId) -> bool {
                     tcx.sess.bug("unexpected variant: type impl item in \
                                   has_nested_returns")
                 }
+                ast::ConstImplItem(_) => {
+                    tcx.sess.bug("unexpected variant: const impl item in \
+                                  has_nested_returns")
+                }
             }
         }
         Some(ast_map::NodeExpr(e)) => {
@@ -1458,6 +1526,7 @@ pub fn new_fn_ctxt<'a, 'tcx>(ccx: &'a CrateContext<'a, 'tcx>,
           caller_expects_out_pointer: uses_outptr,
           lllocals: RefCell::new(NodeMap::new()),
           llupvars: RefCell::new(NodeMap::new()),
+          loop_dests: RefCell::new(NodeMap::new()),
           id: id,
           param_substs: param_substs,
           span: sp,
@@ -1933,7 +2002,13 @@ pub fn trans_fn<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                           param_substs: &Substs<'tcx>,
                           id: ast::NodeId,
                           attrs: &[ast::Attribute]) {
-    let _s = StatRecorder::new(ccx, ccx.tcx().map.path_to_string(id).to_string());
+    let path = ccx.tcx().map.path_to_string(id);
+    if !ccx.sess().opts.emit_filter.is_empty() &&
+       ccx.sess().emit_filter_matches(path.as_slice()) {
+        let name = unsafe { CString::new(llvm::LLVMGetValueName(llfndecl), false) };
+        ccx.emit_filtered_fns().borrow_mut().insert(name.as_str().unwrap().to_string());
+    }
+    let _s = StatRecorder::new(ccx, path);
     debug!("trans_fn(param_substs={})", param_substs.repr(ccx.tcx()));
     let _icx = push_ctxt("trans_fn");
     let fn_ty = ty::node_id_to_type(ccx.tcx(), id);
@@ -2750,6 +2825,7 @@ pub fn get_item_val(ccx: &CrateContext, id: ast::NodeId) -> ValueRef {
                         if attr::contains_name(i.attrs.as_slice(),
                                                "thread_local") {
                             llvm::set_thread_local(g, true);
+                            set_tls_model(ccx, g, true);
                         }
                         ccx.item_symbols().borrow_mut().insert(i.id, sym);
                         g
@@ -2808,6 +2884,20 @@ pub fn get_item_val(ccx: &CrateContext, id: ast::NodeId) -> ValueRef {
                 ast::ProvidedMethod(ref m) => {
                     register_method(ccx, id, &**m)
                 }
+                ast::ConstTraitItem(ref associated_const) => {
+                    let expr = match associated_const.expr {
+                        Some(ref expr) => expr,
+                        None => {
+                            ccx.sess().span_bug(associated_const.span,
+                                                "unexpected variant: associated \
+                                                 constant without a value in \
+                                                 get_item_val()")
+                        }
+                    };
+                    let (v, _) = consts::const_expr(ccx, &**expr);
+                    ccx.const_values().borrow_mut().insert(id, v);
+                    v
+                }
             }
         }
 
@@ -2819,6 +2909,12 @@ pub fn get_item_val(ccx: &CrateContext, id: ast::NodeId) -> ValueRef {
                                         "unexpected variant: required impl \
                                          method in get_item_val()")
                 }
+                ast::ConstImplItem(ref associated_const) => {
+                    let expr = associated_const.expr.as_ref().unwrap();
+                    let (v, _) = consts::const_expr(ccx, &**expr);
+                    ccx.const_values().borrow_mut().insert(id, v);
+                    v
+                }
             }
         }
 
@@ -3184,6 +3280,7 @@ pub fn trans_crate<'tcx>(analysis: CrateAnalysis<'tcx>)
     };
     let formats = shared_ccx.tcx().dependency_formats.borrow().clone();
     let no_builtins = attr::contains_name(krate.attrs.as_slice(), "no_builtins");
+    let emit_filtered_fns = shared_ccx.emit_filtered_fns().borrow().clone();
 
     let translation = CrateTranslation {
         modules: modules,
@@ -3193,6 +3290,7 @@ pub fn trans_crate<'tcx>(analysis: CrateAnalysis<'tcx>)
         reachable: reachable,
         crate_formats: formats,
         no_builtins: no_builtins,
+        emit_filtered_fns: emit_filtered_fns,
     };
 
     (shared_ccx.take_tcx(), translation)
@@ -197,6 +197,7 @@ fn trans<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, expr: &ast::Expr)
             }
             def::DefStatic(..) |
             def::DefConst(..) |
+            def::DefAssociatedConst(..) |
             def::DefLocal(..) |
             def::DefUpvar(..) => {
                 datum_callee(bcx, ref_expr)
@@ -477,6 +478,10 @@ pub fn trans_fn_ref_with_substs<'blk, 'tcx>(
                     bcx.tcx().sess.bug("trans_fn_ref_with_vtables() tried \
                                         to translate an associated type?!")
                 }
+                ty::ConstTraitItem(_) => {
+                    bcx.tcx().sess.bug("trans_fn_ref_with_vtables() tried \
+                                        to translate an associated constant?!")
+                }
             }
         }
     };
@@ -238,6 +238,10 @@ pub struct FunctionContext<'a, 'tcx: 'a> {
     // Same as above, but for closure upvars
     pub llupvars: RefCell<NodeMap<ValueRef>>,
 
+    // Maps the NodeId of each `loop` being translated whose value is used
+    // to the slot that its `break`s store their values into.
+    pub loop_dests: RefCell<NodeMap<ValueRef>>,
+
     // The NodeId of the function, or -1 if it doesn't correspond to
     // a user-defined function.
     pub id: ast::NodeId,
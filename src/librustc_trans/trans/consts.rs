@@ -16,7 +16,7 @@ use llvm::{IntEQ, IntNE, IntUGT, IntUGE, IntULT, IntULE, IntSGT, IntSGE, IntSLT,
            RealOEQ, RealOGT, RealOGE, RealOLT, RealOLE, RealONE};
 use metadata::csearch;
 use middle::{const_eval, def};
-use trans::{adt, closure, consts, debuginfo, expr, inline, machine};
+use trans::{adt, closure, consts, debuginfo, expr, inline, machine, meth};
 use trans::base::{mod, push_ctxt};
 use trans::common::*;
 use trans::type_::Type;
@@ -26,7 +26,7 @@ use util::ppaux::{Repr, ty_to_string};
 
 use std::c_str::ToCStr;
 use libc::c_uint;
-use syntax::{ast, ast_util};
+use syntax::{ast, ast_map, ast_util};
 use syntax::ptr::P;
 
 pub fn const_lit(cx: &CrateContext, e: &ast::Expr, lit: &ast::Lit)
@@ -171,8 +171,13 @@ pub fn get_const_val(cx: &CrateContext,
             def_id = inline::maybe_instantiate_inline(cx, def_id);
         }
 
-        if let ast::ItemConst(..) = cx.tcx().map.expect_item(def_id.node).node {
-            base::get_item_val(cx, def_id.node);
+        match cx.tcx().map.get(def_id.node) {
+            ast_map::NodeItem(&ast::Item { node: ast::ItemConst(..), .. }) |
+            ast_map::NodeTraitItem(&ast::ConstTraitItem(_)) |
+            ast_map::NodeImplItem(&ast::ConstImplItem(_)) => {
+                base::get_item_val(cx, def_id.node);
+            }
+            _ => {}
         }
     }
 
@@ -627,7 +632,14 @@ fn const_expr_unadjusted(cx: &CrateContext, e: &ast::Expr) -> ValueRef {
                         base::get_item_val(cx, def_id.node)
                     }
                 }
-                Some(def::DefConst(def_id)) => {
+                Some(def::DefConst(def_id)) |
+                Some(def::DefAssociatedConst(def_id, def::FromImpl(_))) => {
+                    get_const_val(cx, def_id)
+                }
+                Some(def::DefAssociatedConst(def_id, def::FromTrait(trait_id))) => {
+                    let substs = ty::node_id_item_substs(cx.tcx(), e.id).substs;
+                    let def_id = meth::resolve_associated_const(cx, def_id,
+                                                                trait_id, &substs);
                     get_const_val(cx, def_id)
                 }
                 Some(def::DefVariant(enum_did, variant_did, _)) => {
@@ -674,6 +686,7 @@ fn const_expr_unadjusted(cx: &CrateContext, e: &ast::Expr) -> ValueRef {
               }
           }
           ast::ExprParen(ref e) => const_expr(cx, &**e).val0(),
+          ast::ExprType(ref e, _) => const_expr(cx, &**e).val0(),
           ast::ExprBlock(ref block) => {
             match block.expr {
                 Some(ref expr) => const_expr(cx, &**expr).val0(),
@@ -71,6 +71,9 @@ pub struct SharedCrateContext<'tcx> {
 
     available_monomorphizations: RefCell<FnvHashSet<String>>,
     available_drop_glues: RefCell<FnvHashMap<Ty<'tcx>, String>>,
+
+    /// Symbols of the functions matched by `-Z emit-filter`.
+    emit_filtered_fns: RefCell<FnvHashSet<String>>,
 }
 
 /// The local portion of a `CrateContext`.  There is one `LocalCrateContext`
@@ -273,6 +276,7 @@ impl<'tcx> SharedCrateContext<'tcx> {
             },
             available_monomorphizations: RefCell::new(FnvHashSet::new()),
             available_drop_glues: RefCell::new(FnvHashMap::new()),
+            emit_filtered_fns: RefCell::new(FnvHashSet::new()),
         };
 
         for i in range(0, local_count) {
@@ -365,6 +369,10 @@ impl<'tcx> SharedCrateContext<'tcx> {
     pub fn stats<'a>(&'a self) -> &'a Stats {
         &self.stats
     }
+
+    pub fn emit_filtered_fns<'a>(&'a self) -> &'a RefCell<FnvHashSet<String>> {
+        &self.emit_filtered_fns
+    }
 }
 
 impl<'tcx> LocalCrateContext<'tcx> {
@@ -671,6 +679,10 @@ impl<'b, 'tcx> CrateContext<'b, 'tcx> {
         &self.shared.available_drop_glues
     }
 
+    pub fn emit_filtered_fns<'a>(&'a self) -> &'a RefCell<FnvHashSet<String>> {
+        &self.shared.emit_filtered_fns
+    }
+
     pub fn int_type(&self) -> Type {
         self.local.int_type
     }
@@ -869,6 +881,7 @@ fn declare_intrinsic(ccx: &CrateContext, key: & &'static str) -> Option<ValueRef
 
     ifn!("llvm.expect.i1" fn(i1, i1) -> i1);
     ifn!("llvm.assume" fn(i1) -> void);
+    ifn!("llvm.prefetch" fn(i8p, t_i32, t_i32, t_i32) -> void);
 
     // Some intrinsics were introduced in later versions of LLVM, but they have
     // fallbacks in libc or libm and such. Currently, all of these intrinsics
@@ -375,7 +375,8 @@ pub fn trans_for<'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
 
 pub fn trans_loop<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                               loop_id: ast::NodeId,
-                              body: &ast::Block)
+                              body: &ast::Block,
+                              dest: expr::Dest)
                               -> Block<'blk, 'tcx> {
     let _icx = push_ctxt("trans_loop");
     let fcx = bcx.fcx;
@@ -389,7 +390,11 @@ pub fn trans_loop<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     // next_bcx
     //
     // Links between body_bcx_in and next_bcx are created by
-    // break statements.
+    // break statements, which first store their value (if any) into dest.
+
+    if let expr::SaveIn(lldest) = dest {
+        fcx.loop_dests.borrow_mut().insert(loop_id, lldest);
+    }
 
     let next_bcx_in = bcx.fcx.new_id_block("loop_exit", loop_id);
     let body_bcx_in = bcx.fcx.new_id_block("loop_body", body.id);
@@ -401,6 +406,7 @@ pub fn trans_loop<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     Br(body_bcx_out, body_bcx_in.llbb);
 
     fcx.pop_loop_cleanup_scope(loop_id);
+    fcx.loop_dests.borrow_mut().remove(&loop_id);
 
     return next_bcx_in;
 }
@@ -418,8 +424,19 @@ pub fn trans_break_cont<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     }
 
     // Locate loop that we will break to
-    let loop_id = match opt_label {
-        None => fcx.top_loop_scope(),
+    let loop_id = target_loop_id(bcx, expr_id, opt_label);
+
+    // Generate appropriate cleanup code and branch
+    let cleanup_llbb = fcx.normal_exit_block(loop_id, exit);
+    Br(bcx, cleanup_llbb);
+    Unreachable(bcx); // anything afterwards should be ignored
+    return bcx;
+}
+
+fn target_loop_id(bcx: Block, expr_id: ast::NodeId, opt_label: Option<Ident>)
+                  -> ast::NodeId {
+    match opt_label {
+        None => bcx.fcx.top_loop_scope(),
         Some(_) => {
             match bcx.tcx().def_map.borrow().get(&expr_id) {
                 Some(&def::DefLabel(loop_id)) => loop_id,
@@ -429,19 +446,23 @@ pub fn trans_break_cont<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                 }
             }
         }
-    };
-
-    // Generate appropriate cleanup code and branch
-    let cleanup_llbb = fcx.normal_exit_block(loop_id, exit);
-    Br(bcx, cleanup_llbb);
-    Unreachable(bcx); // anything afterwards should be ignored
-    return bcx;
+    }
 }
 
 pub fn trans_break<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                                expr_id: ast::NodeId,
-                               label_opt: Option<Ident>)
+                               label_opt: Option<Ident>,
+                               value: Option<&ast::Expr>)
                                -> Block<'blk, 'tcx> {
+    let mut bcx = bcx;
+    if let Some(value) = value {
+        let loop_id = target_loop_id(bcx, expr_id, label_opt);
+        let dest = match bcx.fcx.loop_dests.borrow().get(&loop_id) {
+            Some(&lldest) => expr::SaveIn(lldest),
+            None => expr::Ignore,
+        };
+        bcx = expr::trans_into(bcx, value, dest);
+    }
     return trans_break_cont(bcx, expr_id, label_opt, cleanup::EXIT_BREAK);
 }
 
@@ -212,7 +212,7 @@ use std::cell::{Cell, RefCell};
 use std::ptr;
 use std::rc::{Rc, Weak};
 use syntax::util::interner::Interner;
-use syntax::codemap::{Span, Pos};
+use syntax::codemap::{Span, Pos, span_contains};
 use syntax::{ast, codemap, ast_util, ast_map};
 use syntax::ast_util::PostExpansionMethod;
 use syntax::parse::token::{mod, special_idents};
@@ -658,6 +658,16 @@ pub struct CrateDebugContext<'tcx> {
     // This collection is used to assert that composite types (structs, enums,
     // ...) have their members only set once:
     composite_types_completed: RefCell<FnvHashSet<DIType>>,
+
+    // The subprograms standing for macro bodies, keyed by the start of the
+    // macro's definition.
+    macro_subprograms: RefCell<FnvHashMap<uint, DISubprogram>>,
+
+    // The lexical blocks of blocks written in macro bodies, which are nested
+    // in the macro's subprogram like the code in them is. Each maps to the
+    // key of that subprogram and to the innermost scope of the function
+    // that the macro was invoked in.
+    macro_lexical_blocks: RefCell<FnvHashMap<DIScope, (uint, DIScope)>>,
 }
 
 impl<'tcx> CrateDebugContext<'tcx> {
@@ -675,6 +685,8 @@ impl<'tcx> CrateDebugContext<'tcx> {
             type_map: RefCell::new(TypeMap::new()),
             namespace_map: RefCell::new(FnvHashMap::new()),
             composite_types_completed: RefCell::new(FnvHashSet::new()),
+            macro_subprograms: RefCell::new(FnvHashMap::new()),
+            macro_lexical_blocks: RefCell::new(FnvHashMap::new()),
         };
     }
 }
@@ -1111,7 +1123,8 @@ pub fn get_cleanup_debug_loc_for_ast_node<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
 ///
 /// Maps to a call to llvm::LLVMSetCurrentDebugLocation(...). The node_id
 /// parameter is used to reliably find the correct visibility scope for the code
-/// position.
+/// position. Code expanded from a macro is attributed to the outermost macro
+/// invocation, see `expanded_debug_location`.
 pub fn set_source_location(fcx: &FunctionContext,
                            node_id: ast::NodeId,
                            span: Span) {
@@ -1127,12 +1140,8 @@ pub fn set_source_location(fcx: &FunctionContext,
             debug!("set_source_location: {}", cx.sess().codemap().span_to_string(span));
 
             if function_debug_context.source_locations_enabled.get() {
-                let loc = span_start(cx, span);
                 let scope = scope_metadata(fcx, node_id, span);
-
-                set_debug_location(cx, DebugLocation::new(scope,
-                                                          loc.line,
-                                                          loc.col.to_uint()));
+                set_debug_location(cx, expanded_debug_location(cx, scope, span));
             } else {
                 set_debug_location(cx, UnknownLocation);
             }
@@ -1231,6 +1240,11 @@ pub fn create_function_debug_context<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                                        "create_function_debug_context() \
                                         called on associated type?!")
                 }
+                ast::ConstImplItem(ref associated_const) => {
+                    cx.sess().span_bug(associated_const.span,
+                                       "create_function_debug_context() \
+                                        called on associated constant?!")
+                }
             }
         }
         ast_map::NodeExpr(ref expr) => {
@@ -1532,6 +1546,16 @@ fn create_DIArray(builder: DIBuilderRef, arr: &[DIDescriptor]) -> DIArray {
     };
 }
 
+/// The working directory with `--remap-path-prefix` applied. A directory
+/// whose path isn't UTF-8 can't match a prefix, so it's only made lossy.
+fn remapped_working_dir(cx: &CrateContext) -> String {
+    let work_dir = &cx.sess().working_dir;
+    match work_dir.as_str() {
+        Some(work_dir) => cx.sess().codemap().path_mapping().map_prefix(work_dir).val0(),
+        None => work_dir.display().to_string(),
+    }
+}
+
 fn compile_unit_metadata(cx: &CrateContext) {
     let work_dir = &cx.sess().working_dir;
     let compile_unit_name = match cx.sess().local_crate_source_file {
@@ -1567,7 +1591,8 @@ fn compile_unit_metadata(cx: &CrateContext) {
                            (option_env!("CFG_VERSION")).expect("CFG_VERSION"));
 
     let compile_unit_name = compile_unit_name.as_ptr();
-    work_dir.as_vec().with_c_str(|work_dir| {
+    let work_dir = remapped_working_dir(cx);
+    work_dir.with_c_str(|work_dir| {
         producer.with_c_str(|producer| {
             "".with_c_str(|flags| {
                 "".with_c_str(|split_name| {
@@ -1652,9 +1677,14 @@ fn declare_local<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
         }
     });
 
-    set_debug_location(cx, DebugLocation::new(scope_metadata,
-                                              loc.line,
-                                              loc.col.to_uint()));
+    // A variable in a block of a macro body is declared where the macro's
+    // body is inlined, as the rest of the code in the block is.
+    let declare_location = if is_macro_lexical_block(cx, scope_metadata) {
+        expanded_debug_location(cx, scope_metadata, span)
+    } else {
+        DebugLocation::new(scope_metadata, loc.line, loc.col.to_uint())
+    };
+    set_debug_location(cx, declare_location);
     unsafe {
         let instr = llvm::LLVMDIBuilderInsertDeclareAtEnd(
             DIB(cx),
@@ -1686,8 +1716,11 @@ fn file_metadata(cx: &CrateContext, full_path: &str) -> DIFile {
 
     debug!("file_metadata: {}", full_path);
 
+    // `full_path` has already had `--remap-path-prefix` applied, so compare
+    // it against the equally remapped working directory.
     // FIXME (#9639): This needs to handle non-utf8 paths
-    let work_dir = cx.sess().working_dir.as_str().unwrap();
+    let work_dir = remapped_working_dir(cx);
+    let work_dir = work_dir.as_slice();
     let file_name =
         if full_path.starts_with(work_dir) {
             full_path.slice(work_dir.len() + 1u, full_path.len())
@@ -3044,7 +3077,10 @@ impl MetadataCreationResult {
 
 #[deriving(PartialEq)]
 enum DebugLocation {
-    KnownLocation { scope: DIScope, line: uint, col: uint },
+    /// A line in `scope`. When `scope` is the subprogram of a macro body,
+    /// `inlined_at` is the location node of the macro's invocation, and
+    /// otherwise it's null.
+    KnownLocation { scope: DIScope, line: uint, col: uint, inlined_at: ValueRef },
     UnknownLocation
 }
 
@@ -3054,35 +3090,169 @@ impl DebugLocation {
             scope: scope,
             line: line,
             col: col,
+            inlined_at: ptr::null_mut(),
         }
     }
 }
 
-fn set_debug_location(cx: &CrateContext, debug_location: DebugLocation) {
-    if debug_location == debug_context(cx).current_debug_location.get() {
-        return;
+/// The debug location for code at `span` in the scope `scope`.
+///
+/// Users expect panics and debugger frames in code expanded from a macro to
+/// point at their own invocation of the macro, not into its definition,
+/// which may be in another crate. So the line of such code is that of the
+/// outermost invocation, or its own line if it was passed in as an argument
+/// written by the user, and each macro body the code came through is
+/// described as a subroutine inlined at the invocation of that macro, which
+/// keeps the definition reachable from the debugger.
+///
+/// `scope` may be the lexical block of a block in a macro body, which is
+/// nested in the macro's subprogram; code in the block is then in it, and
+/// the rest is in the function's scope around it.
+fn expanded_debug_location(cx: &CrateContext, scope: DIScope, span: Span) -> DebugLocation {
+    let (block_macro, function_scope) = match macro_lexical_block(cx, scope) {
+        Some((key, function_scope)) => (Some(key), function_scope),
+        None => (None, scope),
+    };
+    let backtrace = cx.sess().codemap().macro_backtrace(span);
+    let outermost_call_site = match backtrace.last() {
+        Some(level) => level.call_site,
+        None => {
+            let loc = span_start(cx, span);
+            return DebugLocation::new(function_scope, loc.line, loc.col.to_uint());
+        }
+    };
+    let loc = span_start(cx, outermost_call_site);
+    let mut location = DebugLocation::new(function_scope, loc.line, loc.col.to_uint());
+
+    // Work inwards from the outermost invocation. Within each macro body, the
+    // code is at the next invocation in, or at `span` itself for the
+    // innermost one.
+    for (i, level) in backtrace.iter().enumerate().rev() {
+        let inner = if i == 0 { span } else { backtrace[i - 1].call_site };
+        let def_span = match level.def_site_span {
+            Some(def_span) if span_contains(def_span, inner) => def_span,
+            // Code that came from the macro's arguments rather than its body,
+            // or from a macro built into the compiler, which has no body to
+            // point into, stays attributed to the enclosing location. Unless
+            // that code is itself inside a macro body further out, it was
+            // written by the user right where its span says.
+            _ => {
+                if !backtrace.iter().any(|level| {
+                    level.def_site_span.map_or(false, |def_span| span_contains(def_span, inner))
+                }) {
+                    let loc = span_start(cx, inner);
+                    location = DebugLocation::new(function_scope, loc.line, loc.col.to_uint());
+                }
+                continue
+            }
+        };
+        let loc = span_start(cx, inner);
+        let macro_scope = if block_macro == Some(def_span.lo.to_uint()) {
+            scope
+        } else {
+            macro_subprogram(cx, level.macro_decl_name.as_slice(), def_span)
+        };
+        location = KnownLocation {
+            scope: macro_scope,
+            line: loc.line,
+            col: loc.col.to_uint(),
+            inlined_at: debug_location_node(cx, location),
+        };
     }
+    location
+}
 
-    let metadata_node;
+/// The name and definition of the innermost macro whose body `span` was
+/// written in, if any.
+fn macro_body_containing(cx: &CrateContext, span: Span) -> Option<(String, Span)> {
+    cx.sess().codemap().macro_backtrace(span).into_iter().filter_map(|level| {
+        match level.def_site_span {
+            Some(def_span) if span_contains(def_span, span) => {
+                Some((level.macro_decl_name, def_span))
+            }
+            _ => None
+        }
+    }).next()
+}
+
+/// For the lexical block of a block in a macro body, the key of the macro's
+/// subprogram and the function scope the macro was invoked in.
+fn macro_lexical_block(cx: &CrateContext, scope: DIScope) -> Option<(uint, DIScope)> {
+    debug_context(cx).macro_lexical_blocks.borrow().get(&scope).map(|&entry| entry)
+}
+
+fn is_macro_lexical_block(cx: &CrateContext, scope: DIScope) -> bool {
+    macro_lexical_block(cx, scope).is_some()
+}
+
+/// The subprogram standing for the body of the macro `name` defined at
+/// `def_span`, which code expanded from the macro is described as inlined
+/// from.
+fn macro_subprogram(cx: &CrateContext, name: &str, def_span: Span) -> DISubprogram {
+    let key = def_span.lo.to_uint();
+    match debug_context(cx).macro_subprograms.borrow().get(&key) {
+        Some(&subprogram) => return subprogram,
+        None => {}
+    }
 
+    let loc = span_start(cx, def_span);
+    let file_metadata = file_metadata(cx, loc.file.name.as_slice());
+    let subprogram = name.with_c_str(|name| {
+        unsafe {
+            let signature = create_DIArray(DIB(cx), &[ptr::null_mut()]);
+            let type_metadata = llvm::LLVMDIBuilderCreateSubroutineType(DIB(cx),
+                                                                        file_metadata,
+                                                                        signature);
+            llvm::LLVMDIBuilderCreateFunction(
+                DIB(cx),
+                file_metadata,
+                name,
+                name,
+                file_metadata,
+                loc.line as c_uint,
+                type_metadata,
+                true,
+                true,
+                loc.line as c_uint,
+                FlagPrototyped as c_uint,
+                cx.sess().opts.optimize != config::No,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut())
+        }
+    });
+    debug_context(cx).macro_subprograms.borrow_mut().insert(key, subprogram);
+    subprogram
+}
+
+/// The metadata node for a debug location, or null for an unknown one.
+fn debug_location_node(cx: &CrateContext, debug_location: DebugLocation) -> ValueRef {
     match debug_location {
-        KnownLocation { scope, line, .. } => {
+        KnownLocation { scope, line, inlined_at, .. } => {
             // Always set the column to zero like Clang and GCC
             let col = UNKNOWN_COLUMN_NUMBER;
             debug!("setting debug location to {} {}", line, col);
             let elements = [C_i32(cx, line as i32), C_i32(cx, col as i32),
-                            scope, ptr::null_mut()];
+                            scope, inlined_at];
             unsafe {
-                metadata_node = llvm::LLVMMDNodeInContext(debug_context(cx).llcontext,
-                                                          elements.as_ptr(),
-                                                          elements.len() as c_uint);
+                llvm::LLVMMDNodeInContext(debug_context(cx).llcontext,
+                                          elements.as_ptr(),
+                                          elements.len() as c_uint)
             }
         }
         UnknownLocation => {
             debug!("clearing debug location ");
-            metadata_node = ptr::null_mut();
+            ptr::null_mut()
         }
-    };
+    }
+}
+
+fn set_debug_location(cx: &CrateContext, debug_location: DebugLocation) {
+    if debug_location == debug_context(cx).current_debug_location.get() {
+        return;
+    }
+
+    let metadata_node = debug_location_node(cx, debug_location);
 
     unsafe {
         llvm::LLVMSetCurrentDebugLocation(cx.raw_builder(), metadata_node);
@@ -3214,7 +3384,24 @@ fn populate_scope_map(cx: &CrateContext,
         // Create a new lexical scope and push it onto the stack
         let loc = cx.sess().codemap().lookup_char_pos(scope_span.lo);
         let file_metadata = file_metadata(cx, loc.file.name.as_slice());
-        let parent_scope = scope_stack.last().unwrap().scope_metadata;
+        let enclosing_scope = scope_stack.last().unwrap().scope_metadata;
+        let enclosing_macro_block = macro_lexical_block(cx, enclosing_scope);
+        let function_scope = enclosing_macro_block.map_or(enclosing_scope, |(_, scope)| scope);
+
+        // A block in a macro body is nested in the macro's subprogram, or in
+        // the block of the same body around it, and any other block in the
+        // function's scopes.
+        let (parent_scope, macro_block) = match macro_body_containing(cx, scope_span) {
+            Some((name, def_span)) => {
+                let key = def_span.lo.to_uint();
+                let parent_scope = match enclosing_macro_block {
+                    Some((enclosing_key, _)) if enclosing_key == key => enclosing_scope,
+                    _ => macro_subprogram(cx, name.as_slice(), def_span),
+                };
+                (parent_scope, Some((key, function_scope)))
+            }
+            None => (function_scope, None),
+        };
 
         let scope_metadata = unsafe {
             llvm::LLVMDIBuilderCreateLexicalBlock(
@@ -3224,6 +3411,9 @@ fn populate_scope_map(cx: &CrateContext,
                 loc.line as c_uint,
                 loc.col.to_uint() as c_uint)
         };
+        if let Some(entry) = macro_block {
+            debug_context(cx).macro_lexical_blocks.borrow_mut().insert(scope_metadata, entry);
+        }
 
         scope_stack.push(ScopeStackEntry { scope_metadata: scope_metadata,
                                            ident: None });
@@ -3349,6 +3539,12 @@ fn populate_scope_map(cx: &CrateContext,
                                 loc.line as c_uint,
                                 loc.col.to_uint() as c_uint)
                         };
+                        // Within a block of a macro body, the new scope is
+                        // part of that block
+                        if let Some(entry) = macro_lexical_block(cx, parent_scope) {
+                            debug_context(cx).macro_lexical_blocks.borrow_mut()
+                                             .insert(scope_metadata, entry);
+                        }
 
                         scope_stack.push(ScopeStackEntry {
                             scope_metadata: scope_metadata,
@@ -3453,11 +3649,12 @@ fn populate_scope_map(cx: &CrateContext,
 
         match exp.node {
             ast::ExprLit(_)   |
-            ast::ExprBreak(_) |
+            ast::ExprBreak(_, None) |
             ast::ExprAgain(_) |
             ast::ExprPath(_)  => {}
 
             ast::ExprCast(ref sub_exp, _)     |
+            ast::ExprType(ref sub_exp, _)     |
             ast::ExprAddrOf(_, ref sub_exp)  |
             ast::ExprField(ref sub_exp, _) |
             ast::ExprTupField(ref sub_exp, _) |
@@ -3469,6 +3666,10 @@ fn populate_scope_map(cx: &CrateContext,
                 walk_expr(cx, &**sub_expr, scope_stack, scope_map);
             }
 
+            ast::ExprBreak(_, Some(ref sub_exp)) => {
+                walk_expr(cx, &**sub_exp, scope_stack, scope_map);
+            }
+
             ast::ExprRet(ref exp_opt) => match *exp_opt {
                 Some(ref sub_exp) => walk_expr(cx, &**sub_exp, scope_stack, scope_map),
                 None => ()
@@ -3539,6 +3740,14 @@ fn populate_scope_map(cx: &CrateContext,
                 })
             }
 
+            ast::ExprTry(..) => {
+                cx.sess().span_bug(exp.span, "debuginfo::populate_scope_map() - \
+                                              Found unexpanded `?` operator.");
+            }
+            ast::ExprInclusiveRange(..) => {
+                cx.sess().span_bug(exp.span, "debuginfo::populate_scope_map() - \
+                                              Found unexpanded inclusive range.");
+            }
             ast::ExprWhileLet(..) => {
                 cx.sess().span_bug(exp.span, "debuginfo::populate_scope_map() - \
                                               Found unexpanded while-let.");
@@ -3652,12 +3861,11 @@ fn populate_scope_map(cx: &CrateContext,
             ast::ExprInlineAsm(ast::InlineAsm { ref inputs,
                                                 ref outputs,
                                                 .. }) => {
-                // inputs, outputs: Vec<(String, P<Expr>)>
-                for &(_, ref exp) in inputs.iter() {
+                for &(_, ref exp, _) in inputs.iter() {
                     walk_expr(cx, &**exp, scope_stack, scope_map);
                 }
 
-                for &(_, ref exp, _) in outputs.iter() {
+                for &(_, ref exp, _, _) in outputs.iter() {
                     walk_expr(cx, &**exp, scope_stack, scope_map);
                 }
             }
@@ -639,6 +639,11 @@ fn trans_datum_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             // Datum output mode means this is a scalar cast:
             trans_imm_cast(bcx, &**val, expr.id)
         }
+        ast::ExprType(ref val, _) => {
+            // Any coercion the ascription required is an adjustment on `val`.
+            let datum = unpack_datum!(bcx, trans(bcx, &**val));
+            datum.to_rvalue_datum(bcx, "type_ascription").to_expr_datumblock()
+        }
         _ => {
             bcx.tcx().sess.span_bug(
                 expr.span,
@@ -851,32 +856,20 @@ fn trans_def<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             DatumBlock::new(bcx, Datum::new(val, const_ty, LvalueExpr))
         }
         def::DefConst(did) => {
-            // First, inline any external constants into the local crate so we
-            // can be sure to get the LLVM value corresponding to it.
-            let did = inline::maybe_instantiate_inline(bcx.ccx(), did);
-            if did.krate != ast::LOCAL_CRATE {
-                bcx.tcx().sess.span_bug(ref_expr.span,
-                                        "cross crate constant could not \
-                                         be inlined");
-            }
-            let val = base::get_item_val(bcx.ccx(), did.node);
-
-            // Next, we need to crate a ByRef rvalue datum to return. We can't
-            // use the normal .to_ref_datum() function because the type of
-            // `val` is not actually the same as `const_ty`.
-            //
-            // To get around this, we make a custom alloca slot with the
-            // appropriate type (const_ty), and then we cast it to a pointer of
-            // typeof(val), store the value, and then hand this slot over to
-            // the datum infrastructure.
-            let const_ty = expr_ty(bcx, ref_expr);
-            let llty = type_of::type_of(bcx.ccx(), const_ty);
-            let slot = alloca(bcx, llty, "const");
-            let pty = Type::from_ref(unsafe { llvm::LLVMTypeOf(val) }).ptr_to();
-            Store(bcx, val, PointerCast(bcx, slot, pty));
-
-            let datum = Datum::new(slot, const_ty, Rvalue::new(ByRef));
-            DatumBlock::new(bcx, datum.to_expr_datum())
+            trans_const_ref(bcx, ref_expr, did)
+        }
+        def::DefAssociatedConst(did, def::FromImpl(_)) => {
+            trans_const_ref(bcx, ref_expr, did)
+        }
+        def::DefAssociatedConst(did, def::FromTrait(trait_did)) => {
+            // The constant to use depends on the impl selected for the
+            // (monomorphized) `Self` type of the path.
+            let substs = node_id_substs(bcx, ExprId(ref_expr.id));
+            let did = meth::resolve_associated_const(bcx.ccx(),
+                                                     did,
+                                                     trait_did,
+                                                     &substs);
+            trans_const_ref(bcx, ref_expr, did)
         }
         _ => {
             DatumBlock::new(bcx, trans_local_var(bcx, def).to_expr_datum())
@@ -884,6 +877,40 @@ fn trans_def<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     }
 }
 
+/// Translates a reference to the constant `did`, which is either a `const`
+/// item or an associated constant.
+fn trans_const_ref<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
+                               ref_expr: &ast::Expr,
+                               did: ast::DefId)
+                               -> DatumBlock<'blk, 'tcx, Expr> {
+    // First, inline any external constants into the local crate so we
+    // can be sure to get the LLVM value corresponding to it.
+    let did = inline::maybe_instantiate_inline(bcx.ccx(), did);
+    if did.krate != ast::LOCAL_CRATE {
+        bcx.tcx().sess.span_bug(ref_expr.span,
+                                "cross crate constant could not \
+                                 be inlined");
+    }
+    let val = base::get_item_val(bcx.ccx(), did.node);
+
+    // Next, we need to crate a ByRef rvalue datum to return. We can't
+    // use the normal .to_ref_datum() function because the type of
+    // `val` is not actually the same as `const_ty`.
+    //
+    // To get around this, we make a custom alloca slot with the
+    // appropriate type (const_ty), and then we cast it to a pointer of
+    // typeof(val), store the value, and then hand this slot over to
+    // the datum infrastructure.
+    let const_ty = expr_ty(bcx, ref_expr);
+    let llty = type_of::type_of(bcx.ccx(), const_ty);
+    let slot = alloca(bcx, llty, "const");
+    let pty = Type::from_ref(unsafe { llvm::LLVMTypeOf(val) }).ptr_to();
+    Store(bcx, val, PointerCast(bcx, slot, pty));
+
+    let datum = Datum::new(slot, const_ty, Rvalue::new(ByRef));
+    DatumBlock::new(bcx, datum.to_expr_datum())
+}
+
 fn trans_rvalue_stmt_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                                             expr: &ast::Expr)
                                             -> Block<'blk, 'tcx> {
@@ -900,8 +927,8 @@ fn trans_rvalue_stmt_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
         ast::ExprParen(ref e) => {
             trans_into(bcx, &**e, Ignore)
         }
-        ast::ExprBreak(label_opt) => {
-            controlflow::trans_break(bcx, expr.id, label_opt)
+        ast::ExprBreak(label_opt, ref value) => {
+            controlflow::trans_break(bcx, expr.id, label_opt, value.as_ref().map(|e| &**e))
         }
         ast::ExprAgain(label_opt) => {
             controlflow::trans_cont(bcx, expr.id, label_opt)
@@ -919,9 +946,6 @@ fn trans_rvalue_stmt_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                                    &**head,
                                    &**body)
         }
-        ast::ExprLoop(ref body, _) => {
-            controlflow::trans_loop(bcx, expr.id, &**body)
-        }
         ast::ExprAssign(ref dst, ref src) => {
             let src_datum = unpack_datum!(bcx, trans(bcx, &**src));
             let dst_datum = unpack_datum!(bcx, trans_to_lvalue(bcx, &**dst, "assign"));
@@ -997,6 +1021,9 @@ fn trans_rvalue_dps_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
         ast::ExprBlock(ref blk) => {
             controlflow::trans_block(bcx, &**blk, dest)
         }
+        ast::ExprLoop(ref body, _) => {
+            controlflow::trans_loop(bcx, expr.id, &**body, dest)
+        }
         ast::ExprStruct(_, ref fields, ref base) => {
             trans_struct(bcx,
                          fields.as_slice(),
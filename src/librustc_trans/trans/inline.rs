@@ -145,6 +145,11 @@ fn instantiate_inline(ccx: &CrateContext, fn_id: ast::DefId)
                 ast::TypeTraitItem(_) => {
                     ccx.sess().bug("found TypeTraitItem IITraitItem")
                 }
+                ast::ConstTraitItem(ref associated_const) => {
+                    ccx.external().borrow_mut().insert(fn_id, Some(associated_const.id));
+                    ccx.external_srcs().borrow_mut().insert(associated_const.id, fn_id);
+                    local_def(associated_const.id)
+                }
             }
         }
         csearch::found(&ast::IIImplItem(impl_did, ref impl_item)) => {
@@ -177,6 +182,11 @@ fn instantiate_inline(ccx: &CrateContext, fn_id: ast::DefId)
                 ast::TypeImplItem(_) => {
                     ccx.sess().bug("found TypeImplItem IIImplItem")
                 }
+                ast::ConstImplItem(ref associated_const) => {
+                    ccx.external().borrow_mut().insert(fn_id, Some(associated_const.id));
+                    ccx.external_srcs().borrow_mut().insert(associated_const.id, fn_id);
+                    local_def(associated_const.id)
+                }
             }
         }
     };
@@ -12,6 +12,7 @@
 
 use llvm;
 use llvm::{SequentiallyConsistent, Acquire, Release, AtomicXchg, ValueRef};
+use middle::const_eval;
 use middle::subst;
 use middle::subst::FnSpace;
 use trans::base::*;
@@ -31,6 +32,7 @@ use trans::type_::Type;
 use middle::ty::{mod, Ty};
 use syntax::abi::RustIntrinsic;
 use syntax::ast;
+use syntax::codemap::Span;
 use syntax::parse::token;
 use util::ppaux::ty_to_string;
 
@@ -196,6 +198,19 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
         }
     }
 
+    // LLVM only accepts an immediate prefetch locality, which the
+    // intrinsicck pass has checked is a constant between 0 and 3.
+    let prefetch_locality = match args {
+        callee::ArgExprs(arg_exprs) if name.get().starts_with("prefetch_") => {
+            match const_eval::eval_const_expr_partial(tcx, &*arg_exprs[1]) {
+                Ok(const_eval::const_int(locality)) => Some(locality as i32),
+                Ok(const_eval::const_uint(locality)) => Some(locality as i32),
+                _ => None
+            }
+        }
+        _ => None
+    };
+
     // Push the arguments.
     let mut llargs = Vec::new();
     bcx = callee::trans_args(bcx,
@@ -349,6 +364,21 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
             memset_intrinsic(bcx, true, *substs.types.get(FnSpace, 0),
                              llargs[0], llargs[1], llargs[2])
         }
+        (_, "likely") => expect_intrinsic(bcx, llargs[0], true),
+        (_, "unlikely") => expect_intrinsic(bcx, llargs[0], false),
+        (_, "prefetch_read_data") => {
+            prefetch_intrinsic(bcx, call_info.span, llargs[0], prefetch_locality, false, true)
+        }
+        (_, "prefetch_write_data") => {
+            prefetch_intrinsic(bcx, call_info.span, llargs[0], prefetch_locality, true, true)
+        }
+        (_, "prefetch_read_instruction") => {
+            prefetch_intrinsic(bcx, call_info.span, llargs[0], prefetch_locality, false, false)
+        }
+        (_, "prefetch_write_instruction") => {
+            prefetch_intrinsic(bcx, call_info.span, llargs[0], prefetch_locality, true, false)
+        }
+
         (_, "volatile_load") => {
             VolatileLoad(bcx, llargs[0])
         },
@@ -587,6 +617,26 @@ fn copy_intrinsic<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                       C_bool(ccx, volatile)], None)
 }
 
+fn expect_intrinsic(bcx: Block, val: ValueRef, expected: bool) -> ValueRef {
+    let llfn = bcx.ccx().get_intrinsic(&("llvm.expect.i1"));
+    Call(bcx, llfn, &[val, C_bool(bcx.ccx(), expected)], None)
+}
+
+fn prefetch_intrinsic(bcx: Block, span: Span, ptr: ValueRef, locality: Option<i32>,
+                      write: bool, data: bool) -> ValueRef {
+    let ccx = bcx.ccx();
+    let locality = match locality {
+        Some(locality) => C_i32(ccx, locality),
+        None => ccx.sess().span_bug(span, "prefetch locality isn't a constant")
+    };
+
+    let llfn = ccx.get_intrinsic(&("llvm.prefetch"));
+    Call(bcx, llfn, &[PointerCast(bcx, ptr, Type::i8p(ccx)),
+                      C_i32(ccx, write as i32),
+                      locality,
+                      C_i32(ccx, data as i32)], None)
+}
+
 fn memset_intrinsic<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, volatile: bool, tp_ty: Ty<'tcx>,
                                 dst: ValueRef, val: ValueRef, count: ValueRef) -> ValueRef {
     let ccx = bcx.ccx();
@@ -69,6 +69,9 @@ pub fn trans_impl(ccx: &CrateContext,
                 ast::MethodImplItem(ref method) => {
                     visit::walk_method_helper(&mut v, &**method);
                 }
+                ast::ConstImplItem(ref associated_const) => {
+                    visit::walk_expr_opt(&mut v, &associated_const.expr);
+                }
                 ast::TypeImplItem(_) => {}
             }
         }
@@ -99,6 +102,11 @@ pub fn trans_impl(ccx: &CrateContext,
                 };
                 visit::walk_method_helper(&mut v, &**method);
             }
+            ast::ConstImplItem(ref associated_const) => {
+                // Recurse on the expression to catch items in blocks
+                let mut v = TransItemVisitor { ccx: ccx };
+                visit::walk_expr_opt(&mut v, &associated_const.expr);
+            }
             ast::TypeImplItem(_) => {}
         }
     }
@@ -190,6 +198,10 @@ pub fn trans_static_method_callee(bcx: Block,
                         bcx.tcx().sess.bug("trans_static_method_callee() on \
                                             an associated type?!")
                     }
+                    ast::ConstTraitItem(_) => {
+                        bcx.tcx().sess.bug("trans_static_method_callee() on \
+                                            an associated constant?!")
+                    }
                 };
                 ident.name
             }
@@ -299,6 +311,55 @@ pub fn trans_static_method_callee(bcx: Block,
     }
 }
 
+/// Finds the constant that a path to the trait's associated constant
+/// `const_id` refers to, given the substitutions of the path. This is the
+/// constant from the impl selected for `Self`, or the default from the
+/// trait if the impl doesn't define one.
+pub fn resolve_associated_const<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                          const_id: ast::DefId,
+                                          trait_id: ast::DefId,
+                                          rcvr_substs: &subst::Substs<'tcx>)
+                                          -> ast::DefId {
+    let _icx = push_ctxt("meth::resolve_associated_const");
+
+    let subst::SeparateVecsPerParamSpace {
+        types: rcvr_type,
+        selfs: rcvr_self,
+        assocs: rcvr_assoc,
+        fns: _
+    } = rcvr_substs.types.clone().split();
+    let trait_substs =
+        Substs::erased(VecPerParamSpace::new(rcvr_type,
+                                             rcvr_self,
+                                             rcvr_assoc,
+                                             Vec::new()));
+    let trait_ref = Rc::new(ty::TraitRef { def_id: trait_id,
+                                           substs: trait_substs });
+    debug!("resolve_associated_const(const_id={}, trait_ref={})",
+           const_id,
+           trait_ref.repr(ccx.tcx()));
+
+    match fulfill_obligation(ccx, DUMMY_SP, trait_ref) {
+        traits::VtableImpl(traits::VtableImplData { impl_def_id: impl_did, .. }) => {
+            let name = ty::impl_or_trait_item(ccx.tcx(), const_id).name();
+            let impl_items = ccx.tcx().impl_items.borrow();
+            let impl_const = impl_items[impl_did].iter().find(|&did| {
+                ty::impl_or_trait_item(ccx.tcx(), did.def_id()).name() == name
+            });
+            match impl_const {
+                Some(did) => did.def_id(),
+                // Use the default from the trait.
+                None => const_id,
+            }
+        }
+        vtbl => {
+            ccx.sess().bug(
+                format!("associated constant used with invalid vtable: {}",
+                        vtbl.repr(ccx.tcx())).as_slice());
+        }
+    }
+}
+
 fn method_with_name(ccx: &CrateContext, impl_id: ast::DefId, name: ast::Name)
                     -> ast::DefId {
     match ccx.impl_method_cache().borrow().get(&(impl_id, name)).cloned() {
@@ -338,6 +399,10 @@ fn trans_monomorphized_callee<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                     bcx.tcx().sess.bug("can't monomorphize an associated \
                                         type")
                 }
+                ty::ConstTraitItem(_) => {
+                    bcx.tcx().sess.bug("can't monomorphize an associated \
+                                        constant")
+                }
             };
             let mth_id = method_with_name(bcx.ccx(), impl_did, mname);
 
@@ -707,7 +772,7 @@ fn emit_vtable_methods<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                     Some(fn_ref).into_iter()
                 }
             }
-            ty::TypeTraitItem(_) => {
+            ty::TypeTraitItem(_) | ty::ConstTraitItem(_) => {
                 None.into_iter()
             }
         }
@@ -226,6 +226,9 @@ pub fn monomorphic_fn<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                 ast::TypeImplItem(_) => {
                     ccx.sess().bug("can't monomorphize an associated type")
                 }
+                ast::ConstImplItem(_) => {
+                    ccx.sess().bug("can't monomorphize an associated constant")
+                }
             }
         }
         ast_map::NodeTraitItem(method) => {
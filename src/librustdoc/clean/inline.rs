@@ -316,6 +316,12 @@ fn build_impl(cx: &DocContext, tcx: &ty::ctxt,
                 // FIXME(pcwalton): Implement.
                 None
             }
+            ty::ConstTraitItem(associated_const) => {
+                if associated_const.vis != ast::Public && associated_trait.is_none() {
+                    return None
+                }
+                Some(associated_const.clean(cx))
+            }
         }
     }).collect();
     return Some(clean::Item {
@@ -26,6 +26,7 @@ pub use self::TyParamBound::*;
 pub use self::SelfTy::*;
 pub use self::FunctionRetTy::*;
 pub use self::TraitMethod::*;
+pub use self::WherePredicate::*;
 
 use syntax;
 use syntax::ast;
@@ -686,16 +687,26 @@ impl Clean<Option<Lifetime>> for ty::Region {
 }
 
 #[deriving(Clone, Encodable, Decodable, PartialEq)]
-pub struct WherePredicate {
-    pub name: String,
-    pub bounds: Vec<TyParamBound>
+pub enum WherePredicate {
+    BoundPredicate { name: String, bounds: Vec<TyParamBound> },
+    EqPredicate { lhs: Type, rhs: Type }
 }
 
 impl Clean<WherePredicate> for ast::WherePredicate {
     fn clean(&self, cx: &DocContext) -> WherePredicate {
-        WherePredicate {
-            name: self.ident.clean(cx),
-            bounds: self.bounds.clean(cx)
+        match *self {
+            ast::BoundPredicate(ref wbp) => {
+                BoundPredicate {
+                    name: wbp.ident.clean(cx),
+                    bounds: wbp.bounds.clean(cx)
+                }
+            }
+            ast::EqPredicate(ref wep) => {
+                EqPredicate {
+                    lhs: wep.lhs_ty.clean(cx),
+                    rhs: wep.rhs_ty.clean(cx)
+                }
+            }
         }
     }
 }
@@ -1012,6 +1023,7 @@ pub enum TraitMethod {
     RequiredMethod(Item),
     ProvidedMethod(Item),
     TypeTraitItem(Item),
+    ConstTraitItem(Item),
 }
 
 impl TraitMethod {
@@ -1033,11 +1045,18 @@ impl TraitMethod {
             _ => false,
         }
     }
+    pub fn is_const(&self) -> bool {
+        match self {
+            &ConstTraitItem(..) => true,
+            _ => false,
+        }
+    }
     pub fn item<'a>(&'a self) -> &'a Item {
         match *self {
             RequiredMethod(ref item) => item,
             ProvidedMethod(ref item) => item,
             TypeTraitItem(ref item) => item,
+            ConstTraitItem(ref item) => item,
         }
     }
 }
@@ -1048,6 +1067,7 @@ impl Clean<TraitMethod> for ast::TraitItem {
             &ast::RequiredMethod(ref t) => RequiredMethod(t.clean(cx)),
             &ast::ProvidedMethod(ref t) => ProvidedMethod(t.clean(cx)),
             &ast::TypeTraitItem(ref t) => TypeTraitItem(t.clean(cx)),
+            &ast::ConstTraitItem(ref t) => ConstTraitItem(t.clean(cx)),
         }
     }
 }
@@ -1056,6 +1076,7 @@ impl Clean<TraitMethod> for ast::TraitItem {
 pub enum ImplMethod {
     MethodImplItem(Item),
     TypeImplItem(Item),
+    ConstImplItem(Item),
 }
 
 impl Clean<ImplMethod> for ast::ImplItem {
@@ -1063,6 +1084,7 @@ impl Clean<ImplMethod> for ast::ImplItem {
         match self {
             &ast::MethodImplItem(ref t) => MethodImplItem(t.clean(cx)),
             &ast::TypeImplItem(ref t) => TypeImplItem(t.clean(cx)),
+            &ast::ConstImplItem(ref t) => ConstImplItem(t.clean(cx)),
         }
     }
 }
@@ -1118,6 +1140,7 @@ impl<'tcx> Clean<Item> for ty::ImplOrTraitItem<'tcx> {
         match *self {
             ty::MethodTraitItem(ref mti) => mti.clean(cx),
             ty::TypeTraitItem(ref tti) => tti.clean(cx),
+            ty::ConstTraitItem(ref cti) => cti.clean(cx),
         }
     }
 }
@@ -1882,6 +1905,7 @@ impl Clean<Item> for doctree::Impl {
                         match ti {
                             MethodImplItem(i) => i,
                             TypeImplItem(i) => i,
+                            ConstImplItem(i) => i,
                         }
                     }).collect(),
                 derived: detect_derived(self.attrs.as_slice()),
@@ -2299,6 +2323,41 @@ impl Clean<Item> for ty::AssociatedType {
     }
 }
 
+impl Clean<Item> for ast::AssociatedConst {
+    fn clean(&self, cx: &DocContext) -> Item {
+        Item {
+            source: self.span.clean(cx),
+            name: Some(self.ident.clean(cx)),
+            attrs: self.attrs.clean(cx),
+            inner: ConstantItem(Constant {
+                type_: self.ty.clean(cx),
+                expr: self.expr.as_ref().map_or(String::new(), |e| e.span.to_src(cx)),
+            }),
+            visibility: self.vis.clean(cx),
+            def_id: ast_util::local_def(self.id),
+            stability: None,
+        }
+    }
+}
+
+impl<'tcx> Clean<Item> for ty::AssociatedConst<'tcx> {
+    fn clean(&self, cx: &DocContext) -> Item {
+        Item {
+            source: DUMMY_SP.clean(cx),
+            name: Some(self.name.clean(cx)),
+            attrs: Vec::new(),
+            // The value isn't available across crates.
+            inner: ConstantItem(Constant {
+                type_: self.ty.clean(cx),
+                expr: String::new(),
+            }),
+            visibility: self.vis.clean(cx),
+            def_id: self.def_id,
+            stability: None,
+        }
+    }
+}
+
 impl Clean<Item> for ast::Typedef {
     fn clean(&self, cx: &DocContext) -> Item {
         Item {
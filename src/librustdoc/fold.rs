@@ -61,6 +61,12 @@ pub trait DocFolder {
                                 None => return None,
                             }
                         }
+                        ConstTraitItem(it) => {
+                            match this.fold_item(it) {
+                                Some(x) => return Some(ConstTraitItem(x)),
+                                None => return None,
+                            }
+                        }
                     }
                 }
                 let mut foo = Vec::new(); swap(&mut foo, &mut i.items);
@@ -125,8 +125,15 @@ impl<'a> fmt::Show for WhereClause<'a> {
             if i > 0 {
                 try!(f.write(", ".as_bytes()));
             }
-            let bounds = pred.bounds.as_slice();
-            try!(write!(f, "{}: {}", pred.name, TyParamBounds(bounds)));
+            match *pred {
+                clean::BoundPredicate { ref name, ref bounds } => {
+                    let bounds = bounds.as_slice();
+                    try!(write!(f, "{}: {}", name, TyParamBounds(bounds)));
+                }
+                clean::EqPredicate { ref lhs, ref rhs } => {
+                    try!(write!(f, "{} == {}", *lhs, *rhs));
+                }
+            }
         }
         Ok(())
     }
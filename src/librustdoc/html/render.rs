@@ -1691,6 +1691,7 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                   WhereClause(&t.generics)));
 
     let types = t.items.iter().filter(|m| m.is_type()).collect::<Vec<_>>();
+    let consts = t.items.iter().filter(|m| m.is_const()).collect::<Vec<_>>();
     let required = t.items.iter().filter(|m| m.is_req()).collect::<Vec<_>>();
     let provided = t.items.iter().filter(|m| m.is_def()).collect::<Vec<_>>();
 
@@ -1703,7 +1704,15 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
             try!(render_method(w, t.item()));
             try!(write!(w, ";\n"));
         }
-        if types.len() > 0 && required.len() > 0 {
+        if types.len() > 0 && consts.len() > 0 {
+            try!(w.write("\n".as_bytes()));
+        }
+        for t in consts.iter() {
+            try!(write!(w, "    "));
+            try!(render_method(w, t.item()));
+            try!(write!(w, ";\n"));
+        }
+        if (types.len() > 0 || consts.len() > 0) && required.len() > 0 {
             try!(w.write("\n".as_bytes()));
         }
         for m in required.iter() {
@@ -1749,6 +1758,17 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
         try!(write!(w, "</div>"));
     }
 
+    if consts.len() > 0 {
+        try!(write!(w, "
+            <h2 id='associated-constants'>Associated Constants</h2>
+            <div class='methods'>
+        "));
+        for t in consts.iter() {
+            try!(trait_item(w, *t));
+        }
+        try!(write!(w, "</div>"));
+    }
+
     // Output the documentation for each function individually
     if required.len() > 0 {
         try!(write!(w, "
@@ -1829,6 +1849,13 @@ fn render_method(w: &mut fmt::Formatter, meth: &clean::Item) -> fmt::Result {
         }
         Ok(())
     }
+    fn assoc_const(w: &mut fmt::Formatter, it: &clean::Item,
+                   c: &clean::Constant) -> fmt::Result {
+        write!(w, "const {}: {}{}",
+               it.name.as_ref().unwrap(),
+               c.type_,
+               Initializer(c.expr.as_slice()))
+    }
     match meth.inner {
         clean::TyMethodItem(ref m) => {
             method(w, meth, m.fn_style, &m.generics, &m.self_, &m.decl)
@@ -1839,6 +1866,9 @@ fn render_method(w: &mut fmt::Formatter, meth: &clean::Item) -> fmt::Result {
         clean::AssociatedTypeItem(ref typ) => {
             assoc_type(w, meth, typ)
         }
+        clean::ConstantItem(ref c) => {
+            assoc_const(w, meth, c)
+        }
         _ => panic!("render_method called on non-method")
     }
 }
@@ -2111,6 +2141,14 @@ fn render_impl(w: &mut fmt::Formatter, i: &Impl) -> fmt::Result {
                 try!(write!(w, "type {} = {}", name, tydef.type_));
                 try!(write!(w, "</code></h4>\n"));
             }
+            clean::ConstantItem(..) => {
+                try!(write!(w, "<h4 id='assoc_const.{}' class='{}'>{}<code>",
+                            *item.name.as_ref().unwrap(),
+                            shortty(item),
+                            ConciseStability(&item.stability)));
+                try!(render_method(w, item));
+                try!(write!(w, "</code></h4>\n"));
+            }
             _ => panic!("can't make docs for trait item with name {}", item.name)
         }
         match item.doc_value() {
@@ -21,7 +21,7 @@ use syntax::ast::Public;
 
 use clean::{Crate, Item, ModuleItem, Module, EnumItem, Enum};
 use clean::{ImplItem, Impl, Trait, TraitItem, TraitMethod, ProvidedMethod, RequiredMethod};
-use clean::{TypeTraitItem, ViewItemItem, PrimitiveItem, Stability};
+use clean::{TypeTraitItem, ConstTraitItem, ViewItemItem, PrimitiveItem, Stability};
 
 use html::render::cache;
 
@@ -168,7 +168,8 @@ fn summarize_item(item: &Item) -> (Counts, Option<ModuleSummary>) {
                 match *trait_item {
                     ProvidedMethod(ref item) |
                     RequiredMethod(ref item) |
-                    TypeTraitItem(ref item) => item
+                    TypeTraitItem(ref item) |
+                    ConstTraitItem(ref item) => item
                 }
             }
             let subcounts = trait_items.iter()
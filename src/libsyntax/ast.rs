@@ -57,6 +57,7 @@ pub use self::VariantKind::*;
 pub use self::ViewItem_::*;
 pub use self::ViewPath_::*;
 pub use self::Visibility::*;
+pub use self::WherePredicate::*;
 pub use self::PathParameters::*;
 
 use codemap::{Span, Spanned, DUMMY_SP, ExpnId};
@@ -398,13 +399,29 @@ pub struct WhereClause {
 }
 
 #[deriving(Clone, PartialEq, Eq, Encodable, Decodable, Hash, Show)]
-pub struct WherePredicate {
+pub enum WherePredicate {
+    /// A type parameter bound, e.g. `T: Clone + Send`
+    BoundPredicate(WhereBoundPredicate),
+    /// An equality constraint, e.g. `<T as Iterator>::Item == uint`
+    EqPredicate(WhereEqPredicate),
+}
+
+#[deriving(Clone, PartialEq, Eq, Encodable, Decodable, Hash, Show)]
+pub struct WhereBoundPredicate {
     pub id: NodeId,
     pub span: Span,
     pub ident: Ident,
     pub bounds: OwnedSlice<TyParamBound>,
 }
 
+#[deriving(Clone, PartialEq, Eq, Encodable, Decodable, Hash, Show)]
+pub struct WhereEqPredicate {
+    pub id: NodeId,
+    pub span: Span,
+    pub lhs_ty: P<Ty>,
+    pub rhs_ty: P<Ty>,
+}
+
 /// The set of MetaItems that define the compilation environment of the crate,
 /// used to drive conditional compilation
 pub type CrateConfig = Vec<P<MetaItem>> ;
@@ -618,6 +635,8 @@ pub struct Field {
     pub ident: SpannedIdent,
     pub expr: P<Expr>,
     pub span: Span,
+    /// True for `Foo { x }`, which is sugar for `Foo { x: x }`
+    pub is_shorthand: bool,
 }
 
 pub type SpannedIdent = Spanned<Ident>;
@@ -653,6 +672,12 @@ pub enum Expr_ {
     ExprUnary(UnOp, P<Expr>),
     ExprLit(P<Lit>),
     ExprCast(P<Expr>, P<Ty>),
+    /// A type ascription, e.g. `x: uint`
+    ExprType(P<Expr>, P<Ty>),
+    /// An inclusive range, `start...end`; desugared during expansion
+    ExprInclusiveRange(P<Expr>, P<Expr>),
+    /// An early-return `expr?`; desugared during expansion
+    ExprTry(P<Expr>),
     ExprIf(P<Expr>, P<Block>, Option<P<Expr>>),
     ExprIfLet(P<Pat>, P<Expr>, P<Block>, Option<P<Expr>>),
     // FIXME #6993: change to Option<Name> ... or not, if these are hygienic.
@@ -681,7 +706,9 @@ pub enum Expr_ {
     ExprPath(Path),
 
     ExprAddrOf(Mutability, P<Expr>),
-    ExprBreak(Option<Ident>),
+    /// A `break`, with an optional label and, when it exits a `loop`, an
+    /// optional value for the loop expression
+    ExprBreak(Option<Ident>, Option<P<Expr>>),
     ExprAgain(Option<Ident>),
     ExprRet(Option<P<Expr>>),
 
@@ -810,7 +837,9 @@ pub enum TokenTree {
 impl TokenTree {
     pub fn len(&self) -> uint {
         match *self {
-            TtToken(_, token::DocComment(_)) => 2,
+            TtToken(_, ref tok @ token::DocComment(_)) => {
+                if tok.is_inner_doc_comment() { 3 } else { 2 }
+            }
             TtToken(_, token::SubstNt(..)) => 2,
             TtToken(_, token::MatchNt(..)) => 3,
             TtDelimited(_, ref delimed) => {
@@ -828,14 +857,31 @@ impl TokenTree {
             (&TtToken(sp, token::DocComment(_)), 0) => {
                 TtToken(sp, token::Pound)
             }
-            (&TtToken(sp, token::DocComment(name)), 1) => {
+            (&TtToken(sp, ref tok @ token::DocComment(_)), 1) if tok.is_inner_doc_comment() => {
+                TtToken(sp, token::Not)
+            }
+            (&TtToken(sp, token::DocComment(name)), _) => {
+                // The comment's text is kept as it was written, in a raw
+                // string with enough hashes that no quote in it ends the
+                // literal early.
+                let num_of_hashes = name.as_str().chars().scan(0u, |cnt, c| {
+                    *cnt = if c == '"' {
+                        1
+                    } else if *cnt != 0 && c == '#' {
+                        *cnt + 1
+                    } else {
+                        0
+                    };
+                    Some(*cnt)
+                }).max().unwrap_or(0);
                 TtDelimited(sp, Rc::new(Delimited {
                     delim: token::Bracket,
                     open_span: sp,
                     tts: vec![TtToken(sp, token::Ident(token::str_to_ident("doc"),
                                                        token::Plain)),
                               TtToken(sp, token::Eq),
-                              TtToken(sp, token::Literal(token::Str_(name), None))],
+                              TtToken(sp, token::Literal(token::StrRaw(name, num_of_hashes),
+                                                         None))],
                     close_span: sp,
                 }))
             }
@@ -982,12 +1028,14 @@ pub enum TraitItem {
     RequiredMethod(TypeMethod),
     ProvidedMethod(P<Method>),
     TypeTraitItem(P<AssociatedType>),
+    ConstTraitItem(P<AssociatedConst>),
 }
 
 #[deriving(Clone, PartialEq, Eq, Encodable, Decodable, Hash, Show)]
 pub enum ImplItem {
     MethodImplItem(P<Method>),
     TypeImplItem(P<Typedef>),
+    ConstImplItem(P<AssociatedConst>),
 }
 
 #[deriving(Clone, PartialEq, Eq, Encodable, Decodable, Hash, Show)]
@@ -1006,6 +1054,20 @@ pub struct Typedef {
     pub typ: P<Ty>,
 }
 
+/// An associated constant, `const NAME: Ty = expr;`. The value is optional in
+/// traits, where it's the default for impls that don't give their own, and
+/// required in impls.
+#[deriving(Clone, PartialEq, Eq, Encodable, Decodable, Hash, Show)]
+pub struct AssociatedConst {
+    pub id: NodeId,
+    pub span: Span,
+    pub ident: Ident,
+    pub vis: Visibility,
+    pub attrs: Vec<Attribute>,
+    pub ty: P<Ty>,
+    pub expr: Option<P<Expr>>,
+}
+
 #[deriving(Clone, PartialEq, Eq, Encodable, Decodable, Hash)]
 pub enum IntTy {
     TyI,
@@ -1175,11 +1237,17 @@ pub enum AsmDialect {
 pub struct InlineAsm {
     pub asm: InternedString,
     pub asm_str_style: StrStyle,
-    pub outputs: Vec<(InternedString, P<Expr>, bool)>,
-    pub inputs: Vec<(InternedString, P<Expr>)>,
-    pub clobbers: Vec<InternedString>,
+    /// Output operands as `(constraint, expr, is_rw, span)`, where `span`
+    /// covers the constraint string and the parenthesized expression.
+    pub outputs: Vec<(InternedString, P<Expr>, bool, Span)>,
+    /// Input operands as `(constraint, expr, span)`.
+    pub inputs: Vec<(InternedString, P<Expr>, Span)>,
+    pub clobbers: Vec<(InternedString, Span)>,
     pub volatile: bool,
     pub alignstack: bool,
+    /// The asm never falls through to the code after it, as when it ends in
+    /// a jump or a halt, so the expression diverges.
+    pub noreturn: bool,
     pub dialect: AsmDialect,
     pub expn_id: ExpnId,
 }
@@ -1599,6 +1667,8 @@ mod test {
     use serialize::json;
     use serialize;
     use codemap::*;
+    use parse::token;
+    use util::parser_testing::string_to_tts;
     use super::*;
 
     // are ASTs encodable?
@@ -1627,4 +1697,49 @@ mod test {
         // doesn't matter which encoder we use....
         let _f = &e as &serialize::Encodable<json::Encoder, io::IoError>;
     }
+
+    fn doc_attr_literal(tt: &TokenTree) -> token::Token {
+        match *tt {
+            TtDelimited(_, ref delimed) => {
+                assert_eq!(delimed.delim, token::Bracket);
+                match delimed.tts.as_slice() {
+                    [TtToken(_, token::Ident(id, token::Plain)),
+                     TtToken(_, token::Eq),
+                     TtToken(_, ref lit)] => {
+                        assert_eq!(token::get_ident(id).get(), "doc");
+                        lit.clone()
+                    }
+                    _ => panic!("unexpected doc attribute: {}", delimed.tts)
+                }
+            }
+            _ => panic!("expected a delimited doc attribute, found {}", tt)
+        }
+    }
+
+    #[test]
+    fn outer_doc_comment_tts_keep_raw_text() {
+        let tts = string_to_tts("/// Says \"hi\"# to C:\\dir.\nfn f() {}".to_string());
+        assert_eq!(tts[0].len(), 2);
+        match tts[0].get_tt(0) {
+            TtToken(_, token::Pound) => {}
+            tt => panic!("expected `#`, found {}", tt)
+        }
+        assert_eq!(doc_attr_literal(&tts[0].get_tt(1)),
+                   token::Literal(token::StrRaw(token::intern("/// Says \"hi\"# to C:\\dir."),
+                                                2),
+                                  None));
+    }
+
+    #[test]
+    fn inner_doc_comment_tts_keep_raw_text() {
+        let tts = string_to_tts("/*! Block\n    docs */".to_string());
+        assert_eq!(tts[0].len(), 3);
+        match (tts[0].get_tt(0), tts[0].get_tt(1)) {
+            (TtToken(_, token::Pound), TtToken(_, token::Not)) => {}
+            (a, b) => panic!("expected `#!`, found {} {}", a, b)
+        }
+        assert_eq!(doc_attr_literal(&tts[0].get_tt(2)),
+                   token::Literal(token::StrRaw(token::intern("/*! Block\n    docs */"), 0),
+                                  None));
+    }
 }
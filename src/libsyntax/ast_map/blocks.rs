@@ -209,7 +209,7 @@ impl<'a> FnLikeNode<'a> {
             ast_map::NodeImplItem(ii) => {
                 match *ii {
                     ast::MethodImplItem(ref m) => method(&**m),
-                    ast::TypeImplItem(_) => {
+                    ast::TypeImplItem(_) | ast::ConstImplItem(_) => {
                         panic!("impl method FnLikeNode that is not fn-like")
                     }
                 }
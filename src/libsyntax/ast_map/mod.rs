@@ -24,7 +24,10 @@ use visit::{mod, Visitor};
 
 use arena::TypedArena;
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::hash_map::{Occupied, Vacant};
 use std::fmt;
+use std::hash;
 use std::io::IoResult;
 use std::iter;
 use std::mem;
@@ -217,6 +220,35 @@ impl<'ast> MapEntry<'ast> {
     }
 }
 
+/// An identifier for a node that, unlike its `NodeId`, survives edits to
+/// unrelated parts of the crate, so that tools can correlate nodes across
+/// compilations. It only changes when the innermost item containing the
+/// node, or another item with the same path, changes.
+#[deriving(Clone, PartialEq, Eq, Hash, Show)]
+pub struct StableId {
+    /// Hash of the path of the innermost item containing the node.
+    pub path_hash: u64,
+    /// The position of the node among all nodes under items with that path.
+    pub disambiguator: uint,
+}
+
+/// The `StableId`s of all nodes in an AST map, see `Map::stable_ids`.
+pub struct StableIds {
+    ids: Vec<Option<StableId>>,
+    nodes: HashMap<StableId, NodeId>,
+}
+
+impl StableIds {
+    pub fn get(&self, id: NodeId) -> Option<StableId> {
+        self.ids.as_slice().get(id as uint).and_then(|id| id.clone())
+    }
+
+    /// Finds the node with the given stable id in this compilation.
+    pub fn find_node(&self, id: &StableId) -> Option<NodeId> {
+        self.nodes.get(id).map(|&node| node)
+    }
+}
+
 /// Stores a crate and any number of inlined items from other crates.
 pub struct Forest {
     krate: Crate,
@@ -251,7 +283,12 @@ pub struct Map<'ast> {
     ///
     /// Also, indexing is pretty quick when you've got a vector and
     /// plain old integers.
-    map: RefCell<Vec<MapEntry<'ast>>>
+    map: RefCell<Vec<MapEntry<'ast>>>,
+
+    /// The item-like nodes (items, foreign items, trait and impl items,
+    /// variants and tuple struct constructors) whose parent is the node
+    /// with the given id, in source order. Indexed like `map`.
+    children: RefCell<Vec<Vec<NodeId>>>,
 }
 
 impl<'ast> Map<'ast> {
@@ -288,6 +325,40 @@ impl<'ast> Map<'ast> {
         self.find_entry(id).and_then(|x| x.parent()).unwrap_or(id)
     }
 
+    /// Returns an iterator over the ancestors of `id`: its parent, then
+    /// that node's parent, and so on up to the crate root (or the root of
+    /// an inlined item).
+    pub fn ancestors<'a>(&'a self, id: NodeId) -> Ancestors<'a, 'ast> {
+        Ancestors {
+            map: self,
+            current: id,
+        }
+    }
+
+    /// Returns the id of the module `id` is in, which is the crate root's
+    /// id for nodes at the top level.
+    pub fn get_module_parent(&self, id: NodeId) -> NodeId {
+        self.ancestors(id).find(|&a| match self.find(a) {
+            Some(NodeItem(item)) => match item.node {
+                ItemMod(_) => true,
+                _ => false
+            },
+            _ => false
+        }).unwrap_or(CRATE_NODE_ID)
+    }
+
+    /// Calls `f` with the ids of the item-like nodes (items, foreign items,
+    /// trait and impl items, variants and tuple struct constructors) whose
+    /// parent is `id`, in source order. Use `CRATE_NODE_ID` for the items
+    /// at the top level.
+    pub fn with_children<T>(&self, id: NodeId, f: |&[NodeId]| -> T) -> T {
+        let children = self.children.borrow();
+        match children.as_slice().get(id as uint) {
+            Some(ids) => f(ids.as_slice()),
+            None => f(&[])
+        }
+    }
+
     pub fn get_parent_did(&self, id: NodeId) -> DefId {
         let parent = self.get_parent(id);
         match self.find_entry(parent) {
@@ -397,6 +468,7 @@ impl<'ast> Map<'ast> {
                         }
                     }
                     TypeImplItem(ref t) => PathName(t.ident.name),
+                    ConstImplItem(ref c) => PathName(c.ident.name),
                 }
             },
             NodeTraitItem(tm) => match *tm {
@@ -412,6 +484,7 @@ impl<'ast> Map<'ast> {
                 TypeTraitItem(ref m) => {
                     PathName(m.ty_param.ident.name)
                 }
+                ConstTraitItem(ref c) => PathName(c.ident.name),
             },
             NodeVariant(v) => PathName(v.node.name.name),
             _ => panic!("no path elem for {}", node)
@@ -426,6 +499,12 @@ impl<'ast> Map<'ast> {
         self.with_path(id, |path| path_to_string(path))
     }
 
+    /// Returns the path of the definition `id` from the crate root, ending
+    /// with the definition itself.
+    pub fn def_path(&self, id: NodeId) -> Vec<PathElem> {
+        self.with_path(id, |path| path.collect())
+    }
+
     fn path_to_str_with_ident(&self, id: NodeId, i: Ident) -> String {
         self.with_path(id, |path| {
             path_to_string(path.chain(Some(PathName(i.name)).into_iter()))
@@ -472,11 +551,13 @@ impl<'ast> Map<'ast> {
                 RequiredMethod(ref type_m) => Some(type_m.attrs.as_slice()),
                 ProvidedMethod(ref m) => Some(m.attrs.as_slice()),
                 TypeTraitItem(ref typ) => Some(typ.attrs.as_slice()),
+                ConstTraitItem(ref c) => Some(c.attrs.as_slice()),
             },
             NodeImplItem(ref ii) => {
                 match **ii {
                     MethodImplItem(ref m) => Some(m.attrs.as_slice()),
                     TypeImplItem(ref t) => Some(t.attrs.as_slice()),
+                    ConstImplItem(ref c) => Some(c.attrs.as_slice()),
                 }
             }
             NodeVariant(ref v) => Some(v.node.attrs.as_slice()),
@@ -517,12 +598,14 @@ impl<'ast> Map<'ast> {
                     RequiredMethod(ref type_method) => type_method.span,
                     ProvidedMethod(ref method) => method.span,
                     TypeTraitItem(ref typedef) => typedef.ty_param.span,
+                    ConstTraitItem(ref c) => c.span,
                 }
             }
             Some(NodeImplItem(ref impl_item)) => {
                 match **impl_item {
                     MethodImplItem(ref method) => method.span,
                     TypeImplItem(ref typedef) => typedef.span,
+                    ConstImplItem(ref c) => c.span,
                 }
             }
             Some(NodeVariant(variant)) => variant.span,
@@ -557,6 +640,77 @@ impl<'ast> Map<'ast> {
     pub fn node_to_user_string(&self, id: NodeId) -> String {
         node_id_to_string(self, id, false)
     }
+
+    /// Computes the `StableId` of every node in the map. Nothing computes
+    /// these unless asked to, and since this walks the whole map, tools
+    /// should do it once and keep the result.
+    pub fn stable_ids(&self) -> StableIds {
+        let mut ids = Vec::with_capacity(self.entry_count());
+        let mut nodes = HashMap::new();
+        let mut counts = HashMap::new();
+        for id in range(0, self.entry_count() as NodeId) {
+            match self.find_entry(id) {
+                Some(NotPresent) | Some(RootInlinedParent(_)) | None => {
+                    ids.push(None);
+                    continue;
+                }
+                _ => {}
+            }
+            // Paths are hashed as strings, since the values of interned
+            // names depend on the order things were interned in.
+            let owner = self.stable_id_owner(id);
+            let path_hash = hash::hash(&self.with_path(owner, |path| path_to_string(path)));
+            let count = match counts.entry(path_hash) {
+                Vacant(entry) => entry.set(0u),
+                Occupied(entry) => entry.into_mut(),
+            };
+            let stable_id = StableId {
+                path_hash: path_hash,
+                disambiguator: *count,
+            };
+            *count += 1;
+            nodes.insert(stable_id.clone(), id);
+            ids.push(Some(stable_id));
+        }
+        StableIds {
+            ids: ids,
+            nodes: nodes,
+        }
+    }
+
+    /// The innermost node containing `id` that has a path of its own.
+    fn stable_id_owner(&self, id: NodeId) -> NodeId {
+        let mut id = id;
+        loop {
+            match self.find_entry(id) {
+                Some(EntryItem(..)) | Some(EntryForeignItem(..)) |
+                Some(EntryTraitItem(..)) | Some(EntryImplItem(..)) |
+                Some(EntryVariant(..)) => return id,
+                _ => {}
+            }
+            let parent = self.get_parent(id);
+            if parent == id {
+                return id;
+            }
+            id = parent;
+        }
+    }
+}
+
+pub struct Ancestors<'a, 'ast: 'a> {
+    map: &'a Map<'ast>,
+    current: NodeId,
+}
+
+impl<'a, 'ast> Iterator<NodeId> for Ancestors<'a, 'ast> {
+    fn next(&mut self) -> Option<NodeId> {
+        let parent = self.map.get_parent(self.current);
+        if parent == self.current {
+            return None;
+        }
+        self.current = parent;
+        Some(parent)
+    }
 }
 
 pub struct NodesMatchingSuffix<'a, 'ast:'a, S:'a> {
@@ -661,6 +815,7 @@ impl Named for TraitItem {
             RequiredMethod(ref tm) => tm.ident.name,
             ProvidedMethod(ref m) => m.name(),
             TypeTraitItem(ref at) => at.ty_param.ident.name,
+            ConstTraitItem(ref ac) => ac.ident.name,
         }
     }
 }
@@ -669,6 +824,7 @@ impl Named for ImplItem {
         match *self {
             MethodImplItem(ref m) => m.name(),
             TypeImplItem(ref td) => td.ident.name,
+            ConstImplItem(ref ac) => ac.ident.name,
         }
     }
 }
@@ -711,6 +867,7 @@ impl<F: FoldOps> Folder for IdAndSpanUpdater<F> {
 /// A Visitor that walks over an AST and collects Node's into an AST Map.
 struct NodeCollector<'ast> {
     map: Vec<MapEntry<'ast>>,
+    children: Vec<Vec<NodeId>>,
     /// The node in which we are currently mapping (an item or a method).
     parent: NodeId
 }
@@ -726,6 +883,22 @@ impl<'ast> NodeCollector<'ast> {
     }
 
     fn insert(&mut self, id: NodeId, node: Node<'ast>) {
+        let is_child = match node {
+            // Trait references in a trait's bounds are also mapped to the
+            // trait, but aren't children of anything.
+            NodeItem(i) => i.id == id,
+            NodeForeignItem(_) | NodeTraitItem(_) | NodeImplItem(_) |
+            NodeVariant(_) | NodeStructCtor(_) => true,
+            _ => false
+        };
+        if is_child {
+            let parent = self.parent as uint;
+            let len = self.children.len();
+            if parent >= len {
+                self.children.grow(parent - len + 1, Vec::new());
+            }
+            self.children[parent].push(id);
+        }
         let entry = MapEntry::from_node(self.parent, node);
         self.insert_entry(id, entry);
     }
@@ -752,6 +925,9 @@ impl<'ast> Visitor<'ast> for NodeCollector<'ast> {
                         TypeImplItem(ref t) => {
                             self.insert(t.id, NodeImplItem(impl_item));
                         }
+                        ConstImplItem(ref c) => {
+                            self.insert(c.id, NodeImplItem(impl_item));
+                        }
                     }
                 }
             }
@@ -792,6 +968,9 @@ impl<'ast> Visitor<'ast> for NodeCollector<'ast> {
                         TypeTraitItem(ref typ) => {
                             self.insert(typ.ty_param.id, NodeTraitItem(tm));
                         }
+                        ConstTraitItem(ref c) => {
+                            self.insert(c.id, NodeTraitItem(tm));
+                        }
                     }
                 }
             }
@@ -889,11 +1068,12 @@ pub fn map_crate<'ast, F: FoldOps>(forest: &'ast mut Forest, fold_ops: F) -> Map
 
     let mut collector = NodeCollector {
         map: vec![],
+        children: vec![],
         parent: CRATE_NODE_ID
     };
     collector.insert_entry(CRATE_NODE_ID, RootCrate);
     visit::walk_crate(&mut collector, &forest.krate);
-    let map = collector.map;
+    let NodeCollector { map, children, .. } = collector;
 
     if log_enabled!(::log::DEBUG) {
         // This only makes sense for ordered stores; note the
@@ -913,7 +1093,8 @@ pub fn map_crate<'ast, F: FoldOps>(forest: &'ast mut Forest, fold_ops: F) -> Map
 
     Map {
         forest: forest,
-        map: RefCell::new(map)
+        map: RefCell::new(map),
+        children: RefCell::new(children),
     }
 }
 
@@ -943,6 +1124,11 @@ pub fn map_decoded_item<'ast, F: FoldOps>(map: &Map<'ast>,
                     fld.fold_ops.new_def_id(d),
                     TypeTraitItem(P(fld.fold_associated_type((*at).clone()))))
             }
+            ConstTraitItem(ac) => {
+                IITraitItem(
+                    fld.fold_ops.new_def_id(d),
+                    ConstTraitItem(P(fld.fold_associated_const((*ac).clone()))))
+            }
         },
         IIImplItem(d, m) => match m {
             MethodImplItem(m) => {
@@ -954,6 +1140,10 @@ pub fn map_decoded_item<'ast, F: FoldOps>(map: &Map<'ast>,
                 IIImplItem(fld.fold_ops.new_def_id(d),
                            TypeImplItem(P(fld.fold_typedef((*t).clone()))))
             }
+            ConstImplItem(c) => {
+                IIImplItem(fld.fold_ops.new_def_id(d),
+                           ConstImplItem(P(fld.fold_associated_const((*c).clone()))))
+            }
         },
         IIForeign(i) => IIForeign(fld.fold_foreign_item(i))
     };
@@ -965,6 +1155,7 @@ pub fn map_decoded_item<'ast, F: FoldOps>(map: &Map<'ast>,
 
     let mut collector = NodeCollector {
         map: mem::replace(&mut *map.map.borrow_mut(), vec![]),
+        children: mem::replace(&mut *map.children.borrow_mut(), vec![]),
         parent: fld.new_id(DUMMY_NODE_ID)
     };
     let ii_parent_id = collector.parent;
@@ -981,6 +1172,7 @@ pub fn map_decoded_item<'ast, F: FoldOps>(map: &Map<'ast>,
                 ProvidedMethod(ref m) => m.id,
                 RequiredMethod(ref m) => m.id,
                 TypeTraitItem(ref ty) => ty.ty_param.id,
+                ConstTraitItem(ref c) => c.id,
             };
 
             collector.insert(trait_item_id, NodeTraitItem(trait_item));
@@ -989,6 +1181,7 @@ pub fn map_decoded_item<'ast, F: FoldOps>(map: &Map<'ast>,
             let impl_item_id = match *impl_item {
                 MethodImplItem(ref m) => m.id,
                 TypeImplItem(ref ti) => ti.id,
+                ConstImplItem(ref c) => c.id,
             };
 
             collector.insert(impl_item_id, NodeImplItem(impl_item));
@@ -998,6 +1191,7 @@ pub fn map_decoded_item<'ast, F: FoldOps>(map: &Map<'ast>,
         }
     }
     *map.map.borrow_mut() = collector.map;
+    *map.children.borrow_mut() = collector.children;
     &ii_parent.ii
 }
 
@@ -1074,6 +1268,12 @@ fn node_id_to_string(map: &Map, id: NodeId, include_id: bool) -> String {
                             map.path_to_string(id),
                             id_str)
                 }
+                ConstImplItem(ref c) => {
+                    format!("const {} in {}{}",
+                            token::get_ident(c.ident),
+                            map.path_to_string(id),
+                            id_str)
+                }
             }
         }
         Some(NodeTraitItem(ref tm)) => {
@@ -1091,6 +1291,12 @@ fn node_id_to_string(map: &Map, id: NodeId, include_id: bool) -> String {
                             map.path_to_string(id),
                             id_str)
                 }
+                ConstTraitItem(ref c) => {
+                    format!("const item {} in {}{}",
+                            token::get_ident(c.ident),
+                            map.path_to_string(id),
+                            id_str)
+                }
             }
         }
         Some(NodeVariant(ref variant)) => {
@@ -1128,3 +1334,61 @@ fn node_id_to_string(map: &Map, id: NodeId, include_id: bool) -> String {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ast::{CRATE_NODE_ID, NodeId};
+    use parse::token;
+    use print::pprust;
+    use super::*;
+    use util::parser_testing::{IdCounter, string_to_crate};
+
+    // The stable id of the expression printed as `expr` in the crate `src`.
+    fn stable_id_of(src: &str, expr: &str) -> StableId {
+        let mut forest = Forest::new(string_to_crate(src.to_string()));
+        let map = map_crate(&mut forest, IdCounter::new());
+        let id = range(0, map.entry_count() as NodeId).find(|&id| match map.find(id) {
+            Some(NodeExpr(e)) => pprust::expr_to_string(e).as_slice() == expr,
+            _ => false
+        }).unwrap();
+        map.stable_ids().get(id).unwrap()
+    }
+
+    #[test]
+    fn stable_ids_survive_unrelated_edits() {
+        let before = stable_id_of("fn a() { 1i; } fn b() { 2i; }", "2i");
+        let after = stable_id_of("fn z() { 3i; } fn a() { 1i + 1i; } fn b() { 2i; }", "2i");
+        assert_eq!(before, after);
+        assert!(before != stable_id_of("fn c() { 2i; }", "2i"));
+    }
+
+    #[test]
+    fn parents_and_children() {
+        let src = "mod m { fn f() {} struct S(int); } fn g() { fn h() { 1i; } }";
+        let mut forest = Forest::new(string_to_crate(src.to_string()));
+        let map = map_crate(&mut forest, IdCounter::new());
+        let item = |name: &str| range(0, map.entry_count() as NodeId).find(|&id| {
+            match map.find(id) {
+                Some(NodeItem(i)) => i.id == id && token::get_ident(i.ident).get() == name,
+                _ => false
+            }
+        }).unwrap();
+        let (m, f, s, g, h) = (item("m"), item("f"), item("S"), item("g"), item("h"));
+
+        map.with_children(CRATE_NODE_ID, |ids| assert_eq!(ids, [m, g].as_slice()));
+        map.with_children(g, |ids| assert_eq!(ids, [h].as_slice()));
+        map.with_children(m, |ids| assert_eq!(ids, [f, s].as_slice()));
+        let ctor = map.with_children(s, |ids| ids[0]);
+        assert_eq!(map.get_parent(ctor), s);
+
+        let one = range(0, map.entry_count() as NodeId).find(|&id| match map.find(id) {
+            Some(NodeExpr(_)) => true,
+            _ => false
+        }).unwrap();
+        assert_eq!(map.ancestors(one).collect::<Vec<NodeId>>(), vec![h, g, CRATE_NODE_ID]);
+        assert_eq!(map.get_module_parent(one), CRATE_NODE_ID);
+        assert_eq!(map.get_module_parent(f), m);
+        assert_eq!(map.path_to_string(f), "m::f".to_string());
+        assert_eq!(map.def_path(f).len(), 2);
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! AST statistics
+//!
+//! This module counts the nodes of the expanded crate, and their sizes,
+//! by kind of node, by the macro that produced them and by module, to
+//! help find the macros that bloat compile times.
+
+use ast;
+use codemap::{CodeMap, ExpnId, Span, MacroAttribute, MacroBang, NO_EXPANSION};
+use parse::token;
+use visit;
+use visit::Visitor;
+
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::{Occupied, Vacant};
+use std::default::Default;
+use std::mem;
+
+#[deriving(Default)]
+struct NodeStats {
+    count: uint,
+    /// The total of the nodes' own sizes, not counting what they point to
+    bytes: uint,
+}
+
+impl NodeStats {
+    fn add(&mut self, bytes: uint) {
+        self.count += 1;
+        self.bytes += bytes;
+    }
+}
+
+struct MacroStats {
+    nodes: NodeStats,
+    /// The distinct expansions of the macro that produced nodes
+    expansions: HashSet<ExpnId>,
+}
+
+struct StatsVisitor<'a> {
+    cm: &'a CodeMap,
+    by_kind: HashMap<&'static str, NodeStats>,
+    by_macro: HashMap<String, MacroStats>,
+    by_module: HashMap<String, NodeStats>,
+    /// The path of the module being visited
+    module: Vec<String>,
+}
+
+impl<'a> StatsVisitor<'a> {
+    fn record(&mut self, kind: &'static str, sp: Span, bytes: uint) {
+        match self.by_kind.entry(kind) {
+            Occupied(entry) => entry.into_mut().add(bytes),
+            Vacant(entry) => entry.set(NodeStats::default()).add(bytes),
+        }
+
+        let module = self.module.connect("::");
+        match self.by_module.entry(module) {
+            Occupied(entry) => entry.into_mut().add(bytes),
+            Vacant(entry) => entry.set(NodeStats::default()).add(bytes),
+        }
+
+        if sp.expn_id == NO_EXPANSION {
+            return;
+        }
+        let name = self.cm.with_expn_info(sp.expn_id, |info| {
+            info.map(|info| match info.callee.format {
+                MacroAttribute => format!("#[{}]", info.callee.name),
+                MacroBang => format!("{}!", info.callee.name),
+            })
+        });
+        let name = match name {
+            Some(name) => name,
+            None => return,
+        };
+        let stats = match self.by_macro.entry(name) {
+            Occupied(entry) => entry.into_mut(),
+            Vacant(entry) => entry.set(MacroStats {
+                nodes: NodeStats::default(),
+                expansions: HashSet::new(),
+            }),
+        };
+        stats.nodes.add(bytes);
+        stats.expansions.insert(sp.expn_id);
+    }
+
+    fn print(&self) {
+        let mut by_kind: Vec<_> = self.by_kind.iter().collect();
+        by_kind.sort_by(|&(_, a), &(_, b)| b.bytes.cmp(&a.bytes));
+        let (mut count, mut bytes) = (0u, 0u);
+        println!("ast-stats: nodes by kind");
+        println!("{:<16} {:>10} {:>12}", "kind", "count", "bytes");
+        for &(kind, stats) in by_kind.iter() {
+            println!("{:<16} {:>10} {:>12}", kind, stats.count, stats.bytes);
+            count += stats.count;
+            bytes += stats.bytes;
+        }
+        println!("{:<16} {:>10} {:>12}", "total", count, bytes);
+
+        let mut by_macro: Vec<_> = self.by_macro.iter().collect();
+        by_macro.sort_by(|&(_, a), &(_, b)| b.nodes.bytes.cmp(&a.nodes.bytes));
+        println!("\nast-stats: nodes by macro");
+        println!("{:<24} {:>11} {:>10} {:>12} {:>10}",
+                 "macro", "expansions", "nodes", "bytes", "nodes/exp");
+        for &(name, stats) in by_macro.iter() {
+            let expansions = stats.expansions.len();
+            println!("{:<24} {:>11} {:>10} {:>12} {:>10.1}",
+                     name, expansions, stats.nodes.count, stats.nodes.bytes,
+                     stats.nodes.count as f64 / expansions as f64);
+        }
+
+        let mut by_module: Vec<_> = self.by_module.iter().collect();
+        by_module.sort_by(|&(_, a), &(_, b)| b.bytes.cmp(&a.bytes));
+        println!("\nast-stats: nodes by module");
+        println!("{:<32} {:>10} {:>12}", "module", "nodes", "bytes");
+        for &(module, stats) in by_module.iter() {
+            println!("{:<32} {:>10} {:>12}", module, stats.count, stats.bytes);
+        }
+    }
+}
+
+impl<'a, 'v> Visitor<'v> for StatsVisitor<'a> {
+    fn visit_item(&mut self, i: &ast::Item) {
+        self.record("Item", i.span, mem::size_of::<ast::Item>());
+        match i.node {
+            ast::ItemMod(_) => {
+                self.module.push(token::get_ident(i.ident).get().to_string());
+                visit::walk_item(self, i);
+                self.module.pop();
+            }
+            _ => visit::walk_item(self, i),
+        }
+    }
+
+    fn visit_view_item(&mut self, i: &ast::ViewItem) {
+        self.record("ViewItem", i.span, mem::size_of::<ast::ViewItem>());
+        visit::walk_view_item(self, i)
+    }
+
+    fn visit_foreign_item(&mut self, i: &ast::ForeignItem) {
+        self.record("ForeignItem", i.span, mem::size_of::<ast::ForeignItem>());
+        visit::walk_foreign_item(self, i)
+    }
+
+    fn visit_local(&mut self, l: &ast::Local) {
+        self.record("Local", l.span, mem::size_of::<ast::Local>());
+        visit::walk_local(self, l)
+    }
+
+    fn visit_block(&mut self, b: &ast::Block) {
+        self.record("Block", b.span, mem::size_of::<ast::Block>());
+        visit::walk_block(self, b)
+    }
+
+    fn visit_stmt(&mut self, s: &ast::Stmt) {
+        self.record("Stmt", s.span, mem::size_of::<ast::Stmt>());
+        visit::walk_stmt(self, s)
+    }
+
+    fn visit_arm(&mut self, a: &ast::Arm) {
+        self.record("Arm", a.body.span, mem::size_of::<ast::Arm>());
+        visit::walk_arm(self, a)
+    }
+
+    fn visit_pat(&mut self, p: &ast::Pat) {
+        self.record("Pat", p.span, mem::size_of::<ast::Pat>());
+        visit::walk_pat(self, p)
+    }
+
+    fn visit_decl(&mut self, d: &ast::Decl) {
+        self.record("Decl", d.span, mem::size_of::<ast::Decl>());
+        visit::walk_decl(self, d)
+    }
+
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        self.record("Expr", e.span, mem::size_of::<ast::Expr>());
+        visit::walk_expr(self, e)
+    }
+
+    fn visit_ty(&mut self, t: &ast::Ty) {
+        self.record("Ty", t.span, mem::size_of::<ast::Ty>());
+        visit::walk_ty(self, t)
+    }
+
+    fn visit_struct_field(&mut self, s: &ast::StructField) {
+        self.record("StructField", s.span, mem::size_of::<ast::StructField>());
+        visit::walk_struct_field(self, s)
+    }
+
+    fn visit_variant(&mut self, v: &ast::Variant, g: &ast::Generics) {
+        self.record("Variant", v.span, mem::size_of::<ast::Variant>());
+        visit::walk_variant(self, v, g)
+    }
+
+    fn visit_path(&mut self, path: &ast::Path, _id: ast::NodeId) {
+        self.record("Path", path.span, mem::size_of::<ast::Path>());
+        visit::walk_path(self, path)
+    }
+
+    fn visit_attribute(&mut self, attr: &ast::Attribute) {
+        self.record("Attribute", attr.span, mem::size_of::<ast::Attribute>());
+    }
+}
+
+/// Prints statistics about the nodes of `krate` to stdout.
+pub fn print_ast_stats(cm: &CodeMap, krate: &ast::Crate, crate_name: &str) {
+    let mut v = StatsVisitor {
+        cm: cm,
+        by_kind: HashMap::new(),
+        by_macro: HashMap::new(),
+        by_module: HashMap::new(),
+        module: vec![crate_name.to_string()],
+    };
+    visit::walk_crate(&mut v, krate);
+    v.print();
+}
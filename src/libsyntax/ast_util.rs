@@ -85,6 +85,15 @@ pub fn is_shift_binop(b: BinOp) -> bool {
     }
 }
 
+/// Whether `b` is a comparison. Comparisons don't associate, so
+/// `a == b == c` has to be written with parentheses.
+pub fn is_comparison_binop(b: BinOp) -> bool {
+    match b {
+      BiEq | BiNe | BiLt | BiLe | BiGe | BiGt => true,
+      _ => false
+    }
+}
+
 pub fn unop_to_string(op: UnOp) -> &'static str {
     match op {
       UnUniq => "box() ",
@@ -250,6 +259,10 @@ pub fn trait_item_to_ty_method(method: &TraitItem) -> TypeMethod {
             panic!("trait_method_to_ty_method(): expected method but found \
                    typedef")
         }
+        ConstTraitItem(_) => {
+            panic!("trait_method_to_ty_method(): expected method but found \
+                   const")
+        }
     }
 }
 
@@ -261,7 +274,7 @@ pub fn split_trait_methods(trait_methods: &[TraitItem])
         match *trt_method {
             RequiredMethod(ref tm) => reqd.push((*tm).clone()),
             ProvidedMethod(ref m) => provd.push((*m).clone()),
-            TypeTraitItem(_) => {}
+            TypeTraitItem(_) | ConstTraitItem(_) => {}
         }
     };
     (reqd, provd)
@@ -295,6 +308,29 @@ pub fn operator_prec(op: ast::BinOp) -> uint {
 #[allow(non_upper_case_globals)]
 pub static as_prec: uint = 12u;
 
+/// Precedence of the prefix operators (`-`, `!`, `*`, `&` and `box`)
+pub const PREC_PREFIX: uint = 13u;
+
+/// Precedence of the postfix operators (calls, method calls, field access,
+/// indexing and `?`), and of expressions that can't be split up, like
+/// paths, literals and blocks
+pub const PREC_POSTFIX: uint = 14u;
+
+/// Returns how tightly `e` binds, on the scale of `operator_prec`. An
+/// operand of an operator of precedence `p` needs parentheses if its own
+/// precedence is lower than `p`. Expressions that extend as far to the
+/// right as they can, like assignments, closures and `return`, have 0.
+pub fn expr_precedence(e: &Expr) -> uint {
+    match e.node {
+        ExprBinary(op, _, _) => operator_prec(op),
+        ExprCast(..) | ExprType(..) => as_prec,
+        ExprUnary(..) | ExprAddrOf(..) | ExprBox(..) => PREC_PREFIX,
+        ExprAssign(..) | ExprAssignOp(..) | ExprClosure(..) | ExprProc(..) |
+        ExprRet(..) | ExprBreak(..) | ExprAgain(..) | ExprInclusiveRange(..) => 0u,
+        _ => PREC_POSTFIX,
+    }
+}
+
 pub fn empty_generics() -> Generics {
     Generics {
         lifetimes: Vec::new(),
@@ -412,10 +448,20 @@ impl<'a, 'v, O: IdVisitingOperation> Visitor<'v> for IdVisitor<'a, O> {
         }
 
         self.operation.visit_id(item.id);
-        if let ItemEnum(ref enum_definition, _) = item.node {
-            for variant in enum_definition.variants.iter() {
-                self.operation.visit_id(variant.node.id)
+        match item.node {
+            ItemEnum(ref enum_definition, _) => {
+                for variant in enum_definition.variants.iter() {
+                    self.operation.visit_id(variant.node.id)
+                }
             }
+            ItemImpl(_, _, _, ref impl_items) => {
+                for impl_item in impl_items.iter() {
+                    if let ConstImplItem(ref associated_const) = *impl_item {
+                        self.operation.visit_id(associated_const.id)
+                    }
+                }
+            }
+            _ => {}
         }
 
         visit::walk_item(self, item);
@@ -522,6 +568,7 @@ impl<'a, 'v, O: IdVisitingOperation> Visitor<'v> for IdVisitor<'a, O> {
             ast::RequiredMethod(ref m) => self.operation.visit_id(m.id),
             ast::ProvidedMethod(ref m) => self.operation.visit_id(m.id),
             ast::TypeTraitItem(ref typ) => self.operation.visit_id(typ.ty_param.id),
+            ast::ConstTraitItem(ref c) => self.operation.visit_id(c.id),
         }
         visit::walk_trait_item(self, tm);
     }
@@ -543,6 +590,9 @@ pub fn visit_ids_for_inlined_item<O: IdVisitingOperation>(item: &InlinedItem,
         visited_outermost: false,
     };
 
+    if let IIImplItem(_, ConstImplItem(ref associated_const)) = *item {
+        id_visitor.operation.visit_id(associated_const.id);
+    }
     visit::walk_inlined_item(&mut id_visitor, item);
 }
 
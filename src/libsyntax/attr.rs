@@ -247,6 +247,73 @@ pub fn last_meta_item_value_str_by_name(items: &[P<MetaItem>], name: &str)
          .and_then(|i| i.value_str())
 }
 
+/* Typed accessors */
+
+/// A meta item that doesn't have the shape its attribute calls for, along
+/// with where it is.
+#[deriving(Clone, PartialEq, Show)]
+pub struct MetaError {
+    pub span: Span,
+    pub msg: String,
+}
+
+impl MetaError {
+    fn new(span: Span, msg: String) -> MetaError {
+        MetaError { span: span, msg: msg }
+    }
+
+    pub fn emit(&self, diagnostic: &SpanHandler) {
+        diagnostic.span_err(self.span, self.msg.as_slice());
+    }
+}
+
+pub type MetaResult<T> = Result<T, MetaError>;
+
+/// The name of `mi`, which must be a bare word like the `C` in `repr(C)`.
+pub fn expect_word(mi: &MetaItem) -> MetaResult<InternedString> {
+    match mi.node {
+        MetaWord(ref name) => Ok(name.clone()),
+        _ => Err(MetaError::new(mi.span, format!("expected a word, found `{}`", mi.name())))
+    }
+}
+
+/// The items of `mi`, which must be a list like `inline(always)`.
+pub fn expect_list<'a>(mi: &'a MetaItem) -> MetaResult<&'a [P<MetaItem>]> {
+    match mi.node {
+        MetaList(_, ref items) => Ok(items.as_slice()),
+        _ => Err(MetaError::new(mi.span, format!("expected `{}(...)`", mi.name())))
+    }
+}
+
+/// The value of `mi`, which must be a name with a string value like
+/// `crate_name = "foo"`.
+pub fn expect_value_str(mi: &MetaItem) -> MetaResult<InternedString> {
+    match mi.value_str() {
+        Some(value) => Ok(value),
+        None => Err(MetaError::new(mi.span, format!("expected `{} = \"...\"`", mi.name())))
+    }
+}
+
+/// The names of the items of the list `mi`, all of which must be words.
+pub fn expect_list_of_words(mi: &MetaItem) -> MetaResult<Vec<(InternedString, Span)>> {
+    let items = try!(expect_list(mi));
+    items.iter().map(|item| expect_word(&**item).map(|word| (word, item.span))).collect()
+}
+
+/// Finds the first attribute named `name`, marking it used.
+pub fn find_by_name<'a>(attrs: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    attrs.iter().find(|attr| attr.check_name(name))
+}
+
+/// The value of the first `#[name = "..."]` attribute, if there is one.
+/// Attributes named `name` of another shape are an error.
+pub fn value_str_of(attrs: &[Attribute], name: &str) -> MetaResult<Option<InternedString>> {
+    match find_by_name(attrs, name) {
+        Some(attr) => expect_value_str(attr.meta()).map(Some),
+        None => Ok(None)
+    }
+}
+
 /* Higher-level applications */
 
 pub fn sort_meta_items(items: Vec<P<MetaItem>>) -> Vec<P<MetaItem>> {
@@ -323,7 +390,8 @@ pub fn cfg_matches(diagnostic: &SpanHandler, cfgs: &[P<MetaItem>], cfg: &ast::Me
             mis.iter().all(|mi| cfg_matches(diagnostic, cfgs, &**mi)),
         ast::MetaList(ref pred, ref mis) if pred.get() == "not" => {
             if mis.len() != 1 {
-                diagnostic.span_err(cfg.span, "expected 1 cfg-pattern");
+                let sp = mis.get(1).map_or(cfg.span, |mi| mi.span);
+                diagnostic.span_err(sp, "expected 1 cfg-pattern");
                 return false;
             }
             !cfg_matches(diagnostic, cfgs, &*mis[0])
@@ -336,11 +404,14 @@ pub fn cfg_matches(diagnostic: &SpanHandler, cfgs: &[P<MetaItem>], cfg: &ast::Me
     }
 }
 
-/// Represents the #[deprecated="foo"] and friends attributes.
+/// Represents the #[deprecated="foo"] and friends attributes, also written
+/// as #[deprecated(note="foo", replacement="bar")].
 #[deriving(Encodable,Decodable,Clone,Show)]
 pub struct Stability {
     pub level: StabilityLevel,
-    pub text: Option<InternedString>
+    pub text: Option<InternedString>,
+    /// The path to use instead of the item
+    pub replacement: Option<InternedString>,
 }
 
 /// The available stability levels.
@@ -370,14 +441,24 @@ pub fn find_stability_generic<'a,
             _ => continue // not a stability level
         };
 
+        let (text, replacement) = match attr.meta_item_list() {
+            Some(items) => (stability_field(items, "note"), stability_field(items, "replacement")),
+            None => (attr.value_str(), None),
+        };
+
         return Some((Stability {
             level: level,
-                text: attr.value_str()
-            }, attr));
+            text: text,
+            replacement: replacement,
+        }, attr));
     }
     None
 }
 
+fn stability_field(items: &[P<MetaItem>], field: &str) -> Option<InternedString> {
+    items.iter().find(|item| item.name().equiv(&field)).and_then(|item| item.value_str())
+}
+
 /// Find the first stability attribute. `None` if none exists.
 pub fn find_stability(attrs: &[Attribute]) -> Option<Stability> {
     find_stability_generic(attrs.iter()).map(|(s, attr)| {
@@ -386,6 +467,34 @@ pub fn find_stability(attrs: &[Attribute]) -> Option<Stability> {
     })
 }
 
+/// Like `find_stability`, but reports the elements of a stability attribute
+/// written as a list, like `#[deprecated(reason)]`, that aren't the string
+/// fields `note` and `replacement`.
+pub fn find_stability_checked(diagnostic: &SpanHandler, attrs: &[Attribute])
+                              -> Option<Stability> {
+    find_stability_generic(attrs.iter()).map(|(s, attr)| {
+        mark_used(attr);
+        if let MetaList(ref name, ref items) = attr.node.value.node {
+            if items.is_empty() {
+                diagnostic.span_err(attr.node.value.span,
+                                    format!("expected `#[{} = \"...\"]`", name).as_slice());
+            }
+            for item in items.iter() {
+                let field = item.name();
+                if (field.equiv(&("note")) || field.equiv(&("replacement"))) &&
+                   item.value_str().is_some() {
+                    continue;
+                }
+                diagnostic.span_err(item.span,
+                                    format!("expected `#[{} = \"...\"]`, or the fields \
+                                             `note = \"...\"` and `replacement = \"...\"`",
+                                            name).as_slice());
+            }
+        }
+        s
+    })
+}
+
 pub fn require_unique_names(diagnostic: &SpanHandler, metas: &[P<MetaItem>]) {
     let mut set = HashSet::new();
     for meta in metas.iter() {
@@ -412,8 +521,8 @@ pub fn find_repr_attrs(diagnostic: &SpanHandler, attr: &Attribute) -> Vec<ReprAt
         ast::MetaList(ref s, ref items) if s.equiv(&("repr")) => {
             mark_used(attr);
             for item in items.iter() {
-                match item.node {
-                    ast::MetaWord(ref word) => {
+                match expect_word(&**item) {
+                    Ok(ref word) => {
                         let hint = match word.get() {
                             // Can't use "extern" because it's not a lexical identifier.
                             "C" => Some(ReprExtern),
@@ -435,7 +544,9 @@ pub fn find_repr_attrs(diagnostic: &SpanHandler, attr: &Attribute) -> Vec<ReprAt
                         }
                     }
                     // Not a word:
-                    _ => diagnostic.span_err(item.span, "unrecognized enum representation hint")
+                    Err(_) => {
+                        diagnostic.span_err(item.span, "unrecognized enum representation hint")
+                    }
                 }
             }
         }
@@ -445,6 +556,11 @@ pub fn find_repr_attrs(diagnostic: &SpanHandler, attr: &Attribute) -> Vec<ReprAt
     acc
 }
 
+/// The representation hints of all the `#[repr(...)]` attributes in `attrs`.
+pub fn find_repr(diagnostic: &SpanHandler, attrs: &[Attribute]) -> Vec<ReprAttr> {
+    attrs.iter().flat_map(|attr| find_repr_attrs(diagnostic, attr).into_iter()).collect()
+}
+
 fn int_type_of_word(s: &str) -> Option<IntType> {
     match s {
         "i8" => Some(SignedInt(ast::TyI8)),
@@ -504,3 +620,87 @@ impl IntType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ast::MetaItem;
+    use parse::token::InternedString;
+    use parse::token;
+    use ptr::P;
+
+    fn s(string: &str) -> InternedString {
+        token::get_name(token::intern(string))
+    }
+
+    fn word(name: &str) -> P<MetaItem> {
+        mk_word_item(s(name))
+    }
+
+    fn list(name: &str, items: Vec<P<MetaItem>>) -> P<MetaItem> {
+        mk_list_item(s(name), items)
+    }
+
+    fn name_value(name: &str, value: &str) -> P<MetaItem> {
+        mk_name_value_item_str(s(name), s(value))
+    }
+
+    #[test]
+    fn test_expect_word() {
+        assert_eq!(expect_word(&*word("C")), Ok(s("C")));
+        assert!(expect_word(&*list("repr", vec![word("C")])).is_err());
+        assert!(expect_word(&*name_value("crate_name", "foo")).is_err());
+    }
+
+    #[test]
+    fn test_expect_list() {
+        let items = list("inline", vec![word("always")]);
+        let names: Vec<InternedString> =
+            expect_list(&*items).unwrap().iter().map(|mi| mi.name()).collect();
+        assert_eq!(names, vec![s("always")]);
+        assert!(expect_list(&*list("inline", vec![])).unwrap().is_empty());
+        assert_eq!(expect_list(&*word("inline")).unwrap_err().msg,
+                   "expected `inline(...)`".to_string());
+    }
+
+    #[test]
+    fn test_expect_value_str() {
+        assert_eq!(expect_value_str(&*name_value("crate_name", "foo")), Ok(s("foo")));
+        assert_eq!(expect_value_str(&*word("crate_name")).unwrap_err().msg,
+                   "expected `crate_name = \"...\"`".to_string());
+    }
+
+    #[test]
+    fn test_expect_list_of_words() {
+        let feature = list("feature", vec![word("asm"), word("globs")]);
+        let words: Vec<InternedString> =
+            expect_list_of_words(&*feature).unwrap().into_iter().map(|(w, _)| w).collect();
+        assert_eq!(words, vec![s("asm"), s("globs")]);
+
+        let feature = list("feature", vec![word("asm"), name_value("globs", "yes")]);
+        assert_eq!(expect_list_of_words(&*feature).unwrap_err().msg,
+                   "expected a word, found `globs`".to_string());
+        assert!(expect_list_of_words(&*word("feature")).is_err());
+    }
+
+    #[test]
+    fn test_find_by_name() {
+        let attrs = vec![mk_attr_outer(mk_attr_id(), word("inline")),
+                         mk_attr_outer(mk_attr_id(), name_value("doc", "first")),
+                         mk_attr_outer(mk_attr_id(), name_value("doc", "second"))];
+        assert_eq!(find_by_name(attrs.as_slice(), "doc").and_then(|a| a.value_str()),
+                   Some(s("first")));
+        assert!(is_used(&attrs[1]));
+        assert!(!is_used(&attrs[2]));
+        assert!(find_by_name(attrs.as_slice(), "cold").is_none());
+    }
+
+    #[test]
+    fn test_value_str_of() {
+        let attrs = vec![mk_attr_inner(mk_attr_id(), name_value("crate_name", "foo")),
+                         mk_attr_inner(mk_attr_id(), word("no_std"))];
+        assert_eq!(value_str_of(attrs.as_slice(), "crate_name"), Ok(Some(s("foo"))));
+        assert_eq!(value_str_of(attrs.as_slice(), "crate_type"), Ok(None));
+        assert!(value_str_of(attrs.as_slice(), "no_std").is_err());
+    }
+}
@@ -21,6 +21,10 @@ pub use self::MacroFormat::*;
 
 use serialize::{Encodable, Decodable, Encoder, Decoder};
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash;
+use std::io::File;
+use std::mem;
 use std::rc::Rc;
 use libc::c_uint;
 
@@ -107,10 +111,60 @@ impl PartialEq for Span {
 
 impl Eq for Span {}
 
+impl Span {
+    /// The part of the span from `lo` to `hi` bytes past its start, to
+    /// point at the offending characters within a longer span.
+    pub fn sub_span(&self, lo: uint, hi: uint) -> Span {
+        assert!(lo <= hi && self.lo.to_uint() + hi <= self.hi.to_uint());
+        Span {
+            lo: self.lo + Pos::from_uint(lo),
+            hi: self.lo + Pos::from_uint(hi),
+            expn_id: self.expn_id
+        }
+    }
+}
+
+thread_local!(static SPAN_ENCODING_CODEMAP: RefCell<Option<CodeMap>> = RefCell::new(None))
+
+/// Runs `f` with span encoding resolved against the files of `cm`: spans
+/// encoded while `f` runs are emitted as their file name plus 1-based start
+/// and end line/column, rather than as nil. Used to dump the AST as JSON.
+pub fn with_span_locations<T>(cm: &CodeMap, f: || -> T) -> T {
+    let resolver = CodeMap {
+        files: RefCell::new(cm.files.borrow().clone()),
+        expansions: RefCell::new(Vec::new()),
+        expansion_ids: RefCell::new(HashMap::new()),
+        path_mapping: FilePathMapping::empty(),
+    };
+    let prev = SPAN_ENCODING_CODEMAP.with(|slot| {
+        mem::replace(&mut *slot.borrow_mut(), Some(resolver))
+    });
+    let ret = f();
+    SPAN_ENCODING_CODEMAP.with(|slot| *slot.borrow_mut() = prev);
+    ret
+}
+
 impl<S:Encoder<E>, E> Encodable<S, E> for Span {
     /* Note #1972 -- spans are encoded but not decoded */
     fn encode(&self, s: &mut S) -> Result<(), E> {
-        s.emit_nil()
+        SPAN_ENCODING_CODEMAP.with(|slot| {
+            match *slot.borrow() {
+                Some(ref cm) if *self != DUMMY_SP && !cm.files.borrow().is_empty() => {
+                    let lo = cm.lookup_char_pos(self.lo);
+                    let hi = cm.lookup_char_pos(self.hi);
+                    s.emit_struct("Span", 5, |s| {
+                        try!(s.emit_struct_field("file", 0, |s| lo.file.name.encode(s)));
+                        try!(s.emit_struct_field("lo_line", 1, |s| lo.line.encode(s)));
+                        try!(s.emit_struct_field("lo_col", 2, |s| {
+                            (lo.col.to_uint() + 1).encode(s)
+                        }));
+                        try!(s.emit_struct_field("hi_line", 3, |s| hi.line.encode(s)));
+                        s.emit_struct_field("hi_col", 4, |s| (hi.col.to_uint() + 1).encode(s))
+                    })
+                }
+                _ => s.emit_nil()
+            }
+        })
     }
 }
 
@@ -132,11 +186,39 @@ pub fn dummy_spanned<T>(t: T) -> Spanned<T> {
     respan(DUMMY_SP, t)
 }
 
+/// A primary span plus secondary spans labeled with the part they play,
+/// for diagnostics about several places at once, like the two halves of a
+/// conflict. Labels near the primary span are rendered in its snippet, the
+/// others as notes of their own.
+#[deriving(Clone, Show)]
+pub struct MultiSpan {
+    pub primary: Span,
+    pub labels: Vec<(Span, String)>,
+}
+
+impl MultiSpan {
+    pub fn new(primary: Span) -> MultiSpan {
+        MultiSpan {
+            primary: primary,
+            labels: Vec::new(),
+        }
+    }
+
+    pub fn push_label(&mut self, sp: Span, label: String) {
+        self.labels.push((sp, label));
+    }
+}
+
 /* assuming that we're not in macro expansion */
 pub fn mk_sp(lo: BytePos, hi: BytePos) -> Span {
     Span {lo: lo, hi: hi, expn_id: NO_EXPANSION}
 }
 
+/// Whether the source `inner` points at lies within `outer`.
+pub fn span_contains(outer: Span, inner: Span) -> bool {
+    outer.lo <= inner.lo && inner.hi <= outer.hi
+}
+
 /// Return the span itself if it doesn't come from a macro expansion,
 /// otherwise return the call site span up to the `enclosing_sp` by
 /// following the `expn_info` chain.
@@ -157,7 +239,10 @@ pub struct Loc {
     /// The (1-based) line number
     pub line: uint,
     /// The (0-based) column offset
-    pub col: CharPos
+    pub col: CharPos,
+    /// The (0-based) column the location is displayed at, which differs
+    /// from `col` after tabs and after wide or zero-width characters
+    pub col_display: uint,
 }
 
 /// A source code location used as the result of lookup_char_pos_adj
@@ -175,7 +260,7 @@ pub struct FileMapAndLine { pub fm: Rc<FileMap>, pub line: uint }
 pub struct FileMapAndBytePos { pub fm: Rc<FileMap>, pub pos: BytePos }
 
 /// The syntax with which a macro was invoked.
-#[deriving(Clone, Hash, Show)]
+#[deriving(Clone, PartialEq, Eq, Hash, Show)]
 pub enum MacroFormat {
     /// e.g. #[deriving(...)] <item>
     MacroAttribute,
@@ -247,6 +332,21 @@ pub struct MultiByteChar {
     pub pos: BytePos,
     /// The number of bytes, >=2
     pub bytes: uint,
+    /// The number of extra bytes taken by this and all earlier multi-byte
+    /// characters in the FileMap
+    pub extra_bytes: uint,
+}
+
+/// The number of columns a tab is displayed as in diagnostics
+pub const TAB_WIDTH: uint = 4;
+
+/// Identifies the offset of a character in a FileMap that isn't displayed
+/// one column wide: a tab, a wide (e.g. CJK) or a zero-width character
+pub struct NonNarrowChar {
+    /// The absolute offset of the character in the CodeMap
+    pub pos: BytePos,
+    /// The number of columns the character is displayed as
+    pub width: uint,
 }
 
 /// A single source in the CodeMap
@@ -255,14 +355,35 @@ pub struct FileMap {
     /// originate from files has names between angle brackets by convention,
     /// e.g. `<anon>`
     pub name: FileName,
-    /// The complete source code
-    pub src: String,
+    /// The name the file was opened under, before any `--remap-path-prefix`
+    /// rewriting of `name`. This is what relative paths (`mod` and
+    /// `include!`) are resolved against.
+    pub unmapped_name: FileName,
+    /// Whether the source was imported from another crate's metadata (like
+    /// the definition of an exported macro) rather than read from disk.
+    pub is_imported: bool,
+    /// For imported source, the name of the crate it was imported from.
+    pub imported_from: Option<String>,
+    /// For imported source that was produced by macro expansion in its own
+    /// crate, the expansions it came from, innermost first, each described
+    /// as `name! at file:line:col`.
+    pub imported_expansion: Vec<String>,
+    /// The complete source code, or `None` if it has been dropped by
+    /// `drop_src` (see `src` for getting it back)
+    src: RefCell<Option<Rc<String>>>,
+    /// The length of the source in bytes, which is kept when it's dropped
+    pub src_len: uint,
+    /// A hash of the source, to check that the file hasn't changed on disk
+    /// when a dropped source is re-read
+    src_hash: u64,
     /// The start position of this source in the CodeMap
     pub start_pos: BytePos,
     /// Locations of lines beginnings in the source code
     pub lines: RefCell<Vec<BytePos> >,
     /// Locations of multi-byte characters in the source code
     pub multibyte_chars: RefCell<Vec<MultiByteChar> >,
+    /// Locations of characters not displayed one column wide
+    pub non_narrow_chars: RefCell<Vec<NonNarrowChar> >,
 }
 
 impl FileMap {
@@ -286,11 +407,15 @@ impl FileMap {
     /// get a line from the list of pre-computed line-beginnings
     ///
     pub fn get_line(&self, line_number: uint) -> Option<String> {
+        let src = match self.src() {
+            Some(src) => src,
+            None => return None,
+        };
         let lines = self.lines.borrow();
         lines.get(line_number).map(|&line| {
             let begin: BytePos = line - self.start_pos;
             let begin = begin.to_uint();
-            let slice = self.src.as_slice().slice_from(begin);
+            let slice = src.as_slice().slice_from(begin);
             match slice.find('\n') {
                 Some(e) => slice.slice_to(e),
                 None => slice
@@ -298,64 +423,230 @@ impl FileMap {
         })
     }
 
+    /// UNCHECKED INVARIANT: multi-byte characters must be recorded in the
+    /// order they appear in the source.
     pub fn record_multibyte_char(&self, pos: BytePos, bytes: uint) {
         assert!(bytes >=2 && bytes <= 4);
+        let mut multibyte_chars = self.multibyte_chars.borrow_mut();
+        let extra_bytes = multibyte_chars.last().map_or(0, |mbc| mbc.extra_bytes);
         let mbc = MultiByteChar {
             pos: pos,
             bytes: bytes,
+            extra_bytes: extra_bytes + bytes - 1,
+        };
+        multibyte_chars.push(mbc);
+    }
+
+    /// Records the character `c` at `pos` if it isn't displayed one column
+    /// wide. Like multi-byte characters, these must be recorded in order.
+    pub fn record_char_width(&self, pos: BytePos, c: char) {
+        let width = match c {
+            '\t' => TAB_WIDTH,
+            c if (c as u32) < 0x80 => return,
+            c => match c.width(false) {
+                Some(1) | None => return,
+                Some(width) => width,
+            },
         };
-        self.multibyte_chars.borrow_mut().push(mbc);
+        self.non_narrow_chars.borrow_mut().push(NonNarrowChar { pos: pos, width: width });
+    }
+
+    /// Converts the position `pos`, which is `col` characters into the line
+    /// starting at `line_pos`, to the column it's displayed at.
+    fn col_display(&self, line_pos: BytePos, pos: BytePos, col: CharPos) -> uint {
+        let chars = self.non_narrow_chars.borrow();
+        // Find the first non-narrow character on the line
+        let mut a = 0u;
+        let mut b = chars.len();
+        while a < b {
+            let m = (a + b) / 2u;
+            if (*chars)[m].pos < line_pos { a = m + 1; } else { b = m; }
+        }
+        chars.slice_from(a).iter()
+             .take_while(|c| c.pos < pos)
+             .fold(col.to_uint(), |col, c| col - 1 + c.width)
     }
 
     pub fn is_real_file(&self) -> bool {
+        !self.is_imported &&
         !(self.name.as_slice().starts_with("<") &&
           self.name.as_slice().ends_with(">"))
     }
+
+    /// Returns the source code, re-reading it from disk if it has been
+    /// dropped. Returns `None` if it can't be re-read, or if the file has
+    /// changed since it was first read.
+    pub fn src(&self) -> Option<Rc<String>> {
+        if let Some(ref src) = *self.src.borrow() {
+            return Some(src.clone());
+        }
+
+        let src = match File::open(&Path::new(self.unmapped_name.as_slice())).read_to_end() {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(src) => normalize_src(src),
+                Err(_) => return None,
+            },
+            Err(_) => return None,
+        };
+        if src.len() != self.src_len || hash::hash(&src) != self.src_hash {
+            return None;
+        }
+        let src = Rc::new(src);
+        *self.src.borrow_mut() = Some(src.clone());
+        Some(src)
+    }
+
+    /// Frees the source code of a real file, which `src` can read back from
+    /// disk on demand. Sources that don't come from files are kept.
+    pub fn drop_src(&self) {
+        if self.is_real_file() {
+            *self.src.borrow_mut() = None;
+        }
+    }
+
+    /// Whether the source code is currently held in memory
+    pub fn is_src_loaded(&self) -> bool {
+        self.src.borrow().is_some()
+    }
+}
+
+/// Removes a utf-8 BOM from `src` and makes sure it ends in a newline, as
+/// all sources in the CodeMap do.
+fn normalize_src(src: String) -> String {
+    // Remove utf-8 BOM if any.
+    // FIXME #12884: no efficient/safe way to remove from the start of a string
+    // and reuse the allocation.
+    let mut src = if src.as_slice().starts_with("\ufeff") {
+        String::from_str(src.as_slice().slice_from(3))
+    } else {
+        src
+    };
+
+    // Append '\n' in case it's not already there.
+    // This is a workaround to prevent CodeMap.lookup_filemap_idx from accidentally
+    // overflowing into the next filemap in case the last byte of span is also the last
+    // byte of filemap, which leads to incorrect results from CodeMap.span_to_*.
+    if src.len() > 0 && !src.as_slice().ends_with("\n") {
+        src.push('\n');
+    }
+    src
+}
+
+/// Rewrites file names starting with one of a list of prefixes, so that
+/// paths specific to the build machine don't leak into the output.
+#[deriving(Clone, Show)]
+pub struct FilePathMapping {
+    mapping: Vec<(String, String)>,
+}
+
+impl FilePathMapping {
+    pub fn empty() -> FilePathMapping {
+        FilePathMapping { mapping: Vec::new() }
+    }
+
+    /// Creates a mapping from `(from, to)` prefix pairs. When several
+    /// prefixes match, the one given last wins.
+    pub fn new(mapping: Vec<(String, String)>) -> FilePathMapping {
+        FilePathMapping { mapping: mapping }
+    }
+
+    /// Applies the mapping to `path`, returning the rewritten path and
+    /// whether any prefix matched.
+    pub fn map_prefix(&self, path: &str) -> (String, bool) {
+        for &(ref from, ref to) in self.mapping.iter().rev() {
+            if path.starts_with(from.as_slice()) {
+                let rest = path.slice_from(from.len());
+                return (format!("{}{}", to, rest), true);
+            }
+        }
+        (path.to_string(), false)
+    }
+}
+
+/// An `ExpnInfo` with its spans split into their fields, as `Span`'s
+/// equality ignores `expn_id`.
+type ExpnKey = ((BytePos, BytePos, ExpnId), String, MacroFormat,
+                Option<(BytePos, BytePos, ExpnId)>);
+
+fn expn_key(info: &ExpnInfo) -> ExpnKey {
+    fn span_key(sp: Span) -> (BytePos, BytePos, ExpnId) { (sp.lo, sp.hi, sp.expn_id) }
+    (span_key(info.call_site),
+     info.callee.name.clone(),
+     info.callee.format.clone(),
+     info.callee.span.map(span_key))
 }
 
 pub struct CodeMap {
     pub files: RefCell<Vec<Rc<FileMap>>>,
-    expansions: RefCell<Vec<ExpnInfo>>
+    expansions: RefCell<Vec<ExpnInfo>>,
+    /// The id of each distinct recorded expansion, so that repeating an
+    /// identical expansion doesn't record it again
+    expansion_ids: RefCell<HashMap<ExpnKey, ExpnId>>,
+    path_mapping: FilePathMapping,
 }
 
 impl CodeMap {
     pub fn new() -> CodeMap {
+        CodeMap::with_file_path_mapping(FilePathMapping::empty())
+    }
+
+    pub fn with_file_path_mapping(path_mapping: FilePathMapping) -> CodeMap {
         CodeMap {
             files: RefCell::new(Vec::new()),
             expansions: RefCell::new(Vec::new()),
+            expansion_ids: RefCell::new(HashMap::new()),
+            path_mapping: path_mapping,
         }
     }
 
+    pub fn path_mapping(&self) -> &FilePathMapping {
+        &self.path_mapping
+    }
+
     pub fn new_filemap(&self, filename: FileName, src: String) -> Rc<FileMap> {
+        self.new_filemap_(filename, src, None, Vec::new())
+    }
+
+    /// Registers source imported from another crate, which started at the
+    /// given 0-based `line` and `col` of `filename` there. The source is
+    /// placed at the same line and column in its own FileMap, so that spans
+    /// into it are reported against the file it really came from.
+    /// `expansion` describes the macro expansions the source was produced
+    /// by there, if any (see `FileMap::imported_expansion`).
+    pub fn new_imported_filemap(&self, filename: FileName, crate_name: String,
+                                line: uint, col: uint,
+                                src: String, expansion: Vec<String>) -> Rc<FileMap> {
+        let mut padded = String::with_capacity(line + col + src.len());
+        for _ in range(0, line) { padded.push('\n'); }
+        for _ in range(0, col) { padded.push(' '); }
+        padded.push_str(src.as_slice());
+        self.new_filemap_(filename, padded, Some(crate_name), expansion)
+    }
+
+    fn new_filemap_(&self, filename: FileName, src: String, imported_from: Option<String>,
+                    imported_expansion: Vec<String>) -> Rc<FileMap> {
         let mut files = self.files.borrow_mut();
         let start_pos = match files.last() {
             None => 0,
-            Some(last) => last.start_pos.to_uint() + last.src.len(),
+            Some(last) => last.start_pos.to_uint() + last.src_len,
         };
 
-        // Remove utf-8 BOM if any.
-        // FIXME #12884: no efficient/safe way to remove from the start of a string
-        // and reuse the allocation.
-        let mut src = if src.as_slice().starts_with("\ufeff") {
-            String::from_str(src.as_slice().slice_from(3))
-        } else {
-            String::from_str(src.as_slice())
-        };
-
-        // Append '\n' in case it's not already there.
-        // This is a workaround to prevent CodeMap.lookup_filemap_idx from accidentally
-        // overflowing into the next filemap in case the last byte of span is also the last
-        // byte of filemap, which leads to incorrect results from CodeMap.span_to_*.
-        if src.len() > 0 && !src.as_slice().ends_with("\n") {
-            src.push('\n');
-        }
+        let src = normalize_src(src);
 
+        let (name, _) = self.path_mapping.map_prefix(filename.as_slice());
         let filemap = Rc::new(FileMap {
-            name: filename,
-            src: src.to_string(),
+            name: name,
+            unmapped_name: filename,
+            is_imported: imported_from.is_some(),
+            imported_from: imported_from,
+            imported_expansion: imported_expansion,
+            src_len: src.len(),
+            src_hash: hash::hash(&src),
+            src: RefCell::new(Some(Rc::new(src))),
             start_pos: Pos::from_uint(start_pos),
             lines: RefCell::new(Vec::new()),
             multibyte_chars: RefCell::new(Vec::new()),
+            non_narrow_chars: RefCell::new(Vec::new()),
         });
 
         files.push(filemap.clone());
@@ -405,6 +696,12 @@ impl CodeMap {
         self.lookup_char_pos(sp.lo).file.name.to_string()
     }
 
+    /// Like `span_to_filename`, but ignores `--remap-path-prefix`, giving a
+    /// path that can be used to find files relative to this one.
+    pub fn span_to_unmapped_filename(&self, sp: Span) -> FileName {
+        self.lookup_char_pos(sp.lo).file.unmapped_name.to_string()
+    }
+
     pub fn span_to_lines(&self, sp: Span) -> FileLines {
         let lo = self.lookup_char_pos(sp.lo);
         let hi = self.lookup_char_pos(sp.hi);
@@ -426,14 +723,15 @@ impl CodeMap {
         if begin.fm.start_pos != end.fm.start_pos {
             None
         } else {
-            Some(begin.fm.src.as_slice().slice(begin.pos.to_uint(),
-                                               end.pos.to_uint()).to_string())
+            begin.fm.src().map(|src| {
+                src.as_slice().slice(begin.pos.to_uint(), end.pos.to_uint()).to_string()
+            })
         }
     }
 
     pub fn get_filemap(&self, filename: &str) -> Rc<FileMap> {
         for fm in self.files.borrow().iter() {
-            if filename == fm.name.as_slice() {
+            if filename == fm.name.as_slice() || filename == fm.unmapped_name.as_slice() {
                 return fm.clone();
             }
         }
@@ -453,22 +751,25 @@ impl CodeMap {
         let files = self.files.borrow();
         let map = &(*files)[idx];
 
-        // The number of extra bytes due to multibyte chars in the FileMap
-        let mut total_extra_bytes = 0;
-
-        for mbc in map.multibyte_chars.borrow().iter() {
-            debug!("{}-byte char at {}", mbc.bytes, mbc.pos);
-            if mbc.pos < bpos {
-                // every character is at least one byte, so we only
-                // count the actual extra bytes.
-                total_extra_bytes += mbc.bytes - 1;
-                // We should never see a byte position in the middle of a
-                // character
-                assert!(bpos.to_uint() >= mbc.pos.to_uint() + mbc.bytes);
-            } else {
-                break;
-            }
+        // Find the last multibyte char before `bpos`, which knows the number
+        // of extra bytes due to it and all multibyte chars before it.
+        let multibyte_chars = map.multibyte_chars.borrow();
+        let mut a = 0u;
+        let mut b = multibyte_chars.len();
+        while a < b {
+            let m = (a + b) / 2u;
+            if (*multibyte_chars)[m].pos < bpos { a = m + 1; } else { b = m; }
         }
+        let total_extra_bytes = if a == 0 {
+            0
+        } else {
+            let mbc = &(*multibyte_chars)[a - 1];
+            debug!("{}-byte char at {}", mbc.bytes, mbc.pos);
+            // We should never see a byte position in the middle of a
+            // character
+            assert!(bpos.to_uint() >= mbc.pos.to_uint() + mbc.bytes);
+            mbc.extra_bytes
+        };
 
         assert!(map.start_pos.to_uint() + total_extra_bytes <= bpos.to_uint());
         CharPos(bpos.to_uint() - map.start_pos.to_uint() - total_extra_bytes)
@@ -541,17 +842,38 @@ impl CodeMap {
                chpos, linechpos);
         debug!("byte is on line: {}", line);
         assert!(chpos >= linechpos);
+        let col = chpos - linechpos;
+        let col_display = f.col_display(linebpos, pos, col);
         Loc {
             file: f,
             line: line,
-            col: chpos - linechpos
+            col: col,
+            col_display: col_display,
         }
     }
 
+    /// Records an expansion, returning its id. An expansion identical to
+    /// one that was already recorded (the same macro invoked from the same
+    /// place, as happens in macro-heavy code) shares its id.
     pub fn record_expansion(&self, expn_info: ExpnInfo) -> ExpnId {
+        let key = expn_key(&expn_info);
+        if let Some(&id) = self.expansion_ids.borrow().get(&key) {
+            return id;
+        }
         let mut expansions = self.expansions.borrow_mut();
         expansions.push(expn_info);
-        ExpnId(expansions.len().to_u32().expect("too many ExpnInfo's!") - 1)
+        let id = ExpnId(expansions.len().to_u32().expect("too many ExpnInfo's!") - 1);
+        self.expansion_ids.borrow_mut().insert(key, id);
+        id
+    }
+
+    /// Drops the source code of every real file, to be re-read from disk
+    /// if it's needed again (see `FileMap::src`). All lexing of the files
+    /// must be finished.
+    pub fn drop_sources(&self) {
+        for fm in self.files.borrow().iter() {
+            fm.drop_src();
+        }
     }
 
     pub fn with_expn_info<T>(&self, id: ExpnId, f: |Option<&ExpnInfo>| -> T) -> T {
@@ -560,6 +882,138 @@ impl CodeMap {
             ExpnId(i) => f(Some(&(*self.expansions.borrow())[i as uint]))
         }
     }
+
+    /// Whether `sp` was produced by a macro expansion rather than written
+    /// directly in the source.
+    pub fn is_from_expansion(&self, sp: Span) -> bool {
+        sp.expn_id != NO_EXPANSION
+    }
+
+    /// Whether `sp` points into the definition of a macro it was expanded
+    /// from, i.e. the code was written by the macro. Code passed to a macro
+    /// as an argument is from an expansion too, but keeps its span in the
+    /// source it was written in.
+    pub fn is_from_macro_def(&self, sp: Span) -> bool {
+        self.macro_backtrace(sp).iter().any(|level| match level.def_site_span {
+            Some(def_sp) => span_contains(def_sp, sp),
+            None => false,
+        })
+    }
+
+    /// Returns the span of the outermost macro invocation that `sp` was
+    /// (possibly indirectly) expanded from, which is the code the user
+    /// actually wrote, or `sp` itself if it isn't from an expansion.
+    pub fn outer_call_site(&self, sp: Span) -> Span {
+        let mut sp = sp;
+        // As in `macro_backtrace`, the chain can't be longer than the number
+        // of recorded expansions.
+        let mut limit = self.expansions.borrow().len();
+        while sp.expn_id != NO_EXPANSION && limit > 0 {
+            limit -= 1;
+            sp = match self.with_expn_info(sp.expn_id, |info| info.map(|info| info.call_site)) {
+                Some(call_site) => call_site,
+                None => break,
+            };
+        }
+        sp
+    }
+
+    /// Whether `sp` was produced by a macro that isn't defined in this
+    /// crate's source: one built into the compiler, or one imported from
+    /// another crate. The user can't change the code such a macro expands
+    /// to, so lints should usually leave it alone.
+    ///
+    /// Built-in macros have no source of their own, so the code they
+    /// generate is given the span of their invocation; the arguments the
+    /// user passed them keep spans strictly inside it, and aren't external.
+    /// Likewise, only the code written in the definition of an imported
+    /// macro is external, not the arguments it was passed.
+    pub fn is_from_external_macro(&self, sp: Span) -> bool {
+        self.with_expn_info(sp.expn_id, |info| match info {
+            None => false,
+            Some(info) => match info.callee.span {
+                None => {
+                    let call_site = info.call_site;
+                    let whole = call_site.lo == sp.lo && call_site.hi == sp.hi;
+                    !span_contains(call_site, sp) || whole
+                }
+                Some(def_sp) => {
+                    self.lookup_char_pos(def_sp.lo).file.is_imported && span_contains(def_sp, sp)
+                }
+            },
+        })
+    }
+
+    /// For a span into the source of a macro imported from another crate,
+    /// the invocation in this crate's code that the expansion came from,
+    /// along with the name of the macro invoked there and the crate that
+    /// defines it. `None` for spans into this crate's own source.
+    pub fn imported_macro_call_site(&self, sp: Span)
+                                    -> Option<(Span, String, Option<String>)> {
+        let mut sp = sp;
+        let mut invoked = None;
+        let mut limit = self.expansions.borrow().len();
+        while self.lookup_char_pos(sp.lo).file.is_imported {
+            if sp.expn_id == NO_EXPANSION || limit == 0 {
+                return None;
+            }
+            limit -= 1;
+            let (call_site, name, krate) = self.with_expn_info(sp.expn_id, |info| {
+                let info = info.unwrap();
+                let name = match info.callee.format {
+                    MacroAttribute => format!("#[{}]", info.callee.name),
+                    MacroBang => format!("{}!", info.callee.name),
+                };
+                let krate = info.callee.span.and_then(|def_sp| {
+                    self.lookup_char_pos(def_sp.lo).file.imported_from.clone()
+                });
+                (info.call_site, name, krate)
+            });
+            invoked = Some((name, krate));
+            sp = call_site;
+        }
+        invoked.map(|(name, krate)| (sp, name, krate))
+    }
+
+    /// Returns the complete chain of macro invocations that `sp` was
+    /// expanded from, innermost first.
+    pub fn macro_backtrace(&self, sp: Span) -> Vec<MacroBacktrace> {
+        let mut backtrace = Vec::new();
+        let mut id = sp.expn_id;
+        // Each expansion is recorded before any expansion it contains, so
+        // the ids strictly decrease along a well-formed chain.
+        let mut limit = self.expansions.borrow().len();
+        while id != NO_EXPANSION && limit > 0 {
+            limit -= 1;
+            id = self.with_expn_info(id, |info| {
+                let info = info.unwrap();
+                let (pre, post) = match info.callee.format {
+                    MacroAttribute => ("#[", "]"),
+                    MacroBang => ("", "!")
+                };
+                backtrace.push(MacroBacktrace {
+                    call_site: info.call_site,
+                    macro_decl_name: format!("{}{}{}", pre, info.callee.name, post),
+                    def_site_span: info.callee.span,
+                });
+                info.call_site.expn_id
+            });
+        }
+        backtrace
+    }
+}
+
+/// One frame of a macro backtrace: a macro invocation some span was
+/// (possibly indirectly) expanded from.
+#[deriving(Clone, Show)]
+pub struct MacroBacktrace {
+    /// The span of the invocation itself, e.g. `foo!(...)`.
+    pub call_site: Span,
+    /// The name of the macro as written at the invocation, e.g. `foo!`
+    /// or `#[deriving]`.
+    pub macro_decl_name: String,
+    /// The span of the macro's definition, if it has one.
+    pub def_site_span: Option<Span>,
 }
 
 #[cfg(test)]
@@ -694,6 +1148,29 @@ mod test {
         assert_eq!(cp4, CharPos(15));
     }
 
+    #[test]
+    fn t_col_display() {
+        // Test display columns after tabs and wide chars
+        let cm = CodeMap::new();
+        // 漢 and 字 are three byte utf8 chars, each two columns wide.
+        let fm = cm.new_filemap("blork.rs".to_string(),
+                                "\tlet 漢字 = 1;".to_string());
+        fm.next_line(BytePos(0));
+        fm.record_char_width(BytePos(0), '\t');
+        fm.record_multibyte_char(BytePos(5), 3);
+        fm.record_char_width(BytePos(5), '漢');
+        fm.record_multibyte_char(BytePos(8), 3);
+        fm.record_char_width(BytePos(8), '字');
+
+        let loc1 = cm.lookup_char_pos(BytePos(5));
+        assert_eq!(loc1.col, CharPos(5));
+        assert_eq!(loc1.col_display, TAB_WIDTH + 4);
+
+        let loc2 = cm.lookup_char_pos(BytePos(12));
+        assert_eq!(loc2.col, CharPos(8));
+        assert_eq!(loc2.col_display, TAB_WIDTH + 4 + 4 + 1);
+    }
+
     #[test]
     fn t7() {
         // Test span_to_lines for a span ending at the end of filemap
@@ -725,4 +1202,163 @@ mod test {
 
         assert_eq!(sstr, "blork.rs:2:1: 2:12".to_string());
     }
+
+    #[test]
+    fn t_remap_path_prefix() {
+        let mapping = FilePathMapping::new(vec![
+            ("/home/build/".to_string(), "/rust/".to_string()),
+            ("/home/build/src/".to_string(), "".to_string()),
+        ]);
+        assert_eq!(mapping.map_prefix("/home/build/lib.rs"), ("/rust/lib.rs".to_string(), true));
+        assert_eq!(mapping.map_prefix("/home/build/src/a.rs"), ("a.rs".to_string(), true));
+        assert_eq!(mapping.map_prefix("/tmp/b.rs"), ("/tmp/b.rs".to_string(), false));
+
+        let cm = CodeMap::with_file_path_mapping(mapping);
+        let fm = cm.new_filemap("/home/build/lib.rs".to_string(), "fn main() {}".to_string());
+        assert_eq!(fm.name, "/rust/lib.rs".to_string());
+        assert_eq!(fm.unmapped_name, "/home/build/lib.rs".to_string());
+        assert_eq!(cm.get_filemap("/home/build/lib.rs").name, "/rust/lib.rs".to_string());
+    }
+
+    #[test]
+    fn t_imported_filemap() {
+        let cm = init_code_map();
+        let fm = cm.new_imported_filemap("macros.rs".to_string(), "mac".to_string(), 2, 4,
+                                         "macro_rules! m ( () => () )".to_string(),
+                                         Vec::new());
+        assert!(!fm.is_real_file());
+        let start = fm.start_pos + BytePos(2 + 4);
+        let span = Span {lo: start, hi: start + BytePos(12), expn_id: NO_EXPANSION};
+        assert_eq!(cm.span_to_string(span), "macros.rs:3:5: 3:17".to_string());
+    }
+
+    #[test]
+    fn t10() {
+        // Test macro_backtrace through nested expansions
+        let cm = init_code_map();
+        let outer = cm.record_expansion(ExpnInfo {
+            call_site: Span {lo: BytePos(12), hi: BytePos(18), expn_id: NO_EXPANSION},
+            callee: NameAndSpan {
+                name: "foo".to_string(),
+                format: MacroBang,
+                span: Some(Span {lo: BytePos(0), hi: BytePos(5), expn_id: NO_EXPANSION}),
+            },
+        });
+        let inner = cm.record_expansion(ExpnInfo {
+            call_site: Span {lo: BytePos(1), hi: BytePos(4), expn_id: outer},
+            callee: NameAndSpan {
+                name: "deriving".to_string(),
+                format: MacroAttribute,
+                span: None,
+            },
+        });
+        let span = Span {lo: BytePos(2), hi: BytePos(3), expn_id: inner};
+        let backtrace = cm.macro_backtrace(span);
+
+        assert_eq!(backtrace.len(), 2);
+        assert_eq!(backtrace[0].macro_decl_name, "#[deriving]".to_string());
+        assert_eq!(backtrace[0].call_site.lo, BytePos(1));
+        assert_eq!(backtrace[0].def_site_span, None);
+        assert_eq!(backtrace[1].macro_decl_name, "foo!".to_string());
+        assert_eq!(backtrace[1].call_site.lo, BytePos(12));
+        assert!(cm.macro_backtrace(backtrace[1].call_site).is_empty());
+    }
+
+    #[test]
+    fn t_span_source() {
+        let cm = init_code_map();
+        let fm = cm.new_imported_filemap("macros.rs".to_string(), "mac".to_string(), 0, 0,
+                                         "macro_rules! m ( () => () )".to_string(),
+                                         Vec::new());
+        fm.next_line(fm.start_pos);
+        let written = Span {lo: BytePos(12), hi: BytePos(18), expn_id: NO_EXPANSION};
+        let local = cm.record_expansion(ExpnInfo {
+            call_site: written,
+            callee: NameAndSpan {
+                name: "foo".to_string(),
+                format: MacroBang,
+                span: Some(Span {lo: BytePos(0), hi: BytePos(5), expn_id: NO_EXPANSION}),
+            },
+        });
+        let external = cm.record_expansion(ExpnInfo {
+            call_site: Span {lo: BytePos(1), hi: BytePos(4), expn_id: local},
+            callee: NameAndSpan {
+                name: "m".to_string(),
+                format: MacroBang,
+                span: Some(Span {lo: fm.start_pos, hi: fm.start_pos + BytePos(5),
+                                 expn_id: NO_EXPANSION}),
+            },
+        });
+
+        assert!(!cm.is_from_expansion(written));
+        assert_eq!(cm.outer_call_site(written), written);
+        assert!(!cm.is_from_external_macro(written));
+
+        let span = Span {lo: BytePos(2), hi: BytePos(3), expn_id: local};
+        assert!(cm.is_from_expansion(span));
+        assert!(!cm.is_from_external_macro(span));
+        assert_eq!(cm.outer_call_site(span), written);
+
+        let span = Span {lo: BytePos(2), hi: BytePos(3), expn_id: external};
+        assert!(!cm.is_from_external_macro(span));
+        assert_eq!(cm.outer_call_site(span).lo, BytePos(12));
+        let span = Span {lo: fm.start_pos + BytePos(1), hi: fm.start_pos + BytePos(3),
+                         expn_id: external};
+        assert!(cm.is_from_external_macro(span));
+        assert!(cm.imported_macro_call_site(span).is_none());
+
+        let builtin = cm.record_expansion(ExpnInfo {
+            call_site: written,
+            callee: NameAndSpan {
+                name: "println".to_string(),
+                format: MacroBang,
+                span: None,
+            },
+        });
+        let span = Span {lo: BytePos(12), hi: BytePos(18), expn_id: builtin};
+        assert!(cm.is_from_external_macro(span));
+        let span = Span {lo: BytePos(14), hi: BytePos(16), expn_id: builtin};
+        assert!(!cm.is_from_external_macro(span));
+
+        let span = Span {lo: fm.start_pos + BytePos(17), hi: fm.start_pos + BytePos(19),
+                         expn_id: external};
+        let (call_site, name, krate) = cm.imported_macro_call_site(span).unwrap();
+        assert_eq!(call_site.lo, BytePos(1));
+        assert_eq!(call_site.expn_id, local);
+        assert_eq!(name, "m!".to_string());
+        assert_eq!(krate, Some("mac".to_string()));
+    }
+
+    #[test]
+    fn t_repeated_expansion() {
+        let cm = init_code_map();
+        let info = || ExpnInfo {
+            call_site: Span {lo: BytePos(12), hi: BytePos(18), expn_id: NO_EXPANSION},
+            callee: NameAndSpan {
+                name: "foo".to_string(),
+                format: MacroBang,
+                span: None,
+            },
+        };
+        let first = cm.record_expansion(info());
+        assert_eq!(cm.record_expansion(info()), first);
+        let mut nested = info();
+        nested.call_site.expn_id = first;
+        assert!(cm.record_expansion(nested) != first);
+    }
+
+    #[test]
+    fn t_dropped_source() {
+        let cm = init_code_map();
+        let anon = cm.new_filemap("<anon>".to_string(), "fn main() {}".to_string());
+        let fm = cm.new_filemap("/nonexistent/lib.rs".to_string(),
+                                "fn main() {}".to_string());
+        cm.drop_sources();
+        assert!(anon.is_src_loaded());
+        assert!(!fm.is_src_loaded());
+        assert_eq!(fm.src_len, 13);
+        // The file can't be re-read, so there's no snippet
+        let span = Span {lo: fm.start_pos, hi: fm.start_pos + BytePos(2), expn_id: NO_EXPANSION};
+        assert_eq!(cm.span_to_snippet(span), None);
+    }
 }
@@ -193,8 +193,8 @@ fn retain_stmt(cx: &mut Context, stmt: &ast::Stmt) -> bool {
 
 fn fold_block(cx: &mut Context, b: P<ast::Block>) -> P<ast::Block> {
     b.map(|ast::Block {id, view_items, stmts, expr, rules, span}| {
-        let resulting_stmts: Vec<P<ast::Stmt>> =
-            stmts.into_iter().filter(|a| retain_stmt(cx, &**a)).collect();
+        let mut resulting_stmts = stmts;
+        resulting_stmts.retain(|a| retain_stmt(cx, &**a));
         let resulting_stmts = resulting_stmts.into_iter()
             .flat_map(|stmt| cx.fold_stmt(stmt).into_iter())
             .collect();
@@ -213,20 +213,12 @@ fn fold_block(cx: &mut Context, b: P<ast::Block>) -> P<ast::Block> {
 }
 
 fn fold_expr(cx: &mut Context, expr: P<ast::Expr>) -> P<ast::Expr> {
-    expr.map(|ast::Expr {id, span, node}| {
-        fold::noop_fold_expr(ast::Expr {
-            id: id,
-            node: match node {
-                ast::ExprMatch(m, arms, source) => {
-                    ast::ExprMatch(m, arms.into_iter()
-                                        .filter(|a| (cx.in_cfg)(a.attrs.as_slice()))
-                                        .collect(), source)
-                }
-                _ => node
-            },
-            span: span
-        }, cx)
-    })
+    expr.map_in_place(|e| match e.node {
+        ast::ExprMatch(_, ref mut arms, _) => {
+            arms.retain(|a| (cx.in_cfg)(a.attrs.as_slice()))
+        }
+        _ => {}
+    }).map(|e| fold::noop_fold_expr(e, cx))
 }
 
 fn item_in_cfg(cx: &mut Context, item: &ast::Item) -> bool {
@@ -246,6 +238,7 @@ fn trait_method_in_cfg(cx: &mut Context, meth: &ast::TraitItem) -> bool {
         ast::RequiredMethod(ref meth) => (cx.in_cfg)(meth.attrs.as_slice()),
         ast::ProvidedMethod(ref meth) => (cx.in_cfg)(meth.attrs.as_slice()),
         ast::TypeTraitItem(ref typ) => (cx.in_cfg)(typ.attrs.as_slice()),
+        ast::ConstTraitItem(ref c) => (cx.in_cfg)(c.attrs.as_slice()),
     }
 }
 
@@ -253,6 +246,7 @@ fn impl_item_in_cfg(cx: &mut Context, impl_item: &ast::ImplItem) -> bool {
     match *impl_item {
         ast::MethodImplItem(ref meth) => (cx.in_cfg)(meth.attrs.as_slice()),
         ast::TypeImplItem(ref typ) => (cx.in_cfg)(typ.attrs.as_slice()),
+        ast::ConstImplItem(ref c) => (cx.in_cfg)(c.attrs.as_slice()),
     }
 }
 
@@ -266,7 +260,8 @@ fn in_cfg(diagnostic: &SpanHandler, cfg: &[P<ast::MetaItem>], attrs: &[ast::Attr
         };
 
         if mis.len() != 1 {
-            diagnostic.span_err(attr.span, "expected 1 cfg-pattern");
+            let sp = mis.get(1).map_or(attr.node.value.span, |mi| mi.span);
+            diagnostic.span_err(sp, "expected 1 cfg-pattern");
             return true;
         }
 
@@ -11,17 +11,24 @@
 pub use self::Level::*;
 pub use self::RenderSpan::*;
 pub use self::ColorConfig::*;
+pub use self::MacroBacktraceMode::*;
 use self::Destination::*;
 
-use codemap::{Pos, Span};
+use codemap::{Pos, Span, MultiSpan, NO_EXPANSION};
 use codemap;
 use diagnostics;
 
 use std::cell::{RefCell, Cell};
+use std::cmp;
+use std::collections::HashMap;
+use std::collections::hash_map::{Occupied, Vacant};
 use std::fmt;
 use std::io;
 use std::iter::range;
+use std::mem;
 use std::string::String;
+use std::task;
+use std::uint;
 use term::WriterWrapper;
 use term;
 
@@ -38,18 +45,23 @@ pub enum RenderSpan {
     /// A FileLine renders with just a line for the message prefixed
     /// by file:linenum.
     FileLine(Span),
+
+    /// A Suggestion renders with a line for the message prefixed by
+    /// file:linenum, followed by the source lines covered by the span
+    /// with the span's text replaced by the given string.
+    Suggestion(Span, String),
 }
 
 impl RenderSpan {
-    fn span(self) -> Span {
-        match self {
-            FullSpan(s) | FileLine(s) => s
+    pub fn span(&self) -> Span {
+        match *self {
+            FullSpan(s) | FileLine(s) | Suggestion(s, _) => s
         }
     }
     fn is_full_span(&self) -> bool {
         match self {
             &FullSpan(..) => true,
-            &FileLine(..) => false,
+            &FileLine(..) | &Suggestion(..) => false,
         }
     }
 }
@@ -61,11 +73,42 @@ pub enum ColorConfig {
     Never
 }
 
+/// How much of the chain of macro invocations a diagnostic came from is
+/// printed after it.
+#[deriving(Clone, PartialEq)]
+pub enum MacroBacktraceMode {
+    /// No frames at all.
+    NoBacktrace,
+    /// Every frame of a macro defined in the crate being compiled, with
+    /// each run of frames of macros from other crates, or built into the
+    /// compiler, summarized in a single line.
+    CollapsedBacktrace,
+    /// Every frame, along with where imported macros were defined.
+    FullBacktrace,
+}
+
 pub trait Emitter {
     fn emit(&mut self, cmsp: Option<(&codemap::CodeMap, Span)>,
             msg: &str, code: Option<&str>, lvl: Level);
     fn custom_emit(&mut self, cm: &codemap::CodeMap,
                    sp: RenderSpan, msg: &str, lvl: Level);
+
+    /// Emits a diagnostic about several spans at once. Emitters that can't
+    /// show them together emit a note for each labeled span instead.
+    fn emit_multi(&mut self, cm: &codemap::CodeMap, msp: &MultiSpan,
+                  msg: &str, code: Option<&str>, lvl: Level) {
+        self.emit(Some((cm, msp.primary)), msg, code, lvl);
+        for &(sp, ref label) in msp.labels.iter() {
+            self.emit(Some((cm, sp)), label.as_slice(), None, Note);
+        }
+    }
+
+    /// Holds back the diagnostics emitted from now on until
+    /// `flush_buffered`, which emits them sorted by the span they point at,
+    /// each with the notes that followed it. Emitters that can't hold them
+    /// back emit them right away.
+    fn start_buffering(&mut self) {}
+    fn flush_buffered(&mut self) {}
 }
 
 /// This structure is used to signify that a task has panicked with a fatal error
@@ -88,15 +131,17 @@ pub struct SpanHandler {
 impl SpanHandler {
     pub fn span_fatal(&self, sp: Span, msg: &str) -> ! {
         self.handler.emit(Some((&self.cm, sp)), msg, Fatal);
+        self.handler.emit_summary();
         panic!(FatalError);
     }
     pub fn span_err(&self, sp: Span, msg: &str) {
         self.handler.emit(Some((&self.cm, sp)), msg, Error);
-        self.handler.bump_err_count();
     }
     pub fn span_err_with_code(&self, sp: Span, msg: &str, code: &str) {
         self.handler.emit_with_code(Some((&self.cm, sp)), msg, code, Error);
-        self.handler.bump_err_count();
+    }
+    pub fn span_err_multi(&self, msp: &MultiSpan, msg: &str) {
+        self.handler.emit_multi(&self.cm, msp, msg, None, Error);
     }
     pub fn span_warn(&self, sp: Span, msg: &str) {
         self.handler.emit(Some((&self.cm, sp)), msg, Warning);
@@ -104,6 +149,9 @@ impl SpanHandler {
     pub fn span_warn_with_code(&self, sp: Span, msg: &str, code: &str) {
         self.handler.emit_with_code(Some((&self.cm, sp)), msg, code, Warning);
     }
+    pub fn span_warn_multi(&self, msp: &MultiSpan, msg: &str) {
+        self.handler.emit_multi(&self.cm, msp, msg, None, Warning);
+    }
     pub fn span_note(&self, sp: Span, msg: &str) {
         self.handler.emit(Some((&self.cm, sp)), msg, Note);
     }
@@ -113,6 +161,11 @@ impl SpanHandler {
     pub fn span_help(&self, sp: Span, msg: &str) {
         self.handler.emit(Some((&self.cm, sp)), msg, Help);
     }
+    /// Suggests replacing the text of `sp` with `suggestion`, as a help
+    /// message following the diagnostic it fixes.
+    pub fn span_suggestion(&self, sp: Span, msg: &str, suggestion: String) {
+        self.handler.custom_emit(&self.cm, Suggestion(sp, suggestion), msg, Help);
+    }
     pub fn fileline_note(&self, sp: Span, msg: &str) {
         self.handler.custom_emit(&self.cm, FileLine(sp), msg, Note);
     }
@@ -126,6 +179,144 @@ impl SpanHandler {
     pub fn handler<'a>(&'a self) -> &'a Handler {
         &self.handler
     }
+
+    /// Starts building an error at `sp`, which is reported once `emit` is
+    /// called on the builder.
+    pub fn struct_span_err<'a>(&'a self, sp: Span, msg: &str) -> DiagnosticBuilder<'a> {
+        DiagnosticBuilder::new(self, Error, Some(sp), msg)
+    }
+    pub fn struct_span_err_with_code<'a>(&'a self, sp: Span, msg: &str,
+                                         code: &str) -> DiagnosticBuilder<'a> {
+        DiagnosticBuilder::new(self, Error, Some(sp), msg).code(code)
+    }
+    pub fn struct_span_warn<'a>(&'a self, sp: Span, msg: &str) -> DiagnosticBuilder<'a> {
+        DiagnosticBuilder::new(self, Warning, Some(sp), msg)
+    }
+    pub fn struct_err<'a>(&'a self, msg: &str) -> DiagnosticBuilder<'a> {
+        DiagnosticBuilder::new(self, Error, None, msg)
+    }
+    pub fn struct_warn<'a>(&'a self, msg: &str) -> DiagnosticBuilder<'a> {
+        DiagnosticBuilder::new(self, Warning, None, msg)
+    }
+}
+
+/// A note or help message attached to a `DiagnosticBuilder`.
+struct SubDiagnostic {
+    level: Level,
+    message: String,
+    /// Where the message points, rendered as by `span_note`
+    span: Option<Span>,
+    /// Where the message points, when it's rendered in another way
+    render_span: Option<RenderSpan>,
+}
+
+/// An error or warning under construction. Notes, help messages and
+/// suggestions are attached with chained calls, and nothing is reported
+/// until `emit` is called, which prints the diagnostic followed by its
+/// children, in the order they were added:
+///
+/// ```ignore
+/// cx.struct_span_err(sp, "expected a literal")
+///   .span_note(def_sp, "the macro is defined here")
+///   .help("pass a string literal instead")
+///   .emit();
+/// ```
+#[must_use]
+pub struct DiagnosticBuilder<'a> {
+    handler: &'a SpanHandler,
+    level: Level,
+    message: String,
+    code: Option<String>,
+    span: Option<Span>,
+    children: Vec<SubDiagnostic>,
+    cancelled: bool,
+}
+
+impl<'a> DiagnosticBuilder<'a> {
+    fn new(handler: &'a SpanHandler, level: Level, span: Option<Span>,
+           msg: &str) -> DiagnosticBuilder<'a> {
+        DiagnosticBuilder {
+            handler: handler,
+            level: level,
+            message: msg.to_string(),
+            code: None,
+            span: span,
+            children: Vec::new(),
+            cancelled: false,
+        }
+    }
+
+    fn sub(mut self, level: Level, msg: &str, span: Option<Span>,
+           render_span: Option<RenderSpan>) -> DiagnosticBuilder<'a> {
+        self.children.push(SubDiagnostic {
+            level: level,
+            message: msg.to_string(),
+            span: span,
+            render_span: render_span,
+        });
+        self
+    }
+
+    pub fn code(mut self, code: &str) -> DiagnosticBuilder<'a> {
+        self.code = Some(code.to_string());
+        self
+    }
+    pub fn note(self, msg: &str) -> DiagnosticBuilder<'a> {
+        self.sub(Note, msg, None, None)
+    }
+    pub fn span_note(self, sp: Span, msg: &str) -> DiagnosticBuilder<'a> {
+        self.sub(Note, msg, Some(sp), None)
+    }
+    pub fn span_end_note(self, sp: Span, msg: &str) -> DiagnosticBuilder<'a> {
+        self.sub(Note, msg, None, Some(FullSpan(sp)))
+    }
+    pub fn fileline_note(self, sp: Span, msg: &str) -> DiagnosticBuilder<'a> {
+        self.sub(Note, msg, None, Some(FileLine(sp)))
+    }
+    pub fn help(self, msg: &str) -> DiagnosticBuilder<'a> {
+        self.sub(Help, msg, None, None)
+    }
+    pub fn span_help(self, sp: Span, msg: &str) -> DiagnosticBuilder<'a> {
+        self.sub(Help, msg, Some(sp), None)
+    }
+    /// Suggests replacing the text of `sp` with `suggestion`.
+    pub fn span_suggestion(self, sp: Span, msg: &str,
+                           suggestion: String) -> DiagnosticBuilder<'a> {
+        self.sub(Help, msg, None, Some(Suggestion(sp, suggestion)))
+    }
+
+    /// Marks the diagnostic as not to be reported, for when it turns out not
+    /// to be needed after all; `emit` then does nothing.
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+    }
+    pub fn cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Reports the diagnostic and its children.
+    pub fn emit(self) {
+        if self.cancelled {
+            return;
+        }
+        let handler = &self.handler.handler;
+        let cm = &self.handler.cm;
+        let cmsp = self.span.map(|sp| (cm, sp));
+        match self.code {
+            Some(ref code) => {
+                handler.emit_with_code(cmsp, self.message.as_slice(), code.as_slice(),
+                                       self.level)
+            }
+            None => handler.emit(cmsp, self.message.as_slice(), self.level),
+        }
+        for child in self.children.into_iter() {
+            let msg = child.message.as_slice();
+            match child.render_span {
+                Some(rsp) => handler.custom_emit(cm, rsp, msg, child.level),
+                None => handler.emit(child.span.map(|sp| (cm, sp)), msg, child.level),
+            }
+        }
+    }
 }
 
 /// A handler deals with errors; certain errors
@@ -133,17 +324,192 @@ impl SpanHandler {
 /// others log errors for later reporting.
 pub struct Handler {
     err_count: Cell<uint>,
+    warn_count: Cell<uint>,
+    /// The number of errors after which compilation stops, or 0
+    error_limit: Cell<uint>,
+    /// The error reported as a bug instead, or 0
+    treat_err_as_bug: Cell<uint>,
     emit: RefCell<Box<Emitter + Send>>,
+    /// Whether errors and warnings repeated by several expansions of a
+    /// macro are only reported once
+    dedup_expansions: Cell<bool>,
+    /// The index in `repeated` of each error or warning reported for a
+    /// span from an expansion, keyed by the message and the source span
+    expansion_diagnostics: RefCell<HashMap<(String, uint, uint), uint>>,
+    repeated: RefCell<Vec<RepeatedDiagnostic>>,
+    /// Whether the last error or warning was a repeat, so that the notes
+    /// following it are left out too
+    in_repeat: Cell<bool>,
+    /// The number of errors left out as repeats, which `err_count` doesn't
+    /// include
+    repeated_errors: Cell<uint>,
+    /// The number of warnings left out as repeats
+    repeated_warnings: Cell<uint>,
+    /// Whether to note how many errors and warnings were reported when
+    /// compilation ends
+    summary: Cell<bool>,
+}
+
+/// An error or warning reported for a span from a macro expansion, and
+/// the number of times other expansions of the macro repeated it.
+struct RepeatedDiagnostic {
+    location: String,
+    msg: String,
+    repeats: uint,
 }
 
 impl Handler {
     pub fn fatal(&self, msg: &str) -> ! {
+        self.flush_diagnostics();
+        self.report_repeats();
         self.emit.borrow_mut().emit(None, msg, None, Fatal);
+        self.emit_summary();
         panic!(FatalError);
     }
+    /// Holds back the diagnostics reported from now on until
+    /// `flush_diagnostics`, so that they're emitted in the order of the
+    /// spans they point at rather than the order they were found in. A
+    /// fatal error or bug flushes them before it is emitted.
+    pub fn buffer_diagnostics(&self) {
+        self.emit.borrow_mut().start_buffering();
+    }
+    pub fn flush_diagnostics(&self) {
+        self.emit.borrow_mut().flush_buffered();
+    }
+    /// Whether to note the number of errors and warnings reported, and of
+    /// the repeats left out, when compilation ends.
+    pub fn set_summary(&self, summary: bool) {
+        self.summary.set(summary);
+    }
+    fn emit_summary(&self) {
+        if !self.summary.get() {
+            return;
+        }
+        self.summary.set(false);
+        fn count(n: uint, what: &str) -> String {
+            format!("{} {}{}", n, what, if n == 1 { "" } else { "s" })
+        }
+        let msg = format!("{}, {} emitted; {}, {} suppressed as duplicates",
+                          count(self.err_count.get(), "error"),
+                          count(self.warn_count.get(), "warning"),
+                          count(self.repeated_errors.get(), "error"),
+                          count(self.repeated_warnings.get(), "warning"));
+        self.emit.borrow_mut().emit(None, msg.as_slice(), None, Note);
+    }
+    /// Whether only the first of the diagnostics that differ only by the
+    /// expansion of a macro they come from is reported. On by default.
+    pub fn set_dedup_expansions(&self, dedup: bool) {
+        self.dedup_expansions.set(dedup);
+    }
+    /// Whether a diagnostic is a repeat of one reported for another
+    /// expansion of the same macro, and should be left out.
+    fn is_repeat(&self, cmsp: Option<(&codemap::CodeMap, Span)>,
+                 msg: &str, code: Option<&str>, lvl: Level) -> bool {
+        match lvl {
+            Note | Help => return self.in_repeat.get(),
+            Bug | Fatal => return false,
+            Error | Warning => {}
+        }
+        let (cm, sp) = match cmsp {
+            Some((cm, sp)) if self.dedup_expansions.get() && sp.expn_id != NO_EXPANSION => {
+                (cm, sp)
+            }
+            _ => {
+                self.in_repeat.set(false);
+                return false;
+            }
+        };
+        let msg = format!("{}: {}{}", lvl, msg, code.map_or(String::new(), |c| {
+            format!(" [{}]", c)
+        }));
+        let key = (msg.clone(), sp.lo.to_uint(), sp.hi.to_uint());
+        let mut repeated = self.repeated.borrow_mut();
+        let repeat = match self.expansion_diagnostics.borrow_mut().entry(key) {
+            Occupied(entry) => {
+                repeated.as_mut_slice()[*entry.get()].repeats += 1;
+                let count = if lvl == Error {
+                    &self.repeated_errors
+                } else {
+                    &self.repeated_warnings
+                };
+                count.set(count.get() + 1);
+                true
+            }
+            Vacant(entry) => {
+                entry.set(repeated.len());
+                repeated.push(RepeatedDiagnostic {
+                    location: cm.span_to_string(sp),
+                    msg: msg,
+                    repeats: 0,
+                });
+                false
+            }
+        };
+        self.in_repeat.set(repeat);
+        repeat
+    }
+    /// Notes how many times the diagnostics left out as repeats were
+    /// repeated, and starts counting again.
+    pub fn report_repeats(&self) {
+        let repeated = mem::replace(&mut *self.repeated.borrow_mut(), Vec::new());
+        self.expansion_diagnostics.borrow_mut().clear();
+        for diag in repeated.iter().filter(|diag| diag.repeats > 0) {
+            let msg = format!("`{}` at {} was repeated {} more time{} by other expansions \
+                               of the same macro (pass `-Z show-duplicate-diagnostics` \
+                               to see them)",
+                              diag.msg, diag.location, diag.repeats,
+                              if diag.repeats == 1 { "" } else { "s" });
+            self.emit.borrow_mut().emit(None, msg.as_slice(), None, Note);
+        }
+    }
     pub fn err(&self, msg: &str) {
-        self.emit.borrow_mut().emit(None, msg, None, Error);
-        self.bump_err_count();
+        self.in_repeat.set(false);
+        let lvl = self.prepare_emit(Error);
+        self.emit.borrow_mut().emit(None, msg, None, lvl);
+        self.panic_if_bug(lvl);
+    }
+    /// Stops compilation before the error following the `limit`th one is
+    /// emitted, so that the notes of the last error are still printed. A
+    /// limit of 0 means no limit.
+    pub fn set_error_limit(&self, limit: uint) {
+        self.error_limit.set(limit);
+    }
+    /// Reports the `n`th error as a bug, panicking once it is emitted so
+    /// that a backtrace shows where it comes from. An `n` of 0 means no
+    /// error is.
+    pub fn set_treat_err_as_bug(&self, n: uint) {
+        self.treat_err_as_bug.set(n);
+    }
+    /// Checks the error limit before a diagnostic that isn't a repeat is
+    /// emitted, counts it, and returns the level to emit it at, which is
+    /// `Bug` for the error picked by `set_treat_err_as_bug`.
+    fn prepare_emit(&self, lvl: Level) -> Level {
+        self.check_error_limit(lvl);
+        let n = self.treat_err_as_bug.get();
+        let lvl = if lvl == Error && n != 0 && self.err_count.get() + 1 >= n {
+            Bug
+        } else {
+            lvl
+        };
+        match lvl {
+            Bug | Fatal => self.flush_diagnostics(),
+            Error => self.bump_err_count(),
+            Warning => self.warn_count.set(self.warn_count.get() + 1),
+            _ => {}
+        }
+        lvl
+    }
+    fn panic_if_bug(&self, lvl: Level) {
+        if lvl == Bug {
+            panic!(ExplicitBug);
+        }
+    }
+    fn check_error_limit(&self, lvl: Level) {
+        let limit = self.error_limit.get();
+        if lvl == Error && limit != 0 && self.err_count.get() >= limit {
+            self.fatal(format!("aborting after {} errors, the limit set by `--error-limit`",
+                               limit).as_slice());
+        }
     }
     pub fn bump_err_count(&self) {
         self.err_count.set(self.err_count.get() + 1u);
@@ -167,15 +533,24 @@ impl Handler {
         self.fatal(s.as_slice());
     }
     pub fn warn(&self, msg: &str) {
-        self.emit.borrow_mut().emit(None, msg, None, Warning);
+        self.in_repeat.set(false);
+        let lvl = self.prepare_emit(Warning);
+        self.emit.borrow_mut().emit(None, msg, None, lvl);
     }
     pub fn note(&self, msg: &str) {
+        if self.in_repeat.get() {
+            return;
+        }
         self.emit.borrow_mut().emit(None, msg, None, Note);
     }
     pub fn help(&self, msg: &str) {
+        if self.in_repeat.get() {
+            return;
+        }
         self.emit.borrow_mut().emit(None, msg, None, Help);
     }
     pub fn bug(&self, msg: &str) -> ! {
+        self.flush_diagnostics();
         self.emit.borrow_mut().emit(None, msg, None, Bug);
         panic!(ExplicitBug);
     }
@@ -186,18 +561,42 @@ impl Handler {
                 cmsp: Option<(&codemap::CodeMap, Span)>,
                 msg: &str,
                 lvl: Level) {
+        if self.is_repeat(cmsp, msg, None, lvl) {
+            return;
+        }
+        let lvl = self.prepare_emit(lvl);
         self.emit.borrow_mut().emit(cmsp, msg, None, lvl);
+        self.panic_if_bug(lvl);
     }
     pub fn emit_with_code(&self,
                           cmsp: Option<(&codemap::CodeMap, Span)>,
                           msg: &str,
                           code: &str,
                           lvl: Level) {
+        if self.is_repeat(cmsp, msg, Some(code), lvl) {
+            return;
+        }
+        let lvl = self.prepare_emit(lvl);
         self.emit.borrow_mut().emit(cmsp, msg, Some(code), lvl);
+        self.panic_if_bug(lvl);
     }
     pub fn custom_emit(&self, cm: &codemap::CodeMap,
                        sp: RenderSpan, msg: &str, lvl: Level) {
+        if self.is_repeat(Some((cm, sp.span())), msg, None, lvl) {
+            return;
+        }
+        let lvl = self.prepare_emit(lvl);
         self.emit.borrow_mut().custom_emit(cm, sp, msg, lvl);
+        self.panic_if_bug(lvl);
+    }
+    pub fn emit_multi(&self, cm: &codemap::CodeMap, msp: &MultiSpan,
+                      msg: &str, code: Option<&str>, lvl: Level) {
+        if self.is_repeat(Some((cm, msp.primary)), msg, code, lvl) {
+            return;
+        }
+        let lvl = self.prepare_emit(lvl);
+        self.emit.borrow_mut().emit_multi(cm, msp, msg, code, lvl);
+        self.panic_if_bug(lvl);
     }
 }
 
@@ -216,7 +615,28 @@ pub fn default_handler(color_config: ColorConfig,
 pub fn mk_handler(e: Box<Emitter + Send>) -> Handler {
     Handler {
         err_count: Cell::new(0),
+        warn_count: Cell::new(0),
+        error_limit: Cell::new(0),
+        treat_err_as_bug: Cell::new(0),
         emit: RefCell::new(e),
+        dedup_expansions: Cell::new(true),
+        expansion_diagnostics: RefCell::new(HashMap::new()),
+        repeated: RefCell::new(Vec::new()),
+        in_repeat: Cell::new(false),
+        repeated_errors: Cell::new(0),
+        repeated_warnings: Cell::new(0),
+        summary: Cell::new(false),
+    }
+}
+
+impl Drop for Handler {
+    fn drop(&mut self) {
+        // Emitting while unwinding from a fatal error could panic again
+        if !task::failing() {
+            self.flush_diagnostics();
+            self.report_repeats();
+            self.emit_summary();
+        }
     }
 }
 
@@ -287,6 +707,10 @@ fn print_maybe_styled(w: &mut EmitterWriter,
         Raw(ref mut w) => {
             w.write_str(msg)
         }
+        Buffer(_, ref mut diags) => {
+            last_buffered(diags).output.push((msg.to_string(), Some(color)));
+            Ok(())
+        }
     }
 }
 
@@ -316,12 +740,31 @@ fn print_diagnostic(dst: &mut EmitterWriter, topic: &str, lvl: Level,
 
 pub struct EmitterWriter {
     dst: Destination,
-    registry: Option<diagnostics::registry::Registry>
+    registry: Option<diagnostics::registry::Registry>,
+    macro_backtrace: MacroBacktraceMode,
 }
 
 enum Destination {
     Terminal(Box<term::Terminal<WriterWrapper> + Send>),
     Raw(Box<Writer + Send>),
+    /// Holds back the output meant for the destination while diagnostics
+    /// are buffered.
+    Buffer(Box<Destination>, Vec<BufferedDiagnostic>),
+}
+
+/// The output of a held back diagnostic and of the notes following it,
+/// with the position of its span to sort by. Styled parts of the output
+/// keep their style, to be applied once it's written.
+struct BufferedDiagnostic {
+    pos: uint,
+    output: Vec<(String, Option<term::attr::Attr>)>,
+}
+
+fn last_buffered<'a>(diags: &'a mut Vec<BufferedDiagnostic>) -> &'a mut BufferedDiagnostic {
+    if diags.is_empty() {
+        diags.push(BufferedDiagnostic { pos: uint::MAX, output: Vec::new() });
+    }
+    diags.last_mut().unwrap()
 }
 
 impl EmitterWriter {
@@ -336,19 +779,45 @@ impl EmitterWriter {
         };
 
         if use_color {
-            let dst = match term::stderr() {
-                Some(t) => Terminal(t),
-                None    => Raw(box stderr),
+            let dst = match (term::stderr(), color_config) {
+                (Some(t), _) => Terminal(t),
+                // Colors were asked for explicitly, e.g. for a build log
+                // that will be shown in a terminal later
+                (None, Always) => Terminal(term::ansi_stderr()),
+                (None, _) => Raw(box stderr),
             };
-            EmitterWriter { dst: dst, registry: registry }
+            EmitterWriter { dst: dst, registry: registry, macro_backtrace: CollapsedBacktrace }
         } else {
-            EmitterWriter { dst: Raw(box stderr), registry: registry }
+            EmitterWriter {
+                dst: Raw(box stderr),
+                registry: registry,
+                macro_backtrace: CollapsedBacktrace,
+            }
         }
     }
 
     pub fn new(dst: Box<Writer + Send>,
                registry: Option<diagnostics::registry::Registry>) -> EmitterWriter {
-        EmitterWriter { dst: Raw(dst), registry: registry }
+        EmitterWriter { dst: Raw(dst), registry: registry, macro_backtrace: CollapsedBacktrace }
+    }
+
+    pub fn set_macro_backtrace(&mut self, mode: MacroBacktraceMode) {
+        self.macro_backtrace = mode;
+    }
+
+    /// Starts the held back output of a diagnostic, unless it's a note or
+    /// help following the last one.
+    fn start_diagnostic(&mut self, sp: Option<Span>, lvl: Level) {
+        if let Buffer(_, ref mut diags) = self.dst {
+            match lvl {
+                Note | Help if !diags.is_empty() => return,
+                _ => {}
+            }
+            diags.push(BufferedDiagnostic {
+                pos: sp.map_or(uint::MAX, |sp| sp.lo.to_uint()),
+                output: Vec::new(),
+            });
+        }
     }
 }
 
@@ -357,6 +826,11 @@ impl Writer for Destination {
         match *self {
             Terminal(ref mut t) => t.write(bytes),
             Raw(ref mut w) => w.write(bytes),
+            Buffer(_, ref mut diags) => {
+                let text = String::from_utf8_lossy(bytes).into_string();
+                last_buffered(diags).output.push((text, None));
+                Ok(())
+            }
         }
     }
 }
@@ -365,6 +839,7 @@ impl Emitter for EmitterWriter {
     fn emit(&mut self,
             cmsp: Option<(&codemap::CodeMap, Span)>,
             msg: &str, code: Option<&str>, lvl: Level) {
+        self.start_diagnostic(cmsp.map(|(_, sp)| sp), lvl);
         let error = match cmsp {
             Some((cm, sp)) => emit(self, cm, FullSpan(sp), msg, code, lvl, false),
             None => print_diagnostic(self, "", lvl, msg, code),
@@ -378,18 +853,76 @@ impl Emitter for EmitterWriter {
 
     fn custom_emit(&mut self, cm: &codemap::CodeMap,
                    sp: RenderSpan, msg: &str, lvl: Level) {
+        self.start_diagnostic(Some(sp.span()), lvl);
         match emit(self, cm, sp, msg, None, lvl, true) {
             Ok(()) => {}
             Err(e) => panic!("failed to print diagnostics: {}", e),
         }
     }
+
+    fn emit_multi(&mut self, cm: &codemap::CodeMap, msp: &MultiSpan,
+                  msg: &str, code: Option<&str>, lvl: Level) {
+        self.start_diagnostic(Some(msp.primary), lvl);
+        match emit_multi(self, cm, msp, msg, code, lvl) {
+            Ok(()) => {}
+            Err(e) => panic!("failed to print diagnostics: {}", e),
+        }
+    }
+
+    fn start_buffering(&mut self) {
+        if let Buffer(..) = self.dst {
+            return;
+        }
+        let dst = mem::replace(&mut self.dst, Raw(box io::util::NullWriter));
+        self.dst = Buffer(box dst, Vec::new());
+    }
+
+    fn flush_buffered(&mut self) {
+        let (dst, mut diags) = match mem::replace(&mut self.dst, Raw(box io::util::NullWriter)) {
+            Buffer(dst, diags) => (*dst, diags),
+            dst => {
+                self.dst = dst;
+                return;
+            }
+        };
+        self.dst = dst;
+        // A stable sort, so diagnostics about the same place stay in order
+        diags.sort_by(|a, b| a.pos.cmp(&b.pos));
+        for diag in diags.into_iter() {
+            for (text, style) in diag.output.into_iter() {
+                let result = match style {
+                    Some(style) => print_maybe_styled(self, text.as_slice(), style),
+                    None => self.dst.write_str(text.as_slice()),
+                };
+                match result {
+                    Ok(()) => {}
+                    Err(e) => panic!("failed to print diagnostics: {}", e),
+                }
+            }
+        }
+    }
 }
 
 fn emit(dst: &mut EmitterWriter, cm: &codemap::CodeMap, rsp: RenderSpan,
         msg: &str, code: Option<&str>, lvl: Level, custom: bool) -> io::IoResult<()> {
-    let sp = rsp.span();
+    let orig_sp = rsp.span();
+    // The source of a macro from another crate is of little use to the
+    // user, who can't change it: point at where this crate invoked it.
+    // Suggestions keep their span, since they are edits of that text.
+    let imported = match rsp {
+        Suggestion(..) => None,
+        _ => cm.imported_macro_call_site(orig_sp),
+    };
+    let sp = match imported {
+        Some((call_site, _, _)) => call_site,
+        None => orig_sp,
+    };
     let ss = cm.span_to_string(sp);
     let lines = cm.span_to_lines(sp);
+    if let Suggestion(_, ref suggestion) = rsp {
+        try!(print_diagnostic(dst, ss.as_slice(), lvl, msg, code));
+        return show_suggestion(dst, cm, sp, suggestion.as_slice(), lines);
+    }
     if custom {
         // we want to tell compiletest/runtest to look at the last line of the
         // span (since `custom_highlight_lines` displays an arrow to the end of
@@ -406,7 +939,20 @@ fn emit(dst: &mut EmitterWriter, cm: &codemap::CodeMap, rsp: RenderSpan,
             try!(highlight_lines(dst, cm, sp, lvl, lines));
         }
     }
-    try!(print_macro_backtrace(dst, cm, sp));
+    match imported {
+        Some((_, ref name, ref krate)) => {
+            let origin = match *krate {
+                Some(ref krate) => format!("in this expansion of {}, a macro from crate `{}`",
+                                           name, krate),
+                None => format!("in this expansion of {}, a macro from another crate", name),
+            };
+            try!(print_diagnostic(dst, ss.as_slice(), Note, origin.as_slice(), None));
+        }
+        None => {}
+    }
+    // The full backtrace still goes through the imported macros
+    let backtrace_sp = if dst.macro_backtrace == FullBacktrace { orig_sp } else { sp };
+    try!(print_macro_backtrace(dst, cm, backtrace_sp));
     match code {
         Some(code) =>
             match dst.registry.as_ref().and_then(|registry| registry.find_description(code)) {
@@ -422,6 +968,22 @@ fn emit(dst: &mut EmitterWriter, cm: &codemap::CodeMap, rsp: RenderSpan,
     Ok(())
 }
 
+/// Expands the tabs in a source line, so that it lines up with the markers
+/// printed under it, which are placed by display column.
+fn expand_tabs(line: &str) -> String {
+    let mut s = String::with_capacity(line.len());
+    for c in line.chars() {
+        if c == '\t' {
+            for _ in range(0, codemap::TAB_WIDTH) {
+                s.push(' ');
+            }
+        } else {
+            s.push(c);
+        }
+    }
+    s
+}
+
 fn highlight_lines(err: &mut EmitterWriter,
                    cm: &codemap::CodeMap,
                    sp: Span,
@@ -439,7 +1001,7 @@ fn highlight_lines(err: &mut EmitterWriter,
     for &line_number in display_lines.iter() {
         if let Some(line) = fm.get_line(line_number) {
             try!(write!(&mut err.dst, "{}:{} {}\n", fm.name,
-                        line_number + 1, line));
+                        line_number + 1, expand_tabs(line.as_slice())));
         }
     }
     if elided {
@@ -458,35 +1020,19 @@ fn highlight_lines(err: &mut EmitterWriter,
         // how many digits must be indent past?
         while num > 0u { num /= 10u; digits += 1u; }
 
-        // indent past |name:## | and the 0-offset column location
-        let left = fm.name.len() + digits + lo.col.to_uint() + 3u;
+        // indent past |name:## | and the 0-offset display column
+        let left = fm.name.len() + digits + lo.col_display + 3u;
         let mut s = String::new();
-        // Skip is the number of characters we need to skip because they are
-        // part of the 'filename:line ' part of the previous line.
-        let skip = fm.name.len() + digits + 3u;
-        for _ in range(0, skip) {
+        for _ in range(0, left) {
             s.push(' ');
         }
-        if let Some(orig) = fm.get_line(lines.lines[0]) {
-            for pos in range(0u, left - skip) {
-                let cur_char = orig.as_bytes()[pos] as char;
-                // Whenever a tab occurs on the previous line, we insert one on
-                // the error-point-squiggly-line as well (instead of a space).
-                // That way the squiggly line will usually appear in the correct
-                // position.
-                match cur_char {
-                    '\t' => s.push('\t'),
-                    _ => s.push(' '),
-                };
-            }
-        }
 
         try!(write!(&mut err.dst, "{}", s));
         let mut s = String::from_str("^");
         let hi = cm.lookup_char_pos(sp.hi);
-        if hi.col != lo.col {
+        if hi.col_display > lo.col_display {
             // the ^ already takes up one space
-            let num_squigglies = hi.col.to_uint() - lo.col.to_uint() - 1u;
+            let num_squigglies = hi.col_display - lo.col_display - 1u;
             for _ in range(0, num_squigglies) {
                 s.push('~');
             }
@@ -498,6 +1044,145 @@ fn highlight_lines(err: &mut EmitterWriter,
     Ok(())
 }
 
+/// Prints the lines covered by `sp` as they would read with its text
+/// replaced by `suggestion`.
+fn show_suggestion(err: &mut EmitterWriter,
+                   cm: &codemap::CodeMap,
+                   sp: Span,
+                   suggestion: &str,
+                   lines: codemap::FileLines) -> io::IoResult<()> {
+    let fm = &*lines.file;
+    let (first, last) = match (lines.lines.head(), lines.lines.last()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return Ok(()),
+    };
+    let (first_line, last_line) = match (fm.get_line(first), fm.get_line(last)) {
+        (Some(first_line), Some(last_line)) => (first_line, last_line),
+        _ => return Ok(()),
+    };
+    let lo = cm.lookup_byte_offset(sp.lo).pos;
+    let hi = cm.lookup_byte_offset(sp.hi).pos;
+    let (first_start, last_start) = {
+        let starts = fm.lines.borrow();
+        ((starts[first] - fm.start_pos).to_uint(), (starts[last] - fm.start_pos).to_uint())
+    };
+    let mut fixed = String::from_str(first_line.as_slice().slice_to(lo.to_uint() - first_start));
+    fixed.push_str(suggestion);
+    // A span can end past the newline of its last line
+    let hi = cmp::min(hi.to_uint() - last_start, last_line.len());
+    fixed.push_str(last_line.as_slice().slice_from(hi));
+
+    for (i, line) in fixed.as_slice().lines().enumerate() {
+        try!(write!(&mut err.dst, "{}:{} {}\n", fm.name,
+                    first + i + 1, expand_tabs(line)));
+    }
+    Ok(())
+}
+
+/// Labeled spans starting more than this many lines away from the primary
+/// span, or in another file, are shown as separate notes.
+static MAX_LABEL_DISTANCE: uint = MAX_LINES;
+
+fn emit_multi(dst: &mut EmitterWriter, cm: &codemap::CodeMap, msp: &MultiSpan,
+              msg: &str, code: Option<&str>, lvl: Level) -> io::IoResult<()> {
+    let primary = cm.lookup_char_pos(msp.primary.lo);
+    let (near, far) = msp.labels.partitioned(|&(sp, _)| {
+        let lo = cm.lookup_char_pos(sp.lo);
+        let distance = if lo.line > primary.line {
+            lo.line - primary.line
+        } else {
+            primary.line - lo.line
+        };
+        lo.file.start_pos == primary.file.start_pos && distance <= MAX_LABEL_DISTANCE
+    });
+    let near = MultiSpan { primary: msp.primary, labels: near };
+
+    let ss = cm.span_to_string(msp.primary);
+    try!(print_diagnostic(dst, ss.as_slice(), lvl, msg, code));
+    try!(highlight_multi(dst, cm, &near, lvl));
+    try!(print_macro_backtrace(dst, cm, msp.primary));
+    for &(sp, ref label) in far.iter() {
+        try!(emit(dst, cm, FullSpan(sp), label.as_slice(), None, Note, false));
+    }
+    Ok(())
+}
+
+/// Prints the lines the spans of `msp` start on, grouped by file, each
+/// followed by a line marking the spans starting on it: `^~~~` for the
+/// primary span and `----` plus the label for the others. Spans covering
+/// several lines are only marked on their first.
+fn highlight_multi(err: &mut EmitterWriter,
+                   cm: &codemap::CodeMap,
+                   msp: &MultiSpan,
+                   lvl: Level) -> io::IoResult<()> {
+    let mut spans = vec![(msp.primary, None)];
+    spans.extend(msp.labels.iter().map(|&(sp, ref label)| (sp, Some(label.as_slice()))));
+    let starts: Vec<codemap::Loc> = spans.iter().map(|&(sp, _)| cm.lookup_char_pos(sp.lo))
+                                         .collect();
+
+    let mut files: Vec<&codemap::Loc> = Vec::new();
+    for lo in starts.iter() {
+        if !files.iter().any(|f| f.file.start_pos == lo.file.start_pos) {
+            files.push(lo);
+        }
+    }
+
+    for first in files.iter() {
+        let fm = &*first.file;
+        let mut lines: Vec<uint> = starts.iter()
+                                         .filter(|lo| lo.file.start_pos == fm.start_pos)
+                                         .map(|lo| lo.line)
+                                         .collect();
+        lines.sort();
+        lines.dedup();
+
+        for &line in lines.iter() {
+            let orig = match fm.get_line(line - 1) {
+                Some(orig) => orig,
+                None => continue
+            };
+            let prefix = format!("{}:{} ", fm.name, line);
+            try!(write!(&mut err.dst, "{}{}\n", prefix, expand_tabs(orig.as_slice())));
+
+            for (&(sp, label), lo) in spans.iter().zip(starts.iter()) {
+                if lo.file.start_pos != fm.start_pos || lo.line != line {
+                    continue;
+                }
+                let hi = cm.lookup_char_pos(sp.hi);
+                let width = if hi.line == lo.line && hi.col_display > lo.col_display {
+                    hi.col_display - lo.col_display
+                } else {
+                    1
+                };
+                let mut s = String::new();
+                for _ in range(0, prefix.len() + lo.col_display) {
+                    s.push(' ');
+                }
+                let color = match label {
+                    None => {
+                        s.push('^');
+                        for _ in range(1, width) {
+                            s.push('~');
+                        }
+                        lvl.color()
+                    }
+                    Some(label) => {
+                        for _ in range(0, width) {
+                            s.push('-');
+                        }
+                        s.push(' ');
+                        s.push_str(label);
+                        Note.color()
+                    }
+                };
+                s.push('\n');
+                try!(print_maybe_styled(err, s.as_slice(), term::attr::ForegroundColor(color)));
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Here are the differences between this and the normal `highlight_lines`:
 /// `custom_highlight_lines` will always put arrow on the last byte of the
 /// span (instead of the first byte). Also, when the span is too long (more
@@ -516,26 +1201,26 @@ fn custom_highlight_lines(w: &mut EmitterWriter,
     if lines.len() > MAX_LINES {
         if let Some(line) = fm.get_line(lines[0]) {
             try!(write!(&mut w.dst, "{}:{} {}\n", fm.name,
-                        lines[0] + 1, line));
+                        lines[0] + 1, expand_tabs(line.as_slice())));
         }
         try!(write!(&mut w.dst, "...\n"));
         let last_line_number = lines[lines.len() - 1];
         if let Some(last_line) = fm.get_line(last_line_number) {
             try!(write!(&mut w.dst, "{}:{} {}\n", fm.name,
-                        last_line_number + 1, last_line));
+                        last_line_number + 1, expand_tabs(last_line.as_slice())));
         }
     } else {
         for &line_number in lines.iter() {
             if let Some(line) = fm.get_line(line_number) {
                 try!(write!(&mut w.dst, "{}:{} {}\n", fm.name,
-                            line_number + 1, line));
+                            line_number + 1, expand_tabs(line.as_slice())));
             }
         }
     }
     let last_line_start = format!("{}:{} ", fm.name, lines[lines.len()-1]+1);
     let hi = cm.lookup_char_pos(sp.hi);
     // Span seems to use half-opened interval, so subtract 1
-    let skip = last_line_start.len() + hi.col.to_uint() - 1;
+    let skip = last_line_start.len() + hi.col_display - 1;
     let mut s = String::new();
     for _ in range(0, skip) {
         s.push(' ');
@@ -547,28 +1232,100 @@ fn custom_highlight_lines(w: &mut EmitterWriter,
                        term::attr::ForegroundColor(lvl.color()))
 }
 
+/// Shows where a macro imported from another crate was defined, since the
+/// user may not have that crate's source at hand: the first line of the
+/// definition, and the expansions that produced it in its crate, if any.
+fn print_imported_definition(w: &mut EmitterWriter,
+                             cm: &codemap::CodeMap,
+                             macro_decl_name: &str,
+                             def_site: Span)
+                             -> io::IoResult<()> {
+    let lo = cm.lookup_char_pos(def_site.lo);
+    if !lo.file.is_imported {
+        return Ok(());
+    }
+    let first_line = Span { lo: def_site.lo, hi: def_site.lo, expn_id: def_site.expn_id };
+    let lines = codemap::FileLines { file: lo.file.clone(), lines: vec![lo.line - 1] };
+    try!(highlight_lines(w, cm, first_line, Note, lines));
+    let def_site = cm.span_to_string(def_site);
+    for expansion in lo.file.imported_expansion.iter() {
+        try!(print_diagnostic(w, def_site.as_slice(), Note,
+                              format!("{} was defined by the expansion of {}",
+                                      macro_decl_name, expansion).as_slice(), None));
+    }
+    Ok(())
+}
+
+/// Whether the macro of `frame` is built into the compiler or imported
+/// from another crate.
+fn is_external_frame(cm: &codemap::CodeMap, frame: &codemap::MacroBacktrace) -> bool {
+    match frame.def_site_span {
+        None => true,
+        Some(span) => cm.lookup_char_pos(span.lo).file.is_imported,
+    }
+}
+
+/// Where the macros of a collapsed run of frames come from, all of which
+/// are external.
+fn collapsed_origin(frames: &[codemap::MacroBacktrace]) -> &'static str {
+    let built_in = frames.iter().filter(|frame| frame.def_site_span.is_none()).count();
+    if built_in == frames.len() {
+        "built-in macros"
+    } else if built_in == 0 {
+        "macros from other crates"
+    } else {
+        "built-in macros and macros from other crates"
+    }
+}
+
 fn print_macro_backtrace(w: &mut EmitterWriter,
                          cm: &codemap::CodeMap,
                          sp: Span)
                          -> io::IoResult<()> {
-    let cs = try!(cm.with_expn_info(sp.expn_id, |expn_info| match expn_info {
-        Some(ei) => {
-            let ss = ei.callee.span.map_or(String::new(), |span| cm.span_to_string(span));
-            let (pre, post) = match ei.callee.format {
-                codemap::MacroAttribute => ("#[", "]"),
-                codemap::MacroBang => ("", "!")
-            };
-            try!(print_diagnostic(w, ss.as_slice(), Note,
-                                  format!("in expansion of {}{}{}", pre,
-                                          ei.callee.name,
-                                          post).as_slice(), None));
-            let ss = cm.span_to_string(ei.call_site);
-            try!(print_diagnostic(w, ss.as_slice(), Note, "expansion site", None));
-            Ok(Some(ei.call_site))
-        }
-        None => Ok(None)
-    }));
-    cs.map_or(Ok(()), |call_site| print_macro_backtrace(w, cm, call_site))
+    if w.macro_backtrace == NoBacktrace {
+        return Ok(());
+    }
+    let backtrace = cm.macro_backtrace(sp);
+    let mut start = 0u;
+    while start < backtrace.len() {
+        let collapse = w.macro_backtrace == CollapsedBacktrace &&
+                       is_external_frame(cm, &backtrace[start]);
+        let mut end = start + 1;
+        if collapse {
+            while end < backtrace.len() && is_external_frame(cm, &backtrace[end]) {
+                end += 1;
+            }
+        }
+        let (first, last) = (&backtrace[start], &backtrace[end - 1]);
+
+        let def_site = first.def_site_span.map_or(String::new(), |span| cm.span_to_string(span));
+        let invoker = match backtrace.as_slice().get(end) {
+            Some(outer) => format!("from {} ", outer.macro_decl_name),
+            None => String::new()
+        };
+        let call_site = cm.lookup_char_pos(last.call_site.lo);
+        let call_site = format!("{}at {}:{}:{}", invoker, call_site.file.name,
+                                call_site.line, call_site.col.to_uint() + 1);
+        let msg = if end - start == 1 {
+            format!("[{}] in expansion of {}, which was invoked {}",
+                    start + 1, first.macro_decl_name, call_site)
+        } else {
+            let names: Vec<&str> = backtrace.as_slice()[start..end].iter()
+                                            .map(|frame| frame.macro_decl_name.as_slice())
+                                            .collect();
+            format!("[{}-{}] in expansion of {}, {}, which were invoked {}",
+                    start + 1, end, names.connect(", "),
+                    collapsed_origin(backtrace.slice(start, end)), call_site)
+        };
+        try!(print_diagnostic(w, def_site.as_slice(), Note, msg.as_slice(), None));
+        if !collapse {
+            if let Some(span) = first.def_site_span {
+                try!(print_imported_definition(w, cm, first.macro_decl_name.as_slice(), span));
+            }
+        }
+        start = end;
+    }
+    Ok(())
 }
 
 pub fn expect<T>(diag: &SpanHandler, opt: Option<T>, msg: || -> String) -> T {
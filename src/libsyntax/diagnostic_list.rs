@@ -0,0 +1,57 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(non_snake_case)]
+
+// Error codes for diagnostics reported by libsyntax. librustc uses codes up
+// to E0174, so the codes here start after it; new librustc codes continue
+// after the last one here.
+
+register_diagnostic!(E0175, r##"
+    An output operand of `asm!` must say how the operand is written, by
+    starting its constraint with either `=` (the operand is only written)
+    or `+` (the operand is both read and written).
+
+    For example, the constraint of `x` below is missing its modifier:
+
+        asm!("mov $1, $0" : "r"(x) : "r"(y));
+
+    and should be written as one of:
+
+        asm!("mov $1, $0" : "=r"(x) : "r"(y));
+        asm!("add $1, $0" : "+r"(x) : "r"(y));
+"##)
+
+register_diagnostic!(E0176, r##"
+    An input operand of `asm!` was given a constraint starting with `=`,
+    which is only meaningful for outputs: inputs are never written.
+
+    For example:
+
+        asm!("mov $1, $0" : "=r"(x) : "=r"(y));
+
+    should be written with a plain constraint for the input:
+
+        asm!("mov $1, $0" : "=r"(x) : "r"(y));
+"##)
+
+register_diagnostic!(E0177, r##"
+    An input operand of `asm!` was given a constraint starting with `+`.
+    Operands that are both read and written have to be listed with the
+    outputs, where `+` expands them into an output and a tied input.
+
+    For example:
+
+        asm!("add $1, $0" : : "+r"(x), "r"(y));
+
+    should be written as:
+
+        asm!("add $1, $0" : "+r"(x) : "r"(y));
+"##)
@@ -24,6 +24,15 @@ macro_rules! span_err(
     })
 )
 
+#[macro_export]
+macro_rules! struct_span_err(
+    ($session:expr, $span:expr, $code:ident, $($message:tt)*) => ({
+        __diagnostic_used!($code);
+        $session.struct_span_err_with_code($span, format!($($message)*).as_slice(),
+                                           stringify!($code))
+    })
+)
+
 #[macro_export]
 macro_rules! span_warn(
     ($session:expr, $span:expr, $code:ident, $($message:tt)*) => ({
@@ -48,7 +48,7 @@ pub fn expand_diagnostic_used<'cx>(ecx: &'cx mut ExtCtxt,
     with_registered_diagnostics(|diagnostics| {
         if !diagnostics.contains_key(&code.name) {
             ecx.span_err(span, format!(
-                "unknown diagnostic code {}; add to librustc/diagnostics.rs",
+                "unknown diagnostic code {}; add it to the crate's list of diagnostics",
                 token::get_ident(code).get()
             ).as_slice());
         }
@@ -44,7 +44,7 @@ impl State {
     }
 }
 
-static OPTIONS: &'static [&'static str] = &["volatile", "alignstack", "intel"];
+static OPTIONS: &'static [&'static str] = &["volatile", "alignstack", "intel", "noreturn"];
 
 pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                        -> Box<base::MacResult+'cx> {
@@ -54,8 +54,10 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
     let mut outputs = Vec::new();
     let mut inputs = Vec::new();
     let mut clobs = Vec::new();
+    let mut option_spans = Vec::new();
     let mut volatile = false;
     let mut alignstack = false;
+    let mut noreturn = None;
     let mut dialect = ast::AsmAtt;
 
     let mut state = Asm;
@@ -63,7 +65,7 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
     'statement: loop {
         match state {
             Asm => {
-                let (s, style) = match expr_to_string(cx, p.parse_expr(),
+                let (s, style) = match expr_to_string(cx, p.parse_expr_before_colon(),
                                                    "inline assembly must be a string literal") {
                     Some((s, st)) => (s, st),
                     // let compilation continue
@@ -81,13 +83,14 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                         p.eat(&token::Comma);
                     }
 
-                    let (constraint, _str_style) = p.parse_str();
+                    let (constraint, str_style) = p.parse_str();
 
                     let span = p.last_span;
 
                     p.expect(&token::OpenDelim(token::Paren));
                     let out = p.parse_expr();
                     p.expect(&token::CloseDelim(token::Paren));
+                    let operand_span = codemap::mk_sp(span.lo, p.last_span.hi);
 
                     // Expands a read+write operand into two operands.
                     //
@@ -104,13 +107,23 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                                         operand).as_slice()))
                         }
                         _ => {
-                            cx.span_err(span, "output operand constraint lacks '=' or '+'");
+                            // Point at where the modifier goes
+                            let first = constraint.get().chars().next()
+                                                  .map_or(0, |c| c.len_utf8());
+                            let modifier_span = str_sub_span(cx, span, constraint.get(),
+                                                             str_style, 0, first);
+                            struct_span_err!(cx, modifier_span, E0175,
+                                             "output operand constraint lacks '=' or '+'")
+                                .span_suggestion(span, "mark the operand as written",
+                                                 format!("\"={}\"",
+                                                         constraint.get().escape_default()))
+                                .emit();
                             None
                         }
                     };
 
                     let is_rw = output.is_some();
-                    outputs.push((output.unwrap_or(constraint), out, is_rw));
+                    outputs.push((output.unwrap_or(constraint), out, is_rw, operand_span));
                 }
             }
             Inputs => {
@@ -122,19 +135,30 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                         p.eat(&token::Comma);
                     }
 
-                    let (constraint, _str_style) = p.parse_str();
+                    let (constraint, str_style) = p.parse_str();
+
+                    let span = p.last_span;
+                    let modifier_span = str_sub_span(cx, span, constraint.get(), str_style, 0, 1);
 
                     if constraint.get().starts_with("=") {
-                        cx.span_err(p.last_span, "input operand constraint contains '='");
+                        struct_span_err!(cx, modifier_span, E0176,
+                                         "input operand constraint contains '='")
+                            .span_suggestion(span, "remove the '='",
+                                             format!("\"{}\"",
+                                                     constraint.get().slice_from(1)
+                                                               .escape_default()))
+                            .emit();
                     } else if constraint.get().starts_with("+") {
-                        cx.span_err(p.last_span, "input operand constraint contains '+'");
+                        span_err!(cx, modifier_span, E0177,
+                                  "input operand constraint contains '+'");
                     }
 
                     p.expect(&token::OpenDelim(token::Paren));
                     let input = p.parse_expr();
                     p.expect(&token::CloseDelim(token::Paren));
 
-                    inputs.push((constraint, input));
+                    let operand_span = codemap::mk_sp(span.lo, p.last_span.hi);
+                    inputs.push((constraint, input, operand_span));
                 }
             }
             Clobbers => {
@@ -147,15 +171,12 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                     }
 
                     let (s, _str_style) = p.parse_str();
-
-                    if OPTIONS.iter().any(|opt| s.equiv(opt)) {
-                        cx.span_warn(p.last_span, "expected a clobber, found an option");
-                    }
-                    clobs.push(s);
+                    clobs.push((s, p.last_span));
                 }
             }
             Options => {
                 let (option, _str_style) = p.parse_str();
+                option_spans.push(p.last_span);
 
                 if option.equiv(&("volatile")) {
                     // Indicates that the inline assembly has side effects
@@ -165,8 +186,12 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                     alignstack = true;
                 } else if option.equiv(&("intel")) {
                     dialect = ast::AsmIntel;
+                } else if option.equiv(&("noreturn")) {
+                    // The asm ends in a jump or a halt, so the code after
+                    // it is unreachable.
+                    noreturn = Some(p.last_span);
                 } else {
-                    cx.span_warn(p.last_span, "unrecognized option");
+                    cx.span_lint("unrecognized_asm_options", p.last_span, "unrecognized option");
                 }
 
                 if p.token == token::Comma {
@@ -196,6 +221,29 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
         }
     }
 
+    // The misplaced options are reported once the options are known, so the
+    // first one can suggest moving them all there.
+    let mut suggestion = options_suggestion(cx, clobs.as_slice(), option_spans.as_slice());
+    for &(ref s, sp) in clobs.iter() {
+        if !is_option(s) {
+            continue;
+        }
+        let msg = "expected a clobber, found an option";
+        match suggestion.take() {
+            Some((suggestion_sp, suggestion)) => {
+                cx.span_lint_with_suggestion("asm_misplaced_options", sp, msg, suggestion_sp,
+                                             "move the options after the clobbers",
+                                             suggestion);
+            }
+            None => cx.span_lint("asm_misplaced_options", sp, msg),
+        }
+    }
+
+    // Nothing runs after a `noreturn` asm to read its outputs.
+    if let (Some(noreturn_sp), false) = (noreturn, outputs.is_empty()) {
+        cx.span_err(noreturn_sp, "asm with the `noreturn` option can't have outputs");
+    }
+
     let expn_id = cx.codemap().record_expansion(codemap::ExpnInfo {
         call_site: sp,
         callee: codemap::NameAndSpan {
@@ -215,9 +263,82 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
             clobbers: clobs,
             volatile: volatile,
             alignstack: alignstack,
+            noreturn: noreturn.is_some(),
             dialect: dialect,
             expn_id: expn_id,
         }),
         span: sp
     }))
 }
+
+/// The span of bytes `lo` to `hi` of the contents of the string literal `s`
+/// at `sp`, to point at the offending characters of a constraint. It is the
+/// whole literal when its source text doesn't start with those bytes, as
+/// when they're escaped or the literal comes from a macro.
+fn str_sub_span(cx: &ExtCtxt, sp: Span, s: &str, style: ast::StrStyle,
+                lo: uint, hi: uint) -> Span {
+    let start = match style {
+        ast::CookedStr => 1,
+        ast::RawStr(hashes) => 2 + hashes,
+    };
+    match cx.codemap().span_to_snippet(sp) {
+        Some(ref snippet) if hi <= s.len() && start + hi <= snippet.len() &&
+                             snippet.as_bytes().slice(start, start + hi) ==
+                                 s.as_bytes().slice_to(hi) => {
+            sp.sub_span(start + lo, start + hi)
+        }
+        _ => sp,
+    }
+}
+
+fn is_option(s: &InternedString) -> bool {
+    OPTIONS.iter().any(|opt| s.equiv(opt))
+}
+
+/// The text to replace the clobbers and options of an `asm!` with, from the
+/// first clobber on, to move the options listed among the clobbers after
+/// them. There's nothing to suggest when none of the clobbers is an option,
+/// or when their source text isn't in one piece, as in macros.
+fn options_suggestion(cx: &ExtCtxt, clobs: &[(InternedString, Span)],
+                      option_spans: &[Span]) -> Option<(Span, String)> {
+    if !clobs.iter().any(|&(ref s, _)| is_option(s)) {
+        return None;
+    }
+
+    let cm = cx.codemap();
+    let mut kept = Vec::new();
+    let mut options = Vec::new();
+    for &sp in option_spans.iter() {
+        options.push(match cm.span_to_snippet(sp) {
+            Some(snippet) => snippet,
+            None => return None,
+        });
+    }
+    for &(ref s, sp) in clobs.iter() {
+        let snippet = match cm.span_to_snippet(sp) {
+            Some(snippet) => snippet,
+            None => return None,
+        };
+        if is_option(s) {
+            options.push(snippet);
+        } else {
+            kept.push(snippet);
+        }
+    }
+
+    let &(_, first) = &clobs[0];
+    let &(_, last) = &clobs[clobs.len() - 1];
+    let hi = option_spans.last().map_or(last.hi, |sp| sp.hi);
+    let sp = Span { lo: first.lo, hi: hi, expn_id: first.expn_id };
+    if sp.hi < sp.lo || cm.span_to_snippet(sp).is_none() {
+        return None;
+    }
+
+    let options = options.connect(", ");
+    let suggestion = if kept.is_empty() {
+        format!(": {}", options)
+    } else {
+        format!("{} : {}", kept.connect(", "), options)
+    };
+    Some((sp, suggestion))
+}
@@ -13,7 +13,8 @@ pub use self::SyntaxExtension::*;
 use ast;
 use ast::Name;
 use codemap;
-use codemap::{CodeMap, Span, ExpnId, ExpnInfo, NO_EXPANSION};
+use codemap::{BytePos, CodeMap, Span, ExpnId, ExpnInfo, NO_EXPANSION, MacroAttribute, MacroBang};
+use diagnostic::DiagnosticBuilder;
 use ext;
 use ext::expand;
 use parse;
@@ -25,8 +26,10 @@ use util::small_vector::SmallVector;
 use ext::mtwt;
 use fold::Folder;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::{Occupied, Vacant};
 use std::rc::Rc;
+use std::time::Duration;
 
 // new-style macro! tt code:
 //
@@ -448,6 +451,63 @@ fn initial_syntax_expander_table(ecfg: &expand::ExpansionConfig) -> SyntaxEnv {
     syntax_expanders
 }
 
+/// The time spent in the expanders of each macro, which is printed after
+/// expansion with `-Z time-macros`.
+pub struct MacroTimings {
+    /// The names of the macros registered by plugins
+    pub plugin_macros: HashSet<String>,
+    /// The timings of each macro, by the way it's shown (`name!` or
+    /// `#[name]`)
+    by_macro: HashMap<String, MacroTiming>,
+}
+
+struct MacroTiming {
+    /// Where the macro comes from: "builtin", "macro_rules" or "plugin"
+    kind: &'static str,
+    invocations: uint,
+    total: Duration,
+}
+
+impl MacroTimings {
+    pub fn new() -> MacroTimings {
+        MacroTimings {
+            plugin_macros: HashSet::new(),
+            by_macro: HashMap::new(),
+        }
+    }
+
+    fn record(&mut self, name: String, kind: &'static str, dur: Duration) {
+        let timing = match self.by_macro.entry(name) {
+            Vacant(entry) => entry.set(MacroTiming {
+                kind: kind,
+                invocations: 0,
+                total: Duration::zero(),
+            }),
+            Occupied(entry) => entry.into_mut(),
+        };
+        timing.invocations += 1;
+        timing.total = timing.total + dur;
+    }
+
+    /// Prints the macros that took the longest first. The time of a macro
+    /// includes that of the macros its expander expands itself.
+    pub fn print(&self) {
+        let mut macros: Vec<(&String, &MacroTiming)> = self.by_macro.iter().collect();
+        macros.sort_by(|&(a_name, a), &(b_name, b)| {
+            match b.total.cmp(&a.total) {
+                Equal => a_name.cmp(b_name),
+                ordering => ordering,
+            }
+        });
+        for &(name, timing) in macros.iter() {
+            println!("time: {}.{:03} \t{} ({}, {} invocation{})",
+                     timing.total.num_seconds(), timing.total.num_milliseconds() % 1000,
+                     name, timing.kind, timing.invocations,
+                     if timing.invocations == 1 { "" } else { "s" });
+        }
+    }
+}
+
 /// One of these is made during expansion and incrementally updated as we go;
 /// when a macro expansion occurs, the resulting nodes have the backtrace()
 /// -> expn_info of their expansion context stored into their span.
@@ -463,12 +523,22 @@ pub struct ExtCtxt<'a> {
 
     pub syntax_env: SyntaxEnv,
     pub recursion_count: uint,
+
+    /// The time spent in each macro, with `-Z time-macros`.
+    pub macro_timings: Option<MacroTimings>,
+
+    /// The names and spans of the `macro_rules!` definitions of the crate
+    /// that aren't exported, to report those that are never invoked.
+    pub local_macros: Vec<(String, Span)>,
+    /// Where the macros that have been invoked start.
+    pub used_macros: HashSet<BytePos>,
 }
 
 impl<'a> ExtCtxt<'a> {
     pub fn new<'a>(parse_sess: &'a parse::ParseSess, cfg: ast::CrateConfig,
                    ecfg: expand::ExpansionConfig) -> ExtCtxt<'a> {
         let env = initial_syntax_expander_table(&ecfg);
+        let macro_timings = if ecfg.time_macros { Some(MacroTimings::new()) } else { None };
         ExtCtxt {
             parse_sess: parse_sess,
             cfg: cfg,
@@ -479,6 +549,9 @@ impl<'a> ExtCtxt<'a> {
             exported_macros: Vec::new(),
             syntax_env: env,
             recursion_count: 0,
+            macro_timings: macro_timings,
+            local_macros: Vec::new(),
+            used_macros: HashSet::new(),
         }
     }
 
@@ -497,6 +570,14 @@ impl<'a> ExtCtxt<'a> {
         parse::tts_to_parser(self.parse_sess, tts.to_vec(), self.cfg())
     }
 
+    /// Reserves `count` consecutive NodeIds and returns the first of them.
+    /// Nodes built by an extension may be given ids from this range instead
+    /// of `DUMMY_NODE_ID`; they keep them through the rest of compilation,
+    /// so tables keyed by them can be built during expansion.
+    pub fn reserve_node_ids(&self, count: ast::NodeId) -> ast::NodeId {
+        self.parse_sess.reserve_node_ids_for_expansion(count)
+    }
+
     pub fn codemap(&self) -> &'a CodeMap { &self.parse_sess.span_diagnostic.cm }
     pub fn parse_sess(&self) -> &'a parse::ParseSess { self.parse_sess }
     pub fn cfg(&self) -> ast::CrateConfig { self.cfg.clone() }
@@ -560,6 +641,10 @@ impl<'a> ExtCtxt<'a> {
                                     ei.callee.name).as_slice());
         }
 
+        if let Some(def_site) = ei.callee.span {
+            self.used_macros.insert(def_site.lo);
+        }
+
         let mut call_site = ei.call_site;
         call_site.expn_id = self.backtrace;
         self.backtrace = self.codemap().record_expansion(ExpnInfo {
@@ -567,6 +652,43 @@ impl<'a> ExtCtxt<'a> {
             callee: ei.callee
         });
     }
+    /// Runs `f`, the expander of the macro whose expansion was just pushed
+    /// with `bt_push`, adding the time it takes to that macro's total when
+    /// macros are being timed.
+    pub fn time_macro<T, U>(&mut self, u: U, f: |&mut ExtCtxt<'a>, U| -> T) -> T {
+        if self.macro_timings.is_none() {
+            return f(self, u);
+        }
+
+        let mut u = Some(u);
+        let mut rv = None;
+        let dur = Duration::span(|| {
+            rv = Some(f(self, u.take().unwrap()))
+        });
+
+        let callee = self.codemap().with_expn_info(self.backtrace, |ei| {
+            ei.map(|ei| ei.callee.clone())
+        });
+        let callee = match callee {
+            Some(callee) => callee,
+            None => self.bug("timed a macro without pushing its expansion"),
+        };
+        let timings = self.macro_timings.as_mut().unwrap();
+        let kind = if callee.span.is_some() {
+            "macro_rules"
+        } else if timings.plugin_macros.contains(&callee.name) {
+            "plugin"
+        } else {
+            "builtin"
+        };
+        let name = match callee.format {
+            MacroAttribute => format!("#[{}]", callee.name),
+            MacroBang => format!("{}!", callee.name),
+        };
+        timings.record(name, kind, dur);
+
+        rv.unwrap()
+    }
     pub fn bt_pop(&mut self) {
         match self.backtrace {
             NO_EXPANSION => self.bug("tried to pop without a push"),
@@ -603,10 +725,53 @@ impl<'a> ExtCtxt<'a> {
         self.print_backtrace();
         self.parse_sess.span_diagnostic.span_err(sp, msg);
     }
+    pub fn span_err_with_code(&self, sp: Span, msg: &str, code: &str) {
+        self.print_backtrace();
+        self.parse_sess.span_diagnostic.span_err_with_code(sp, msg, code);
+    }
     pub fn span_warn(&self, sp: Span, msg: &str) {
         self.print_backtrace();
         self.parse_sess.span_diagnostic.span_warn(sp, msg);
     }
+    pub fn span_warn_with_code(&self, sp: Span, msg: &str, code: &str) {
+        self.print_backtrace();
+        self.parse_sess.span_diagnostic.span_warn_with_code(sp, msg, code);
+    }
+    /// Starts building an error at `sp`, with notes, help messages and
+    /// suggestions added by chained calls. Nothing is reported until `emit`
+    /// is called on the result.
+    pub fn struct_span_err(&self, sp: Span, msg: &str) -> DiagnosticBuilder<'a> {
+        self.print_backtrace();
+        self.parse_sess.span_diagnostic.struct_span_err(sp, msg)
+    }
+    pub fn struct_span_err_with_code(&self, sp: Span, msg: &str,
+                                     code: &str) -> DiagnosticBuilder<'a> {
+        self.print_backtrace();
+        self.parse_sess.span_diagnostic.struct_span_err_with_code(sp, msg, code)
+    }
+    /// Like `struct_span_err`, for a warning.
+    pub fn struct_span_warn(&self, sp: Span, msg: &str) -> DiagnosticBuilder<'a> {
+        self.print_backtrace();
+        self.parse_sess.span_diagnostic.struct_span_warn(sp, msg)
+    }
+    /// Reports a warning as the lint `lint_name`, so that it can be allowed
+    /// or denied where the macro is invoked, like any other lint.
+    pub fn span_lint(&self, lint_name: &'static str, sp: Span, msg: &str) {
+        self.parse_sess.buffer_lint(lint_name, sp, msg, Vec::new());
+    }
+    /// Like `span_lint`, with help messages to show after the lint.
+    pub fn span_lint_with_helps(&self, lint_name: &'static str, sp: Span, msg: &str,
+                                helps: Vec<String>) {
+        self.parse_sess.buffer_lint(lint_name, sp, msg, helps);
+    }
+    /// Like `span_lint`, suggesting `suggestion` for the text of
+    /// `suggestion_sp` after the lint.
+    pub fn span_lint_with_suggestion(&self, lint_name: &'static str, sp: Span, msg: &str,
+                                     suggestion_sp: Span, suggestion_msg: &str,
+                                     suggestion: String) {
+        self.parse_sess.buffer_lint_with_suggestion(lint_name, sp, msg, suggestion_sp,
+                                                    suggestion_msg, suggestion);
+    }
     pub fn span_unimpl(&self, sp: Span, msg: &str) -> ! {
         self.print_backtrace();
         self.parse_sess.span_diagnostic.span_unimpl(sp, msg);
@@ -623,6 +788,10 @@ impl<'a> ExtCtxt<'a> {
         self.print_backtrace();
         self.parse_sess.span_diagnostic.span_help(sp, msg);
     }
+    pub fn span_suggestion(&self, sp: Span, msg: &str, suggestion: String) {
+        self.print_backtrace();
+        self.parse_sess.span_diagnostic.span_suggestion(sp, msg, suggestion);
+    }
     pub fn bug(&self, msg: &str) -> ! {
         self.print_backtrace();
         self.parse_sess.span_diagnostic.handler().bug(msg);
@@ -633,6 +802,11 @@ impl<'a> ExtCtxt<'a> {
     pub fn set_trace_macros(&mut self, x: bool) {
         self.trace_mac = x
     }
+    /// Emits a note at `sp` describing the syntax context of `id` (see
+    /// `mtwt::describe_ident`), to debug hygiene in a syntax extension.
+    pub fn note_ident_hygiene(&self, sp: Span, id: ast::Ident) {
+        self.span_note(sp, format!("hygiene of `{}`", mtwt::describe_ident(id)).as_slice());
+    }
     pub fn ident_of(&self, st: &str) -> ast::Ident {
         str_to_ident(st)
     }
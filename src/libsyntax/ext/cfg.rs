@@ -30,7 +30,7 @@ pub fn expand_cfg<'cx>(cx: &mut ExtCtxt,
     let cfg = p.parse_meta_item();
 
     if !p.eat(&token::Eof) {
-        cx.span_err(sp, "expected 1 cfg-pattern");
+        cx.span_err(p.span, "expected 1 cfg-pattern");
         return DummyResult::expr(sp);
     }
 
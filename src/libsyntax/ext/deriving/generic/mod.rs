@@ -418,13 +418,12 @@ impl<'a> TraitDef<'a> {
                        ty_param.unbound.clone(),
                        None)
         }));
+        // Carry over the `where` clause of the type, since its bounds are
+        // required just like the ones declared on the type parameters.
         let trait_generics = Generics {
             lifetimes: lifetimes,
             ty_params: OwnedSlice::from_vec(ty_params),
-            where_clause: ast::WhereClause {
-                id: ast::DUMMY_NODE_ID,
-                predicates: Vec::new(),
-            },
+            where_clause: generics.where_clause.clone(),
         };
 
         // Create the reference to the trait.
@@ -51,10 +51,10 @@ pub fn expand_meta_deriving(cx: &mut ExtCtxt,
             cx.span_err(l.span, "unexpected value in `deriving`");
         }
         MetaWord(_) => {
-            cx.span_warn(mitem.span, "empty trait list in `deriving`");
+            cx.span_lint("unused_attributes", mitem.span, "empty trait list in `deriving`");
         }
         MetaList(_, ref titems) if titems.len() == 0 => {
-            cx.span_warn(mitem.span, "empty trait list in `deriving`");
+            cx.span_lint("unused_attributes", mitem.span, "empty trait list in `deriving`");
         }
         MetaList(_, ref titems) => {
             for titem in titems.iter().rev() {
@@ -19,7 +19,7 @@ use ext::build::AstBuilder;
 use attr;
 use attr::AttrMetaMethods;
 use codemap;
-use codemap::{Span, Spanned, ExpnInfo, NameAndSpan, MacroBang, MacroAttribute};
+use codemap::{BytePos, Span, Spanned, ExpnInfo, NameAndSpan, MacroBang, MacroAttribute};
 use ext::base::*;
 use fold;
 use fold::*;
@@ -31,12 +31,24 @@ use util::small_vector::SmallVector;
 use visit;
 use visit::Visitor;
 
+use std::mem;
+
 enum Either<L,R> {
     Left(L),
     Right(R)
 }
 
 pub fn expand_expr(e: P<ast::Expr>, fld: &mut MacroExpander) -> P<ast::Expr> {
+    match e.node {
+        ast::ExprMac(..) | ast::ExprWhile(..) | ast::ExprWhileLet(..) |
+        ast::ExprIfLet(..) | ast::ExprIf(..) | ast::ExprLoop(..) |
+        ast::ExprForLoop(..) | ast::ExprClosure(..) | ast::ExprProc(..) |
+        ast::ExprInclusiveRange(..) | ast::ExprTry(..) => {}
+        // Nothing to expand or desugar at this node, so only its children
+        // are folded and the node keeps its allocation.
+        _ => return e.map(|e| noop_fold_expr(e, fld))
+    }
+
     e.and_then(|ast::Expr {id, node, span}| match node {
         // expr_mac should really be expr_ext or something; it's the
         // entry-point for all syntax extensions.
@@ -56,7 +68,7 @@ pub fn expand_expr(e: P<ast::Expr>, fld: &mut MacroExpander) -> P<ast::Expr> {
             fld.cx.bt_pop();
 
             fully_expanded.map(|e| ast::Expr {
-                id: ast::DUMMY_NODE_ID,
+                id: e.id,
                 node: e.node,
                 span: span,
             })
@@ -104,6 +116,71 @@ pub fn expand_expr(e: P<ast::Expr>, fld: &mut MacroExpander) -> P<ast::Expr> {
             fld.cx.expr(span, ast::ExprLoop(loop_block, opt_ident))
         }
 
+        // Desugar ExprInclusiveRange
+        // From: `<start> ... <end>`
+        ast::ExprInclusiveRange(start, end) => {
+            // to: `::std::iter::range_inclusive(<start>, <end>)`
+            let path = vec![fld.cx.ident_of("std"),
+                            fld.cx.ident_of("iter"),
+                            fld.cx.ident_of("range_inclusive")];
+            let call = fld.cx.expr_call_global(span, path, vec![start, end]);
+            fld.fold_expr(call)
+        }
+
+        // Desugar ExprTry
+        // From: `<expr>?`
+        ast::ExprTry(sub) => {
+            // to, just as `try!(<expr>)`:
+            //
+            //   match <expr> {
+            //     ::std::result::Result::Ok(val) => val,
+            //     ::std::result::Result::Err(err) =>
+            //       return ::std::result::Result::Err(
+            //         ::std::error::FromError::from_error(err))
+            //   }
+            //
+            // with every generated piece spanned at the `?`.
+            let qspan = codemap::mk_sp(span.hi - BytePos(1), span.hi);
+            let ok_path = fld.cx.path_global(qspan, vec![fld.cx.ident_of("std"),
+                                                         fld.cx.ident_of("result"),
+                                                         fld.cx.ident_of("Result"),
+                                                         fld.cx.ident_of("Ok")]);
+            let err_path = fld.cx.path_global(qspan, vec![fld.cx.ident_of("std"),
+                                                          fld.cx.ident_of("result"),
+                                                          fld.cx.ident_of("Result"),
+                                                          fld.cx.ident_of("Err")]);
+            let val = fld.cx.ident_of("__try_val");
+            let err = fld.cx.ident_of("__try_err");
+
+            // `Ok(val) => val`
+            let ok_arm = {
+                let pat = fld.cx.pat_enum(qspan, ok_path,
+                                          vec![fld.cx.pat_ident(qspan, val)]);
+                fld.cx.arm(qspan, vec![pat], fld.cx.expr_ident(qspan, val))
+            };
+
+            // `Err(err) => return Err(FromError::from_error(err))`
+            let err_arm = {
+                let pat = fld.cx.pat_enum(qspan, err_path.clone(),
+                                          vec![fld.cx.pat_ident(qspan, err)]);
+                let from_error = fld.cx.expr_call_global(
+                    qspan,
+                    vec![fld.cx.ident_of("std"),
+                         fld.cx.ident_of("error"),
+                         fld.cx.ident_of("FromError"),
+                         fld.cx.ident_of("from_error")],
+                    vec![fld.cx.expr_ident(qspan, err)]);
+                let err_expr = fld.cx.expr_call(qspan,
+                                                fld.cx.expr_path(err_path),
+                                                vec![from_error]);
+                let ret = fld.cx.expr(qspan, ast::ExprRet(Some(err_expr)));
+                fld.cx.arm(qspan, vec![pat], ret)
+            };
+
+            let match_expr = fld.cx.expr_match(span, sub, vec![ok_arm, err_arm]);
+            fld.fold_expr(match_expr)
+        }
+
         // Desugar ExprIfLet
         // From: `if let <pat> = <expr> <body> [<elseopt>]`
         ast::ExprIfLet(pat, expr, body, mut elseopt) => {
@@ -224,13 +301,7 @@ pub fn expand_expr(e: P<ast::Expr>, fld: &mut MacroExpander) -> P<ast::Expr> {
             P(ast::Expr{id:id, node: new_node, span: fld.new_span(span)})
         }
 
-        _ => {
-            P(noop_fold_expr(ast::Expr {
-                id: id,
-                node: node,
-                span: span
-            }, fld))
-        }
+        _ => unreachable!()
     })
 }
 
@@ -291,9 +362,10 @@ fn expand_mac_invoc<T>(mac: ast::Mac, span: codemap::Span,
                         let mac_span = fld.cx.original_span();
 
                         let opt_parsed = {
-                            let expanded = expandfun.expand(fld.cx,
-                                                            mac_span,
-                                                            marked_before.as_slice());
+                            let expanded = fld.cx.time_macro(marked_before.as_slice(),
+                                                             |cx, tts| {
+                                expandfun.expand(cx, mac_span, tts)
+                            });
                             parse_thunk(expanded)
                         };
                         let parsed = match opt_parsed {
@@ -396,8 +468,10 @@ pub fn expand_item(it: P<ast::Item>, fld: &mut MacroExpander)
                     // we'd ideally decorator_items.push_all(expand_item(item, fld)),
                     // but that double-mut-borrows fld
                     let mut items: SmallVector<P<ast::Item>> = SmallVector::zero();
-                    dec.expand(fld.cx, attr.span, &*attr.node.value, &*it,
-                               |item| items.push(item));
+                    fld.cx.time_macro(&*it, |cx, it| {
+                        dec.expand(cx, attr.span, &*attr.node.value, it,
+                                   |item| items.push(item))
+                    });
                     decorator_items.extend(items.into_iter()
                         .flat_map(|item| expand_item(item, fld).into_iter()));
 
@@ -474,7 +548,9 @@ fn expand_item_modifiers(mut it: P<ast::Item>, fld: &mut MacroExpander)
                             span: None,
                         }
                     });
-                    it = mac.expand(fld.cx, attr.span, &*attr.node.value, it);
+                    it = fld.cx.time_macro(it, |cx, it| {
+                        mac.expand(cx, attr.span, &*attr.node.value, it)
+                    });
                     fld.cx.bt_pop();
                 }
                 _ => unreachable!()
@@ -500,6 +576,42 @@ fn expand_item_underscore(item: ast::Item_, fld: &mut MacroExpander) -> ast::Ite
     }
 }
 
+/// The lint level attributes of a macro invocation. They apply to the items
+/// and methods the macro generates as if they enclosed them. (Decorators
+/// such as `deriving` pass on those of the decorated item themselves.)
+fn lint_level_attrs(attrs: &[ast::Attribute]) -> Vec<ast::Attribute> {
+    attrs.iter().filter(|attr| {
+        match attr.name().get() {
+            "allow" | "warn" | "deny" | "forbid" => true,
+            _ => false
+        }
+    }).map(|attr| attr.clone()).collect()
+}
+
+/// Puts `outer` ahead of `attrs`, so that the lint levels a generated item
+/// sets itself override those of the invocation, as an item's do those of
+/// the module around it.
+fn prepend_attrs(outer: &[ast::Attribute], attrs: &mut Vec<ast::Attribute>) {
+    if outer.is_empty() {
+        return;
+    }
+    let own = mem::replace(attrs, outer.to_vec());
+    attrs.extend(own.into_iter());
+}
+
+/// Whether `attrs` allow `unused_macros`, directly or through the `unused`
+/// group. Macro definitions are gone by the time lints are emitted, so their
+/// own attributes are checked here.
+fn allows_unused_macros(attrs: &[ast::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.name().equiv(&("allow")) && attr.meta_item_list().map_or(false, |items| {
+            items.iter().any(|item| {
+                item.name().equiv(&("unused_macros")) || item.name().equiv(&("unused"))
+            })
+        })
+    })
+}
+
 // does this attribute list contain "macro_escape" ?
 fn contains_macro_escape(attrs: &[ast::Attribute]) -> bool {
     attr::contains_name(attrs, "macro_escape")
@@ -520,6 +632,7 @@ pub fn expand_item_mac(it: P<ast::Item>, fld: &mut MacroExpander)
     };
 
     let extnamestr = token::get_ident(extname);
+    let lint_attrs = lint_level_attrs(it.attrs.as_slice());
     let fm = fresh_mark();
     let def_or_items = {
         let mut expanded = match fld.cx.syntax_env.find(&extname.name) {
@@ -552,7 +665,9 @@ pub fn expand_item_mac(it: P<ast::Item>, fld: &mut MacroExpander)
                     });
                     // mark before expansion:
                     let marked_before = mark_tts(tts.as_slice(), fm);
-                    expander.expand(fld.cx, it.span, marked_before.as_slice())
+                    fld.cx.time_macro(marked_before.as_slice(), |cx, tts| {
+                        expander.expand(cx, it.span, tts)
+                    })
                 }
                 IdentTT(ref expander, span) => {
                     if it.ident.name == parse::token::special_idents::invalid.name {
@@ -571,7 +686,9 @@ pub fn expand_item_mac(it: P<ast::Item>, fld: &mut MacroExpander)
                     });
                     // mark before expansion:
                     let marked_tts = mark_tts(tts.as_slice(), fm);
-                    expander.expand(fld.cx, it.span, it.ident, marked_tts)
+                    fld.cx.time_macro(marked_tts, |cx, tts| {
+                        expander.expand(cx, it.span, it.ident, tts)
+                    })
                 }
                 LetSyntaxTT(ref expander, span) => {
                     if it.ident.name == parse::token::special_idents::invalid.name {
@@ -589,7 +706,9 @@ pub fn expand_item_mac(it: P<ast::Item>, fld: &mut MacroExpander)
                         }
                     });
                     // DON'T mark before expansion:
-                    expander.expand(fld.cx, it.span, it.ident, tts)
+                    fld.cx.time_macro(tts, |cx, tts| {
+                        expander.expand(cx, it.span, it.ident, tts)
+                    })
                 }
                 _ => {
                     fld.cx.span_err(it.span,
@@ -615,12 +734,15 @@ pub fn expand_item_mac(it: P<ast::Item>, fld: &mut MacroExpander)
             fld.cx.syntax_env.insert(intern(name.as_slice()), ext);
             if attr::contains_name(it.attrs.as_slice(), "macro_export") {
                 fld.cx.exported_macros.push(it);
+            } else if !allows_unused_macros(it.attrs.as_slice()) {
+                fld.cx.local_macros.push((name, it.span));
             }
             SmallVector::zero()
         }
         Right(Some(items)) => {
             items.into_iter()
                 .map(|i| mark_item(i, fm))
+                .map(|i| i.map_in_place(|i| prepend_attrs(lint_attrs.as_slice(), &mut i.attrs)))
                 .flat_map(|i| fld.fold_item(i).into_iter())
                 .collect()
         }
@@ -895,9 +1017,10 @@ fn expand_pat(p: P<ast::Pat>, fld: &mut MacroExpander) -> P<ast::Pat> {
                     let fm = fresh_mark();
                     let marked_before = mark_tts(tts.as_slice(), fm);
                     let mac_span = fld.cx.original_span();
-                    let expanded = match expander.expand(fld.cx,
-                                        mac_span,
-                                        marked_before.as_slice()).make_pat() {
+                    let expanded = fld.cx.time_macro(marked_before.as_slice(), |cx, tts| {
+                        expander.expand(cx, mac_span, tts)
+                    });
+                    let expanded = match expanded.make_pat() {
                         Some(e) => e,
                         None => {
                             fld.cx.span_err(
@@ -1020,6 +1143,7 @@ fn expand_method(m: P<ast::Method>, fld: &mut MacroExpander) -> SmallVector<P<as
                 }))
         },
         ast::MethMac(mac) => {
+            let lint_attrs = lint_level_attrs(m.attrs.as_slice());
             let maybe_new_methods =
                 expand_mac_invoc(mac, m.span,
                                  |r| r.make_methods(),
@@ -1033,6 +1157,9 @@ fn expand_method(m: P<ast::Method>, fld: &mut MacroExpander) -> SmallVector<P<as
 
             // expand again if necessary
             let new_methods = new_methods.into_iter()
+                                  .map(|m| m.map_in_place(|m| {
+                                      prepend_attrs(lint_attrs.as_slice(), &mut m.attrs)
+                                  }))
                                   .flat_map(|m| fld.fold_method(m).into_iter()).collect();
             fld.cx.bt_pop();
             new_methods
@@ -1082,7 +1209,21 @@ impl<'a, 'b> Folder for MacroExpander<'a, 'b> {
     }
 
     fn fold_stmt(&mut self, stmt: P<ast::Stmt>) -> SmallVector<P<ast::Stmt>> {
-        stmt.and_then(|stmt| expand_stmt(stmt, self))
+        match stmt.node {
+            // Expression statements always fold to a single statement, so
+            // they can be folded without giving up their allocation.
+            StmtExpr(..) | StmtSemi(..) => {
+                SmallVector::one(stmt.map(|Spanned {node, span}| Spanned {
+                    node: match node {
+                        StmtExpr(e, id) => StmtExpr(self.fold_expr(e), self.new_id(id)),
+                        StmtSemi(e, id) => StmtSemi(self.fold_expr(e), self.new_id(id)),
+                        _ => unreachable!()
+                    },
+                    span: self.new_span(span)
+                }))
+            }
+            _ => stmt.and_then(|stmt| expand_stmt(stmt, self))
+        }
     }
 
     fn fold_block(&mut self, block: P<Block>) -> P<Block> {
@@ -1116,6 +1257,9 @@ pub struct ExpansionConfig {
     pub deriving_hash_type_parameter: bool,
     pub enable_quotes: bool,
     pub recursion_limit: uint,
+    /// Whether to time the expansions of each macro, printing the totals
+    /// once the crate is expanded
+    pub time_macros: bool,
 }
 
 impl ExpansionConfig {
@@ -1125,13 +1269,28 @@ impl ExpansionConfig {
             deriving_hash_type_parameter: false,
             enable_quotes: false,
             recursion_limit: 64,
+            time_macros: false,
         }
     }
 }
 
+/// The definition of a macro exported by another crate.
+#[deriving(Clone)]
+pub struct ExportedMacro {
+    pub source: String,
+    /// The file the definition is in, or empty if unknown.
+    pub filename: String,
+    /// The 0-based line and column the definition starts at in `filename`.
+    pub line: uint,
+    pub col: uint,
+    /// If the definition was produced by macro expansion in its crate, the
+    /// expansions it came from, innermost first, as `name! at file:line:col`.
+    pub expansion: Vec<String>,
+}
+
 pub struct ExportedMacros {
     pub crate_name: Ident,
-    pub macros: Vec<String>,
+    pub macros: Vec<ExportedMacro>,
 }
 
 pub fn expand_crate(parse_sess: &parse::ParseSess,
@@ -1146,26 +1305,53 @@ pub fn expand_crate(parse_sess: &parse::ParseSess,
     };
 
     for ExportedMacros { crate_name, macros } in imported_macros.into_iter() {
-        let name = format!("<{} macros>", token::get_ident(crate_name))
-            .into_string();
-
-        for source in macros.into_iter() {
-            let item = parse::parse_item_from_source_str(name.clone(),
-                                                         source,
-                                                         expander.cx.cfg(),
-                                                         expander.cx.parse_sess())
-                    .expect("expected a serialized item");
+        for def in macros.into_iter() {
+            // Give each definition its own FileMap, named after the file it
+            // came from so that spans into it point there.
+            let filename = if def.filename.is_empty() {
+                format!("<{} macros>", token::get_ident(crate_name))
+            } else {
+                def.filename
+            };
+            let filemap = parse_sess.span_diagnostic.cm
+                                    .new_imported_filemap(filename,
+                                                          token::get_ident(crate_name)
+                                                              .get().to_string(),
+                                                          def.line, def.col,
+                                                          def.source, def.expansion);
+            let mut p = parse::filemap_to_parser(expander.cx.parse_sess(),
+                                                 filemap,
+                                                 expander.cx.cfg());
+            let item = p.parse_item_with_outer_attributes()
+                        .expect("expected a serialized item");
+            p.abort_if_errors();
             expand_item_mac(item, &mut expander);
         }
     }
 
+    // Only the crate's own macros can be unused
+    expander.cx.local_macros.clear();
+
     for (name, extension) in user_exts.into_iter() {
+        if let Some(ref mut timings) = expander.cx.macro_timings {
+            timings.plugin_macros.insert(token::get_name(name).get().to_string());
+        }
         expander.cx.syntax_env.insert(name, extension);
     }
 
     let mut ret = expander.fold_crate(c);
     ret.exported_macros = expander.cx.exported_macros.clone();
-    parse_sess.span_diagnostic.handler().abort_if_errors();
+    for &(ref name, sp) in expander.cx.local_macros.iter() {
+        if !expander.cx.used_macros.contains(&sp.lo) {
+            expander.cx.span_lint("unused_macros", sp,
+                                  format!("unused macro definition: `{}`", name).as_slice());
+        }
+    }
+    if let Some(ref timings) = expander.cx.macro_timings {
+        timings.print();
+    }
+    // Errors aren't fatal yet: the lints buffered during expansion are only
+    // emitted by the early lint pass, so the caller aborts after it.
     return ret;
 }
 
@@ -1337,6 +1523,7 @@ mod test {
             Vec::new(), &sess);
         // should fail:
         expand_crate(&sess,test_ecfg(),vec!(),vec!(),crate_ast);
+        sess.span_diagnostic.handler().abort_if_errors();
     }
 
     // make sure that macros can't escape modules
@@ -1350,6 +1537,7 @@ mod test {
             src,
             Vec::new(), &sess);
         expand_crate(&sess,test_ecfg(),vec!(),vec!(),crate_ast);
+        sess.span_diagnostic.handler().abort_if_errors();
     }
 
     // macro_escape modules should allow macros to escape
@@ -1774,3 +1962,51 @@ foo_module!()
 
 
 }
+
+#[cfg(test)]
+mod bench {
+    use super::{expand_crate, ExpansionConfig};
+    use ast;
+    use config;
+    use parse;
+    use test::Bencher;
+    use util::parser_testing::string_to_parser;
+
+    // A crate without macros, so that expanding it and stripping its cfgs
+    // only fold its nodes, which keep their allocations.
+    fn macro_free_crate(ps: &parse::ParseSess) -> ast::Crate {
+        let mut src = String::new();
+        for _ in range(0u, 200) {
+            src.push_str("fn f(x: int) -> int { \
+                              let y = x + 1; \
+                              if y > 2 { y * 3 } else { match y { 0 => 1, _ => y } } \
+                          }\n");
+        }
+        string_to_parser(ps, src).parse_crate_mod()
+    }
+
+    #[bench]
+    fn expand_macro_free_crate(b: &mut Bencher) {
+        let ps = parse::new_parse_sess();
+        let krate = macro_free_crate(&ps);
+        b.iter(|| {
+            expand_crate(&ps, ExpansionConfig::default("bench".to_string()),
+                         vec!(), vec!(), krate.clone())
+        });
+    }
+
+    #[bench]
+    fn strip_cfgs_of_macro_free_crate(b: &mut Bencher) {
+        let ps = parse::new_parse_sess();
+        let krate = macro_free_crate(&ps);
+        b.iter(|| config::strip_unconfigured_items(&ps.span_diagnostic, krate.clone()));
+    }
+
+    #[bench]
+    fn clone_macro_free_crate(b: &mut Bencher) {
+        // What the benchmarks above spend cloning their input.
+        let ps = parse::new_parse_sess();
+        let krate = macro_free_crate(&ps);
+        b.iter(|| krate.clone());
+    }
+}
@@ -18,10 +18,13 @@
 pub use self::SyntaxContext_::*;
 
 use ast::{Ident, Mrk, Name, SyntaxContext};
+use parse::token;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::hash_map::{Occupied, Vacant};
+use std::mem;
+use std::rc::Rc;
 
 /// The SCTable contains a table of SyntaxContext_'s. It
 /// represents a flattened tree structure, to avoid having
@@ -102,10 +105,39 @@ pub fn apply_renames(renames: &RenameList, ctxt: SyntaxContext) -> SyntaxContext
     })
 }
 
+/// The hygiene tables of a session: the syntax contexts it has created
+/// and the memoized resolutions of identifiers in them. Cloning the
+/// tables shares them.
+#[deriving(Clone)]
+pub struct HygieneTables {
+    sctable: Rc<SCTable>,
+    resolve_table: Rc<RefCell<ResolveTable>>,
+}
+
+impl HygieneTables {
+    /// Makes a set of tables holding only the empty and illegal contexts.
+    pub fn new() -> HygieneTables {
+        HygieneTables {
+            sctable: Rc::new(new_sctable_internal()),
+            resolve_table: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+}
+
+thread_local!(static HYGIENE_TABLES: RefCell<HygieneTables> = {
+    RefCell::new(HygieneTables::new())
+})
+
+/// Makes `tables` the hygiene tables of the current thread, returning the
+/// ones they replace.
+pub fn set_tables(tables: HygieneTables) -> HygieneTables {
+    HYGIENE_TABLES.with(|slot| mem::replace(&mut *slot.borrow_mut(), tables))
+}
+
 /// Fetch the SCTable from TLS, create one if it doesn't yet exist.
 pub fn with_sctable<T>(op: |&SCTable| -> T) -> T {
-    thread_local!(static SCTABLE_KEY: SCTable = new_sctable_internal())
-    SCTABLE_KEY.with(|slot| op(slot))
+    let table = HYGIENE_TABLES.with(|slot| slot.borrow().sctable.clone());
+    op(&*table)
 }
 
 // Make a fresh syntax context table with EmptyCtxt in slot zero
@@ -164,13 +196,11 @@ pub fn resolve(id: Ident) -> Name {
 type ResolveTable = HashMap<(Name,SyntaxContext),Name>;
 
 // okay, I admit, putting this in TLS is not so nice:
-// fetch the SCTable from TLS, create one if it doesn't yet exist.
+// fetch the resolve table of the current thread's hygiene tables.
 fn with_resolve_table_mut<T>(op: |&mut ResolveTable| -> T) -> T {
-    thread_local!(static RESOLVE_TABLE_KEY: RefCell<ResolveTable> = {
-        RefCell::new(HashMap::new())
-    })
-
-    RESOLVE_TABLE_KEY.with(|slot| op(&mut *slot.borrow_mut()))
+    let table = HYGIENE_TABLES.with(|slot| slot.borrow().resolve_table.clone());
+    let mut table = table.borrow_mut();
+    op(&mut *table)
 }
 
 /// Resolve a syntax object to a name, per MTWT.
@@ -264,6 +294,50 @@ pub fn outer_mark(ctxt: SyntaxContext) -> Mrk {
     })
 }
 
+/// Describes the syntax context of `id` for debugging hygiene: the marks
+/// and renames it was built from, outermost first, followed by the name it
+/// resolves to, e.g. `x#5: mark 2, rename x#0 to x/37 => x/37`. Names are
+/// shown with their interner index, as renaming keeps the string the same.
+pub fn describe_ident(id: Ident) -> String {
+    fn name_str(name: Name) -> String {
+        format!("{}/{}", token::get_name(name), name.uint())
+    }
+
+    let mut steps = Vec::new();
+    let resolvable = with_sctable(|table| {
+        let mut ctxt = id.ctxt;
+        loop {
+            match (*table.table.borrow())[ctxt as uint] {
+                EmptyCtxt => return true,
+                Mark(mark, tl) => {
+                    steps.push(format!("mark {}", mark));
+                    ctxt = tl;
+                }
+                Rename(from, to, tl) => {
+                    steps.push(format!("rename {}#{} to {}",
+                                       token::get_ident(from), from.ctxt, name_str(to)));
+                    ctxt = tl;
+                }
+                IllegalCtxt => {
+                    steps.push("illegal context".to_string());
+                    return false;
+                }
+            }
+        }
+    });
+
+    let mut s = format!("{}#{}", token::get_ident(id), id.ctxt);
+    if !steps.is_empty() {
+        s.push_str(": ");
+        s.push_str(steps.connect(", ").as_slice());
+    }
+    if resolvable {
+        s.push_str(" => ");
+        s.push_str(name_str(resolve(id)).as_slice());
+    }
+    s
+}
+
 /// Push a name... unless it matches the one on top, in which
 /// case pop and discard (so two of the same marks cancel)
 fn xor_push(marks: &mut Vec<Mrk>, mark: Mrk) {
@@ -281,6 +355,8 @@ mod tests {
     use super::{resolve, xor_push, apply_mark_internal, new_sctable_internal};
     use super::{apply_rename_internal, apply_renames, marksof_internal, resolve_internal};
     use super::{SCTable, EmptyCtxt, Mark, Rename, IllegalCtxt};
+    use super::{apply_mark, apply_rename, describe_ident};
+    use parse::token;
     use std::collections::HashMap;
 
     #[test]
@@ -503,4 +579,17 @@ mod tests {
         assert_eq!(resolve(Ident{name:Name(23),ctxt:new_ctxt1}),Name(24));
         assert_eq!(resolve(Ident{name:Name(29),ctxt:new_ctxt1}),Name(29));
     }
+
+    #[test]
+    fn describe_ident_test() {
+        let a = token::intern("a");
+        let a2 = token::gensym("a");
+        let ctxt = apply_mark(7, apply_rename(Ident{name:a,ctxt:EMPTY_CTXT}, a2,
+                                              EMPTY_CTXT));
+        assert_eq!(describe_ident(Ident{name:a,ctxt:EMPTY_CTXT}),
+                   format!("a#0 => a/{}", a.uint()));
+        assert_eq!(describe_ident(Ident{name:a,ctxt:ctxt}),
+                   format!("a#{}: mark 7, rename a#0 to a/{} => a/{}",
+                           ctxt, a2.uint(), a2.uint()));
+    }
 }
@@ -513,7 +513,7 @@ pub fn parse_nt(p: &mut Parser, name: &str) -> Nonterminal {
       "block" => token::NtBlock(p.parse_block()),
       "stmt" => token::NtStmt(p.parse_stmt(Vec::new())),
       "pat" => token::NtPat(p.parse_pat()),
-      "expr" => token::NtExpr(p.parse_expr()),
+      "expr" => token::NtExpr(p.parse_expr_before_colon()),
       "ty" => token::NtTy(p.parse_ty()),
       // this could be handled like a token, since it is one
       "ident" => match p.token {
@@ -23,7 +23,7 @@ use abi::RustIntrinsic;
 use ast::NodeId;
 use ast;
 use attr;
-use attr::AttrMetaMethods;
+use attr::{AttrMetaMethods, AttributeMethods};
 use codemap::Span;
 use diagnostic::SpanHandler;
 use visit;
@@ -72,6 +72,15 @@ static KNOWN_FEATURES: &'static [(&'static str, Status)] = &[
 
     ("if_let", Active),
     ("while_let", Active),
+    ("where_clause_equality", Active),
+    ("field_init_shorthand", Active),
+    ("loop_break_value", Active),
+    ("type_ascription", Active),
+    ("inclusive_range_syntax", Active),
+    ("question_mark", Active),
+    ("associated_consts", Active),
+    ("higher_ranked_trait_bounds", Active),
+    ("parser_recursion_limit", Active),
 
     // if you change this list without updating src/doc/reference.md, cmr will be sad
 
@@ -231,6 +240,12 @@ impl<'a, 'v> Visitor<'v> for Context<'a> {
                                               "associated types are \
                                                experimental")
                         }
+                        ast::ConstImplItem(ref associated_const) => {
+                            self.gate_feature("associated_consts",
+                                              associated_const.span,
+                                              "associated constants are \
+                                               experimental")
+                        }
                     }
                 }
             }
@@ -249,6 +264,11 @@ impl<'a, 'v> Visitor<'v> for Context<'a> {
                                   ti.ty_param.span,
                                   "associated types are experimental")
             }
+            ast::ConstTraitItem(ref associated_const) => {
+                self.gate_feature("associated_consts",
+                                  associated_const.span,
+                                  "associated constants are experimental")
+            }
         }
     }
 
@@ -327,6 +347,30 @@ impl<'a, 'v> Visitor<'v> for Context<'a> {
                 self.gate_feature("while_let", e.span,
                                   "`while let` syntax is experimental");
             }
+            ast::ExprTry(..) => {
+                self.gate_feature("question_mark", e.span,
+                                  "the `?` operator is experimental");
+            }
+            ast::ExprInclusiveRange(..) => {
+                self.gate_feature("inclusive_range_syntax", e.span,
+                                  "inclusive range syntax is experimental");
+            }
+            ast::ExprType(..) => {
+                self.gate_feature("type_ascription", e.span,
+                                  "type ascription is experimental");
+            }
+            ast::ExprBreak(_, Some(_)) => {
+                self.gate_feature("loop_break_value", e.span,
+                                  "`break` with a value is experimental");
+            }
+            ast::ExprStruct(_, ref fields, _) => {
+                for field in fields.iter() {
+                    if field.is_shorthand {
+                        self.gate_feature("field_init_shorthand", field.span,
+                                          "struct field shorthands are experimental");
+                    }
+                }
+            }
             _ => {}
         }
         visit::walk_expr(self, e);
@@ -343,6 +387,16 @@ impl<'a, 'v> Visitor<'v> for Context<'a> {
                 None => {}
             }
         }
+        for predicate in generics.where_clause.predicates.iter() {
+            match *predicate {
+                ast::EqPredicate(ref eq_pred) => {
+                    self.gate_feature("where_clause_equality", eq_pred.span,
+                                      "equality constraints in `where` clauses \
+                                       are experimental");
+                }
+                ast::BoundPredicate(..) => {}
+            }
+        }
         visit::walk_generics(self, generics);
     }
 
@@ -352,6 +406,11 @@ impl<'a, 'v> Visitor<'v> for Context<'a> {
                               attr.span,
                               "language items are subject to change");
         }
+        if attr::contains_name(slice::ref_slice(attr), "parser_recursion_limit") {
+            self.gate_feature("parser_recursion_limit",
+                              attr.span,
+                              "the `parser_recursion_limit` attribute is experimental");
+        }
     }
 
     fn visit_pat(&mut self, pattern: &ast::Pat) {
@@ -385,6 +444,15 @@ impl<'a, 'v> Visitor<'v> for Context<'a> {
         visit::walk_fn(self, fn_kind, fn_decl, block, span);
     }
 
+    fn visit_poly_trait_ref(&mut self, t: &ast::PolyTraitRef) {
+        if !t.bound_lifetimes.is_empty() {
+            self.gate_feature("higher_ranked_trait_bounds",
+                              t.trait_ref.path.span,
+                              "higher-ranked trait bounds (`for<'a>`) are experimental");
+        }
+        visit::walk_poly_trait_ref(self, t);
+    }
+
     fn visit_path_parameters(&mut self, path_span: Span, parameters: &'v ast::PathParameters) {
         match *parameters {
             ast::ParenthesizedParameters(..) => {
@@ -412,16 +480,16 @@ pub fn check_crate(span_handler: &SpanHandler, krate: &ast::Crate) -> (Features,
             continue
         }
 
-        match attr.meta_item_list() {
-            None => {
+        match attr::expect_list(attr.meta()) {
+            Err(_) => {
                 span_handler.span_err(attr.span, "malformed feature attribute, \
                                                   expected #![feature(...)]");
             }
-            Some(list) => {
+            Ok(list) => {
                 for mi in list.iter() {
-                    let name = match mi.node {
-                        ast::MetaWord(ref word) => (*word).clone(),
-                        _ => {
+                    let name = match attr::expect_word(&**mi) {
+                        Ok(word) => word,
+                        Err(_) => {
                             span_handler.span_err(mi.span,
                                                   "malformed feature, expected just \
                                                    one word");
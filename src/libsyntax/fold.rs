@@ -320,6 +320,11 @@ pub trait Folder {
         noop_fold_associated_type(associated_type, self)
     }
 
+    fn fold_associated_const(&mut self, associated_const: AssociatedConst)
+                             -> AssociatedConst {
+        noop_fold_associated_const(associated_const, self)
+    }
+
     fn new_id(&mut self, i: NodeId) -> NodeId {
         i
     }
@@ -807,14 +812,26 @@ pub fn noop_fold_where_clause<T: Folder>(
 }
 
 pub fn noop_fold_where_predicate<T: Folder>(
-                                 WherePredicate {id, ident, bounds, span}: WherePredicate,
+                                 pred: WherePredicate,
                                  fld: &mut T)
                                  -> WherePredicate {
-    WherePredicate {
-        id: fld.new_id(id),
-        ident: fld.fold_ident(ident),
-        bounds: bounds.move_map(|x| fld.fold_ty_param_bound(x)),
-        span: fld.new_span(span)
+    match pred {
+        ast::BoundPredicate(ast::WhereBoundPredicate{id, ident, bounds, span}) => {
+            ast::BoundPredicate(ast::WhereBoundPredicate {
+                id: fld.new_id(id),
+                ident: fld.fold_ident(ident),
+                bounds: bounds.move_map(|x| fld.fold_ty_param_bound(x)),
+                span: fld.new_span(span)
+            })
+        }
+        ast::EqPredicate(ast::WhereEqPredicate{id, lhs_ty, rhs_ty, span}) => {
+            ast::EqPredicate(ast::WhereEqPredicate {
+                id: fld.new_id(id),
+                lhs_ty: fld.fold_ty(lhs_ty),
+                rhs_ty: fld.fold_ty(rhs_ty),
+                span: fld.new_span(span)
+            })
+        }
     }
 }
 
@@ -853,6 +870,21 @@ pub fn noop_fold_associated_type<T>(at: AssociatedType, folder: &mut T)
     }
 }
 
+pub fn noop_fold_associated_const<T>(ac: AssociatedConst, folder: &mut T)
+                                     -> AssociatedConst
+                                     where T: Folder {
+    let AssociatedConst { id, span, ident, vis, attrs, ty, expr } = ac;
+    ast::AssociatedConst {
+        id: folder.new_id(id),
+        span: folder.new_span(span),
+        ident: folder.fold_ident(ident),
+        vis: vis,
+        attrs: attrs.into_iter().map(|attr| folder.fold_attribute(attr)).collect(),
+        ty: folder.fold_ty(ty),
+        expr: expr.map(|e| folder.fold_expr(e)),
+    }
+}
+
 pub fn noop_fold_struct_def<T: Folder>(struct_def: P<StructDef>, fld: &mut T) -> P<StructDef> {
     struct_def.map(|StructDef { fields, ctor_id }| StructDef {
         fields: fields.move_map(|f| fld.fold_struct_field(f)),
@@ -892,11 +924,13 @@ pub fn noop_fold_struct_field<T: Folder>(f: StructField, fld: &mut T) -> StructF
     }
 }
 
-pub fn noop_fold_field<T: Folder>(Field {ident, expr, span}: Field, folder: &mut T) -> Field {
+pub fn noop_fold_field<T: Folder>(Field {ident, expr, span, is_shorthand}: Field,
+                                  folder: &mut T) -> Field {
     Field {
         ident: respan(ident.span, folder.fold_ident(ident.node)),
         expr: folder.fold_expr(expr),
-        span: folder.new_span(span)
+        span: folder.new_span(span),
+        is_shorthand: is_shorthand,
     }
 }
 
@@ -1001,6 +1035,10 @@ pub fn noop_fold_item_underscore<T: Folder>(i: Item_, folder: &mut T) -> Item_ {
                         new_impl_items.push(TypeImplItem(
                                 P(folder.fold_typedef((**t).clone()))));
                     }
+                    ConstImplItem(ref c) => {
+                        new_impl_items.push(ConstImplItem(
+                                P(folder.fold_associated_const((**c).clone()))));
+                    }
                 }
             }
             let ifce = match ifce {
@@ -1039,6 +1077,12 @@ pub fn noop_fold_item_underscore<T: Folder>(i: Item_, folder: &mut T) -> Item_ {
                                         (*at).clone()))))
                             .into_iter()
                     }
+                    ConstTraitItem(ac) => {
+                        SmallVector::one(ConstTraitItem(P(
+                                    folder.fold_associated_const(
+                                        (*ac).clone()))))
+                            .into_iter()
+                    }
                 };
                 r
             }).collect();
@@ -1290,6 +1334,13 @@ pub fn noop_fold_expr<T: Folder>(Expr {id, node, span}: Expr, folder: &mut T) ->
             ExprCast(expr, ty) => {
                 ExprCast(folder.fold_expr(expr), folder.fold_ty(ty))
             }
+            ExprType(expr, ty) => {
+                ExprType(folder.fold_expr(expr), folder.fold_ty(ty))
+            }
+            ExprInclusiveRange(start, end) => {
+                ExprInclusiveRange(folder.fold_expr(start), folder.fold_expr(end))
+            }
+            ExprTry(ex) => ExprTry(folder.fold_expr(ex)),
             ExprAddrOf(m, ohs) => ExprAddrOf(m, folder.fold_expr(ohs)),
             ExprIf(cond, tr, fl) => {
                 ExprIf(folder.fold_expr(cond),
@@ -1365,7 +1416,10 @@ pub fn noop_fold_expr<T: Folder>(Expr {id, node, span}: Expr, folder: &mut T) ->
                           m)
             }
             ExprPath(pth) => ExprPath(folder.fold_path(pth)),
-            ExprBreak(opt_ident) => ExprBreak(opt_ident.map(|x| folder.fold_ident(x))),
+            ExprBreak(opt_ident, e) => {
+                ExprBreak(opt_ident.map(|x| folder.fold_ident(x)),
+                          e.map(|x| folder.fold_expr(x)))
+            }
             ExprAgain(opt_ident) => ExprAgain(opt_ident.map(|x| folder.fold_ident(x))),
             ExprRet(e) => ExprRet(e.map(|x| folder.fold_expr(x))),
             ExprInlineAsm(InlineAsm {
@@ -1376,20 +1430,22 @@ pub fn noop_fold_expr<T: Folder>(Expr {id, node, span}: Expr, folder: &mut T) ->
                 clobbers,
                 volatile,
                 alignstack,
+                noreturn,
                 dialect,
                 expn_id,
             }) => ExprInlineAsm(InlineAsm {
-                inputs: inputs.move_map(|(c, input)| {
-                    (c, folder.fold_expr(input))
+                inputs: inputs.move_map(|(c, input, sp)| {
+                    (c, folder.fold_expr(input), folder.new_span(sp))
                 }),
-                outputs: outputs.move_map(|(c, out, is_rw)| {
-                    (c, folder.fold_expr(out), is_rw)
+                outputs: outputs.move_map(|(c, out, is_rw, sp)| {
+                    (c, folder.fold_expr(out), is_rw, folder.new_span(sp))
                 }),
                 asm: asm,
                 asm_str_style: asm_str_style,
-                clobbers: clobbers,
+                clobbers: clobbers.move_map(|(c, sp)| (c, folder.new_span(sp))),
                 volatile: volatile,
                 alignstack: alignstack,
+                noreturn: noreturn,
                 dialect: dialect,
                 expn_id: expn_id,
             }),
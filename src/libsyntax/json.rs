@@ -0,0 +1,266 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A JSON emitter for errors.
+//!
+//! This works by converting each diagnostic into a structure that is
+//! serialized as one JSON object per line, for editors and build tools to
+//! consume instead of scraping the human readable output. Notes and help
+//! messages following an error or warning are nested in it as children.
+
+use codemap::{CodeMap, Span, MultiSpan, Pos, DUMMY_SP};
+use diagnostic::{Emitter, Level, RenderSpan, Suggestion};
+use diagnostic::{Bug, Fatal, Error, Warning, Note, Help};
+use diagnostics::registry::Registry;
+
+use serialize::json;
+use std::io;
+use std::uint;
+
+pub struct JsonEmitter {
+    dst: Box<Writer + Send>,
+    registry: Option<Registry>,
+    /// The last error or warning, which notes and help messages are
+    /// attached to until the next one is emitted, with the position of its
+    /// primary span
+    pending: Option<(uint, Diagnostic)>,
+    /// The diagnostics held back while buffering, with the positions of
+    /// their primary spans to sort them by
+    buffered: Option<Vec<(uint, Diagnostic)>>,
+}
+
+impl JsonEmitter {
+    pub fn stderr(registry: Option<Registry>) -> JsonEmitter {
+        JsonEmitter::new(box io::stderr(), registry)
+    }
+
+    pub fn new(dst: Box<Writer + Send>, registry: Option<Registry>) -> JsonEmitter {
+        JsonEmitter {
+            dst: dst,
+            registry: registry,
+            pending: None,
+            buffered: None,
+        }
+    }
+
+    fn add(&mut self, diag: Diagnostic, pos: uint, lvl: Level) {
+        match lvl {
+            Note | Help => if let Some((_, ref mut pending)) = self.pending {
+                pending.children.push(diag);
+                return;
+            },
+            _ => {}
+        }
+        self.flush_pending();
+        match lvl {
+            // Nothing follows these, as the compiler stops right after them
+            Bug | Fatal => self.write(&diag),
+            Error | Warning => self.pending = Some((pos, diag)),
+            Note | Help => self.write_or_buffer(pos, diag),
+        }
+    }
+
+    fn flush_pending(&mut self) {
+        if let Some((pos, diag)) = self.pending.take() {
+            self.write_or_buffer(pos, diag);
+        }
+    }
+
+    fn write_or_buffer(&mut self, pos: uint, diag: Diagnostic) {
+        match self.buffered {
+            Some(ref mut buffered) => buffered.push((pos, diag)),
+            None => self.write(&diag),
+        }
+    }
+
+    fn write(&mut self, diag: &Diagnostic) {
+        if let Err(e) = writeln!(&mut self.dst, "{}", json::encode(diag)) {
+            panic!("failed to print diagnostics: {}", e);
+        }
+    }
+
+    fn diagnostic(&self, cm: Option<&CodeMap>, spans: &[(Span, Option<&str>)],
+                  msg: &str, code: Option<&str>, lvl: Level) -> Diagnostic {
+        let (spans, macro_backtrace) = match cm {
+            Some(cm) if !spans.is_empty() => {
+                let (primary, _) = spans[0];
+                let backtrace = cm.macro_backtrace(primary).into_iter().map(|frame| {
+                    DiagnosticMacroFrame {
+                        macro_decl_name: frame.macro_decl_name,
+                        call_site: DiagnosticSpan::from_span(cm, frame.call_site, false, None),
+                        def_site_span: frame.def_site_span.map(|sp| {
+                            DiagnosticSpan::from_span(cm, sp, false, None)
+                        }),
+                    }
+                }).collect();
+                let spans = spans.iter().enumerate().filter(|&(_, &(sp, _))| sp != DUMMY_SP)
+                                 .map(|(i, &(sp, label))| {
+                    DiagnosticSpan::from_span(cm, sp, i == 0, label)
+                }).collect();
+                (spans, backtrace)
+            }
+            _ => (Vec::new(), Vec::new()),
+        };
+        Diagnostic {
+            message: msg.to_string(),
+            code: code.map(|code| DiagnosticCode {
+                code: code.to_string(),
+                explanation: self.registry.as_ref()
+                                 .and_then(|registry| registry.find_description(code))
+                                 .map(|s| s.to_string()),
+            }),
+            level: level_name(lvl).to_string(),
+            spans: spans,
+            macro_backtrace: macro_backtrace,
+            children: Vec::new(),
+        }
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit(&mut self, cmsp: Option<(&CodeMap, Span)>,
+            msg: &str, code: Option<&str>, lvl: Level) {
+        let diag = match cmsp {
+            Some((cm, sp)) => self.diagnostic(Some(cm), &[(sp, None)], msg, code, lvl),
+            None => self.diagnostic(None, &[], msg, code, lvl),
+        };
+        self.add(diag, cmsp.map_or(uint::MAX, |(_, sp)| sp.lo.to_uint()), lvl);
+    }
+
+    fn custom_emit(&mut self, cm: &CodeMap,
+                   sp: RenderSpan, msg: &str, lvl: Level) {
+        let mut diag = self.diagnostic(Some(cm), &[(sp.span(), None)], msg, None, lvl);
+        let pos = sp.span().lo.to_uint();
+        if let Suggestion(_, suggestion) = sp {
+            for span in diag.spans.iter_mut() {
+                span.suggested_replacement = Some(suggestion.clone());
+            }
+        }
+        self.add(diag, pos, lvl);
+    }
+
+    fn emit_multi(&mut self, cm: &CodeMap, msp: &MultiSpan,
+                  msg: &str, code: Option<&str>, lvl: Level) {
+        let mut spans = vec![(msp.primary, None)];
+        spans.extend(msp.labels.iter().map(|&(sp, ref label)| (sp, Some(label.as_slice()))));
+        let diag = self.diagnostic(Some(cm), spans.as_slice(), msg, code, lvl);
+        self.add(diag, msp.primary.lo.to_uint(), lvl);
+    }
+
+    fn start_buffering(&mut self) {
+        self.flush_pending();
+        if self.buffered.is_none() {
+            self.buffered = Some(Vec::new());
+        }
+    }
+
+    fn flush_buffered(&mut self) {
+        self.flush_pending();
+        if let Some(mut buffered) = self.buffered.take() {
+            // A stable sort, so diagnostics about the same place stay in order
+            buffered.sort_by(|&(a, _), &(b, _)| a.cmp(&b));
+            for &(_, ref diag) in buffered.iter() {
+                self.write(diag);
+            }
+        }
+    }
+}
+
+impl Drop for JsonEmitter {
+    fn drop(&mut self) {
+        self.flush_buffered();
+    }
+}
+
+fn level_name(lvl: Level) -> &'static str {
+    match lvl {
+        Bug => "error: internal compiler error",
+        // Kept apart from errors, as it's also used for the final "aborting
+        // due to previous errors", which isn't an error of its own
+        Fatal => "fatal",
+        Error => "error",
+        Warning => "warning",
+        Note => "note",
+        Help => "help",
+    }
+}
+
+// The following data types are provided just for serialisation.
+
+#[deriving(Encodable)]
+struct Diagnostic {
+    /// The primary error message.
+    message: String,
+    code: Option<DiagnosticCode>,
+    /// "error: internal compiler error", "fatal", "error", "warning", "note",
+    /// "help".
+    level: String,
+    /// The primary span comes first, followed by any labeled spans.
+    spans: Vec<DiagnosticSpan>,
+    /// The macro invocations the primary span was expanded from,
+    /// innermost first.
+    macro_backtrace: Vec<DiagnosticMacroFrame>,
+    /// Associated notes and help messages.
+    children: Vec<Diagnostic>,
+}
+
+#[deriving(Encodable)]
+struct DiagnosticSpan {
+    file_name: String,
+    /// Offsets into the file, in bytes.
+    byte_start: uint,
+    byte_end: uint,
+    /// 1-based.
+    line_start: uint,
+    line_end: uint,
+    /// 1-based, in characters.
+    column_start: uint,
+    column_end: uint,
+    is_primary: bool,
+    label: Option<String>,
+    /// The text the span should be replaced with, for suggestions.
+    suggested_replacement: Option<String>,
+}
+
+#[deriving(Encodable)]
+struct DiagnosticMacroFrame {
+    /// The name of the macro as invoked, e.g. `foo!` or `#[deriving]`.
+    macro_decl_name: String,
+    call_site: DiagnosticSpan,
+    def_site_span: Option<DiagnosticSpan>,
+}
+
+#[deriving(Encodable)]
+struct DiagnosticCode {
+    /// The code itself.
+    code: String,
+    /// An explanation for the code, as given by `--explain`.
+    explanation: Option<String>,
+}
+
+impl DiagnosticSpan {
+    fn from_span(cm: &CodeMap, sp: Span, is_primary: bool, label: Option<&str>)
+                 -> DiagnosticSpan {
+        let start = cm.lookup_char_pos(sp.lo);
+        let end = cm.lookup_char_pos(sp.hi);
+        DiagnosticSpan {
+            file_name: start.file.name.clone(),
+            byte_start: (sp.lo - start.file.start_pos).to_uint(),
+            byte_end: (sp.hi - start.file.start_pos).to_uint(),
+            line_start: start.line,
+            line_end: end.line,
+            column_start: start.col.to_uint() + 1,
+            column_end: end.col.to_uint() + 1,
+            is_primary: is_primary,
+            label: label.map(|label| label.to_string()),
+            suggested_replacement: None,
+        }
+    }
+}
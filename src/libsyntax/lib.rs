@@ -24,7 +24,7 @@
 
 #![allow(unknown_features)]
 #![feature(if_let, macro_rules, globs, default_type_params, phase, slicing_syntax)]
-#![feature(quote, unsafe_destructor, import_shadowing)]
+#![feature(quote, unsafe_destructor, import_shadowing, rustc_diagnostic_macros)]
 
 extern crate arena;
 extern crate fmt_macros;
@@ -33,6 +33,8 @@ extern crate serialize;
 extern crate term;
 extern crate libc;
 
+#[cfg(test)] extern crate test;
+
 pub mod util {
     pub mod interner;
     #[cfg(test)]
@@ -46,6 +48,8 @@ pub mod diagnostics {
     pub mod registry;
 }
 
+mod diagnostic_list;
+
 pub mod syntax {
     pub use ext;
     pub use parse;
@@ -55,6 +59,7 @@ pub mod syntax {
 pub mod abi;
 pub mod ast;
 pub mod ast_map;
+pub mod ast_stats;
 pub mod ast_util;
 pub mod attr;
 pub mod codemap;
@@ -62,6 +67,7 @@ pub mod config;
 pub mod diagnostic;
 pub mod feature_gate;
 pub mod fold;
+pub mod json;
 pub mod owned_slice;
 pub mod parse;
 pub mod ptr;
@@ -101,3 +107,5 @@ pub mod ext {
         pub mod macro_rules;
     }
 }
+
+__build_diagnostic_array!(DIAGNOSTICS)
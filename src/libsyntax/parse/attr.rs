@@ -193,12 +193,12 @@ impl<'a> ParserAttr for Parser<'a> {
                             "non-string literals are not allowed in meta-items");
                     }
                 }
-                let hi = self.span.hi;
+                let hi = self.last_span.hi;
                 P(spanned(lo, hi, ast::MetaNameValue(name, lit)))
             }
             token::OpenDelim(token::Paren) => {
                 let inner_items = self.parse_meta_seq();
-                let hi = self.span.hi;
+                let hi = self.last_span.hi;
                 P(spanned(lo, hi, ast::MetaList(name, inner_items)))
             }
             _ => {
@@ -212,7 +212,7 @@ impl<'a> ParserAttr for Parser<'a> {
     fn parse_meta_seq(&mut self) -> Vec<P<ast::MetaItem>> {
         self.parse_seq(&token::OpenDelim(token::Paren),
                        &token::CloseDelim(token::Paren),
-                       seq_sep_trailing_disallowed(token::Comma),
+                       seq_sep_trailing_allowed(token::Comma),
                        |p| p.parse_meta_item()).node
     }
 
@@ -11,11 +11,12 @@
 use ast;
 use codemap::{BytePos, CharPos, CodeMap, Pos, Span};
 use codemap;
-use diagnostic::SpanHandler;
+use diagnostic::{SpanHandler, Level, Error, Help};
 use ext::tt::transcribe::tt_next_token;
 use parse::token;
 use parse::token::{str_to_ident};
 
+use std::cell::RefCell;
 use std::char;
 use std::fmt;
 use std::mem::replace;
@@ -38,13 +39,8 @@ pub trait Reader {
     /// Get a token the parser cares about.
     fn real_token(&mut self) -> TokenAndSpan {
         let mut t = self.next_token();
-        loop {
-            match t.tok {
-                token::Whitespace | token::Comment | token::Shebang(_) => {
-                    t = self.next_token();
-                },
-                _ => break
-            }
+        while t.tok.is_trivia() {
+            t = self.next_token();
         }
         t
     }
@@ -67,6 +63,15 @@ pub struct StringReader<'a> {
     /// The last character to be read
     pub curr: Option<char>,
     pub filemap: Rc<codemap::FileMap>,
+    /// The source of `filemap`, held for as long as it's being lexed
+    src: Rc<String>,
+    /// The end of the text whose lines and multibyte characters have been
+    /// recorded in `filemap`, which is past `pos` after a `rewind`
+    recorded_to: BytePos,
+    /// Errors in the lines of a string literal after its first, held back
+    /// until the literal turns out to be closed; otherwise those lines are
+    /// lexed again as code
+    held_errors: RefCell<Option<Vec<(Span, String, Level)>>>,
     /* cached: */
     pub peek_tok: token::Token,
     pub peek_span: Span,
@@ -77,6 +82,14 @@ pub struct StringReader<'a> {
     read_embedded_ident: bool,
 }
 
+/// A position of a `StringReader`, to move it back to with `rewind`
+struct Mark {
+    pos: BytePos,
+    last_pos: BytePos,
+    col: CharPos,
+    curr: Option<char>,
+}
+
 impl<'a> Reader for StringReader<'a> {
     fn is_eof(&self) -> bool { self.curr.is_none() }
     /// Return the next token. EFFECT: advances the string_reader.
@@ -137,21 +150,50 @@ pub fn make_reader_with_embedded_idents<'b>(span_diagnostic: &'b SpanHandler,
     sr
 }
 
+/// Lex all of `filemap` without throwing anything away. Whitespace,
+/// comments (`Comment` or `DocComment`) and a leading shebang are returned
+/// as tokens in their own right, so the spans of the result cover the
+/// source text exactly, in order and without gaps. The trailing `Eof` is
+/// not included.
+pub fn lossless_tokens<'b>(span_diagnostic: &'b SpanHandler,
+                           filemap: Rc<codemap::FileMap>)
+                           -> Vec<TokenAndSpan> {
+    let mut sr = StringReader::new(span_diagnostic, filemap);
+    let mut tokens = Vec::new();
+    loop {
+        let t = sr.next_token();
+        if t.tok == token::Eof {
+            break;
+        }
+        tokens.push(t);
+    }
+    tokens
+}
+
 impl<'a> StringReader<'a> {
     /// For comments.rs, which hackily pokes into pos and curr
     pub fn new_raw<'b>(span_diagnostic: &'b SpanHandler,
                    filemap: Rc<codemap::FileMap>) -> StringReader<'b> {
+        let src = match filemap.src() {
+            Some(src) => src,
+            None => span_diagnostic.handler().fatal(
+                format!("couldn't re-read {}: the file has changed",
+                        filemap.name).as_slice()),
+        };
         let mut sr = StringReader {
             span_diagnostic: span_diagnostic,
             pos: filemap.start_pos,
             last_pos: filemap.start_pos,
+            recorded_to: filemap.start_pos,
             col: CharPos(0),
             curr: Some('\n'),
             filemap: filemap,
+            src: src,
             /* dummy values; not read */
             peek_tok: token::Eof,
             peek_span: codemap::DUMMY_SP,
             read_embedded_ident: false,
+            held_errors: RefCell::new(None),
         };
         sr.bump();
         sr
@@ -175,7 +217,38 @@ impl<'a> StringReader<'a> {
 
     /// Report a lexical error with a given span.
     pub fn err_span(&self, sp: Span, m: &str) {
-        self.span_diagnostic.span_err(sp, m)
+        match *self.held_errors.borrow_mut() {
+            Some(ref mut held) => held.push((sp, m.to_string(), Error)),
+            None => self.span_diagnostic.span_err(sp, m),
+        }
+    }
+
+    /// Attach help to the last lexical error.
+    fn help_span(&self, sp: Span, m: &str) {
+        match *self.held_errors.borrow_mut() {
+            Some(ref mut held) => held.push((sp, m.to_string(), Help)),
+            None => self.span_diagnostic.span_help(sp, m),
+        }
+    }
+
+    /// Hold back the lexical errors reported from now on.
+    fn hold_errors(&self) {
+        *self.held_errors.borrow_mut() = Some(Vec::new());
+    }
+
+    /// Stop holding back lexical errors, reporting the ones held if `report`
+    /// is true and dropping them otherwise.
+    fn release_errors(&self, report: bool) {
+        let held = self.held_errors.borrow_mut().take().unwrap_or(Vec::new());
+        if !report {
+            return;
+        }
+        for (sp, m, level) in held.into_iter() {
+            match level {
+                Help => self.span_diagnostic.span_help(sp, m.as_slice()),
+                _ => self.span_diagnostic.span_err(sp, m.as_slice()),
+            }
+        }
     }
 
     /// Report a fatal error spanning [`from_pos`, `to_pos`).
@@ -208,12 +281,12 @@ impl<'a> StringReader<'a> {
 
     /// Report a lexical error spanning [`from_pos`, `to_pos`), appending the
     /// offending string to the error message
-    fn fatal_span_verbose(&self, from_pos: BytePos, to_pos: BytePos, mut m: String) -> ! {
+    fn err_span_verbose(&self, from_pos: BytePos, to_pos: BytePos, mut m: String) {
         m.push_str(": ");
         let from = self.byte_offset(from_pos).to_uint();
         let to = self.byte_offset(to_pos).to_uint();
-        m.push_str(self.filemap.src.as_slice().slice(from, to));
-        self.fatal_span_(from_pos, to_pos, m.as_slice());
+        m.push_str(self.src.as_slice().slice(from, to));
+        self.err_span_(from_pos, to_pos, m.as_slice());
     }
 
     /// Advance peek_tok and peek_span to refer to the next token, and
@@ -265,7 +338,7 @@ impl<'a> StringReader<'a> {
     /// Calls `f` with a string slice of the source text spanning from `start`
     /// up to but excluding `end`.
     fn with_str_from_to<T>(&self, start: BytePos, end: BytePos, f: |s: &str| -> T) -> T {
-        f(self.filemap.src.as_slice().slice(
+        f(self.src.as_slice().slice(
                 self.byte_offset(start).to_uint(),
                 self.byte_offset(end).to_uint()))
     }
@@ -316,11 +389,10 @@ impl<'a> StringReader<'a> {
     pub fn bump(&mut self) {
         self.last_pos = self.pos;
         let current_byte_offset = self.byte_offset(self.pos).to_uint();
-        if current_byte_offset < self.filemap.src.len() {
+        if current_byte_offset < self.src.len() {
             assert!(self.curr.is_some());
             let last_char = self.curr.unwrap();
-            let next = self.filemap
-                          .src
+            let next = self.src
                           .as_slice()
                           .char_range_at(current_byte_offset);
             let byte_offset_diff = next.next - current_byte_offset;
@@ -328,22 +400,55 @@ impl<'a> StringReader<'a> {
             self.curr = Some(next.ch);
             self.col = self.col + CharPos(1u);
             if last_char == '\n' {
-                self.filemap.next_line(self.last_pos);
                 self.col = CharPos(0u);
             }
 
-            if byte_offset_diff > 1 {
-                self.filemap.record_multibyte_char(self.last_pos, byte_offset_diff);
+            // Text read again after a `rewind` has been recorded already
+            if self.last_pos >= self.recorded_to {
+                if last_char == '\n' {
+                    self.filemap.next_line(self.last_pos);
+                }
+                if byte_offset_diff > 1 {
+                    self.filemap.record_multibyte_char(self.last_pos, byte_offset_diff);
+                }
+                self.filemap.record_char_width(self.last_pos, next.ch);
+                self.recorded_to = self.pos;
             }
         } else {
             self.curr = None;
         }
     }
 
+    /// Skip the rest of the current line, leaving `curr` on the newline (or
+    /// at EOF). Used to recover from unterminated literals.
+    fn bump_to_end_of_line(&mut self) {
+        while !self.is_eof() && !self.curr_is('\n') {
+            self.bump();
+        }
+    }
+
+    /// Where the reader is now, for `rewind` to move it back to.
+    fn mark(&self) -> Mark {
+        Mark {
+            pos: self.pos,
+            last_pos: self.last_pos,
+            col: self.col,
+            curr: self.curr,
+        }
+    }
+
+    /// Move the reader back to `mark`, to lex the text after it again.
+    fn rewind(&mut self, mark: Mark) {
+        self.pos = mark.pos;
+        self.last_pos = mark.last_pos;
+        self.col = mark.col;
+        self.curr = mark.curr;
+    }
+
     pub fn nextch(&self) -> Option<char> {
         let offset = self.byte_offset(self.pos).to_uint();
-        if offset < self.filemap.src.len() {
-            Some(self.filemap.src.as_slice().char_at(offset))
+        if offset < self.src.len() {
+            Some(self.src.as_slice().char_at(offset))
         } else {
             None
         }
@@ -355,7 +460,7 @@ impl<'a> StringReader<'a> {
 
     pub fn nextnextch(&self) -> Option<char> {
         let offset = self.byte_offset(self.pos).to_uint();
-        let s = self.filemap.deref().src.as_slice();
+        let s = self.src.as_slice();
         if offset >= s.len() { return None }
         let str::CharRange { next, .. } = s.char_range_at(offset);
         if next < s.len() {
@@ -468,7 +573,6 @@ impl<'a> StringReader<'a> {
                 let loc = cmap.lookup_char_pos_adj(self.last_pos);
                 debug!("Skipping a shebang");
                 if loc.line == 1u && loc.col == CharPos(0u) {
-                    // FIXME: Add shebang "token", return it
                     let start = self.last_pos;
                     while !self.curr_is('\n') && !self.is_eof() { self.bump(); }
                     return Some(TokenAndSpan {
@@ -806,7 +910,7 @@ impl<'a> StringReader<'a> {
                                     c);
                                 if e == '\r' {
                                     let sp = codemap::mk_sp(escaped_pos, last_pos);
-                                    self.span_diagnostic.span_help(
+                                    self.help_span(
                                         sp,
                                         "this is an isolated carriage return; consider checking \
                                          your editor and version control settings")
@@ -1068,13 +1172,17 @@ impl<'a> StringReader<'a> {
             // Otherwise it is a character constant:
             let valid = self.scan_char_or_byte(start, c2, /* ascii_only = */ false, '\'');
             if !self.curr_is('\'') {
+                // Report the error, then resume lexing on the next line so
+                // that later errors in the file are still seen.
+                self.bump_to_end_of_line();
                 let last_bpos = self.last_pos;
-                self.fatal_span_verbose(
+                self.err_span_verbose(
                                    // Byte offsetting here is okay because the
                                    // character before position `start` is an
                                    // ascii single quote.
                                    start - BytePos(1), last_bpos,
                                    "unterminated character constant".to_string());
+                return token::Literal(token::Char(token::intern("0")), None);
             }
             let id = if valid { self.name_from(start) } else { token::intern("0") };
             self.bump(); // advance curr past token
@@ -1095,11 +1203,26 @@ impl<'a> StringReader<'a> {
           '"' => {
             let start_bpos = self.last_pos;
             let mut valid = true;
+            let mut line_end = None;
             self.bump();
             while !self.curr_is('"') {
                 if self.is_eof() {
+                    // Rather than swallowing the rest of the file, end the
+                    // literal at the end of the line it starts on.
+                    match line_end {
+                        Some(mark) => {
+                            self.release_errors(false);
+                            self.rewind(mark);
+                        }
+                        None => {}
+                    }
                     let last_bpos = self.last_pos;
-                    self.fatal_span_(start_bpos, last_bpos, "unterminated double quote string");
+                    self.err_span_(start_bpos, last_bpos, "unterminated double quote string");
+                    return token::Literal(token::Str_(token::intern("??")), None);
+                }
+                if line_end.is_none() && self.curr_is('\n') {
+                    line_end = Some(self.mark());
+                    self.hold_errors();
                 }
 
                 let ch_start = self.last_pos;
@@ -1107,6 +1230,9 @@ impl<'a> StringReader<'a> {
                 self.bump();
                 valid &= self.scan_char_or_byte(ch_start, ch, /* ascii_only = */ false, '"');
             }
+            if line_end.is_some() {
+                self.release_errors(true);
+            }
             // adjust for the ASCII " at the start of the literal
             let id = if valid { self.name_from(start_bpos + BytePos(1)) }
                      else { token::intern("??") };
@@ -1259,13 +1385,15 @@ impl<'a> StringReader<'a> {
 
         let valid = self.scan_char_or_byte(start, c2, /* ascii_only = */ true, '\'');
         if !self.curr_is('\'') {
+            self.bump_to_end_of_line();
             // Byte offsetting here is okay because the
             // character before position `start` are an
             // ascii single quote and ascii 'b'.
             let last_pos = self.last_pos;
-            self.fatal_span_verbose(
+            self.err_span_verbose(
                 start - BytePos(2), last_pos,
                 "unterminated byte constant".to_string());
+            return token::Byte(token::intern("??"));
         }
 
         let id = if valid { self.name_from(start) } else { token::intern("??") };
@@ -1277,12 +1405,25 @@ impl<'a> StringReader<'a> {
         self.bump();
         let start = self.last_pos;
         let mut valid = true;
+        let mut line_end = None;
 
         while !self.curr_is('"') {
             if self.is_eof() {
+                // As with strings, end the literal at the end of its line
+                match line_end {
+                    Some(mark) => {
+                        self.release_errors(false);
+                        self.rewind(mark);
+                    }
+                    None => {}
+                }
                 let last_pos = self.last_pos;
-                self.fatal_span_(start, last_pos,
-                                  "unterminated double quote byte string");
+                self.err_span_(start, last_pos, "unterminated double quote byte string");
+                return token::Binary(token::intern("??"));
+            }
+            if line_end.is_none() && self.curr_is('\n') {
+                line_end = Some(self.mark());
+                self.hold_errors();
             }
 
             let ch_start = self.last_pos;
@@ -1290,6 +1431,9 @@ impl<'a> StringReader<'a> {
             self.bump();
             valid &= self.scan_char_or_byte(ch_start, ch, /* ascii_only = */ true, '"');
         }
+        if line_end.is_some() {
+            self.release_errors(true);
+        }
         let id = if valid { self.name_from(start) } else { token::intern("??") };
         self.bump();
         return token::Binary(id);
@@ -1567,4 +1711,40 @@ mod test {
         assert_eq!(lexer.next_token().tok, token::Literal(token::Char(token::intern("a")), None));
     }
 
+    #[test] fn lossless_tokens_cover_source() {
+        let sh = mk_sh();
+        let src = "#!/usr/bin/env rustx\n/// doc\nfn f() { /* c */ 1u } // end\n";
+        let fm = sh.cm.new_filemap("zebra.rs".to_string(), src.to_string());
+        let toks = lossless_tokens(&sh, fm);
+        let kinds: Vec<token::Token> = toks.iter().map(|t| t.tok.clone()).collect();
+        assert_eq!(kinds[0], token::Shebang(token::intern("#!/usr/bin/env rustx")));
+        assert_eq!(kinds[1], token::Whitespace);
+        assert_eq!(kinds[2], token::DocComment(token::intern("/// doc")));
+        assert!(kinds.contains(&token::Comment));
+        let mut text = String::new();
+        let mut pos = BytePos(0);
+        for t in toks.iter() {
+            assert_eq!(t.sp.lo, pos);
+            pos = t.sp.hi;
+            text.push_str(sh.cm.span_to_snippet(t.sp).unwrap().as_slice());
+        }
+        assert_eq!(text.as_slice(), src);
+    }
+
+    #[test] fn multiline_string_literal() {
+        let sh = mk_sh();
+        let mut lexer = setup(&sh, "\"a\nb\" \"c\"".to_string());
+        assert_eq!(lexer.next_token().tok,
+                   token::Literal(token::Str_(token::intern("a\nb")), None));
+    }
+
+    #[test] fn unterminated_string_ends_at_line_end() {
+        let sh = mk_sh();
+        let mut lexer = setup(&sh, "\"a\nb c".to_string());
+        assert_eq!(lexer.next_token().tok,
+                   token::Literal(token::Str_(token::intern("??")), None));
+        assert_eq!(lexer.next_token().tok, token::Whitespace);
+        assert_eq!(lexer.next_token().tok, mk_ident("b", token::Plain));
+    }
+
 }
@@ -33,12 +33,40 @@ pub mod common;
 pub mod classify;
 pub mod obsolete;
 
+/// The default maximum depth of nested expressions, types, patterns and
+/// blocks, overridable with `#![parser_recursion_limit = "N"]`.
+pub const DEFAULT_NESTING_LIMIT: uint = 1024;
+
 /// Info about a parsing session.
 pub struct ParseSess {
     pub span_diagnostic: SpanHandler, // better be the same as the one in the reader!
     /// Used to determine and report recursive mod inclusions
     included_mod_stack: RefCell<Vec<Path>>,
     pub node_id: Cell<ast::NodeId>,
+    /// Ranges of ids reserved by syntax extensions, as `(first, count)`.
+    /// Nodes carrying these ids keep them when ids are assigned after
+    /// expansion.
+    reserved_node_ids: RefCell<Vec<(ast::NodeId, ast::NodeId)>>,
+    /// How deeply expressions, types, patterns and blocks may nest before
+    /// the parser gives up, rather than overflowing its stack
+    pub nesting_limit: Cell<uint>,
+    /// Warnings reported as lints before lint levels are known, like those
+    /// of macro expansion
+    pub buffered_lints: RefCell<Vec<BufferedLint>>,
+}
+
+/// A lint reported before lint checking, to be emitted by it at the level
+/// in effect where the lint points.
+pub struct BufferedLint {
+    /// The name of the lint, as in `#[allow(name)]`
+    pub lint_name: &'static str,
+    pub span: Span,
+    pub msg: String,
+    /// Help messages shown after the lint, if it's emitted
+    pub helps: Vec<String>,
+    /// A suggested replacement for the text of a span, with the help
+    /// message that introduces it
+    pub suggestion: Option<(Span, String, String)>,
 }
 
 pub fn new_parse_sess() -> ParseSess {
@@ -46,6 +74,9 @@ pub fn new_parse_sess() -> ParseSess {
         span_diagnostic: mk_span_handler(default_handler(Auto, None), CodeMap::new()),
         included_mod_stack: RefCell::new(Vec::new()),
         node_id: Cell::new(1),
+        reserved_node_ids: RefCell::new(Vec::new()),
+        nesting_limit: Cell::new(DEFAULT_NESTING_LIMIT),
+        buffered_lints: RefCell::new(Vec::new()),
     }
 }
 
@@ -54,6 +85,9 @@ pub fn new_parse_sess_special_handler(sh: SpanHandler) -> ParseSess {
         span_diagnostic: sh,
         included_mod_stack: RefCell::new(Vec::new()),
         node_id: Cell::new(1),
+        reserved_node_ids: RefCell::new(Vec::new()),
+        nesting_limit: Cell::new(DEFAULT_NESTING_LIMIT),
+        buffered_lints: RefCell::new(Vec::new()),
     }
 }
 
@@ -61,6 +95,31 @@ impl ParseSess {
     pub fn next_node_id(&self) -> ast::NodeId {
         self.reserve_node_ids(1)
     }
+    /// Reports the lint `lint_name` at `sp`, to be emitted during lint
+    /// checking if the lint is enabled there.
+    pub fn buffer_lint(&self, lint_name: &'static str, sp: Span, msg: &str,
+                       helps: Vec<String>) {
+        self.buffered_lints.borrow_mut().push(BufferedLint {
+            lint_name: lint_name,
+            span: sp,
+            msg: msg.to_string(),
+            helps: helps,
+            suggestion: None,
+        });
+    }
+    /// Like `buffer_lint`, suggesting `suggestion` for the text of
+    /// `suggestion_sp`.
+    pub fn buffer_lint_with_suggestion(&self, lint_name: &'static str, sp: Span, msg: &str,
+                                       suggestion_sp: Span, suggestion_msg: &str,
+                                       suggestion: String) {
+        self.buffered_lints.borrow_mut().push(BufferedLint {
+            lint_name: lint_name,
+            span: sp,
+            msg: msg.to_string(),
+            helps: Vec::new(),
+            suggestion: Some((suggestion_sp, suggestion_msg.to_string(), suggestion)),
+        });
+    }
     pub fn reserve_node_ids(&self, count: ast::NodeId) -> ast::NodeId {
         let v = self.node_id.get();
 
@@ -71,6 +130,18 @@ impl ParseSess {
 
         v
     }
+    /// Like `reserve_node_ids`, but also records the range so that the
+    /// ids survive the assignment of ids after expansion.
+    pub fn reserve_node_ids_for_expansion(&self, count: ast::NodeId) -> ast::NodeId {
+        let first = self.reserve_node_ids(count);
+        self.reserved_node_ids.borrow_mut().push((first, count));
+        first
+    }
+    pub fn is_reserved_node_id(&self, id: ast::NodeId) -> bool {
+        self.reserved_node_ids.borrow().iter().any(|&(first, count)| {
+            id >= first && id - first < count
+        })
+    }
 }
 
 // a bunch of utility functions of the form parse_<thing>_from_<source>
@@ -287,6 +358,14 @@ pub fn filemap_to_tts(sess: &ParseSess, filemap: Rc<FileMap>)
     p1.parse_all_token_trees()
 }
 
+/// Given a filemap, produce every token in it, including whitespace,
+/// comments and shebang, for tools that need to reproduce the source text
+/// exactly (formatters, editors). See `lexer::lossless_tokens`.
+pub fn filemap_to_lossless_tokens(sess: &ParseSess, filemap: Rc<FileMap>)
+    -> Vec<lexer::TokenAndSpan> {
+    lexer::lossless_tokens(&sess.span_diagnostic, filemap)
+}
+
 /// Given tts and cfg, produce a parser
 pub fn tts_to_parser<'a>(sess: &'a ParseSess,
                          tts: Vec<ast::TokenTree>,
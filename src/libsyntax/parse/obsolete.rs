@@ -30,7 +30,6 @@ pub enum ObsoleteSyntax {
     ObsoleteOwnedVector,
     ObsoleteOwnedSelf,
     ObsoleteImportRenaming,
-    ObsoleteSubsliceMatch,
     ObsoleteExternCrateRenaming,
 }
 
@@ -77,10 +76,6 @@ impl<'a> ParserObsoleteMethods for parser::Parser<'a> {
                 "`use foo = bar` syntax",
                 "write `use bar as foo` instead"
             ),
-            ObsoleteSubsliceMatch => (
-                "subslice match syntax",
-                "instead of `..xs`, write `xs..` in a pattern"
-            ),
             ObsoleteExternCrateRenaming => (
                 "`extern crate foo = bar` syntax",
                 "write `extern crate bar as foo` instead"
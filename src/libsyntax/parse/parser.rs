@@ -14,7 +14,7 @@ pub use self::PathParsingMode::*;
 use self::ItemOrViewItem::*;
 
 use abi;
-use ast::{AssociatedType, BareFnTy, ClosureTy};
+use ast::{AssociatedConst, AssociatedType, BareFnTy, ClosureTy};
 use ast::{RegionTyParamBound, TraitTyParamBound};
 use ast::{ProvidedMethod, Public, FnStyle};
 use ast::{Mod, BiAdd, Arg, Arm, Attribute, BindByRef, BindByValue};
@@ -26,9 +26,10 @@ use ast::{Expr, Expr_, ExprAddrOf, ExprMatch, ExprAgain};
 use ast::{ExprAssign, ExprAssignOp, ExprBinary, ExprBlock, ExprBox};
 use ast::{ExprBreak, ExprCall, ExprCast};
 use ast::{ExprField, ExprTupField, ExprClosure, ExprIf, ExprIfLet, ExprIndex, ExprSlice};
+use ast::{ExprInclusiveRange, ExprTry};
 use ast::{ExprLit, ExprLoop, ExprMac};
 use ast::{ExprMethodCall, ExprParen, ExprPath, ExprProc};
-use ast::{ExprRepeat, ExprRet, ExprStruct, ExprTup, ExprUnary};
+use ast::{ExprRepeat, ExprRet, ExprStruct, ExprTup, ExprType, ExprUnary};
 use ast::{ExprVec, ExprWhile, ExprWhileLet, ExprForLoop, Field, FnDecl};
 use ast::{Once, Many};
 use ast::{FnUnboxedClosureKind, FnMutUnboxedClosureKind};
@@ -59,12 +60,14 @@ use ast::{TyTypeof, TyInfer, TypeMethod};
 use ast::{TyParam, TyParamBound, TyParen, TyPath, TyPolyTraitRef, TyPtr, TyQPath};
 use ast::{TyRptr, TyTup, TyU32, TyVec, UnUniq};
 use ast::{TypeImplItem, TypeTraitItem, Typedef, UnboxedClosureKind};
+use ast::{ConstImplItem, ConstTraitItem};
 use ast::{UnnamedField, UnsafeBlock};
 use ast::{UnsafeFn, ViewItem, ViewItem_, ViewItemExternCrate, ViewItemUse};
 use ast::{ViewPath, ViewPathGlob, ViewPathList, ViewPathSimple};
-use ast::{Visibility, WhereClause, WherePredicate};
+use ast::{Visibility, WhereClause};
 use ast;
 use ast_util::{mod, as_prec, ident_to_path, operator_prec};
+use attr::AttrMetaMethods;
 use codemap::{mod, Span, BytePos, Spanned, spanned, mk_sp};
 use diagnostic;
 use ext::tt::macro_parser;
@@ -93,7 +96,9 @@ bitflags! {
         const UNRESTRICTED                  = 0b0000,
         const RESTRICTION_STMT_EXPR         = 0b0001,
         const RESTRICTION_NO_BAR_OP         = 0b0010,
-        const RESTRICTION_NO_STRUCT_LITERAL = 0b0100
+        const RESTRICTION_NO_STRUCT_LITERAL = 0b0100,
+        const RESTRICTION_NO_TYPE_ASCRIPTION = 0b1000,
+        const RESTRICTION_NO_RANGE          = 0b10000
     }
 }
 
@@ -294,8 +299,13 @@ pub struct Parser<'a> {
     pub obsolete_set: HashSet<ObsoleteSyntax>,
     /// Used to determine the path to externally loaded source files
     pub mod_path_stack: Vec<InternedString>,
-    /// Stack of spans of open delimiters. Used for error message.
-    pub open_braces: Vec<Span>,
+    /// Stack of open delimiters and their spans. Used for error message.
+    pub open_braces: Vec<(token::DelimToken, Span)>,
+    /// The (open, close) spans of the braces closed so far inside the open
+    /// delimiters, in the order they were closed. When a delimiter turns
+    /// out to be unclosed at the end of the file, the first pair on lines
+    /// indented differently is reported as a hint.
+    pub closed_braces: Vec<(Span, Span)>,
     /// Flag if this parser "owns" the directory that it is currently parsing
     /// in. This will affect how nested files are looked up.
     pub owns_directory: bool,
@@ -303,6 +313,9 @@ pub struct Parser<'a> {
     /// name is not known. This does not change while the parser is descending
     /// into modules, and sub-parsers have new values for this name.
     pub root_module_name: Option<String>,
+    /// How many expressions, types, patterns and blocks we are currently
+    /// nested inside of. Checked against `sess.nesting_limit`.
+    pub nesting_depth: uint,
 }
 
 fn is_plain_ident_or_underscore(t: &token::Token) -> bool {
@@ -345,8 +358,10 @@ impl<'a> Parser<'a> {
             obsolete_set: HashSet::new(),
             mod_path_stack: Vec::new(),
             open_braces: Vec::new(),
+            closed_braces: Vec::new(),
             owns_directory: true,
             root_module_name: None,
+            nesting_depth: 0,
         }
     }
 
@@ -483,6 +498,36 @@ impl<'a> Parser<'a> {
         self.commit_stmt(&[edible], &[])
     }
 
+    /// Called where a statement needs a `;` before the current token. If the
+    /// current token starts a new line and could begin another statement,
+    /// the `;` was most likely forgotten at the end of the previous line:
+    /// report it there and carry on as if it had been written. Returns
+    /// true if it did so.
+    fn recover_missing_semi(&mut self) -> bool {
+        match self.token {
+            token::Semi | token::CloseDelim(token::Brace) | token::Eof => return false,
+            _ => {}
+        }
+        if !self.token.can_begin_expr() && !self.token.is_keyword(keywords::Let) {
+            return false;
+        }
+        let prev_span = self.last_span;
+        let (prev_line, next_line) = {
+            let cm = &self.sess.span_diagnostic.cm;
+            (cm.lookup_char_pos(prev_span.hi).line, cm.lookup_char_pos(self.span.lo).line)
+        };
+        if prev_line == next_line {
+            return false;
+        }
+        let actual = self.this_token_to_string();
+        self.span_err(prev_span,
+                      format!("expected `;`, found `{}`", actual).as_slice());
+        self.span_suggestion(mk_sp(prev_span.hi, prev_span.hi),
+                             "insert `;` at the end of this statement",
+                             ";".to_string());
+        true
+    }
+
     pub fn parse_ident(&mut self) -> ast::Ident {
         self.check_strict_keywords();
         self.check_reserved_keywords();
@@ -685,7 +730,9 @@ impl<'a> Parser<'a> {
             if first {
                 first = false
             } else {
-                self.expect(sep)
+                self.expect(sep);
+                // accept trailing separators
+                if self.token == token::BinOp(token::Or) { break }
             }
 
             vector.push(f(self))
@@ -931,6 +978,9 @@ impl<'a> Parser<'a> {
     pub fn span_help(&mut self, sp: Span, m: &str) {
         self.sess.span_diagnostic.span_help(sp, m)
     }
+    pub fn span_suggestion(&mut self, sp: Span, m: &str, suggestion: String) {
+        self.sess.span_diagnostic.span_suggestion(sp, m, suggestion)
+    }
     pub fn bug(&mut self, m: &str) -> ! {
         self.sess.span_diagnostic.span_bug(self.span, m)
     }
@@ -1259,6 +1309,34 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parses `const NAME: TYPE = EXPR;` in a trait or implementation
+    /// declaration, where the value is optional in traits. The `const`
+    /// keyword has already been parsed.
+    fn parse_associated_const(&mut self, attrs: Vec<Attribute>, vis: Visibility,
+                              value_required: bool) -> AssociatedConst {
+        let lo = self.last_span.lo;
+        let ident = self.parse_ident();
+        self.expect(&token::Colon);
+        let ty = self.parse_ty_sum();
+        let expr = if value_required || self.token == token::Eq {
+            self.expect(&token::Eq);
+            Some(self.parse_expr())
+        } else {
+            None
+        };
+        let hi = self.span.hi;
+        self.expect(&token::Semi);
+        AssociatedConst {
+            id: ast::DUMMY_NODE_ID,
+            span: mk_sp(lo, hi),
+            ident: ident,
+            vis: vis,
+            attrs: attrs,
+            ty: ty,
+            expr: expr,
+        }
+    }
+
     /// Parse the items in a trait declaration
     pub fn parse_trait_items(&mut self) -> Vec<TraitItem> {
         self.parse_unspanned_seq(
@@ -1270,6 +1348,8 @@ impl<'a> Parser<'a> {
 
             if p.eat_keyword(keywords::Type) {
                 TypeTraitItem(P(p.parse_associated_type(attrs)))
+            } else if p.eat_keyword(keywords::Const) {
+                ConstTraitItem(P(p.parse_associated_const(attrs, Inherited, false)))
             } else {
                 let lo = p.span.lo;
 
@@ -1422,10 +1502,18 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse a type.
+    pub fn parse_ty(&mut self) -> P<Ty> {
+        self.enter_nested("type");
+        let ty = self.parse_ty_();
+        self.exit_nested();
+        ty
+    }
+
+    /// Parse a type, without counting it towards the nesting limit.
     ///
     /// The second parameter specifies whether the `+` binary operator is
     /// allowed in the type grammar.
-    pub fn parse_ty(&mut self) -> P<Ty> {
+    fn parse_ty_(&mut self) -> P<Ty> {
         maybe_whole!(no_clone self, NtTy);
 
         let lo = self.span.lo;
@@ -2009,12 +2097,26 @@ impl<'a> Parser<'a> {
         let lo = self.span.lo;
         let i = self.parse_ident();
         let hi = self.last_span.hi;
+
+        // `Foo { x }` is shorthand for `Foo { x: x }`
+        if self.token == token::Comma || self.token == token::CloseDelim(token::Brace) {
+            let path = ident_to_path(mk_sp(lo, hi), i);
+            let e = self.mk_expr(lo, hi, ExprPath(path));
+            return ast::Field {
+                ident: spanned(lo, hi, i),
+                span: mk_sp(lo, hi),
+                expr: e,
+                is_shorthand: true,
+            };
+        }
+
         self.expect(&token::Colon);
         let e = self.parse_expr();
         ast::Field {
             ident: spanned(lo, hi, i),
             span: mk_sp(lo, e.span.hi),
             expr: e,
+            is_shorthand: false,
         }
     }
 
@@ -2276,14 +2378,22 @@ impl<'a> Parser<'a> {
                     }
                 } else if self.eat_keyword(keywords::Break) {
                     // BREAK expression
-                    if self.token.is_lifetime() {
+                    let opt_label = if self.token.is_lifetime() {
                         let lifetime = self.get_lifetime();
                         self.bump();
-                        ex = ExprBreak(Some(lifetime));
+                        Some(lifetime)
                     } else {
-                        ex = ExprBreak(None);
-                    }
-                    hi = self.span.hi;
+                        None
+                    };
+                    hi = self.last_span.hi;
+                    let opt_value = if self.token.can_begin_expr() {
+                        let e = self.parse_expr();
+                        hi = e.span.hi;
+                        Some(e)
+                    } else {
+                        None
+                    };
+                    ex = ExprBreak(opt_label, opt_value);
                 } else if self.token == token::ModSep ||
                         self.token.is_ident() &&
                         !self.token.is_keyword(keywords::True) &&
@@ -2446,22 +2556,44 @@ impl<'a> Parser<'a> {
                         }
                     }
                   }
-                  token::Literal(token::Float(n), _suf) => {
+                  token::Literal(token::Float(n), suf) => {
+                    let sp = self.span;
                     self.bump();
-                    let last_span = self.last_span;
                     let fstr = n.as_str();
-                    self.span_err(last_span,
-                                  format!("unexpected token: `{}`", n.as_str()).as_slice());
-                    if fstr.chars().all(|x| "0123456789.".contains_char(x)) {
-                        let float = match from_str::<f64>(fstr) {
-                            Some(f) => f,
-                            None => continue,
-                        };
-                        self.span_help(last_span,
-                            format!("try parenthesizing the first index; e.g., `(foo.{}){}`",
-                                    float.trunc() as uint,
-                                    float.fract().to_string()[1..]).as_slice());
+
+                    // `x.0.1` lexes `0.1` as a float literal; split it back
+                    // into two tuple indices, one span per component.
+                    let parts: Vec<&str> = fstr.split('.').collect();
+                    let splittable = parts.len() == 2 && parts.iter().all(|p| {
+                        !p.is_empty() && p.chars().all(|c| c.is_digit(10))
+                    });
+                    if splittable {
+                        self.expect_no_suffix(sp, "tuple index", suf);
+
+                        let mut start = sp.lo;
+                        for part in parts.iter() {
+                            let end = start + BytePos(part.len() as u32);
+                            match from_str::<uint>(*part) {
+                                Some(n) => {
+                                    let id = spanned(start, end, n);
+                                    let field = self.mk_tup_field(e, id);
+                                    e = self.mk_expr(lo, end, field);
+                                }
+                                None => {
+                                    self.span_err(mk_sp(start, end),
+                                                  "invalid tuple or tuple struct index");
+                                }
+                            }
+                            // Skip over the `.` separating the two indices.
+                            start = end + BytePos(1);
+                        }
+                        hi = sp.hi;
+                        continue;
                     }
+
+                    let last_span = self.last_span;
+                    self.span_err(last_span,
+                                  format!("unexpected token: `{}`", fstr).as_slice());
                     self.abort_if_errors();
 
                   }
@@ -2512,12 +2644,15 @@ impl<'a> Parser<'a> {
                             token::CloseDelim(token::Bracket) => {
                                 self.bump();
                                 hi = self.span.hi;
+                                let base = pprust::operand_to_suggestion(
+                                    &self.sess.span_diagnostic.cm, &*e, ast_util::PREC_POSTFIX);
                                 let slice = self.mk_slice(e, None, None, mutbl);
                                 e = self.mk_expr(lo, hi, slice);
 
                                 self.span_err(e.span, "incorrect slicing expression: `[..]`");
                                 self.span_note(e.span,
-                                    "use `expr[]` to construct a slice of the whole of expr");
+                                    format!("use `{}[]` to construct a slice of the whole of `{}`",
+                                            base, base).as_slice());
                             }
                             // e[..e]
                             _ => {
@@ -2570,6 +2705,12 @@ impl<'a> Parser<'a> {
                 }
               }
 
+              // expr?
+              token::Question => {
+                self.bump();
+                hi = self.last_span.hi;
+                e = self.mk_expr(lo, hi, ExprTry(e));
+              }
               _ => return e
             }
         }
@@ -2628,7 +2769,7 @@ impl<'a> Parser<'a> {
                   // gotten to them yet.
                   match p.open_braces.last() {
                       None => {}
-                      Some(&sp) => p.span_note(sp, "unclosed delimiter"),
+                      Some(&(_, sp)) => p.span_note(sp, "unclosed delimiter"),
                   };
                   let token_str = p.this_token_to_string();
                   p.fatal(format!("incorrect close delimiter: `{}`",
@@ -2681,20 +2822,43 @@ impl<'a> Parser<'a> {
 
         match self.token {
             token::Eof => {
-                let open_braces = self.open_braces.clone();
-                for sp in open_braces.iter() {
-                    self.span_help(*sp, "did you mean to close this delimiter?");
-                }
+                let suspicious_close = self.closed_braces.iter().find(|&&(open_sp, close_sp)| {
+                    self.is_misindented_close(open_sp, close_sp)
+                }).map(|&pair| pair);
                 // There shouldn't really be a span, but it's easier for the test runner
                 // if we give it one
-                self.fatal("this file contains an un-closed delimiter ");
+                let mut err = self.sess.span_diagnostic
+                                  .struct_span_err(self.span,
+                                                   "this file contains an un-closed delimiter ");
+                // Only the innermost delimiter is reported: the outer ones
+                // are usually closed correctly and are merely waiting on it.
+                match self.open_braces.last() {
+                    Some(&(delim, sp)) => {
+                        let delim_str = Parser::token_to_string(&token::OpenDelim(delim));
+                        err = err.span_note(sp, format!("unclosed delimiter `{}` opened here",
+                                                        delim_str).as_slice())
+                                 .help("did you mean to close this delimiter?");
+                    }
+                    None => {}
+                }
+                match suspicious_close {
+                    Some((open_sp, close_sp)) => {
+                        err = err.span_note(close_sp, "this delimiter is indented differently \
+                                                       from the one it closes; it may be \
+                                                       closing the wrong block")
+                                 .span_note(open_sp, "the delimiter it closes is opened here");
+                    }
+                    None => {}
+                }
+                err.emit();
+                panic!(diagnostic::FatalError);
             },
             token::OpenDelim(delim) => {
                 // The span for beginning of the delimited section
                 let pre_span = self.span;
 
                 // Parse the open delimiter.
-                self.open_braces.push(self.span);
+                self.open_braces.push((delim, self.span));
                 let open_span = self.span;
                 self.bump();
 
@@ -2709,6 +2873,15 @@ impl<'a> Parser<'a> {
                 let close_span = self.span;
                 self.bump();
                 self.open_braces.pop().unwrap();
+                // A misindented close can only explain a delimiter left
+                // unclosed around it, so once all of them are closed the
+                // pairs seen so far are of no more use.
+                if self.open_braces.is_empty() {
+                    self.closed_braces.clear();
+                } else if delim == token::Brace {
+                    // Only braces delimit blocks, whose indentation is telling.
+                    self.closed_braces.push((open_span, close_span));
+                }
 
                 // Expand to cover the entire delimited token tree
                 let span = Span { hi: self.span.hi, ..pre_span };
@@ -2724,6 +2897,30 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Returns true if `close` is on a different line from `open` and the
+    /// two lines do not start at the same indentation level. Looking up the
+    /// lines is costly, so this is only done once an error is reported.
+    fn is_misindented_close(&self, open: Span, close: Span) -> bool {
+        if open.expn_id != codemap::NO_EXPANSION || close.expn_id != codemap::NO_EXPANSION {
+            return false;
+        }
+        let cm = &self.sess.span_diagnostic.cm;
+        let open_loc = cm.lookup_char_pos(open.lo);
+        let close_loc = cm.lookup_char_pos(close.lo);
+        if open_loc.line == close_loc.line || open_loc.file.name != close_loc.file.name {
+            return false;
+        }
+        let indent_of = |loc: &codemap::Loc| {
+            loc.file.get_line(loc.line - 1).map(|line| {
+                line.as_slice().chars().take_while(|c| c.is_whitespace()).count()
+            })
+        };
+        match (indent_of(&open_loc), indent_of(&close_loc)) {
+            (Some(open_indent), Some(close_indent)) => open_indent != close_indent,
+            _ => false
+        }
+    }
+
     // parse a stream of tokens into a list of TokenTree's,
     // up to EOF.
     pub fn parse_all_token_trees(&mut self) -> Vec<TokenTree> {
@@ -2734,6 +2931,15 @@ impl<'a> Parser<'a> {
         tts
     }
 
+    /// Parse the operand of a prefix operator. Each operator recurses, so a
+    /// chain of them counts as nesting.
+    fn parse_prefix_operand(&mut self) -> P<Expr> {
+        self.enter_nested("expression");
+        let e = self.parse_prefix_expr();
+        self.exit_nested();
+        e
+    }
+
     /// Parse a prefix-operator expr
     pub fn parse_prefix_expr(&mut self) -> P<Expr> {
         let lo = self.span.lo;
@@ -2743,26 +2949,26 @@ impl<'a> Parser<'a> {
         match self.token {
           token::Not => {
             self.bump();
-            let e = self.parse_prefix_expr();
+            let e = self.parse_prefix_operand();
             hi = e.span.hi;
             ex = self.mk_unary(UnNot, e);
           }
           token::BinOp(token::Minus) => {
             self.bump();
-            let e = self.parse_prefix_expr();
+            let e = self.parse_prefix_operand();
             hi = e.span.hi;
             ex = self.mk_unary(UnNeg, e);
           }
           token::BinOp(token::Star) => {
             self.bump();
-            let e = self.parse_prefix_expr();
+            let e = self.parse_prefix_operand();
             hi = e.span.hi;
             ex = self.mk_unary(UnDeref, e);
           }
           token::BinOp(token::And) | token::AndAnd => {
             self.expect_and();
             let m = self.parse_mutability();
-            let e = self.parse_prefix_expr();
+            let e = self.parse_prefix_operand();
             hi = e.span.hi;
             ex = ExprAddrOf(m, e);
           }
@@ -2776,7 +2982,7 @@ impl<'a> Parser<'a> {
                 _ => self.obsolete(last_span, ObsoleteOwnedExpr)
             }
 
-            let e = self.parse_prefix_expr();
+            let e = self.parse_prefix_operand();
             hi = e.span.hi;
             ex = self.mk_unary(UnUniq, e);
           }
@@ -2803,11 +3009,14 @@ impl<'a> Parser<'a> {
                                       format!("expected expression, found `{}`",
                                               this_token_to_string).as_slice());
                         let box_span = mk_sp(lo, self.last_span.hi);
+                        let suggestion = pprust::expr_to_suggestion(
+                            &self.sess.span_diagnostic.cm, &*place);
                         self.span_help(box_span,
-                                       "perhaps you meant `box() (foo)` instead?");
+                                       format!("perhaps you meant `box() ({})` instead?",
+                                               suggestion).as_slice());
                         self.abort_if_errors();
                     }
-                    let subexpression = self.parse_prefix_expr();
+                    let subexpression = self.parse_prefix_operand();
                     hi = subexpression.span.hi;
                     ex = ExprBox(place, subexpression);
                     return self.mk_expr(lo, hi, ex);
@@ -2815,7 +3024,7 @@ impl<'a> Parser<'a> {
             }
 
             // Otherwise, we use the unique pointer default.
-            let subexpression = self.parse_prefix_expr();
+            let subexpression = self.parse_prefix_operand();
             hi = subexpression.span.hi;
             ex = self.mk_unary(UnUniq, subexpression);
           }
@@ -2831,45 +3040,78 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse an expression of binops of at least min_prec precedence
-    pub fn parse_more_binops(&mut self, lhs: P<Expr>, min_prec: uint) -> P<Expr> {
-        if self.expr_is_complete(&*lhs) { return lhs; }
+    pub fn parse_more_binops(&mut self, mut lhs: P<Expr>, min_prec: uint) -> P<Expr> {
+        // Operators of the same precedence are folded in here rather than by
+        // recursing, so a long chain like `a + b + c + ...` isn't nesting.
+        loop {
+            if self.expr_is_complete(&*lhs) { return lhs; }
 
-        // Prevent dynamic borrow errors later on by limiting the
-        // scope of the borrows.
-        if self.token == token::BinOp(token::Or) &&
-            self.restrictions.contains(RESTRICTION_NO_BAR_OP) {
-            return lhs;
-        }
+            // Prevent dynamic borrow errors later on by limiting the
+            // scope of the borrows.
+            if self.token == token::BinOp(token::Or) &&
+                self.restrictions.contains(RESTRICTION_NO_BAR_OP) {
+                return lhs;
+            }
 
-        let cur_opt = self.token.to_binop();
-        match cur_opt {
-            Some(cur_op) => {
-                let cur_prec = operator_prec(cur_op);
-                if cur_prec > min_prec {
-                    self.bump();
-                    let expr = self.parse_prefix_expr();
-                    let rhs = self.parse_more_binops(expr, cur_prec);
-                    let lhs_span = lhs.span;
-                    let rhs_span = rhs.span;
-                    let binary = self.mk_binary(cur_op, lhs, rhs);
-                    let bin = self.mk_expr(lhs_span.lo, rhs_span.hi, binary);
-                    self.parse_more_binops(bin, min_prec)
-                } else {
-                    lhs
+            let cur_opt = self.token.to_binop();
+            lhs = match cur_opt {
+                Some(cur_op) => {
+                    let cur_prec = operator_prec(cur_op);
+                    if cur_prec > min_prec {
+                        self.bump();
+                        let expr = self.parse_prefix_expr();
+                        self.enter_nested("expression");
+                        let rhs = self.parse_more_binops(expr, cur_prec);
+                        self.exit_nested();
+                        let lhs_span = lhs.span;
+                        let rhs_span = rhs.span;
+                        let binary = self.mk_binary(cur_op, lhs, rhs);
+                        self.mk_expr(lhs_span.lo, rhs_span.hi, binary)
+                    } else {
+                        return lhs;
+                    }
                 }
-            }
-            None => {
-                if as_prec > min_prec && self.eat_keyword(keywords::As) {
-                    let rhs = self.parse_ty();
-                    let _as = self.mk_expr(lhs.span.lo,
-                                           rhs.span.hi,
-                                           ExprCast(lhs, rhs));
-                    self.parse_more_binops(_as, min_prec)
-                } else {
-                    lhs
+                None => {
+                    if as_prec > min_prec && self.eat_keyword(keywords::As) {
+                        let rhs = self.parse_ty();
+                        self.mk_expr(lhs.span.lo,
+                                     rhs.span.hi,
+                                     ExprCast(lhs, rhs))
+                    } else if as_prec > min_prec && self.token == token::Colon &&
+                            !self.restrictions.contains(RESTRICTION_NO_TYPE_ASCRIPTION) {
+                        self.bump();
+                        self.check_type_ascription_rhs(&*lhs);
+                        let rhs = self.parse_ty();
+                        self.mk_expr(lhs.span.lo,
+                                     rhs.span.hi,
+                                     ExprType(lhs, rhs))
+                    } else {
+                        return lhs;
+                    }
                 }
-            }
+            };
+        }
+    }
+
+    /// Called after the `:` of a type ascription `expr: Type`. A literal
+    /// here is never a type; it usually means a struct literal ended up
+    /// where struct literals are not allowed, so say so.
+    fn check_type_ascription_rhs(&mut self, lhs: &Expr) {
+        if !self.token.is_lit() {
+            return;
         }
+        let span = self.span;
+        let found = self.this_token_to_string();
+        let help = match lhs.node {
+            ExprPath(ref path) if path.segments.len() == 1 => {
+                "if this is a field of a struct literal, the struct literal \
+                 must be wrapped in parentheses here"
+            }
+            _ => "type ascription is written `expr: Type`"
+        };
+        self.span_fatal_help(span,
+                             format!("expected type, found `{}`", found).as_slice(),
+                             help);
     }
 
     /// Parse an assignment expression....
@@ -2904,6 +3146,11 @@ impl<'a> Parser<'a> {
               let assign_op = self.mk_assign_op(aop, lhs, rhs);
               self.mk_expr(lo, rhs_span.hi, assign_op)
           }
+          token::DotDotDot if !self.restrictions.contains(RESTRICTION_NO_RANGE) => {
+              self.bump();
+              let rhs = self.parse_binops();
+              self.mk_expr(lo, rhs.span.hi, ExprInclusiveRange(lhs, rhs))
+          }
           _ => {
               lhs
           }
@@ -3072,11 +3319,41 @@ impl<'a> Parser<'a> {
     pub fn parse_expr_res(&mut self, r: Restrictions) -> P<Expr> {
         let old = self.restrictions;
         self.restrictions = r;
+        self.enter_nested("expression");
         let e = self.parse_assign_expr();
+        self.exit_nested();
         self.restrictions = old;
         return e;
     }
 
+    /// Goes one level deeper into the nested constructs the parser recurses
+    /// on, giving up with a fatal error once `sess.nesting_limit` is reached
+    /// rather than overflowing the stack. `what` names the construct.
+    fn enter_nested(&mut self, what: &str) {
+        let limit = self.sess.nesting_limit.get();
+        if self.nesting_depth >= limit {
+            let span = self.span;
+            self.span_fatal_help(span,
+                                 format!("{} nesting exceeds limit {}; consider \
+                                          refactoring or raising the limit",
+                                         what, limit).as_slice(),
+                                 format!("add `#![parser_recursion_limit = \"{}\"]` \
+                                          to the crate attributes to raise the limit",
+                                         limit * 2).as_slice());
+        }
+        self.nesting_depth += 1;
+    }
+
+    fn exit_nested(&mut self) {
+        self.nesting_depth -= 1;
+    }
+
+    /// Parse an expression that may be directly followed by a `:` that is
+    /// not a type ascription, as in macro arguments and `asm!` operands.
+    pub fn parse_expr_before_colon(&mut self) -> P<Expr> {
+        self.parse_expr_res(RESTRICTION_NO_TYPE_ASCRIPTION)
+    }
+
     /// Parse the RHS of a local variable declaration (e.g. '= 14;')
     fn parse_initializer(&mut self) -> Option<P<Expr>> {
         if self.token == token::Eq {
@@ -3111,6 +3388,8 @@ impl<'a> Parser<'a> {
                 first = false;
             } else {
                 self.expect(&token::Comma);
+                // accept trailing commas
+                if self.token == token::CloseDelim(token::Bracket) { break }
             }
 
             if before_slice {
@@ -3126,9 +3405,9 @@ impl<'a> Parser<'a> {
                         }));
                         before_slice = false;
                     } else {
-                        let _ = self.parse_pat();
-                        let span = self.span;
-                        self.obsolete(span, ObsoleteSubsliceMatch);
+                        // `..tail` binds the rest of the slice, like `tail..`
+                        slice = Some(self.parse_pat());
+                        before_slice = false;
                     }
                     continue
                 }
@@ -3167,11 +3446,12 @@ impl<'a> Parser<'a> {
             let hi;
 
             if self.token == token::DotDot {
+                let dotdot_span = self.span;
                 self.bump();
                 if self.token != token::CloseDelim(token::Brace) {
-                    let token_str = self.this_token_to_string();
-                    self.fatal(format!("expected `{}`, found `{}`", "}",
-                                       token_str).as_slice())
+                    self.span_fatal_help(dotdot_span,
+                                         "`..` must be the last element of a struct pattern",
+                                         "move the `..` after all of the named fields");
                 }
                 etc = true;
                 break;
@@ -3220,6 +3500,13 @@ impl<'a> Parser<'a> {
 
     /// Parse a pattern.
     pub fn parse_pat(&mut self) -> P<Pat> {
+        self.enter_nested("pattern");
+        let pat = self.parse_pat_();
+        self.exit_nested();
+        pat
+    }
+
+    fn parse_pat_(&mut self) -> P<Pat> {
         maybe_whole!(self, NtPat);
 
         let lo = self.span.lo;
@@ -3331,6 +3618,14 @@ impl<'a> Parser<'a> {
                     self.parse_literal_maybe_minus()
                 };
                 pat = PatRange(val, end);
+            } else if self.token == token::DotDot && self.look_ahead(1, |t| t.is_lit()) {
+                let span = self.span;
+                self.span_err(span, "exclusive range patterns are not supported");
+                self.span_suggestion(span, "use `...` for an inclusive range pattern, \
+                                            as in `1...5`", "...".to_string());
+                self.bump();
+                let end = self.parse_literal_maybe_minus();
+                pat = PatRange(val, end);
             } else {
                 pat = PatLit(val);
             }
@@ -3365,9 +3660,9 @@ impl<'a> Parser<'a> {
                     self.look_ahead(2, |t| {
                         *t != token::Comma && *t != token::CloseDelim(token::Bracket)
                     }) {
-                let start = self.parse_expr_res(RESTRICTION_NO_BAR_OP);
+                let start = self.parse_expr_res(RESTRICTION_NO_BAR_OP | RESTRICTION_NO_RANGE);
                 self.eat(&token::DotDotDot);
-                let end = self.parse_expr_res(RESTRICTION_NO_BAR_OP);
+                let end = self.parse_expr_res(RESTRICTION_NO_BAR_OP | RESTRICTION_NO_RANGE);
                 pat = PatRange(start, end);
             } else if self.token.is_plain_ident() && !can_be_enum_or_struct {
                 let id = self.parse_ident();
@@ -3396,6 +3691,18 @@ impl<'a> Parser<'a> {
                 // parse an enum pat
                 let enum_path = self.parse_path(LifetimeAndTypesWithColons);
                 match self.token {
+                    token::DotDotDot => {
+                        // a range whose start is a path, e.g. `consts::MIN ... consts::MAX`
+                        let path_hi = self.last_span.hi;
+                        let start = self.mk_expr(lo, path_hi, ExprPath(enum_path));
+                        self.bump();
+                        let end = self.parse_expr_res(RESTRICTION_NO_BAR_OP | RESTRICTION_NO_RANGE);
+                        return P(ast::Pat {
+                            id: ast::DUMMY_NODE_ID,
+                            span: mk_sp(lo, end.span.hi),
+                            node: PatRange(start, end),
+                        })
+                    }
                     token::OpenDelim(token::Brace) => {
                         self.bump();
                         let (fields, etc) =
@@ -3721,6 +4028,14 @@ impl<'a> Parser<'a> {
     /// Parse the rest of a block expression or function body
     fn parse_block_tail_(&mut self, lo: BytePos, s: BlockCheckMode,
                          first_item_attrs: Vec<Attribute> ) -> P<Block> {
+        self.enter_nested("block");
+        let block = self.parse_block_tail_items(lo, s, first_item_attrs);
+        self.exit_nested();
+        block
+    }
+
+    fn parse_block_tail_items(&mut self, lo: BytePos, s: BlockCheckMode,
+                              first_item_attrs: Vec<Attribute>) -> P<Block> {
         let mut stmts = Vec::new();
         let mut expr = None;
 
@@ -3764,13 +4079,21 @@ impl<'a> Parser<'a> {
                     stmt.and_then(|Spanned {node, span}| match node {
                         StmtExpr(e, stmt_id) => {
                             // expression without semicolon
-                            if classify::expr_requires_semi_to_be_stmt(&*e) {
+                            let requires_semi = classify::expr_requires_semi_to_be_stmt(&*e);
+                            let missing_semi = requires_semi && self.recover_missing_semi();
+                            if requires_semi && !missing_semi {
                                 // Just check for errors and recover; do not eat semicolon yet.
                                 self.commit_stmt(&[], &[token::Semi,
                                     token::CloseDelim(token::Brace)]);
                             }
 
                             match self.token {
+                                _ if missing_semi => {
+                                    stmts.push(P(Spanned {
+                                        node: StmtSemi(e, stmt_id),
+                                        span: span,
+                                    }));
+                                }
                                 token::Semi => {
                                     self.bump();
                                     let span_with_semi = Span {
@@ -3821,7 +4144,8 @@ impl<'a> Parser<'a> {
                             }
                         }
                         _ => { // all other kinds of statements:
-                            if classify::stmt_ends_with_semi(&node) {
+                            if classify::stmt_ends_with_semi(&node) &&
+                                    !self.recover_missing_semi() {
                                 self.commit_stmt_expecting(token::Semi);
                             }
 
@@ -4015,28 +4339,46 @@ impl<'a> Parser<'a> {
         let mut parsed_something = false;
         loop {
             let lo = self.span.lo;
-            let ident = match self.token {
-                token::Ident(..) => self.parse_ident(),
-                _ => break,
-            };
-            self.expect(&token::Colon);
+            match self.token {
+                token::Ident(..) if self.look_ahead(1, |t| *t == token::Colon) => {
+                    let ident = self.parse_ident();
+                    self.bump();
 
-            let bounds = self.parse_ty_param_bounds();
-            let hi = self.span.hi;
-            let span = mk_sp(lo, hi);
+                    let bounds = self.parse_ty_param_bounds();
+                    let hi = self.span.hi;
+                    let span = mk_sp(lo, hi);
 
-            if bounds.len() == 0 {
-                self.span_err(span,
-                              "each predicate in a `where` clause must have \
-                               at least one bound in it");
-            }
+                    if bounds.len() == 0 {
+                        self.span_err(span,
+                                      "each predicate in a `where` clause must have \
+                                       at least one bound in it");
+                    }
 
-            generics.where_clause.predicates.push(ast::WherePredicate {
-                id: ast::DUMMY_NODE_ID,
-                span: span,
-                ident: ident,
-                bounds: bounds,
-            });
+                    generics.where_clause.predicates.push(ast::BoundPredicate(
+                        ast::WhereBoundPredicate {
+                            id: ast::DUMMY_NODE_ID,
+                            span: span,
+                            ident: ident,
+                            bounds: bounds,
+                        }));
+                }
+                token::Ident(..) | token::ModSep | token::Lt => {
+                    // An equality constraint, e.g. `<T as Trait>::Assoc == Ty`
+                    let lhs_ty = self.parse_ty();
+                    self.expect(&token::EqEq);
+                    let rhs_ty = self.parse_ty();
+                    let hi = self.last_span.hi;
+
+                    generics.where_clause.predicates.push(ast::EqPredicate(
+                        ast::WhereEqPredicate {
+                            id: ast::DUMMY_NODE_ID,
+                            span: mk_sp(lo, hi),
+                            lhs_ty: lhs_ty,
+                            rhs_ty: rhs_ty,
+                        }));
+                }
+                _ => break,
+            }
             parsed_something = true;
 
             if !self.eat(&token::Comma) {
@@ -4503,6 +4845,11 @@ impl<'a> Parser<'a> {
                 impl_items.push(TypeImplItem(P(self.parse_typedef(
                             method_attrs,
                             vis))))
+            } else if self.eat_keyword(keywords::Const) {
+                impl_items.push(ConstImplItem(P(self.parse_associated_const(
+                            method_attrs,
+                            vis,
+                            true))))
             } else {
                 impl_items.push(MethodImplItem(self.parse_method(
                             method_attrs,
@@ -4639,6 +4986,11 @@ impl<'a> Parser<'a> {
                                     written as `struct {};`",
                                    token::get_ident(class_name)).as_slice());
             }
+            // The `where` clause of a tuple-like struct may also follow its
+            // fields, as in `struct Foo<T>(T) where T: Copy;`.
+            if generics.where_clause.predicates.is_empty() {
+                self.parse_where_clause(&mut generics);
+            }
             self.expect(&token::Semi);
         } else if self.eat(&token::Semi) {
             // It's a unit-like struct.
@@ -4841,7 +5193,8 @@ impl<'a> Parser<'a> {
                     outer_attrs: &[ast::Attribute],
                     id_sp: Span)
                     -> (ast::Item_, Vec<ast::Attribute> ) {
-        let mut prefix = Path::new(self.sess.span_diagnostic.cm.span_to_filename(self.span));
+        let mut prefix = Path::new(self.sess.span_diagnostic.cm
+                                       .span_to_unmapped_filename(self.span));
         prefix.pop();
         let mod_path = Path::new(".").join_many(self.mod_path_stack.as_slice());
         let dir_path = prefix.join(&mod_path);
@@ -5883,6 +6236,7 @@ impl<'a> Parser<'a> {
         // parse the crate's inner attrs, maybe (oops) one
         // of the attrs of an item:
         let (inner, next) = self.parse_inner_attrs_and_next();
+        self.set_nesting_limit_from_attrs(inner.as_slice());
         let first_item_outer_attrs = next;
         // parse the items inside the crate:
         let m = self.parse_mod_items(token::Eof, first_item_outer_attrs, lo);
@@ -5896,6 +6250,25 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Honour a `#![parser_recursion_limit = "N"]` crate attribute. This
+    /// has to happen while parsing, since the limit applies to the rest of
+    /// the crate being parsed.
+    fn set_nesting_limit_from_attrs(&mut self, attrs: &[Attribute]) {
+        for attr in attrs.iter() {
+            if !attr.check_name("parser_recursion_limit") {
+                continue;
+            }
+            match attr.value_str().and_then(|s| from_str::<uint>(s.get())) {
+                Some(limit) => self.sess.nesting_limit.set(limit),
+                None => {
+                    self.span_err(attr.span, "malformed `parser_recursion_limit` \
+                                              attribute, expected \
+                                              `#![parser_recursion_limit = \"N\"]`");
+                }
+            }
+        }
+    }
+
     pub fn parse_optional_str(&mut self)
                               -> Option<(InternedString, ast::StrStyle, Option<ast::Name>)> {
         let ret = match self.token {
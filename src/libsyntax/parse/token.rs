@@ -22,6 +22,7 @@ use util::interner::{RcStr, StrInterner};
 use util::interner;
 
 use serialize::{Decodable, Decoder, Encodable, Encoder};
+use std::cell::RefCell;
 use std::fmt;
 use std::mem;
 use std::path::BytesContainer;
@@ -216,6 +217,18 @@ impl Token {
         }
     }
 
+    /// Returns `true` if the token is a doc comment for the item enclosing
+    /// it, `//! ...` or `/*! ... */`.
+    pub fn is_inner_doc_comment(&self) -> bool {
+        match *self {
+            DocComment(name) => {
+                let s = name.as_str();
+                s.starts_with("//!") || s.starts_with("/*!")
+            }
+            _ => false,
+        }
+    }
+
     /// Returns `true` if the token is a lifetime.
     pub fn is_lifetime(&self) -> bool {
         match *self {
@@ -224,6 +237,15 @@ impl Token {
         }
     }
 
+    /// Returns `true` if the token is whitespace, a non-doc comment or a
+    /// shebang line, none of which the parser ever sees.
+    pub fn is_trivia(&self) -> bool {
+        match *self {
+            Whitespace | Comment | Shebang(_) => true,
+            _                                 => false,
+        }
+    }
+
     /// Returns `true` if the token is either the `mut` or `const` keyword.
     pub fn is_mutability(&self) -> bool {
         self.is_keyword(keywords::Mut) ||
@@ -265,7 +287,8 @@ impl Token {
     }
 
     /// Returns `true` if the token is either a special identifier, or a strict
-    /// or reserved keyword.
+    /// or reserved keyword. Contextual keywords are not included, since they
+    /// are valid identifiers.
     #[allow(non_upper_case_globals)]
     pub fn is_any_keyword(&self) -> bool {
         match *self {
@@ -276,6 +299,8 @@ impl Token {
                 || n == STATIC_KEYWORD_NAME
                 || n == SUPER_KEYWORD_NAME
                 || STRICT_KEYWORD_START <= n
+                && n <= STRICT_KEYWORD_FINAL
+                || RESERVED_KEYWORD_START <= n
                 && n <= RESERVED_KEYWORD_FINAL
             },
             _ => false
@@ -307,6 +332,16 @@ impl Token {
         }
     }
 
+    /// Returns `true` if the token is a keyword only in some grammatical
+    /// positions, and may be used as an identifier everywhere else.
+    #[allow(non_upper_case_globals)]
+    pub fn is_contextual_keyword(&self) -> bool {
+        match *self {
+            Ident(sid, Plain) => is_contextual_keyword_name(sid.name),
+            _ => false,
+        }
+    }
+
     /// Returns `true` if the token is a keyword that has been reserved for
     /// possible future use.
     #[allow(non_upper_case_globals)]
@@ -393,12 +428,16 @@ macro_rules! declare_special_idents_and_keywords {(
     pub mod keywords {
         'strict:
         $( ($sk_name:expr, $sk_variant:ident, $sk_str:expr); )*
+        'contextual:
+        $( ($ck_name:expr, $ck_variant:ident, $ck_str:expr); )*
         'reserved:
         $( ($rk_name:expr, $rk_variant:ident, $rk_str:expr); )*
     }
 ) => {
     static STRICT_KEYWORD_START: ast::Name = first!($( ast::Name($sk_name), )*);
     static STRICT_KEYWORD_FINAL: ast::Name = last!($( ast::Name($sk_name), )*);
+    static CONTEXTUAL_KEYWORD_START: ast::Name = first!($( ast::Name($ck_name), )*);
+    static CONTEXTUAL_KEYWORD_FINAL: ast::Name = last!($( ast::Name($ck_name), )*);
     static RESERVED_KEYWORD_START: ast::Name = first!($( ast::Name($rk_name), )*);
     static RESERVED_KEYWORD_FINAL: ast::Name = last!($( ast::Name($rk_name), )*);
 
@@ -423,15 +462,18 @@ macro_rules! declare_special_idents_and_keywords {(
 
     /// All the valid words that have meaning in the Rust language.
     ///
-    /// Rust keywords are either 'strict' or 'reserved'.  Strict keywords may not
-    /// appear as identifiers at all. Reserved keywords are not used anywhere in
-    /// the language and may not appear as identifiers.
+    /// Rust keywords are either 'strict', 'contextual' or 'reserved'. Strict
+    /// keywords may not appear as identifiers at all. Contextual keywords only
+    /// have meaning in the positions where the grammar expects them, and are
+    /// ordinary identifiers everywhere else. Reserved keywords are not used
+    /// anywhere in the language and may not appear as identifiers.
     pub mod keywords {
         pub use self::Keyword::*;
         use ast;
 
         pub enum Keyword {
             $( $sk_variant, )*
+            $( $ck_variant, )*
             $( $rk_variant, )*
         }
 
@@ -439,19 +481,22 @@ macro_rules! declare_special_idents_and_keywords {(
             pub fn to_name(&self) -> ast::Name {
                 match *self {
                     $( $sk_variant => ast::Name($sk_name), )*
+                    $( $ck_variant => ast::Name($ck_name), )*
                     $( $rk_variant => ast::Name($rk_name), )*
                 }
             }
         }
     }
 
-    fn mk_fresh_ident_interner() -> IdentInterner {
+    /// Creates an interner holding just the keywords and special idents.
+    pub fn mk_fresh_ident_interner() -> IdentInterner {
         // The indices here must correspond to the numbers in
         // special_idents, in Keyword to_name(), and in static
         // constants below.
         let mut init_vec = Vec::new();
         $(init_vec.push($si_str);)*
         $(init_vec.push($sk_str);)*
+        $(init_vec.push($ck_str);)*
         $(init_vec.push($rk_str);)*
         interner::StrInterner::prefill(init_vec.as_slice())
     }
@@ -532,6 +577,8 @@ declare_special_idents_and_keywords! {
         (42,                         Proc,       "proc");
         (43,                         Box,        "box");
         (44,                         Const,      "const");
+
+        'contextual:
         (45,                         Where,      "where");
 
         'reserved:
@@ -551,17 +598,82 @@ declare_special_idents_and_keywords! {
     }
 }
 
+/// Returns `true` if `name` is a contextual keyword, e.g. `where`.
+#[allow(non_upper_case_globals)]
+pub fn is_contextual_keyword_name(name: ast::Name) -> bool {
+    CONTEXTUAL_KEYWORD_START <= name && name <= CONTEXTUAL_KEYWORD_FINAL
+}
+
 // looks like we can get rid of this completely...
 pub type IdentInterner = StrInterner;
 
+thread_local!(static IDENT_INTERNER: RefCell<Rc<IdentInterner>> = {
+    RefCell::new(Rc::new(mk_fresh_ident_interner()))
+})
+
 // if an interner exists in TLS, return it. Otherwise, prepare a
 // fresh one.
 // FIXME(eddyb) #8726 This should probably use a task-local reference.
 pub fn get_ident_interner() -> Rc<IdentInterner> {
-    thread_local!(static KEY: Rc<::parse::token::IdentInterner> = {
-        Rc::new(mk_fresh_ident_interner())
-    })
-    KEY.with(|k| k.clone())
+    IDENT_INTERNER.with(|k| k.borrow().clone())
+}
+
+/// The ident interner of a session together with the hygiene tables whose
+/// syntax contexts refer to its names. The two must always be installed
+/// together, since the names of one interner mean nothing to another's
+/// tables.
+#[deriving(Clone)]
+pub struct IdentTables {
+    pub interner: Rc<IdentInterner>,
+    pub hygiene: mtwt::HygieneTables,
+}
+
+impl IdentTables {
+    /// Makes fresh tables for a new session.
+    pub fn new() -> IdentTables {
+        IdentTables {
+            interner: Rc::new(mk_fresh_ident_interner()),
+            hygiene: mtwt::HygieneTables::new(),
+        }
+    }
+}
+
+/// Reinstalls the ident interner and hygiene tables that
+/// `install_ident_tables` replaced when it is dropped, including while
+/// unwinding.
+pub struct IdentTablesGuard {
+    previous: Option<IdentTables>,
+}
+
+impl Drop for IdentTablesGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            swap_ident_tables(previous);
+        }
+    }
+}
+
+fn swap_ident_tables(tables: IdentTables) -> IdentTables {
+    let IdentTables { interner, hygiene } = tables;
+    IdentTables {
+        interner: IDENT_INTERNER.with(|k| mem::replace(&mut *k.borrow_mut(), interner)),
+        hygiene: mtwt::set_tables(hygiene),
+    }
+}
+
+/// Makes `tables` the ident interner and hygiene tables of the current
+/// thread until the returned guard is dropped.
+pub fn install_ident_tables(tables: IdentTables) -> IdentTablesGuard {
+    IdentTablesGuard { previous: Some(swap_ident_tables(tables)) }
+}
+
+/// Runs `f` with `tables` installed on the current thread, so that a
+/// session can have an interner and hygiene tables of its own which live
+/// as long as it does. The previous ones are restored afterwards, even if
+/// `f` panics.
+pub fn with_ident_tables<T>(tables: IdentTables, f: || -> T) -> T {
+    let _guard = install_ident_tables(tables);
+    f()
 }
 
 /// Reset the ident interner to its initial state.
@@ -710,11 +822,46 @@ mod test {
     use super::*;
     use ast;
     use ext::mtwt;
+    use std::rc::Rc;
 
     fn mark_ident(id : ast::Ident, m : ast::Mrk) -> ast::Ident {
         ast::Ident { name: id.name, ctxt:mtwt::apply_mark(m, id.ctxt) }
     }
 
+    #[test] fn with_ident_tables_test() {
+        let outer = str_to_ident("with_ident_tables_test").name;
+        let outer_ctxt = mtwt::apply_mark(11, ast::EMPTY_CTXT);
+        let session = IdentTables::new();
+        let (inner, inner_ctxt) = with_ident_tables(session.clone(), || {
+            assert!(get_ident_interner().find("with_ident_tables_test").is_none());
+            (str_to_ident("inner_only").name, mtwt::apply_mark(12, ast::EMPTY_CTXT))
+        });
+        assert_eq!(session.interner.get(inner).as_slice(), "inner_only");
+        // the first context made in fresh tables follows the empty and illegal ones
+        assert_eq!(inner_ctxt, 2);
+        assert_eq!(get_name(outer).get(), "with_ident_tables_test");
+        assert_eq!(mtwt::apply_mark(11, ast::EMPTY_CTXT), outer_ctxt);
+        with_ident_tables(session, || {
+            assert_eq!(mtwt::apply_mark(12, ast::EMPTY_CTXT), inner_ctxt);
+        });
+    }
+
+    #[test] fn ident_tables_guard_restores_on_drop_test() {
+        let outer = get_ident_interner();
+        {
+            let _guard = install_ident_tables(IdentTables::new());
+            assert!(!is_same_interner(&outer, &get_ident_interner()));
+            {
+                let _nested = install_ident_tables(IdentTables::new());
+            }
+        }
+        assert!(is_same_interner(&outer, &get_ident_interner()));
+    }
+
+    fn is_same_interner(a: &Rc<IdentInterner>, b: &Rc<IdentInterner>) -> bool {
+        (&**a as *const IdentInterner) == (&**b as *const IdentInterner)
+    }
+
     #[test] fn mtwt_token_eq_test() {
         assert!(Gt.mtwt_eq(&Gt));
         let a = str_to_ident("bac");
@@ -9,13 +9,16 @@
 // except according to those terms.
 
 pub use self::AnnNode::*;
+pub use self::WhereClauseStyle::*;
 
 use abi;
 use ast::{mod, FnUnboxedClosureKind, FnMutUnboxedClosureKind};
 use ast::{FnOnceUnboxedClosureKind};
 use ast::{MethodImplItem, RegionTyParamBound, TraitTyParamBound};
 use ast::{RequiredMethod, ProvidedMethod, TypeImplItem, TypeTraitItem};
+use ast::{ConstImplItem, ConstTraitItem};
 use ast::{UnboxedClosureKind};
+use ast_map::{mod, NodePrinter};
 use ast_util;
 use owned_slice::OwnedSlice;
 use attr::{AttrMetaMethods, AttributeMethods};
@@ -27,8 +30,12 @@ use parse;
 use print::pp::{mod, break_offset, word, space, zerobreak, hardbreak};
 use print::pp::{Breaks, Consistent, Inconsistent, eof};
 use ptr::P;
+use visit;
+
+use serialize::json;
 
 use std::{ascii, mem};
+use std::default::Default;
 use std::io::{mod, IoResult};
 
 pub enum AnnNode<'a> {
@@ -49,11 +56,89 @@ pub struct NoAnn;
 
 impl PpAnn for NoAnn {}
 
+fn is_synthesized_span(sp: codemap::Span) -> bool {
+    sp == codemap::DUMMY_SP || sp.expn_id != codemap::NO_EXPANSION
+}
+
+/// Looks for nodes under a node spanning `outer` that were synthesized
+/// or moved there, which makes the source text of `outer` stale.
+struct SynthesizedNodeFinder {
+    outer: codemap::Span,
+    found: bool,
+}
+
+impl SynthesizedNodeFinder {
+    fn check(&mut self, sp: codemap::Span) {
+        if is_synthesized_span(sp) || sp.lo < self.outer.lo || sp.hi > self.outer.hi {
+            self.found = true;
+        }
+    }
+}
+
+impl<'v> visit::Visitor<'v> for SynthesizedNodeFinder {
+    fn visit_item(&mut self, i: &'v ast::Item) {
+        self.check(i.span);
+        visit::walk_item(self, i)
+    }
+    fn visit_block(&mut self, b: &'v ast::Block) {
+        self.check(b.span);
+        visit::walk_block(self, b)
+    }
+    fn visit_stmt(&mut self, s: &'v ast::Stmt) {
+        self.check(s.span);
+        visit::walk_stmt(self, s)
+    }
+    fn visit_pat(&mut self, p: &'v ast::Pat) {
+        self.check(p.span);
+        visit::walk_pat(self, p)
+    }
+    fn visit_expr(&mut self, e: &'v ast::Expr) {
+        self.check(e.span);
+        visit::walk_expr(self, e)
+    }
+    fn visit_ty(&mut self, t: &'v ast::Ty) {
+        self.check(t.span);
+        visit::walk_ty(self, t)
+    }
+}
+
 pub struct CurrentCommentAndLiteral {
     cur_cmnt: uint,
     cur_lit: uint,
 }
 
+/// Where a `where` clause is placed relative to the item it belongs to.
+#[deriving(Clone, PartialEq, Show)]
+pub enum WhereClauseStyle {
+    /// `where` follows the item header on the same line, if it fits.
+    WhereSameLine,
+    /// `where` always starts a new line, indented one unit.
+    WhereOwnLine,
+}
+
+/// Layout options for the pretty printer.
+#[deriving(Clone, Show)]
+pub struct PpConfig {
+    /// The column budget lines are broken to fit in.
+    pub max_width: uint,
+    /// The number of columns each nesting level is indented by.
+    pub indent_unit: uint,
+    /// Whether match arm bodies that aren't blocks get wrapped in one.
+    pub wrap_match_arms: bool,
+    pub where_clause_style: WhereClauseStyle,
+}
+
+impl Default for PpConfig {
+    fn default() -> PpConfig {
+        PpConfig {
+            max_width: default_columns,
+            indent_unit: indent_unit,
+            wrap_match_arms: false,
+            where_clause_style: WhereSameLine,
+        }
+    }
+}
+
 pub struct State<'a> {
     pub s: pp::Printer,
     cm: Option<&'a CodeMap>,
@@ -63,17 +148,52 @@ pub struct State<'a> {
     boxes: Vec<pp::Breaks>,
     ann: &'a (PpAnn+'a),
     encode_idents_with_hygiene: bool,
+    /// Emit nodes that still match the original source verbatim from the
+    /// CodeMap, re-printing only synthesized or modified nodes.
+    preserve_source: bool,
+    config: PpConfig,
+    /// Set while printing a node in context, see `node_in_context_to_string`.
+    focus: Option<Focus>,
+}
+
+/// How much of its surroundings `node_in_context_to_string` prints a node
+/// with.
+#[deriving(Clone, Show)]
+pub struct NodeContext {
+    /// The number of enclosing items (functions, methods, impls and so on)
+    /// to print the node inside of.
+    pub ancestors: uint,
+    /// Printed as a comment in place of each run of omitted siblings.
+    pub elision_marker: String,
+}
+
+impl Default for NodeContext {
+    fn default() -> NodeContext {
+        NodeContext {
+            ancestors: 1,
+            elision_marker: "...".to_string(),
+        }
+    }
+}
+
+struct Focus {
+    span: codemap::Span,
+    elision_marker: String,
+    /// Whether the last sibling was elided, so that a run of them gets a
+    /// single marker.
+    eliding: bool,
 }
 
-pub fn rust_printer(writer: Box<io::Writer+'static>) -> State<'static> {
+pub fn rust_printer(writer: Box<io::Writer+'static>, config: PpConfig) -> State<'static> {
     static NO_ANN: NoAnn = NoAnn;
-    rust_printer_annotated(writer, &NO_ANN)
+    rust_printer_annotated(writer, &NO_ANN, config)
 }
 
 pub fn rust_printer_annotated<'a>(writer: Box<io::Writer+'static>,
-                                  ann: &'a PpAnn) -> State<'a> {
+                                  ann: &'a PpAnn,
+                                  config: PpConfig) -> State<'a> {
     State {
-        s: pp::mk_printer(writer, default_columns),
+        s: pp::mk_printer(writer, config.max_width),
         cm: None,
         comments: None,
         literals: None,
@@ -84,9 +204,14 @@ pub fn rust_printer_annotated<'a>(writer: Box<io::Writer+'static>,
         boxes: Vec::new(),
         ann: ann,
         encode_idents_with_hygiene: false,
+        preserve_source: false,
+        config: config,
+        focus: None,
     }
 }
 
+/// The default for `PpConfig::indent_unit`. Printing itself always goes by
+/// the `State`'s config.
 #[allow(non_upper_case_globals)]
 pub const indent_unit: uint = 4u;
 
@@ -103,14 +228,45 @@ pub fn print_crate<'a>(cm: &'a CodeMap,
                        input: &mut io::Reader,
                        out: Box<io::Writer+'static>,
                        ann: &'a PpAnn,
-                       is_expanded: bool) -> IoResult<()> {
+                       is_expanded: bool,
+                       config: PpConfig) -> IoResult<()> {
+    let s = State::new_from_input(cm,
+                                  span_diagnostic,
+                                  filename,
+                                  input,
+                                  out,
+                                  ann,
+                                  is_expanded,
+                                  config);
+    print_crate_with_state(s, krate)
+}
+
+/// Like `print_crate`, but copies every node whose span still covers
+/// unmodified original source straight from the CodeMap, so that the
+/// author's layout survives. Only synthesized nodes (those with dummy or
+/// macro-expansion spans, or containing such nodes) are re-printed.
+pub fn print_crate_preserving<'a>(cm: &'a CodeMap,
+                                  span_diagnostic: &diagnostic::SpanHandler,
+                                  krate: &ast::Crate,
+                                  filename: String,
+                                  input: &mut io::Reader,
+                                  out: Box<io::Writer+'static>,
+                                  ann: &'a PpAnn,
+                                  is_expanded: bool,
+                                  config: PpConfig) -> IoResult<()> {
     let mut s = State::new_from_input(cm,
                                       span_diagnostic,
                                       filename,
                                       input,
                                       out,
                                       ann,
-                                      is_expanded);
+                                      is_expanded,
+                                      config);
+    s.preserve_source = true;
+    print_crate_with_state(s, krate)
+}
+
+fn print_crate_with_state(mut s: State, krate: &ast::Crate) -> IoResult<()> {
     try!(s.print_mod(&krate.module, krate.attrs.as_slice()));
     try!(s.print_remaining_comments());
     eof(&mut s.s)
@@ -123,7 +279,8 @@ impl<'a> State<'a> {
                           input: &mut io::Reader,
                           out: Box<io::Writer+'static>,
                           ann: &'a PpAnn,
-                          is_expanded: bool) -> State<'a> {
+                          is_expanded: bool,
+                          config: PpConfig) -> State<'a> {
         let (cmnts, lits) = comments::gather_comments_and_literals(
             span_diagnostic,
             filename,
@@ -137,16 +294,18 @@ impl<'a> State<'a> {
             // If the code is post expansion, don't use the table of
             // literals, since it doesn't correspond with the literals
             // in the AST anymore.
-            if is_expanded { None } else { Some(lits) })
+            if is_expanded { None } else { Some(lits) },
+            config)
     }
 
     pub fn new(cm: &'a CodeMap,
                out: Box<io::Writer+'static>,
                ann: &'a PpAnn,
                comments: Option<Vec<comments::Comment>>,
-               literals: Option<Vec<comments::Literal>>) -> State<'a> {
+               literals: Option<Vec<comments::Literal>>,
+               config: PpConfig) -> State<'a> {
         State {
-            s: pp::mk_printer(out, default_columns),
+            s: pp::mk_printer(out, config.max_width),
             cm: Some(cm),
             comments: comments,
             literals: literals,
@@ -157,13 +316,20 @@ impl<'a> State<'a> {
             boxes: Vec::new(),
             ann: ann,
             encode_idents_with_hygiene: false,
+            preserve_source: false,
+            config: config,
+            focus: None,
         }
     }
 }
 
 pub fn to_string(f: |&mut State| -> IoResult<()>) -> String {
+    to_string_with_config(Default::default(), f)
+}
+
+pub fn to_string_with_config(config: PpConfig, f: |&mut State| -> IoResult<()>) -> String {
     use std::raw::TraitObject;
-    let mut s = rust_printer(box Vec::new());
+    let mut s = rust_printer(box Vec::new(), config);
     f(&mut s).unwrap();
     eof(&mut s.s).unwrap();
     let wr = unsafe {
@@ -176,6 +342,31 @@ pub fn to_string(f: |&mut State| -> IoResult<()>) -> String {
     String::from_utf8(wr.clone()).unwrap()
 }
 
+/// Prints the node `id` inside of its `context.ancestors` innermost
+/// enclosing items, say a statement along with the signature of the
+/// function it is in. Everything in those items that doesn't contain the
+/// node is left out, with an elision marker in its place.
+pub fn node_in_context_to_string(map: &ast_map::Map, id: ast::NodeId,
+                                 context: &NodeContext) -> String {
+    let mut outer = id;
+    for _ in range(0, context.ancestors) {
+        let parent = map.get_parent(outer);
+        if parent == outer || parent == ast::CRATE_NODE_ID {
+            break;
+        }
+        outer = parent;
+    }
+    let node = map.get(outer);
+    to_string(|s| {
+        s.focus = Some(Focus {
+            span: map.span(id),
+            elision_marker: context.elision_marker.clone(),
+            eliding: false,
+        });
+        s.print_node(&node)
+    })
+}
+
 pub fn binop_to_string(op: BinOpToken) -> &'static str {
     match op {
         token::Plus     => "+",
@@ -371,7 +562,7 @@ pub fn fun_to_string(decl: &ast::FnDecl, fn_style: ast::FnStyle, name: ast::Iden
 pub fn block_to_string(blk: &ast::Block) -> String {
     $to_string(|s| {
         // containing cbox, will be closed by print-block at }
-        try!(s.cbox(indent_unit));
+        try!(s.cbox_indent());
         // head-ibox, will be closed by print-block after {
         try!(s.ibox(0u));
         s.print_block(blk)
@@ -410,6 +601,132 @@ pub fn mac_to_string(arg: &ast::Mac) -> String {
 
 thing_to_string_impls!(to_string)
 
+/// Renders `e` for a suggestion in a diagnostic: the user's own source text
+/// when `e` has some, so that the suggestion reads like their code, or else
+/// the pretty-printed expression.
+pub fn expr_to_suggestion(cm: &CodeMap, e: &ast::Expr) -> String {
+    if !is_synthesized_span(e.span) {
+        if let Some(snippet) = cm.span_to_snippet(e.span) {
+            return snippet;
+        }
+    }
+    expr_to_string(e)
+}
+
+/// Like `expr_to_suggestion`, for `e` used as an operand that must bind at
+/// least as tightly as `prec` (see `ast_util::expr_precedence`). Adds
+/// parentheses if it doesn't, so that the suggestion means what it says.
+pub fn operand_to_suggestion(cm: &CodeMap, e: &ast::Expr, prec: uint) -> String {
+    let s = expr_to_suggestion(cm, e);
+    if ast_util::expr_precedence(e) < prec {
+        format!("({})", s)
+    } else {
+        s
+    }
+}
+
+/// The first difference between the AST of some source and the AST of its
+/// pretty-printed form, found by `roundtrip_check`.
+#[deriving(Clone, Show)]
+pub struct RoundtripMismatch {
+    /// The field names, enum variants and indices leading from the crate
+    /// to the innermost node that differs, e.g.
+    /// `module.items.0.node.ItemFn.4.stmts.1`.
+    pub path: String,
+    /// That node as parsed from the original source, encoded as JSON.
+    pub original: String,
+    /// That node as parsed from the pretty-printed source, encoded as JSON.
+    pub reparsed: String,
+    /// The pretty-printed source.
+    pub printed: String,
+}
+
+/// Parses `src`, pretty-prints the crate, parses the result again and
+/// checks that both parses give the same AST, ignoring spans and ids. This
+/// lets tools check that the pretty printer is faithful on their own code.
+/// Parse errors are reported and abort the task, as in the compiler.
+pub fn roundtrip_check(name: String, src: String) -> Result<(), RoundtripMismatch> {
+    let sess = parse::new_parse_sess();
+    let krate = parse::parse_crate_from_source_str(name.clone(), src, Vec::new(), &sess);
+    let printed = to_string(|s| s.print_mod(&krate.module, krate.attrs.as_slice()));
+    let reparsed = parse::parse_crate_from_source_str(format!("{} (pretty-printed)", name),
+                                                      printed.clone(), Vec::new(), &sess);
+
+    // Spans are encoded as nothing unless a CodeMap is given to encode them
+    // against, so this compares everything but the spans.
+    let to_json = |krate: &ast::Crate| {
+        strip_json_ids(json::from_str(json::encode(krate).as_slice()).unwrap())
+    };
+    let (original, reparsed) = (to_json(&krate), to_json(&reparsed));
+    if original == reparsed {
+        return Ok(());
+    }
+    let mut path = Vec::new();
+    let (original, reparsed) = first_json_mismatch(&original, &reparsed, &mut path);
+    Err(RoundtripMismatch {
+        path: path.connect("."),
+        original: original.to_string(),
+        reparsed: reparsed.to_string(),
+        printed: printed,
+    })
+}
+
+/// Removes the `id` fields from a JSON-encoded AST. Node ids aren't assigned
+/// yet after parsing, and attribute ids differ between parses.
+fn strip_json_ids(value: json::Json) -> json::Json {
+    match value {
+        json::Object(fields) => {
+            json::Object(fields.into_iter()
+                               .filter(|&(ref key, _)| key.as_slice() != "id")
+                               .map(|(key, value)| (key, strip_json_ids(value)))
+                               .collect())
+        }
+        json::Array(elems) => json::Array(elems.into_iter().map(strip_json_ids).collect()),
+        value => value
+    }
+}
+
+/// Descends into two different JSON values for as long as they have the
+/// same shape, returning the innermost values that differ and pushing the
+/// way to them onto `path`.
+fn first_json_mismatch<'a>(a: &'a json::Json, b: &'a json::Json, path: &mut Vec<String>)
+                           -> (&'a json::Json, &'a json::Json) {
+    match (a, b) {
+        (&json::Object(ref a_fields), &json::Object(ref b_fields))
+                if a_fields.keys().collect::<Vec<_>>() == b_fields.keys().collect::<Vec<_>>() => {
+            // Enum values are encoded as `{"variant": name, "fields": [..]}`,
+            // so name the variant in the path rather than `fields`.
+            let variant = match a_fields.get(&"variant".to_string()) {
+                Some(&json::String(ref variant)) => Some(variant.clone()),
+                _ => None
+            };
+            for ((key, a_value), b_value) in a_fields.iter().zip(b_fields.values()) {
+                if a_value != b_value {
+                    match variant {
+                        Some(ref variant) if key.as_slice() == "fields" => {
+                            path.push(variant.clone())
+                        }
+                        _ => path.push(key.clone())
+                    }
+                    return first_json_mismatch(a_value, b_value, path);
+                }
+            }
+            (a, b)
+        }
+        (&json::Array(ref a_elems), &json::Array(ref b_elems))
+                if a_elems.len() == b_elems.len() => {
+            for (i, (a_elem, b_elem)) in a_elems.iter().zip(b_elems.iter()).enumerate() {
+                if a_elem != b_elem {
+                    path.push(i.to_string());
+                    return first_json_mismatch(a_elem, b_elem, path);
+                }
+            }
+            (a, b)
+        }
+        _ => (a, b)
+    }
+}
+
 // FIXME (Issue #16472): the whole `with_hygiene` mod should go away
 // after we revise the syntax::ext::quote::ToToken impls to go directly
 // to token-trees instea of thing -> string -> token-trees.
@@ -418,7 +735,6 @@ pub mod with_hygiene {
     use abi;
     use ast;
     use std::io::IoResult;
-    use super::indent_unit;
 
     // This function is the trick that all the rest of the routines
     // hang on.
@@ -443,7 +759,8 @@ fn needs_parentheses(expr: &ast::Expr) -> bool {
     match expr.node {
         ast::ExprAssign(..) | ast::ExprBinary(..) |
         ast::ExprClosure(..) | ast::ExprProc(..) |
-        ast::ExprAssignOp(..) | ast::ExprCast(..) => true,
+        ast::ExprAssignOp(..) | ast::ExprCast(..) |
+        ast::ExprType(..) | ast::ExprInclusiveRange(..) => true,
         _ => false,
     }
 }
@@ -464,6 +781,25 @@ impl<'a> State<'a> {
         pp::cbox(&mut self.s, u)
     }
 
+    /// Opens an inconsistent box indented by one level, `config.indent_unit`.
+    pub fn ibox_indent(&mut self) -> IoResult<()> {
+        let indent = self.config.indent_unit;
+        self.ibox(indent)
+    }
+
+    /// Opens a consistent box indented by one level, `config.indent_unit`.
+    pub fn cbox_indent(&mut self) -> IoResult<()> {
+        let indent = self.config.indent_unit;
+        self.cbox(indent)
+    }
+
+    /// Opens the consistent box of an `else` branch, one column short of a
+    /// level to make up for the space in front of `else`.
+    fn cbox_else(&mut self) -> IoResult<()> {
+        let indent = self.config.indent_unit.saturating_sub(1);
+        self.cbox(indent)
+    }
+
     // "raw box"
     pub fn rbox(&mut self, u: uint, b: pp::Breaks) -> IoResult<()> {
         self.boxes.push(b);
@@ -488,7 +824,7 @@ impl<'a> State<'a> {
 
     pub fn head(&mut self, w: &str) -> IoResult<()> {
         // outer-box is consistent
-        try!(self.cbox(indent_unit));
+        try!(self.cbox_indent());
         // head-box is inconsistent
         try!(self.ibox(w.len() + 1));
         // keyword that starts the head
@@ -518,7 +854,8 @@ impl<'a> State<'a> {
         Ok(())
     }
     pub fn bclose(&mut self, span: codemap::Span) -> IoResult<()> {
-        self.bclose_(span, indent_unit)
+        let indent = self.config.indent_unit;
+        self.bclose_(span, indent)
     }
 
     pub fn is_begin(&mut self) -> bool {
@@ -566,6 +903,43 @@ impl<'a> State<'a> {
         }
     }
 
+    /// When printing a node in context, replaces each run of siblings that
+    /// neither contain the node nor are part of it with one elision marker.
+    /// Returns whether the sibling spanning `sp` was left out.
+    fn elide_unless_focused(&mut self, sp: codemap::Span) -> IoResult<bool> {
+        let marker = match self.focus {
+            None => return Ok(false),
+            Some(ref mut focus) => {
+                let target = focus.span;
+                if (sp.lo <= target.lo && target.hi <= sp.hi) ||
+                   (target.lo <= sp.lo && sp.hi <= target.hi) {
+                    focus.eliding = false;
+                    return Ok(false);
+                }
+                if focus.eliding {
+                    return Ok(true);
+                }
+                focus.eliding = true;
+                focus.elision_marker.clone()
+            }
+        };
+        try!(self.hardbreak_if_not_bol());
+        try!(self.ibox(0u));
+        try!(self.synth_comment(marker));
+        try!(self.end());
+        Ok(true)
+    }
+
+    /// Ends the current run of elided siblings, so that whatever is elided
+    /// next gets a marker of its own. Called after each list of siblings, as
+    /// the enclosing list carries on once the nested one is closed.
+    fn end_elision_run(&mut self) {
+        match self.focus {
+            Some(ref mut focus) => focus.eliding = false,
+            None => {}
+        }
+    }
+
     // Synthesizes a comment that was not textually present in the original source
     // file.
     pub fn synth_comment(&mut self, text: String) -> IoResult<()> {
@@ -621,11 +995,14 @@ impl<'a> State<'a> {
                      attrs: &[ast::Attribute]) -> IoResult<()> {
         try!(self.print_inner_attributes(attrs));
         for vitem in _mod.view_items.iter() {
+            if try!(self.elide_unless_focused(vitem.span)) { continue }
             try!(self.print_view_item(vitem));
         }
         for item in _mod.items.iter() {
+            if try!(self.elide_unless_focused(item.span)) { continue }
             try!(self.print_item(&**item));
         }
+        self.end_elision_run();
         Ok(())
     }
 
@@ -829,12 +1206,32 @@ impl<'a> State<'a> {
         word(&mut self.s, ";")
     }
 
+    fn print_associated_const(&mut self, associated_const: &ast::AssociatedConst)
+                              -> IoResult<()> {
+        try!(self.print_outer_attributes(associated_const.attrs[]));
+        try!(self.word_space(visibility_qualified(associated_const.vis,
+                                                  "const").as_slice()));
+        try!(self.print_ident(associated_const.ident));
+        try!(self.word_space(":"));
+        try!(self.print_type(&*associated_const.ty));
+        if let Some(ref expr) = associated_const.expr {
+            try!(space(&mut self.s));
+            try!(self.word_space("="));
+            try!(self.print_expr(&**expr));
+        }
+        word(&mut self.s, ";")
+    }
+
     /// Pretty-print an item
     pub fn print_item(&mut self, item: &ast::Item) -> IoResult<()> {
         try!(self.hardbreak_if_not_bol());
         try!(self.maybe_print_comment(item.span.lo));
         try!(self.print_outer_attributes(item.attrs.as_slice()));
         try!(self.ann.pre(self, NodeItem(item)));
+        if let Some(snippet) = self.original_source(item.span, |v| visit::walk_item(v, item)) {
+            try!(self.print_original_source(item.span, snippet.as_slice()));
+            return self.ann.post(self, NodeItem(item));
+        }
         match item.node {
             ast::ItemStatic(ref ty, m, ref expr) => {
                 try!(self.head(visibility_qualified(item.vis,
@@ -897,18 +1294,18 @@ impl<'a> State<'a> {
                 try!(self.bclose(item.span));
             }
             ast::ItemTy(ref ty, ref params) => {
-                try!(self.ibox(indent_unit));
+                try!(self.ibox_indent());
                 try!(self.ibox(0u));
                 try!(self.word_nbsp(visibility_qualified(item.vis,
                                                          "type").as_slice()));
                 try!(self.print_ident(item.ident));
                 try!(self.print_generics(params));
+                try!(self.print_where_clause(params));
                 try!(self.end()); // end the inner ibox
 
                 try!(space(&mut self.s));
                 try!(self.word_space("="));
                 try!(self.print_type(&**ty));
-                try!(self.print_where_clause(params));
                 try!(word(&mut self.s, ";"));
                 try!(self.end()); // end the outer ibox
             }
@@ -955,13 +1352,20 @@ impl<'a> State<'a> {
                 for impl_item in impl_items.iter() {
                     match *impl_item {
                         ast::MethodImplItem(ref meth) => {
+                            if try!(self.elide_unless_focused(meth.span)) { continue }
                             try!(self.print_method(&**meth));
                         }
                         ast::TypeImplItem(ref typ) => {
+                            if try!(self.elide_unless_focused(typ.span)) { continue }
                             try!(self.print_typedef(&**typ));
                         }
+                        ast::ConstImplItem(ref c) => {
+                            if try!(self.elide_unless_focused(c.span)) { continue }
+                            try!(self.print_associated_const(&**c));
+                        }
                     }
                 }
+                self.end_elision_run();
                 try!(self.bclose(item.span));
             }
             ast::ItemTrait(ref generics, ref unbound, ref bounds, ref methods) => {
@@ -991,7 +1395,7 @@ impl<'a> State<'a> {
                 try!(self.print_path(pth, false));
                 try!(word(&mut self.s, "! "));
                 try!(self.print_ident(item.ident));
-                try!(self.cbox(indent_unit));
+                try!(self.cbox_indent());
                 try!(self.popen());
                 try!(self.print_tts(tts.as_slice()));
                 try!(self.pclose());
@@ -1037,7 +1441,7 @@ impl<'a> State<'a> {
             try!(self.space_if_not_bol());
             try!(self.maybe_print_comment(v.span.lo));
             try!(self.print_outer_attributes(v.node.attrs.as_slice()));
-            try!(self.ibox(indent_unit));
+            try!(self.ibox_indent());
             try!(self.print_variant(&**v));
             try!(word(&mut self.s, ","));
             try!(self.end());
@@ -1078,10 +1482,12 @@ impl<'a> State<'a> {
                 ));
                 try!(self.pclose());
             }
+            try!(self.print_where_clause(generics));
             try!(word(&mut self.s, ";"));
             try!(self.end());
             self.end() // close the outer-box
         } else {
+            try!(self.print_where_clause(generics));
             try!(self.nbsp());
             try!(self.bopen());
             try!(self.hardbreak_if_not_bol());
@@ -1213,6 +1619,7 @@ impl<'a> State<'a> {
             RequiredMethod(ref ty_m) => self.print_ty_method(ty_m),
             ProvidedMethod(ref m) => self.print_method(&**m),
             TypeTraitItem(ref t) => self.print_associated_type(&**t),
+            ConstTraitItem(ref c) => self.print_associated_const(&**c),
         }
     }
 
@@ -1220,6 +1627,7 @@ impl<'a> State<'a> {
         match *ii {
             MethodImplItem(ref m) => self.print_method(&**m),
             TypeImplItem(ref td) => self.print_typedef(&**td),
+            ConstImplItem(ref c) => self.print_associated_const(&**c),
         }
     }
 
@@ -1251,7 +1659,7 @@ impl<'a> State<'a> {
                 // code copied from ItemMac:
                 try!(self.print_path(pth, false));
                 try!(word(&mut self.s, "! "));
-                try!(self.cbox(indent_unit));
+                try!(self.cbox_indent());
                 try!(self.popen());
                 try!(self.print_tts(tts.as_slice()));
                 try!(self.pclose());
@@ -1346,7 +1754,8 @@ impl<'a> State<'a> {
     }
 
     pub fn print_block_unclosed(&mut self, blk: &ast::Block) -> IoResult<()> {
-        self.print_block_unclosed_indent(blk, indent_unit)
+        let indent = self.config.indent_unit;
+        self.print_block_unclosed_indent(blk, indent)
     }
 
     pub fn print_block_unclosed_indent(&mut self, blk: &ast::Block,
@@ -1357,7 +1766,8 @@ impl<'a> State<'a> {
     pub fn print_block_with_attrs(&mut self,
                                   blk: &ast::Block,
                                   attrs: &[ast::Attribute]) -> IoResult<()> {
-        self.print_block_maybe_unclosed(blk, indent_unit, attrs, true)
+        let indent = self.config.indent_unit;
+        self.print_block_maybe_unclosed(blk, indent, attrs, true)
     }
 
     pub fn print_block_maybe_unclosed(&mut self,
@@ -1376,19 +1786,24 @@ impl<'a> State<'a> {
         try!(self.print_inner_attributes(attrs));
 
         for vi in blk.view_items.iter() {
+            if try!(self.elide_unless_focused(vi.span)) { continue }
             try!(self.print_view_item(vi));
         }
         for st in blk.stmts.iter() {
+            if try!(self.elide_unless_focused(st.span)) { continue }
             try!(self.print_stmt(&**st));
         }
         match blk.expr {
             Some(ref expr) => {
-                try!(self.space_if_not_bol());
-                try!(self.print_expr(&**expr));
-                try!(self.maybe_print_trailing_comment(expr.span, Some(blk.span.hi)));
+                if !try!(self.elide_unless_focused(expr.span)) {
+                    try!(self.space_if_not_bol());
+                    try!(self.print_expr(&**expr));
+                    try!(self.maybe_print_trailing_comment(expr.span, Some(blk.span.hi)));
+                }
             }
             _ => ()
         }
+        self.end_elision_run();
         try!(self.bclose_maybe_open(blk.span, indented, close_box));
         self.ann.post(self, NodeBlock(blk))
     }
@@ -1399,7 +1814,7 @@ impl<'a> State<'a> {
                 match _else.node {
                     // "another else-if"
                     ast::ExprIf(ref i, ref then, ref e) => {
-                        try!(self.cbox(indent_unit - 1u));
+                        try!(self.cbox_else());
                         try!(self.ibox(0u));
                         try!(word(&mut self.s, " else if "));
                         try!(self.print_expr(&**i));
@@ -1409,7 +1824,7 @@ impl<'a> State<'a> {
                     }
                     // "another else-if-let"
                     ast::ExprIfLet(ref pat, ref expr, ref then, ref e) => {
-                        try!(self.cbox(indent_unit - 1u));
+                        try!(self.cbox_else());
                         try!(self.ibox(0u));
                         try!(word(&mut self.s, " else if let "));
                         try!(self.print_pat(&**pat));
@@ -1422,7 +1837,7 @@ impl<'a> State<'a> {
                     }
                     // "final else"
                     ast::ExprBlock(ref b) => {
-                        try!(self.cbox(indent_unit - 1u));
+                        try!(self.cbox_else());
                         try!(self.ibox(0u));
                         try!(word(&mut self.s, " else "));
                         self.print_block(&**b)
@@ -1490,20 +1905,41 @@ impl<'a> State<'a> {
         Ok(())
     }
 
+    /// Prints `expr` as an operand that must bind at least as tightly as
+    /// `prec` (see `ast_util::expr_precedence`), adding parentheses if it
+    /// doesn't. Parsed code keeps its parentheses as `ExprParen`, so this
+    /// only matters for expressions built or rearranged by the compiler.
+    fn print_expr_as_operand(&mut self, expr: &ast::Expr, prec: uint) -> IoResult<()> {
+        let needs_par = ast_util::expr_precedence(expr) < prec;
+        if needs_par {
+            try!(self.popen());
+        }
+        try!(self.print_expr(expr));
+        if needs_par {
+            try!(self.pclose());
+        }
+        Ok(())
+    }
+
     pub fn print_expr(&mut self, expr: &ast::Expr) -> IoResult<()> {
         try!(self.maybe_print_comment(expr.span.lo));
-        try!(self.ibox(indent_unit));
+        try!(self.ibox_indent());
         try!(self.ann.pre(self, NodeExpr(expr)));
+        if let Some(snippet) = self.original_source(expr.span, |v| visit::walk_expr(v, expr)) {
+            try!(self.print_original_source(expr.span, snippet.as_slice()));
+            try!(self.ann.post(self, NodeExpr(expr)));
+            return self.end();
+        }
         match expr.node {
             ast::ExprBox(ref p, ref e) => {
                 try!(word(&mut self.s, "box"));
                 try!(word(&mut self.s, "("));
                 try!(self.print_expr(&**p));
                 try!(self.word_space(")"));
-                try!(self.print_expr(&**e));
+                try!(self.print_expr_as_operand(&**e, ast_util::PREC_PREFIX));
             }
             ast::ExprVec(ref exprs) => {
-                try!(self.ibox(indent_unit));
+                try!(self.ibox_indent());
                 try!(word(&mut self.s, "["));
                 try!(self.commasep_exprs(Inconsistent, exprs.as_slice()));
                 try!(word(&mut self.s, "]"));
@@ -1511,7 +1947,7 @@ impl<'a> State<'a> {
             }
 
             ast::ExprRepeat(ref element, ref count) => {
-                try!(self.ibox(indent_unit));
+                try!(self.ibox_indent());
                 try!(word(&mut self.s, "["));
                 try!(self.print_expr(&**element));
                 try!(word(&mut self.s, ","));
@@ -1528,16 +1964,18 @@ impl<'a> State<'a> {
                     Consistent,
                     fields.as_slice(),
                     |s, field| {
-                        try!(s.ibox(indent_unit));
+                        try!(s.ibox_indent());
                         try!(s.print_ident(field.ident.node));
-                        try!(s.word_space(":"));
-                        try!(s.print_expr(&*field.expr));
+                        if !field.is_shorthand {
+                            try!(s.word_space(":"));
+                            try!(s.print_expr(&*field.expr));
+                        }
                         s.end()
                     },
                     |f| f.span));
                 match *wth {
                     Some(ref expr) => {
-                        try!(self.ibox(indent_unit));
+                        try!(self.ibox_indent());
                         if !fields.is_empty() {
                             try!(word(&mut self.s, ","));
                             try!(space(&mut self.s));
@@ -1564,7 +2002,7 @@ impl<'a> State<'a> {
             }
             ast::ExprMethodCall(ident, ref tys, ref args) => {
                 let base_args = args.slice_from(1);
-                try!(self.print_expr(&*args[0]));
+                try!(self.print_expr_as_operand(&*args[0], ast_util::PREC_POSTFIX));
                 try!(word(&mut self.s, "."));
                 try!(self.print_ident(ident.node));
                 if tys.len() > 0u {
@@ -1576,27 +2014,46 @@ impl<'a> State<'a> {
                 try!(self.print_call_post(base_args));
             }
             ast::ExprBinary(op, ref lhs, ref rhs) => {
-                try!(self.print_expr(&**lhs));
+                // Binary operators are left-associative, so only the right
+                // operand needs parentheses at equal precedence, except for
+                // comparisons, which don't associate at all.
+                let prec = ast_util::operator_prec(op);
+                let lhs_prec = if ast_util::is_comparison_binop(op) { prec + 1 } else { prec };
+                try!(self.print_expr_as_operand(&**lhs, lhs_prec));
                 try!(space(&mut self.s));
                 try!(self.word_space(ast_util::binop_to_string(op)));
-                try!(self.print_expr(&**rhs));
+                try!(self.print_expr_as_operand(&**rhs, prec + 1));
             }
             ast::ExprUnary(op, ref expr) => {
                 try!(word(&mut self.s, ast_util::unop_to_string(op)));
-                try!(self.print_expr_maybe_paren(&**expr));
+                try!(self.print_expr_as_operand(&**expr, ast_util::PREC_PREFIX));
             }
             ast::ExprAddrOf(m, ref expr) => {
                 try!(word(&mut self.s, "&"));
                 try!(self.print_mutability(m));
-                try!(self.print_expr_maybe_paren(&**expr));
+                try!(self.print_expr_as_operand(&**expr, ast_util::PREC_PREFIX));
             }
             ast::ExprLit(ref lit) => try!(self.print_literal(&**lit)),
             ast::ExprCast(ref expr, ref ty) => {
-                try!(self.print_expr(&**expr));
+                try!(self.print_expr_as_operand(&**expr, ast_util::as_prec));
                 try!(space(&mut self.s));
                 try!(self.word_space("as"));
                 try!(self.print_type(&**ty));
             }
+            ast::ExprType(ref expr, ref ty) => {
+                try!(self.print_expr(&**expr));
+                try!(self.word_space(":"));
+                try!(self.print_type(&**ty));
+            }
+            ast::ExprInclusiveRange(ref start, ref end) => {
+                try!(self.print_expr(&**start));
+                try!(word(&mut self.s, "..."));
+                try!(self.print_expr(&**end));
+            }
+            ast::ExprTry(ref e) => {
+                try!(self.print_expr_as_operand(&**e, ast_util::PREC_POSTFIX));
+                try!(word(&mut self.s, "?"));
+            }
             ast::ExprIf(ref test, ref blk, ref elseopt) => {
                 try!(self.print_if(&**test, &**blk, elseopt.as_ref().map(|e| &**e)));
             }
@@ -1649,7 +2106,7 @@ impl<'a> State<'a> {
                 try!(self.print_block(&**blk));
             }
             ast::ExprMatch(ref expr, ref arms, _) => {
-                try!(self.cbox(indent_unit));
+                try!(self.cbox_indent());
                 try!(self.ibox(4));
                 try!(self.word_nbsp("match"));
                 try!(self.print_expr(&**expr));
@@ -1658,7 +2115,7 @@ impl<'a> State<'a> {
                 for arm in arms.iter() {
                     try!(self.print_arm(arm));
                 }
-                try!(self.bclose_(expr.span, indent_unit));
+                try!(self.bclose(expr.span));
             }
             ast::ExprClosure(capture_clause, opt_kind, ref decl, ref body) => {
                 try!(self.print_capture_clause(capture_clause));
@@ -1715,7 +2172,7 @@ impl<'a> State<'a> {
             }
             ast::ExprBlock(ref blk) => {
                 // containing cbox, will be closed by print-block at }
-                try!(self.cbox(indent_unit));
+                try!(self.cbox_indent());
                 // head-box, will be closed by print-block after {
                 try!(self.ibox(0u));
                 try!(self.print_block(&**blk));
@@ -1734,23 +2191,23 @@ impl<'a> State<'a> {
                 try!(self.print_expr(&**rhs));
             }
             ast::ExprField(ref expr, id) => {
-                try!(self.print_expr(&**expr));
+                try!(self.print_expr_as_operand(&**expr, ast_util::PREC_POSTFIX));
                 try!(word(&mut self.s, "."));
                 try!(self.print_ident(id.node));
             }
             ast::ExprTupField(ref expr, id) => {
-                try!(self.print_expr(&**expr));
+                try!(self.print_expr_as_operand(&**expr, ast_util::PREC_POSTFIX));
                 try!(word(&mut self.s, "."));
                 try!(self.print_uint(id.node));
             }
             ast::ExprIndex(ref expr, ref index) => {
-                try!(self.print_expr(&**expr));
+                try!(self.print_expr_as_operand(&**expr, ast_util::PREC_POSTFIX));
                 try!(word(&mut self.s, "["));
                 try!(self.print_expr(&**index));
                 try!(word(&mut self.s, "]"));
             }
             ast::ExprSlice(ref e, ref start, ref end, ref mutbl) => {
-                try!(self.print_expr(&**e));
+                try!(self.print_expr_as_operand(&**e, ast_util::PREC_POSTFIX));
                 try!(word(&mut self.s, "["));
                 if mutbl == &ast::MutMutable {
                     try!(word(&mut self.s, "mut"));
@@ -1770,13 +2227,17 @@ impl<'a> State<'a> {
                 try!(word(&mut self.s, "]"));
             }
             ast::ExprPath(ref path) => try!(self.print_path(path, true)),
-            ast::ExprBreak(opt_ident) => {
+            ast::ExprBreak(opt_ident, ref opt_expr) => {
                 try!(word(&mut self.s, "break"));
                 try!(space(&mut self.s));
                 for ident in opt_ident.iter() {
                     try!(self.print_ident(*ident));
                     try!(space(&mut self.s));
                 }
+                for expr in opt_expr.iter() {
+                    try!(self.print_expr(&**expr));
+                    try!(space(&mut self.s));
+                }
             }
             ast::ExprAgain(opt_ident) => {
                 try!(word(&mut self.s, "continue"));
@@ -1807,7 +2268,7 @@ impl<'a> State<'a> {
                 try!(self.word_space(":"));
 
                 try!(self.commasep(Inconsistent, a.outputs.as_slice(),
-                                   |s, &(ref co, ref o, is_rw)| {
+                                   |s, &(ref co, ref o, is_rw, _)| {
                     match co.get().slice_shift_char() {
                         Some(('=', operand)) if is_rw => {
                             try!(s.print_string(format!("+{}", operand).as_slice(),
@@ -1824,7 +2285,7 @@ impl<'a> State<'a> {
                 try!(self.word_space(":"));
 
                 try!(self.commasep(Inconsistent, a.inputs.as_slice(),
-                                   |s, &(ref co, ref o)| {
+                                   |s, &(ref co, ref o, _)| {
                     try!(s.print_string(co.get(), ast::CookedStr));
                     try!(s.popen());
                     try!(s.print_expr(&**o));
@@ -1835,7 +2296,7 @@ impl<'a> State<'a> {
                 try!(self.word_space(":"));
 
                 try!(self.commasep(Inconsistent, a.clobbers.as_slice(),
-                                   |s, co| {
+                                   |s, &(ref co, _)| {
                     try!(s.print_string(co.get(), ast::CookedStr));
                     Ok(())
                 }));
@@ -1868,10 +2329,10 @@ impl<'a> State<'a> {
         match decl.node {
             ast::DeclLocal(ref loc) => {
                 try!(self.space_if_not_bol());
-                try!(self.ibox(indent_unit));
+                try!(self.ibox_indent());
                 try!(self.word_nbsp("let"));
 
-                try!(self.ibox(indent_unit));
+                try!(self.ibox_indent());
                 try!(self.print_local_decl(&**loc));
                 try!(self.end());
                 if let Some(ref init) = loc.init {
@@ -2047,7 +2508,7 @@ impl<'a> State<'a> {
                 try!(self.commasep_cmnt(
                     Consistent, fields.as_slice(),
                     |s, f| {
-                        try!(s.cbox(indent_unit));
+                        try!(s.cbox_indent());
                         if !f.node.is_shorthand {
                             try!(s.print_ident(f.node.ident));
                             try!(s.word_nbsp(":"));
@@ -2120,7 +2581,7 @@ impl<'a> State<'a> {
         if arm.attrs.is_empty() {
             try!(space(&mut self.s));
         }
-        try!(self.cbox(indent_unit));
+        try!(self.cbox_indent());
         try!(self.ibox(0u));
         try!(self.print_outer_attributes(arm.attrs.as_slice()));
         let mut first = true;
@@ -2144,13 +2605,20 @@ impl<'a> State<'a> {
         match arm.body.node {
             ast::ExprBlock(ref blk) => {
                 // the block will close the pattern's ibox
-                try!(self.print_block_unclosed_indent(&**blk, indent_unit));
+                try!(self.print_block_unclosed(&**blk));
 
                 // If it is a user-provided unsafe block, print a comma after it
                 if let ast::UnsafeBlock(ast::UserProvided) = blk.rules {
                     try!(word(&mut self.s, ","));
                 }
             }
+            _ if self.config.wrap_match_arms => {
+                try!(self.end()); // close the ibox for the pattern
+                try!(self.word_space("{"));
+                try!(self.print_expr(&*arm.body));
+                try!(space(&mut self.s));
+                try!(word(&mut self.s, "}"));
+            }
             _ => {
                 try!(self.end()); // close the ibox for the pattern
                 try!(self.print_expr(&*arm.body));
@@ -2422,7 +2890,13 @@ impl<'a> State<'a> {
             return Ok(())
         }
 
-        try!(space(&mut self.s));
+        match self.config.where_clause_style {
+            WhereSameLine => try!(space(&mut self.s)),
+            WhereOwnLine => {
+                let offset = self.config.indent_unit as int;
+                try!(self.s.pretty_print(pp::hardbreak_tok_offset(offset)));
+            }
+        }
         try!(self.word_space("where"));
 
         for (i, predicate) in generics.where_clause
@@ -2433,15 +2907,25 @@ impl<'a> State<'a> {
                 try!(self.word_space(","));
             }
 
-            try!(self.print_ident(predicate.ident));
-            try!(self.print_bounds(":", predicate.bounds.as_slice()));
+            match *predicate {
+                ast::BoundPredicate(ref bound_pred) => {
+                    try!(self.print_ident(bound_pred.ident));
+                    try!(self.print_bounds(":", bound_pred.bounds.as_slice()));
+                }
+                ast::EqPredicate(ref eq_pred) => {
+                    try!(self.print_type(&*eq_pred.lhs_ty));
+                    try!(space(&mut self.s));
+                    try!(self.word_space("=="));
+                    try!(self.print_type(&*eq_pred.rhs_ty));
+                }
+            }
         }
 
         Ok(())
     }
 
     pub fn print_meta_item(&mut self, item: &ast::MetaItem) -> IoResult<()> {
-        try!(self.ibox(indent_unit));
+        try!(self.ibox_indent());
         match item.node {
             ast::MetaWord(ref name) => {
                 try!(word(&mut self.s, name.get()));
@@ -2547,7 +3031,7 @@ impl<'a> State<'a> {
     }
 
     pub fn print_arg(&mut self, input: &ast::Arg) -> IoResult<()> {
-        try!(self.ibox(indent_unit));
+        try!(self.ibox_indent());
         match input.ty.node {
             ast::TyInfer => try!(self.print_pat(&*input.pat)),
             _ => {
@@ -2580,7 +3064,7 @@ impl<'a> State<'a> {
         }
 
         try!(self.space_if_not_bol());
-        try!(self.ibox(indent_unit));
+        try!(self.ibox_indent());
         try!(self.word_space("->"));
         match decl.output {
             ast::NoReturn(_) =>
@@ -2607,7 +3091,7 @@ impl<'a> State<'a> {
                        generics: Option<&ast::Generics>,
                        opt_explicit_self: Option<&ast::ExplicitSelf_>)
                        -> IoResult<()> {
-        try!(self.ibox(indent_unit));
+        try!(self.ibox_indent());
 
         // Duplicates the logic in `print_fn_header_info()`.  This is because that
         // function prints the sigil in the wrong place.  That should be fixed.
@@ -2870,6 +3354,54 @@ impl<'a> State<'a> {
         word(&mut self.s, st.as_slice())
     }
 
+    /// The source text of a node spanning `sp`, if it can be copied verbatim:
+    /// source preservation is on, the text is in the CodeMap, and neither the
+    /// node nor any node reached by `walk` was synthesized or moved out of
+    /// `sp`.
+    fn original_source(&self, sp: codemap::Span,
+                       walk: |&mut SynthesizedNodeFinder|) -> Option<String> {
+        if !self.preserve_source || is_synthesized_span(sp) {
+            return None;
+        }
+        let mut finder = SynthesizedNodeFinder { outer: sp, found: false };
+        walk(&mut finder);
+        if finder.found {
+            return None;
+        }
+        self.cm.and_then(|cm| cm.span_to_snippet(sp))
+    }
+
+    /// Prints `snippet`, the source text of `sp`, a line at a time so that
+    /// the lines after the first are indented by the printer rather than by
+    /// the column `sp` started at. The comments inside `sp` are skipped over
+    /// since they are part of the copied text.
+    fn print_original_source(&mut self, sp: codemap::Span, snippet: &str) -> IoResult<()> {
+        let start_col = self.cm.unwrap().lookup_char_pos(sp.lo).col.to_uint();
+        for (i, line) in snippet.lines_any().enumerate() {
+            let line = if i == 0 {
+                line
+            } else {
+                try!(hardbreak(&mut self.s));
+                let indent = line.bytes()
+                                 .take(start_col)
+                                 .take_while(|&b| b == b' ' || b == b'\t')
+                                 .count();
+                line.slice_from(indent)
+            };
+            if !line.is_empty() {
+                try!(word(&mut self.s, line));
+            }
+        }
+        loop {
+            match self.next_comment() {
+                Some(ref cmnt) if cmnt.pos < sp.hi => {
+                    self.cur_cmnt_and_lit.cur_cmnt += 1u;
+                }
+                _ => return Ok(())
+            }
+        }
+    }
+
     pub fn next_comment(&mut self) -> Option<comments::Comment> {
         match self.comments {
             Some(ref cmnts) => {
@@ -2944,10 +3476,79 @@ mod test {
     use super::*;
 
     use ast;
+    use ast_map;
     use ast_util;
     use codemap;
     use parse::token;
     use ptr::P;
+    use serialize::json;
+    use std::default::Default;
+    use util::parser_testing::{IdCounter, string_to_crate, string_to_expr, string_to_item};
+
+    #[test]
+    fn test_synthesized_operands_are_parenthesized() {
+        // Strips the parentheses from the outermost operands of a binary
+        // expression, as if it had been built by the compiler.
+        fn strip_parens(e: P<ast::Expr>) -> P<ast::Expr> {
+            fn strip(e: &P<ast::Expr>) -> P<ast::Expr> {
+                match e.node {
+                    ast::ExprParen(ref inner) => inner.clone(),
+                    _ => e.clone(),
+                }
+            }
+            e.map(|e| {
+                let node = match e.node {
+                    ast::ExprBinary(op, ref lhs, ref rhs) => {
+                        ast::ExprBinary(op, strip(lhs), strip(rhs))
+                    }
+                    ast::ExprUnary(op, ref operand) => ast::ExprUnary(op, strip(operand)),
+                    ast::ExprAddrOf(m, ref operand) => ast::ExprAddrOf(m, strip(operand)),
+                    _ => panic!("expected an operator expression"),
+                };
+                ast::Expr { node: node, ..e }
+            })
+        }
+
+        let e = strip_parens(string_to_expr("(a + b) * c".to_string()));
+        assert_eq!(expr_to_string(&*e), "(a + b) * c".to_string());
+        let e = strip_parens(string_to_expr("a - (b - c)".to_string()));
+        assert_eq!(expr_to_string(&*e), "a - (b - c)".to_string());
+        let e = strip_parens(string_to_expr("(a - b) - (c * d)".to_string()));
+        assert_eq!(expr_to_string(&*e), "a - b - c * d".to_string());
+        let e = strip_parens(string_to_expr("(a == b) == c".to_string()));
+        assert_eq!(expr_to_string(&*e), "(a == b) == c".to_string());
+        let e = strip_parens(string_to_expr("-(a + b)".to_string()));
+        assert_eq!(expr_to_string(&*e), "-(a + b)".to_string());
+        let e = strip_parens(string_to_expr("&mut (a as int)".to_string()));
+        assert_eq!(expr_to_string(&*e), "&mut (a as int)".to_string());
+        let e = strip_parens(string_to_expr("!(-a)".to_string()));
+        assert_eq!(expr_to_string(&*e), "!-a".to_string());
+    }
+
+    #[test]
+    fn test_roundtrip_check() {
+        let src = "#![crate_type = \"lib\"]\n\
+                   /// Docs\n\
+                   pub fn f<T: Clone>(x: &[T]) -> Option<T> {\n\
+                       match x.get(0) { Some(y) => Some(y.clone()), None => None }\n\
+                   }\n\
+                   struct S { a: (int, int) }\n\
+                   fn g(s: S) -> int { let (a, b) = s.a; (a + b) * -a }\n\
+                   fn h(a: bool, b: bool, c: bool) -> bool {\n\
+                       let x = (a == b) == c; !(x && a) || *&(b as bool)\n\
+                   }";
+        assert!(roundtrip_check("<test>".to_string(), src.to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_first_json_mismatch() {
+        let a = json::from_str(r#"{"a": [1, {"variant": "X", "fields": [2, [3]]}]}"#).unwrap();
+        let b = json::from_str(r#"{"a": [1, {"variant": "X", "fields": [2, [4]]}]}"#).unwrap();
+        let mut path = Vec::new();
+        let (x, y) = super::first_json_mismatch(&a, &b, &mut path);
+        assert_eq!(path.connect("."), "a.1.X.1.0".to_string());
+        assert_eq!((x.to_string(), y.to_string()), ("3".to_string(), "4".to_string()));
+    }
 
     #[test]
     fn test_fun_to_string() {
@@ -2984,6 +3585,89 @@ mod test {
         assert_eq!(&varstr,&"pub principal_skinner".to_string());
     }
 
+    #[test]
+    fn test_config_indent_unit() {
+        let expr = string_to_expr("{ foo(); }".to_string());
+        let config = PpConfig { indent_unit: 2, ..Default::default() };
+        let printed = to_string_with_config(config, |s| s.print_expr(&*expr));
+        assert!(printed.as_slice().contains("\n  foo();"));
+    }
+
+    #[test]
+    fn test_config_zero_indent_unit() {
+        let expr = string_to_expr("if a { b(); } else if c { d(); } else { e(); }".to_string());
+        let config = PpConfig { indent_unit: 0, ..Default::default() };
+        let printed = to_string_with_config(config, |s| s.print_expr(&*expr));
+        assert!(printed.as_slice().contains("else if c"));
+        assert!(printed.as_slice().contains("e();"));
+    }
+
+    #[test]
+    fn test_config_wrap_match_arms() {
+        let expr = string_to_expr("match x { 1 => a, _ => b }".to_string());
+        let config = PpConfig { wrap_match_arms: true, ..Default::default() };
+        let printed = to_string_with_config(config, |s| s.print_expr(&*expr));
+        assert!(printed.as_slice().contains("1 => { a }"));
+        assert!(printed.as_slice().contains("_ => { b }"));
+        assert!(expr_to_string(&*expr).as_slice().contains("1 => a,"));
+    }
+
+    #[test]
+    fn test_config_where_clause_style() {
+        let item = string_to_item("fn f<T>(t: T) where T: Clone { }".to_string()).unwrap();
+        let same_line = item_to_string(&*item);
+        assert!(same_line.as_slice().lines().any(|l| l.contains("fn f") && l.contains("where")));
+
+        let config = PpConfig { where_clause_style: WhereOwnLine, ..Default::default() };
+        let own_line = to_string_with_config(config, |s| s.print_item(&*item));
+        assert!(!own_line.as_slice().lines().any(|l| l.contains("fn f") && l.contains("where")));
+        assert!(own_line.as_slice().lines().any(|l| l.trim_left().starts_with("where T: Clone")));
+    }
+
+    #[test]
+    fn test_node_in_context_to_string() {
+        let krate = string_to_crate("fn g() {} \
+                                     fn f(x: int) -> int { let a = 1i; let b = 2i; x + b }"
+                                    .to_string());
+        let mut forest = ast_map::Forest::new(krate);
+        let map = ast_map::map_crate(&mut forest, IdCounter::new());
+        let id = range(0, 100).find(|&id| match map.find(id) {
+            Some(ast_map::NodeStmt(st)) => stmt_to_string(st).as_slice().contains("let b"),
+            _ => false
+        }).unwrap();
+
+        let printed = node_in_context_to_string(&map, id, &Default::default());
+        assert!(printed.as_slice().contains("fn f(x: int) -> int"));
+        assert!(printed.as_slice().contains("let b = 2i;"));
+        assert!(printed.as_slice().contains("/* ... */"));
+        assert!(!printed.as_slice().contains("let a"));
+        assert!(!printed.as_slice().contains("fn g"));
+
+        let bare = NodeContext { ancestors: 0, ..Default::default() };
+        let printed = node_in_context_to_string(&map, id, &bare);
+        assert!(printed.as_slice().contains("let b = 2i;"));
+        assert!(!printed.as_slice().contains("fn f"));
+    }
+
+    #[test]
+    fn test_node_in_context_to_string_nested_block() {
+        let krate = string_to_crate("fn f() { let a = 1i; { let b = 2i; let c = 3i; } \
+                                     let d = 4i; }".to_string());
+        let mut forest = ast_map::Forest::new(krate);
+        let map = ast_map::map_crate(&mut forest, IdCounter::new());
+        let id = range(0, 100).find(|&id| match map.find(id) {
+            Some(ast_map::NodeStmt(st)) => stmt_to_string(st).as_slice().starts_with("let b"),
+            _ => false
+        }).unwrap();
+
+        // `let a`, `let c` and `let d` each get a marker: the run elided at
+        // the end of the inner block must not swallow the one after it.
+        let printed = node_in_context_to_string(&map, id, &Default::default());
+        assert!(printed.as_slice().contains("let b = 2i;"));
+        assert!(!printed.as_slice().contains("let d"));
+        assert_eq!(printed.as_slice().match_indices("/* ... */").count(), 3);
+    }
+
     #[test]
     fn test_signed_int_to_string() {
         let pos_int = ast::LitInt(42, ast::SignedIntLit(ast::TyI32, ast::Plus));
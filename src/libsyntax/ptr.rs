@@ -30,7 +30,7 @@
 //!   case with arenas or a GADT AST using type parameters to toggle features).
 //!
 //! * **Maintainability**: `P<T>` provides a fixed interface - `Deref`,
-//!   `and_then` and `map` - which can remain fully functional even if the
+//!   `and_then`, `map` and `map_in_place` - which can remain fully functional even if the
 //!   implementation changes (using a special thread-local heap, for example).
 //!   Moreover, a switch to, e.g. `P<'a, T>` would be easy and mostly automated.
 
@@ -69,6 +69,14 @@ impl<T: 'static> P<T> {
         }
         self
     }
+
+    /// Mutate the inner value through a reference, consuming `self` and
+    /// returning it again. Unlike `map`, nothing is moved out of the
+    /// allocation, so parts of a node that don't change are never touched.
+    pub fn map_in_place(mut self, f: |&mut T|) -> P<T> {
+        f(&mut *self.ptr);
+        self
+    }
 }
 
 impl<T> Deref<T> for P<T> {
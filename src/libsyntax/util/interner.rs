@@ -132,6 +132,38 @@ impl RcStr {
     }
 }
 
+/// Statistics about the contents of a `StrInterner`.
+#[deriving(Clone, PartialEq, Show)]
+pub struct InternerStats {
+    /// The number of names handed out, including gensyms.
+    pub names: uint,
+    /// How many of those names are gensyms.
+    pub gensyms: uint,
+    /// The total length of the stored strings, in bytes.
+    pub bytes: uint,
+}
+
+/// A copy of the contents of a `StrInterner` made of plain owned strings,
+/// so that unlike the interner it can be sent to and shared with other
+/// threads. Names resolve against it just as they do against the interner
+/// it was taken from.
+#[deriving(Clone, PartialEq, Show)]
+pub struct InternerSnapshot {
+    /// Each name's string, along with whether it is a gensym.
+    entries: Vec<(String, bool)>,
+}
+
+impl InternerSnapshot {
+    pub fn get(&self, idx: Name) -> &str {
+        let (ref s, _) = self.entries[idx.uint()];
+        s.as_slice()
+    }
+
+    pub fn len(&self) -> uint {
+        self.entries.len()
+    }
+}
+
 /// A StrInterner differs from Interner<String> in that it accepts
 /// &str rather than RcStr, resulting in less allocation.
 pub struct StrInterner {
@@ -219,6 +251,38 @@ impl StrInterner {
         *self.map.borrow_mut() = other.map.into_inner();
         *self.vect.borrow_mut() = other.vect.into_inner();
     }
+
+    pub fn stats(&self) -> InternerStats {
+        let vect = self.vect.borrow();
+        InternerStats {
+            names: vect.len(),
+            gensyms: vect.len() - self.map.borrow().len(),
+            bytes: vect.iter().fold(0, |n, s| n + s.as_slice().len()),
+        }
+    }
+
+    pub fn snapshot(&self) -> InternerSnapshot {
+        let map = self.map.borrow();
+        let entries = self.vect.borrow().iter().enumerate().map(|(i, s)| {
+            let is_gensym = map.get(s) != Some(&Name(i as u32));
+            (s.as_slice().to_string(), is_gensym)
+        }).collect();
+        InternerSnapshot { entries: entries }
+    }
+
+    /// Creates an interner that gives out the same names as the one the
+    /// snapshot was taken from.
+    pub fn from_snapshot(snapshot: &InternerSnapshot) -> StrInterner {
+        let rv = StrInterner::new();
+        for &(ref s, is_gensym) in snapshot.entries.iter() {
+            if is_gensym {
+                rv.gensym(s.as_slice());
+            } else {
+                rv.intern(s.as_slice());
+            }
+        }
+        rv
+    }
 }
 
 #[cfg(test)]
@@ -300,4 +364,28 @@ mod tests {
         assert_eq!(i.get(Name(3)), RcStr::new("zebra"));
         assert_eq!(i.get(Name(4)), RcStr::new("dog"));
     }
+
+    #[test]
+    fn string_interner_stats() {
+        let i = StrInterner::prefill(&["dog", "cat"]);
+        i.gensym("zebra");
+        i.intern("dog");
+        assert_eq!(i.stats(), InternerStats { names: 3, gensyms: 1, bytes: 11 });
+    }
+
+    #[test]
+    fn string_interner_snapshot() {
+        let i = StrInterner::prefill(&["dog", "cat"]);
+        let zebra = i.gensym("zebra");
+        let dog = i.gensym("dog");
+        let snapshot = i.snapshot();
+        assert_eq!(snapshot.len(), 4);
+        assert_eq!(snapshot.get(zebra), "zebra");
+
+        let j = StrInterner::from_snapshot(&snapshot);
+        assert_eq!(j.get(dog), RcStr::new("dog"));
+        assert_eq!(j.intern("dog"), Name(0));
+        assert_eq!(j.intern("zebra"), Name(4));
+        assert_eq!(j.stats(), i.stats());
+    }
 }
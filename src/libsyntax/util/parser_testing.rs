@@ -9,6 +9,7 @@
 // except according to those terms.
 
 use ast;
+use ast_map;
 use parse::{new_parse_sess};
 use parse::{ParseSess,string_to_filemap,filemap_to_tts};
 use parse::{new_parser_from_source_str};
@@ -16,6 +17,8 @@ use parse::parser::Parser;
 use parse::token;
 use ptr::P;
 
+use std::cell::Cell;
+
 /// Map a string to tts, using a made-up filename:
 pub fn string_to_tts(source_str: String) -> Vec<ast::TokenTree> {
     let ps = new_parse_sess();
@@ -80,6 +83,26 @@ pub fn string_to_pat(source_str: String) -> P<ast::Pat> {
     string_to_parser(&new_parse_sess(), source_str).parse_pat()
 }
 
+/// Numbers the nodes of a crate from 1 as `ast_map::map_crate` maps them,
+/// since the parser leaves them all with `DUMMY_NODE_ID`.
+pub struct IdCounter {
+    next: Cell<ast::NodeId>,
+}
+
+impl IdCounter {
+    pub fn new() -> IdCounter {
+        IdCounter { next: Cell::new(1) }
+    }
+}
+
+impl ast_map::FoldOps for IdCounter {
+    fn new_id(&self, _: ast::NodeId) -> ast::NodeId {
+        let id = self.next.get();
+        self.next.set(id + 1);
+        id
+    }
+}
+
 /// Convert a vector of strings to a vector of ast::Ident's
 pub fn strs_to_idents(ids: Vec<&str> ) -> Vec<ast::Ident> {
     ids.iter().map(|u| token::str_to_ident(*u)).collect()
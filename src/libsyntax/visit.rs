@@ -145,6 +145,9 @@ pub fn walk_inlined_item<'v,V>(visitor: &mut V, item: &'v InlinedItem)
             visitor.visit_ident(typedef.span, typedef.ident);
             visitor.visit_ty(&*typedef.typ);
         }
+        IIImplItem(_, ConstImplItem(ref associated_const)) => {
+            walk_associated_const(visitor, &**associated_const)
+        }
     }
 }
 
@@ -299,6 +302,9 @@ pub fn walk_item<'v, V: Visitor<'v>>(visitor: &mut V, item: &'v Item) {
                         visitor.visit_ident(typedef.span, typedef.ident);
                         visitor.visit_ty(&*typedef.typ);
                     }
+                    ConstImplItem(ref associated_const) => {
+                        walk_associated_const(visitor, &**associated_const)
+                    }
                 }
             }
         }
@@ -571,8 +577,16 @@ pub fn walk_generics<'v, V: Visitor<'v>>(visitor: &mut V, generics: &'v Generics
     }
     walk_lifetime_decls_helper(visitor, &generics.lifetimes);
     for predicate in generics.where_clause.predicates.iter() {
-        visitor.visit_ident(predicate.span, predicate.ident);
-        walk_ty_param_bounds_helper(visitor, &predicate.bounds);
+        match *predicate {
+            BoundPredicate(ref bound_pred) => {
+                visitor.visit_ident(bound_pred.span, bound_pred.ident);
+                walk_ty_param_bounds_helper(visitor, &bound_pred.bounds);
+            }
+            EqPredicate(ref eq_pred) => {
+                visitor.visit_ty(&*eq_pred.lhs_ty);
+                visitor.visit_ty(&*eq_pred.rhs_ty);
+            }
+        }
     }
 }
 
@@ -659,6 +673,19 @@ pub fn walk_trait_item<'v, V: Visitor<'v>>(visitor: &mut V, trait_method: &'v Tr
             visitor.visit_ident(associated_type.ty_param.span,
                                 associated_type.ty_param.ident)
         }
+        ConstTraitItem(ref associated_const) => {
+            walk_associated_const(visitor, &**associated_const)
+        }
+    }
+}
+
+pub fn walk_associated_const<'v, V: Visitor<'v>>(visitor: &mut V,
+                                                 associated_const: &'v AssociatedConst) {
+    visitor.visit_ident(associated_const.span, associated_const.ident);
+    visitor.visit_ty(&*associated_const.ty);
+    walk_expr_opt(visitor, &associated_const.expr);
+    for attr in associated_const.attrs.iter() {
+        visitor.visit_attribute(attr);
     }
 }
 
@@ -772,7 +799,8 @@ pub fn walk_expr<'v, V: Visitor<'v>>(visitor: &mut V, expression: &'v Expr) {
             visitor.visit_expr(&**subexpression)
         }
         ExprLit(_) => {}
-        ExprCast(ref subexpression, ref typ) => {
+        ExprCast(ref subexpression, ref typ) |
+        ExprType(ref subexpression, ref typ) => {
             visitor.visit_expr(&**subexpression);
             visitor.visit_ty(&**typ)
         }
@@ -791,6 +819,13 @@ pub fn walk_expr<'v, V: Visitor<'v>>(visitor: &mut V, expression: &'v Expr) {
             visitor.visit_block(&**if_block);
             walk_expr_opt(visitor, optional_else);
         }
+        ExprInclusiveRange(ref start, ref end) => {
+            visitor.visit_expr(&**start);
+            visitor.visit_expr(&**end)
+        }
+        ExprTry(ref subexpression) => {
+            visitor.visit_expr(&**subexpression)
+        }
         ExprWhileLet(ref pattern, ref subexpression, ref block, _) => {
             visitor.visit_pat(&**pattern);
             visitor.visit_expr(&**subexpression);
@@ -849,7 +884,10 @@ pub fn walk_expr<'v, V: Visitor<'v>>(visitor: &mut V, expression: &'v Expr) {
         ExprPath(ref path) => {
             visitor.visit_path(path, expression.id)
         }
-        ExprBreak(_) | ExprAgain(_) => {}
+        ExprBreak(_, ref optional_expression) => {
+            walk_expr_opt(visitor, optional_expression)
+        }
+        ExprAgain(_) => {}
         ExprRet(ref optional_expression) => {
             walk_expr_opt(visitor, optional_expression)
         }
@@ -859,11 +897,11 @@ pub fn walk_expr<'v, V: Visitor<'v>>(visitor: &mut V, expression: &'v Expr) {
         }
         ExprInlineAsm(ref ia) => {
             for input in ia.inputs.iter() {
-                let (_, ref input) = *input;
+                let (_, ref input, _) = *input;
                 visitor.visit_expr(&**input)
             }
             for output in ia.outputs.iter() {
-                let (_, ref output, _) = *output;
+                let (_, ref output, _, _) = *output;
                 visitor.visit_expr(&**output)
             }
         }
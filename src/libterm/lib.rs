@@ -137,6 +137,15 @@ pub fn stderr() -> Option<Box<Terminal<WriterWrapper> + Send>> {
     }
 }
 
+/// Return a Terminal wrapping stderr that writes ANSI escape sequences, for
+/// when colors are wanted even though no terminal could be opened, e.g.
+/// when the output is piped to a log that will be displayed later.
+pub fn ansi_stderr() -> Box<Terminal<WriterWrapper> + Send> {
+    TerminfoTerminal::new_ansi(WriterWrapper {
+        wrapped: box std::io::stderr() as Box<Writer + Send>,
+    })
+}
+
 
 /// Terminal color definitions
 pub mod color {
@@ -212,6 +212,14 @@ impl<T: Writer+Send> TerminfoTerminal<T> {
                                           num_colors: nc} as Box<Terminal<T>+Send>);
     }
 
+    /// Returns a terminal writing plain ANSI escape sequences, without
+    /// looking up the terminfo entry of `TERM`.
+    pub fn new_ansi(out: T) -> Box<Terminal<T>+Send+'static> {
+        box TerminfoTerminal {out: out,
+                              ti: msys_terminfo(),
+                              num_colors: 8} as Box<Terminal<T>+Send>
+    }
+
     fn dim_if_necessary(&self, color: color::Color) -> color::Color {
         if color >= self.num_colors && color >= 8 && color < 16 {
             color-8
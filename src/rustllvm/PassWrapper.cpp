@@ -16,6 +16,8 @@
 #include "llvm/Support/FileSystem.h"
 #include "llvm/Target/TargetLibraryInfo.h"
 #include "llvm/Transforms/IPO/PassManagerBuilder.h"
+#include "llvm/Transforms/Utils/Cloning.h"
+#include "llvm/ADT/StringSet.h"
 
 #include "llvm-c/Transforms/PassManagerBuilder.h"
 
@@ -270,6 +272,22 @@ LLVMRustRunRestrictionPass(LLVMModuleRef M, char **symbols, size_t len) {
     passes.run(*unwrap(M));
 }
 
+// Returns a copy of `M` in which every function not named in `symbols` is
+// turned into a declaration, for writing out part of a module.
+extern "C" LLVMModuleRef
+LLVMRustCloneModuleKeeping(LLVMModuleRef M, char **symbols, size_t len) {
+    Module *Clone = CloneModule(unwrap(M));
+    StringSet<> Keep;
+    for (size_t i = 0; i < len; i++)
+        Keep.insert(symbols[i]);
+
+    for (Module::iterator F = Clone->begin(), E = Clone->end(); F != E; ++F) {
+        if (!F->isDeclaration() && !Keep.count(F->getName()))
+            F->deleteBody();
+    }
+    return wrap(Clone);
+}
+
 extern "C" void
 LLVMRustMarkAllFunctionsNounwind(LLVMModuleRef M) {
     for (Module::iterator GV = unwrap(M)->begin(),
@@ -0,0 +1,37 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(associated_consts)]
+
+pub trait Bounded {
+    const MAX: Self;
+}
+
+impl Bounded for u8 {
+    const MAX: u8 = 255;
+}
+
+pub struct Id<T> {
+    pub n: uint,
+}
+
+pub trait HasId {
+    const ID: Id<Self> = Id { n: 7 };
+}
+
+pub struct Custom;
+
+impl HasId for Custom {}
+
+pub struct Buffer;
+
+impl Buffer {
+    pub const SIZE: uint = 4;
+}
@@ -0,0 +1,29 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_rules)]
+
+#[macro_export]
+macro_rules! badly_named(
+    () => (
+        #[allow(dead_code)]
+        struct badly_named_struct;
+        #[allow(dead_code)]
+        fn BadlyNamedFn() -> int { let x = (1i); x }
+    )
+)
+
+#[macro_export]
+macro_rules! bind_to_x(
+    ($e:expr) => (
+        #[allow(dead_code)]
+        fn bound() -> int { let x = $e; x }
+    )
+)
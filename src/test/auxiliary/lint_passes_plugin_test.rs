@@ -0,0 +1,75 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// force-host
+
+#![feature(phase, plugin_registrar)]
+
+extern crate syntax;
+
+// Load rustc as a plugin to get macros
+#[phase(plugin, link)]
+extern crate rustc;
+
+use syntax::ast;
+use syntax::parse::token;
+use rustc::lint::{Context, LintPass, LintPassObject, LintArray};
+use rustc::lint::{EarlyContext, EarlyLintPass, EarlyLintPassObject};
+use rustc::middle::ty;
+use rustc::plugin::Registry;
+use rustc::util::ppaux::ty_to_string;
+
+declare_lint!(EARLY_TEST_LINT, Warn,
+              "Warn about items named 'lintme_early'")
+
+declare_lint!(RETURNS_BOX, Warn,
+              "Warn about functions returning a box")
+
+struct EarlyPass;
+
+impl EarlyLintPass for EarlyPass {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(EARLY_TEST_LINT)
+    }
+
+    fn check_item(&mut self, cx: &EarlyContext, it: &ast::Item) {
+        let name = token::get_ident(it.ident);
+        if name.get() == "lintme_early" {
+            cx.span_lint(EARLY_TEST_LINT, it.span, "item is named 'lintme_early'");
+        }
+    }
+}
+
+struct LatePass;
+
+impl LintPass for LatePass {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(RETURNS_BOX)
+    }
+
+    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+        if let ast::ItemFn(..) = it.node {
+            let fn_ty = ty::node_id_to_type(cx.tcx, it.id);
+            if let ty::FnConverging(ret_ty) = ty::ty_fn_ret(fn_ty) {
+                if let ty::ty_uniq(_) = ret_ty.sty {
+                    cx.span_lint(RETURNS_BOX, it.span,
+                                 format!("function returns `{}`",
+                                         ty_to_string(cx.tcx, ret_ty)).as_slice());
+                }
+            }
+        }
+    }
+}
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    reg.register_early_lint_pass(box EarlyPass as EarlyLintPassObject);
+    reg.register_late_lint_pass(box LatePass as LintPassObject);
+}
@@ -36,7 +36,9 @@ impl LintPass for Pass {
     fn check_item(&mut self, cx: &Context, it: &ast::Item) {
         let name = token::get_ident(it.ident);
         if name.get() == "lintme" {
-            cx.span_lint(TEST_LINT, it.span, "item is named 'lintme'");
+            cx.struct_span_lint(TEST_LINT, it.span, "item is named 'lintme'")
+              .span_help(it.span, "give the item another name")
+              .emit();
         }
     }
 }
@@ -15,9 +15,11 @@
 extern crate syntax;
 extern crate rustc;
 
-use syntax::ast::{TokenTree, Item, MetaItem};
+use syntax::ast;
+use syntax::ast::{TokenTree, Item, MetaItem, Expr};
 use syntax::codemap::Span;
 use syntax::ext::base::*;
+use syntax::ext::build::AstBuilder;
 use syntax::parse::token;
 use syntax::parse;
 use syntax::ptr::P;
@@ -33,6 +35,8 @@ pub fn plugin_registrar(reg: &mut Registry) {
     reg.register_macro("make_a_1", expand_make_a_1);
     reg.register_macro("forged_ident", expand_forged_ident);
     reg.register_macro("identity", expand_identity);
+    reg.register_macro("reserved_ids", expand_reserved_ids);
+    reg.register_macro("one_arg", expand_one_arg);
     reg.register_syntax_extension(
         token::intern("into_foo"),
         Modifier(box expand_into_foo));
@@ -46,6 +50,35 @@ fn expand_make_a_1(cx: &mut ExtCtxt, sp: Span, tts: &[TokenTree])
     MacExpr::new(quote_expr!(cx, 1i))
 }
 
+fn expand_reserved_ids(cx: &mut ExtCtxt, sp: Span, tts: &[TokenTree])
+                       -> Box<MacResult+'static> {
+    if !tts.is_empty() {
+        cx.span_fatal(sp, "reserved_ids takes no arguments");
+    }
+    // The operands get ids of their own, which have to survive the
+    // assignment of node ids after expansion.
+    let first = cx.reserve_node_ids(2);
+    let lhs = cx.expr_uint(sp, 2).map(|e| Expr { id: first, ..e });
+    let rhs = cx.expr_uint(sp, 2).map(|e| Expr { id: first + 1, ..e });
+    MacExpr::new(cx.expr_binary(sp, ast::BiAdd, lhs, rhs))
+}
+
+fn expand_one_arg(cx: &mut ExtCtxt, sp: Span, tts: &[TokenTree])
+                  -> Box<MacResult+'static> {
+    if tts.len() != 1 {
+        let mut db = cx.struct_span_err(sp, "one_arg takes exactly one argument")
+                       .span_note(sp, "the argument is passed through unchanged");
+        if tts.len() > 1 {
+            db = db.span_help(tts[1].get_span(), "remove the extra arguments");
+        }
+        db.emit();
+        return DummyResult::expr(sp);
+    }
+    let mut parser = parse::new_parser_from_tts(cx.parse_sess(),
+        cx.cfg(), tts.to_vec());
+    MacExpr::new(parser.parse_expr())
+}
+
 // See Issue #15750
 fn expand_identity(cx: &mut ExtCtxt, _span: Span, tts: &[TokenTree])
                    -> Box<MacResult+'static> {
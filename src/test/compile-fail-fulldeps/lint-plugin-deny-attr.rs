@@ -18,6 +18,7 @@
 extern crate lint_plugin_test;
 
 fn lintme() { } //~ ERROR item is named 'lintme'
+//~^ HELP give the item another name
 
 pub fn main() {
     lintme();
@@ -0,0 +1,26 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:lint_passes_plugin_test.rs
+// ignore-stage1
+
+#![feature(phase)]
+#![deny(early_test_lint)]
+
+#[phase(plugin)]
+extern crate lint_passes_plugin_test;
+
+// Early lints are checked before type checking, so they're reported even
+// though the crate doesn't type check.
+fn lintme_early() { } //~ ERROR item is named 'lintme_early'
+
+pub fn main() {
+    let x: int = "not an int"; //~ ERROR mismatched types
+}
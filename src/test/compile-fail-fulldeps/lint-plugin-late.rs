@@ -0,0 +1,28 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:lint_passes_plugin_test.rs
+// ignore-stage1
+
+#![feature(phase)]
+#![deny(returns_box)]
+#![allow(dead_code)]
+
+#[phase(plugin)]
+extern crate lint_passes_plugin_test;
+
+type Boxed = Box<int>;
+
+// Late lints see the types behind the syntax, like that of the alias here.
+fn boxed() -> Boxed { box 1 } //~ ERROR function returns `Box<int>`
+
+fn unboxed() -> int { 1 }
+
+pub fn main() { }
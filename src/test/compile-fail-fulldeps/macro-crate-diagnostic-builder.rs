@@ -0,0 +1,29 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:macro_crate_test.rs
+// ignore-stage1
+// ignore-android
+
+// Errors built by a plugin with `DiagnosticBuilder` carry their notes and help.
+
+#![feature(phase)]
+
+#[phase(plugin)]
+extern crate macro_crate_test;
+
+fn main() {
+    let x = one_arg!(1i);
+    let y = one_arg!(); //~ ERROR one_arg takes exactly one argument
+    //~^ NOTE the argument is passed through unchanged
+    let z = one_arg!(1i 2i); //~ ERROR one_arg takes exactly one argument
+    //~^ NOTE the argument is passed through unchanged
+    //~^^ HELP remove the extra arguments
+}
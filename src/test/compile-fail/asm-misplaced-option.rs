@@ -30,6 +30,7 @@ pub fn main() {
         // comma in place of a colon
         asm!("add $2, $1; mov $1, $0" : "=r"(x) : "r"(x), "r"(8u) : "cc", "volatile");
         //~^ WARNING expected a clobber, found an option
+        //~^^ HELP move the options after the clobbers
     }
     assert_eq!(x, 13);
 }
@@ -0,0 +1,31 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(asm)]
+#![deny(unreachable_code)]
+#![allow(dead_code)]
+
+// A `noreturn` asm diverges, so it can end a function returning `!` or any
+// other type, and the code after it is unreachable.
+
+fn halt() -> ! {
+    unsafe { asm!("hlt" :::: "noreturn") }
+}
+
+fn halt_int() -> int {
+    unsafe { asm!("hlt" :::: "volatile", "noreturn") }
+}
+
+fn after_halt() {
+    unsafe { asm!("hlt" :::: "noreturn"); }
+    let _x = 1i; //~ ERROR unreachable statement
+}
+
+fn main() {}
@@ -0,0 +1,52 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the types of inline asm operands are checked against their
+// constraints, with errors pointing at the operand.
+
+#![feature(asm)]
+
+trait Shape {}
+
+struct Plain { x: int, y: int }
+
+#[repr(C)]
+struct Point { x: int, y: int }
+
+#[cfg(any(target_arch = "x86",
+          target_arch = "x86_64"))]
+pub fn main() {
+    let s: &str = "hello";
+    let shape: &Shape = panic!();
+    let plain = Plain { x: 0, y: 0 };
+    let point = Point { x: 0, y: 0 };
+    let f = 1.5f64;
+    unsafe {
+        asm!("" :: "r"(s));
+        //~^ ERROR cannot use a value of type `&str` for the asm constraint `r`: pointers to unsized
+        asm!("" :: "r"(*shape));
+        //~^ ERROR cannot use a value of type `Shape` for the asm constraint `r`: values of unsized
+        asm!("" :: "m"(plain));
+        //~^ ERROR cannot use a value of type `Plain` for the asm constraint `m`: only `#[repr(C)]`
+        asm!("" :: "r"(point));
+        //~^ ERROR cannot use a value of type `Point` for the asm constraint `r`: aggregates must
+        asm!("" :: "i"(f));
+        //~^ ERROR cannot use a value of type `f64` for the asm constraint `i`: immediate
+        asm!("" :: "r"(()));
+        //~^ ERROR cannot use a value of type `()` for the asm constraint `r`: `()` has no value
+
+        // These are all fine.
+        asm!("" :: "m"(point));
+        asm!("" :: "r"(&point), "r"(f), "i"(1u), "r"(&plain));
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn main() {}
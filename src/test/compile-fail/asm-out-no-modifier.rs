@@ -19,6 +19,7 @@ pub fn main() {
     let x: int;
     unsafe {
         asm!("mov $1, $0" : "r"(x) : "r"(5u)); //~ ERROR output operand constraint lacks '='
+        //~^ HELP mark the operand as written
     }
     foo(x);
 }
@@ -0,0 +1,42 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C target-feature=-sse
+
+// Test that asm! registers and register classes which need a target feature
+// are rejected, naming the feature, when it is disabled.
+
+#![feature(asm)]
+#![allow(asm_barriers)]
+
+#[cfg(any(target_arch = "x86",
+          target_arch = "x86_64"))]
+pub fn main() {
+    let x: u32;
+    unsafe {
+        asm!("movd %xmm0, $0" : "={xmm0}"(x));
+        //~^ ERROR the register `xmm0` requires the `sse` target feature, which is disabled
+        //~^^ HELP enable it with `-C target-feature=+sse`
+        asm!("" : : "x"(x));
+        //~^ ERROR the asm constraint `x` requires the `sse` target feature
+        //~^^ HELP enable it
+        asm!("" : : "{ymm0}"(x));
+        //~^ ERROR the register `ymm0` requires the `avx` target feature
+        //~^^ HELP enable it with `-C target-feature=+avx`
+        asm!("" : : : "xmm1");
+        //~^ ERROR clobbering the register `xmm1` requires the `sse` target feature
+        //~^^ HELP enable it
+        asm!("" : : "{eax}"(x) : "ecx");
+    }
+}
+
+#[cfg(not(any(target_arch = "x86",
+              target_arch = "x86_64")))]
+pub fn main() {}
@@ -0,0 +1,33 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-arm
+// ignore-aarch64
+// compile-flags: -C soft-float
+
+// Test that asm! can't use floating point registers in soft-float code.
+
+#![feature(asm)]
+
+#[cfg(any(target_arch = "x86",
+          target_arch = "x86_64"))]
+pub fn main() {
+    let x: u32;
+    unsafe {
+        asm!("movd %xmm0, $0" : "={xmm0}"(x));
+        //~^ ERROR the register `xmm0` needs floating point hardware
+        asm!("" : : "r"(x) : "xmm1");
+        //~^ ERROR clobbering the register `xmm1` needs floating point hardware
+    }
+}
+
+#[cfg(not(any(target_arch = "x86",
+              target_arch = "x86_64")))]
+pub fn main() {}
@@ -0,0 +1,26 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(associated_consts)]
+
+trait Optional {
+    const NONE: Option<Self> = None;
+    //~^ ERROR default values of associated constants whose type refers to `Self`
+}
+
+struct Wrapper<T>;
+
+impl<T> Wrapper<T> {
+    const EMPTY: Option<T> = None;
+    //~^ ERROR associated constants in generic impls cannot have a type that refers
+}
+
+fn main() {}
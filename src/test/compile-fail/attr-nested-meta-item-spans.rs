@@ -0,0 +1,32 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Errors about attributes point at the element that's wrong, not the
+// start of the attribute.
+
+#[cfg(any(foo,
+          not(bar,
+              baz)))] //~ ERROR expected 1 cfg-pattern
+fn f() {}
+
+#[cfg(all(foo,
+          bogus(bar)))] //~ ERROR invalid predicate `bogus`
+fn g() {}
+
+#[repr(C,
+       i32,
+       whatever)] //~ ERROR unrecognized representation hint
+enum E { A }
+
+#[deprecated(
+    use_something_else)] //~ ERROR expected `#[deprecated = "..."]`
+fn h() {}
+
+fn main() {}
@@ -15,7 +15,6 @@ fn main() {
     let y = &mut x;
     let z = &x; //~ ERROR cannot borrow
 }
-//~^ NOTE previous borrow ends here
 
 fn foo() {
     match true {
@@ -25,7 +24,6 @@ fn foo() {
             let y = &x;
             let z = &mut x; //~ ERROR cannot borrow
         }
-     //~^ NOTE previous borrow ends here
         false => ()
     }
 }
@@ -37,5 +35,4 @@ fn bar() {
         let y = &mut x;
         let z = &mut x; //~ ERROR cannot borrow
     };
- //~^ NOTE previous borrow ends here
 }
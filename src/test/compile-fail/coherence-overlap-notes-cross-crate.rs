@@ -0,0 +1,29 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// aux-build:go_trait.rs
+
+// Test that a conflict with an impl from another crate names that crate and
+// describes the impl.
+
+extern crate go_trait;
+
+use go_trait::GoMut;
+
+struct MyThingy;
+
+impl GoMut for MyThingy { //~ ERROR conflicting implementations
+//~^ NOTE conflicting implementation in crate `go_trait`
+//~^^ NOTE both implementations apply to the type `MyThingy`
+    fn go_mut(&mut self, arg: int) { }
+}
+
+fn main() { }
@@ -0,0 +1,24 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// Test that overlapping impls point at each other and name the type that
+// both of them apply to.
+
+trait Foo {}
+
+struct Wrapper<T>;
+
+impl<T> Foo for Wrapper<T> {} //~ ERROR conflicting implementations for trait `Foo`
+//~^ NOTE both implementations apply to the type `Wrapper<_>`
+
+impl<U> Foo for U {} //~ NOTE conflicting implementation here
+
+fn main() {}
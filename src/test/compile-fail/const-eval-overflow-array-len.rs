@@ -0,0 +1,19 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that an overflowing array length is reported at the operation that
+// overflowed, even when it happens inside another constant.
+
+const LEN: uint = 0u - 1;
+//~^ ERROR expected constant expr for array length: attempted to subtract with overflow
+
+fn main() {
+    let _a: [u8, ..LEN];
+}
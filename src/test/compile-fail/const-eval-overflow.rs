@@ -0,0 +1,31 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that overflow while evaluating an enum discriminant is reported at
+// the operation that overflowed.
+
+const BIG: i64 = 0x7fff_ffff_ffff_ffff;
+const TOO_BIG: i64 = BIG + 1; //~ ERROR expected constant: attempted to add with overflow
+
+enum Overflow {
+    Add = (BIG + 1) as int, //~ ERROR expected constant: attempted to add with overflow
+    Mul = (BIG * 2) as int, //~ ERROR expected constant: attempted to multiply with overflow
+    Neg = -(-BIG - 1) as int, //~ ERROR expected constant: attempted to negate with overflow
+    Shl = 1 << 64, //~ ERROR expected constant: attempted to shift left with overflow
+    Shr = 1 >> -1, //~ ERROR expected constant: attempted to shift right with overflow
+    Indirect = TOO_BIG as int,
+    // overflow is checked at the width of the operands' type
+    Add8 = (200u8 + 100) as int, //~ ERROR expected constant: attempted to add with overflow
+    Mul8 = (100i8 * 2) as int, //~ ERROR expected constant: attempted to multiply with overflow
+    Neg8 = -(-128i8) as int, //~ ERROR expected constant: attempted to negate with overflow
+    Shl8 = (1u8 << 8) as int, //~ ERROR expected constant: attempted to shift left with overflow
+}
+
+fn main() {}
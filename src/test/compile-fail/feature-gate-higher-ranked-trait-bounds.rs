@@ -0,0 +1,25 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+trait Get<A, R> {
+    fn get(&self, arg: A) -> R;
+}
+
+fn want_hr<G>(g: G)
+    where G: for<'a> Get<&'a int, &'a int>
+    //~^ ERROR higher-ranked trait bounds (`for<'a>`) are experimental
+{
+}
+
+fn want_hr_object(g: Box<for<'a> Get<&'a int, &'a int>>) {
+    //~^ ERROR higher-ranked trait bounds (`for<'a>`) are experimental
+}
+
+fn main() {}
@@ -10,6 +10,8 @@
 
 // Test HRTB supertraits with several levels of expansion required.
 
+#![feature(higher_ranked_trait_bounds)]
+
 trait Foo<'tcx>
 {
     fn foo(&'tcx self) -> &'tcx int;
@@ -10,6 +10,8 @@
 
 // Test a trait (`Bar`) with a higher-ranked supertrait.
 
+#![feature(higher_ranked_trait_bounds)]
+
 trait Foo<'tcx>
 {
     fn foo(&'tcx self) -> &'tcx int;
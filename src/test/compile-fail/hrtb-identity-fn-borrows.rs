@@ -11,6 +11,8 @@
 // Test that the `'a` in the where clause correctly links the region
 // of the output to the region of the input.
 
+#![feature(higher_ranked_trait_bounds)]
+
 trait FnLike<A,R> {
     fn call(&self, arg: A) -> R;
 }
@@ -0,0 +1,44 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that mismatches between higher-ranked and concrete lifetimes name
+// the bound lifetime parameter involved.
+
+#![allow(dead_code)]
+#![allow(unused_variables)]
+
+struct S;
+
+// Given 'cx, return 'cx
+type F = for<'cx> fn(&'cx S) -> &'cx S;
+fn want_F(f: F) { }
+
+// Given anything, return 'static
+type G = for<'cx> fn(&'cx S) -> &'static S;
+fn want_G(f: G) { }
+
+fn only_static(x: &'static S) -> &'static S {
+    x
+}
+
+fn same<'x>(x: &'x S) -> &'x S {
+    x
+}
+
+fn supply_F() {
+    want_F(only_static); //~ ERROR expected bound lifetime parameter `'cx`, found concrete lifetime
+}
+
+fn supply_G() {
+    want_G(same); //~ ERROR expected concrete lifetime, found bound lifetime parameter `'x`
+}
+
+pub fn main() {
+}
@@ -0,0 +1,26 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::intrinsics::prefetch_read_data;
+
+const LOCALITY: i32 = 3;
+const TOO_FAR: i32 = LOCALITY + 1;
+
+fn main() {
+    let x = 0u8;
+    let locality = 1;
+    unsafe {
+        prefetch_read_data(&x as *const u8, 4); //~ ERROR prefetch locality
+        prefetch_read_data(&x as *const u8, locality); //~ ERROR prefetch locality
+        prefetch_read_data(&x as *const u8, TOO_FAR); //~ ERROR prefetch locality
+        prefetch_read_data(&x as *const u8, 2);
+        prefetch_read_data(&x as *const u8, LOCALITY);
+    }
+}
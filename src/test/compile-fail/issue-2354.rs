@@ -8,11 +8,11 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-fn foo() { //~ HELP did you mean to close this delimiter?
-  match Some(x) {
+fn foo() { //~ NOTE unclosed delimiter `{` opened here
+  match Some(x) { //~ NOTE the delimiter it closes is opened here
       Some(y) { panic!(); }
       None    { panic!(); }
-}
+} //~ NOTE this delimiter is indented differently
 
 fn bar() {
     let mut i = 0;
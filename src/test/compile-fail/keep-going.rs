@@ -0,0 +1,39 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z keep-going
+
+// Match checking, liveness and borrow checking all report their errors,
+// instead of compilation stopping after the first of them.
+
+fn f(x: Option<int>) -> int {
+    match x { //~ ERROR non-exhaustive patterns: `None` not covered
+        Some(y) => y,
+    }
+}
+
+fn g(x: bool) -> int { //~ ERROR not all control paths return a value
+    if x {
+        return 1;
+    }
+    2i;
+}
+
+fn h() {
+    let v = vec![1i];
+    drop(v);
+    drop(v); //~ ERROR use of moved value
+}
+
+fn main() {
+    f(None);
+    g(true);
+    h();
+}
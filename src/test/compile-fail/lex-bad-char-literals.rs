@@ -42,8 +42,6 @@ static s: &'static str =
     "\●" //~ ERROR: unknown character escape
 ;
 
-// THIS MUST BE LAST, since unterminated character constants kill the lexer
-
 static c: char =
     '●  //~ ERROR: unterminated character constant
 ;
@@ -0,0 +1,28 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Unterminated literals end at the end of their line, so errors later
+// in the file are still reported.
+
+static c: char =
+    '1a  //~ ERROR: unterminated character constant
+;
+
+static b: u8 =
+    b'ab  //~ ERROR: unterminated byte constant
+;
+
+fn main() {
+    let s = "a string
+             over two lines";
+    let x = "abc //~ ERROR unterminated double quote string
+    ;
+    let y = '\●'; //~ ERROR: unknown character escape
+}
@@ -0,0 +1,18 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An unterminated string ends at the end of its line even when a later
+// string literal could close it, so the code in between is still lexed.
+
+fn main() {
+    let x = "abc //~ ERROR unterminated double quote string
+    ;
+    let y = "d\qf"; //~ ERROR unknown character escape
+}
@@ -0,0 +1,17 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![accept_names = "foo"] //~ ERROR expected `#![accept_names(lint = "pattern", ...)]`
+#![accept_names(dead_code = "*")] //~ ERROR `accept_names` can't give patterns to `dead_code`
+#![accept_names(non_snake_case)] //~ ERROR expected `non_snake_case = "pattern"`
+#![accept_names(non_snake_case = "[a-z")] //~ ERROR invalid name pattern `[a-z`: unclosed set
+
+fn main() {}
@@ -0,0 +1,26 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `accept_names` only has an effect on the crate, so anywhere else it's
+// reported as unused.
+
+#![deny(unused_attributes, non_snake_case)]
+#![allow(dead_code)]
+
+#[accept_names(non_snake_case = "*_ID")] //~ ERROR unused attribute
+//~^ ERROR crate-level attribute should be an inner attribute
+fn lookup_ID() {} //~ ERROR function `lookup_ID` should have a snake case name
+
+mod m {
+    #![accept_names(non_snake_case = "*_ID")] //~ ERROR unused attribute
+    //~^ ERROR crate-level attribute should be in the root module
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![deny(bad_style)]
+#![allow(dead_code)]
+#![accept_names(non_upper_case_globals = "SCREAMING_[A-Z]*",
+                non_snake_case = "*_ID",
+                non_camel_case_types = "Legacy_?")]
+
+static SCREAMING_Mixed: uint = 0;
+static SCREAMING_MIXED: uint = 0;
+static Screaming_Mixed: uint = 0; //~ ERROR static constant `Screaming_Mixed` should have an upper
+
+fn lookup_ID() {}
+fn lookup_Id() {} //~ ERROR function `lookup_Id` should have a snake case name
+
+struct Legacy_A;
+struct Legacy_AB; //~ ERROR type `Legacy_AB` should have a camel case name
+
+fn main() {}
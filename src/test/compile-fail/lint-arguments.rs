@@ -0,0 +1,16 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Only `missing_docs` takes arguments; other lints given them are errors.
+
+#![deny(dead_code(pub_fn))] //~ ERROR malformed lint attribute
+#![allow(unused_variables(x), missing_docs(pub_fn))] //~ ERROR malformed lint attribute
+
+fn main() {}
@@ -0,0 +1,24 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(asm)]
+#![deny(asm_barriers)]
+
+fn main() {
+    unsafe {
+        asm!("" ::: "memory" : "volatile");
+        //~^ ERROR empty `asm!` used as a memory barrier
+        //~^^ HELP use `std::sync::atomic::fence` instead
+        asm!("" :::: "volatile");
+        //~^ ERROR empty `asm!` without a `"memory"` clobber doesn't order memory accesses
+        //~^^ HELP add `"memory"` to its clobbers
+        asm!("" ::: "eax" : "volatile");
+    }
+}
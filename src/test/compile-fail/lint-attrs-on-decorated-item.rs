@@ -0,0 +1,35 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The lint level attributes of a decorated item apply once to what the
+// decorator generates, and override those of the enclosing module.
+
+#![allow(dead_code)]
+#![deny(raw_pointer_deriving)]
+
+#[allow(raw_pointer_deriving)]
+#[deriving(Clone)]
+struct A(*const int);
+
+#[deriving(Clone)]
+struct B(*const int); //~ ERROR use of `#[deriving]` with a raw pointer
+
+mod m {
+    #![allow(raw_pointer_deriving)]
+
+    #[deny(raw_pointer_deriving)]
+    #[deriving(Clone)]
+    struct C(*const int); //~ ERROR use of `#[deriving]` with a raw pointer
+
+    #[deriving(Clone)]
+    struct D(*const int);
+}
+
+fn main() {}
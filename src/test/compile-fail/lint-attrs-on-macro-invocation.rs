@@ -0,0 +1,38 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Lint level attributes on a macro invocation apply to what it expands to,
+// and those of the generated items themselves take precedence.
+
+#![deny(dead_code)]
+
+macro_rules! items(($($i:item)*) => ($($i)*))
+
+macro_rules! method(($name:ident) => (fn $name(&self) {}))
+
+#[allow(dead_code)]
+items!(fn a() {} fn b() {})
+
+items!(fn c() {}) //~ ERROR function is never used: `c`
+
+#[allow(dead_code)]
+items!(#[deny(dead_code)] fn d() {}) //~ ERROR function is never used: `d`
+
+#[deny(dead_code)]
+items!(#[allow(dead_code)] fn e() {})
+
+pub struct S;
+
+impl S {
+    #[allow(dead_code)]
+    method!(f)
+}
+
+fn main() {}
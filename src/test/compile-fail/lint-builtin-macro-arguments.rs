@@ -0,0 +1,20 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Lints still apply to the code the user passes to built-in macros.
+
+#![deny(non_snake_case)]
+
+fn main() {
+    println!("{}", { let BadName = 1i; BadName });
+    //~^ ERROR variable `BadName` should have a snake case name such as `bad_name`
+    assert!({ let Flag = true; Flag });
+    //~^ ERROR variable `Flag` should have a snake case name such as `flag`
+}
@@ -0,0 +1,56 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(improper_ctypes, improper_ctypes_definitions)]
+#![allow(dead_code)]
+
+extern crate libc;
+
+struct Rust { x: i32 } //~ NOTE `Rust` is defined here
+
+enum Data { A(i32), B }
+
+#[repr(C)]
+struct Wrapper { inner: *const Rust }
+
+#[repr(C)]
+struct List { next: *mut List, value: libc::c_int }
+
+#[repr(C)]
+struct Callbacks {
+    good: Option<extern "C" fn(libc::c_int)>,
+    bad: fn(libc::c_int),
+}
+
+extern {
+    fn slice(x: &[u8]); //~ ERROR found type `[u8]` in foreign module, as part of `&[u8]`
+    fn string(x: &str); //~ ERROR found type `str` in foreign module
+    fn owned(x: String); //~ ERROR found type without foreign-function-safe
+    fn boxed(x: Box<u8>); //~ ERROR found type `Box<u8>` in foreign module
+    fn data(x: Data); //~ ERROR found type `Data` in foreign module: enums with fields
+    fn tuple(x: (i32, i32)); //~ ERROR tuples have no C equivalent
+    fn nested(x: Wrapper); //~ ERROR found type without foreign-function-safe
+    fn rust_fn(f: fn(i32)); //~ ERROR function pointer has the Rust calling convention
+    fn extern_fn_int(f: extern "C" fn(uint)); //~ ERROR found rust type `uint`
+    fn callbacks(c: *const Callbacks); //~ ERROR as part of `*const Callbacks`
+
+    fn list(x: *mut List);
+    fn extern_fn(f: extern "C" fn(*const libc::c_char) -> libc::c_int);
+    fn nullable(f: Option<extern "C" fn()>);
+    fn unit() -> ();
+}
+
+pub extern "C" fn defined_slice(x: &[u8]) {} //~ ERROR found type `[u8]`
+
+pub extern "C" fn defined_ok(x: *const u8) -> libc::c_int { 0 }
+
+pub fn rust_abi(x: &[u8], y: String) {}
+
+fn main() { }
@@ -0,0 +1,36 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A deprecated item can name its replacement, which is suggested where the
+// item is used.
+
+#![deny(deprecated)]
+#![allow(dead_code)]
+
+#[deprecated(note = "it was renamed", replacement = "new_name")]
+fn old_name() {}
+fn new_name() {}
+
+#[deprecated(replacement = "Renamed")]
+struct Old { x: int }
+struct Renamed { x: int }
+
+#[deprecated(use_something_else)] //~ ERROR expected `#[deprecated = "..."]`
+fn bad_field() {}
+
+#[deprecated(note = 1)] //~ ERROR expected `#[deprecated = "..."]`
+fn bad_note() {}
+
+fn main() {
+    old_name(); //~ ERROR use of deprecated item: it was renamed
+    //~^ HELP replace it with `new_name`
+    let _ = Old { x: 1 }; //~ ERROR use of deprecated item
+    //~^ HELP replace it with `Renamed`
+}
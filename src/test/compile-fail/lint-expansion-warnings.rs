@@ -0,0 +1,45 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// ignore-android
+
+// The warnings of macro expansion are lints, with the levels in effect
+// where the macro is invoked.
+
+#![feature(asm)]
+#![deny(unrecognized_asm_options)]
+#![allow(dead_code)]
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn denied() {
+    unsafe {
+        asm!("nop" : : : : "bogus"); //~ ERROR unrecognized option
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[allow(unrecognized_asm_options, asm_misplaced_options)]
+fn allowed() {
+    unsafe {
+        asm!("nop" : : : : "bogus");
+        asm!("nop" : : : "volatile");
+    }
+}
+
+#[allow(unused_attributes)]
+#[deriving]
+struct Foo;
+
+#[deny(unused_attributes)]
+#[deriving] //~ ERROR empty trait list in `deriving`
+struct Bar;
+
+pub fn main() {}
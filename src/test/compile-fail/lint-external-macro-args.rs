@@ -0,0 +1,25 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The code a macro from another crate is passed is the user's own, so it's
+// linted like any other.
+
+//aux-build:lint_external_macro.rs
+//ignore-stage1
+
+#![feature(phase)]
+#![deny(unused_parens)]
+
+#[phase(plugin)]
+extern crate lint_external_macro;
+
+bind_to_x!((1i)) //~ ERROR unnecessary parentheses around assigned value
+
+fn main() {}
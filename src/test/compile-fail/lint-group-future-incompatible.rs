@@ -0,0 +1,23 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -D future-incompatible
+
+#[allow(future_incompatible)]
+fn allowed() {
+    let where = 2i;
+    let _ = where;
+}
+
+fn main() {
+    let where = 1i; //~ ERROR `where` is a contextual keyword
+    let _ = where; //~ ERROR `where` is a contextual keyword
+    allowed();
+}
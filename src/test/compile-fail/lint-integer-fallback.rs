@@ -0,0 +1,27 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![deny(integer_fallback)]
+
+fn show<T: std::fmt::Show>(x: T) -> String {
+    format!("{}", x)
+}
+
+fn main() {
+    let a = 1; //~ ERROR this integer literal is not constrained, so it defaults to `i32`
+    let b = a + 2;
+    show(b);
+
+    let c: u8 = 3;
+    show(c);
+    show(4u);
+    show(5); //~ ERROR this integer literal is not constrained, so it defaults to `i32`
+}
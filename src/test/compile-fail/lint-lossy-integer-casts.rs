@@ -0,0 +1,40 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![deny(lossy_integer_casts)]
+#![allow(dead_code)]
+
+fn truncating(x: u64, y: i32, z: uint) {
+    let _ = x as u8; //~ ERROR cast from `u64` to `u8` can truncate the value
+    //~^ HELP use `x.to_u8()` to convert the value if it fits
+    let _ = y as i16; //~ ERROR cast from `i32` to `i16` can truncate the value
+    let _ = z as u32; //~ ERROR cast from `uint` to `u32` can truncate the value
+    let _ = x as uint; //~ ERROR cast from `u64` to `uint` can truncate the value
+}
+
+fn sign_changing(x: i8, y: u32, z: u64) {
+    let _ = x as u64; //~ ERROR cast from `i8` to `u64` can change the sign of the value
+    let _ = y as i32; //~ ERROR cast from `u32` to `i32` can change the sign of the value
+    let _ = z as int; //~ ERROR cast from `u64` to `int` can change the sign of the value
+}
+
+fn lossless(x: u8, y: i16, z: u32) {
+    let _ = x as u16;
+    let _ = x as i16;
+    let _ = y as i64;
+    let _ = y as int;
+    let _ = z as u64;
+    let _ = z as uint;
+    let _ = z as f64;
+    let _ = 3 as u8;
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Allowing some categories of `missing_docs` exempts only those, and the
+// other categories are still checked at the level set outside.
+
+#![deny(missing_docs)]
+#![allow(dead_code)]
+#![crate_type="lib"]
+
+//! Some docs
+
+#[allow(missing_docs(pub_fn))]
+pub mod no_fns { //~ ERROR missing documentation for a module (pub_mod)
+    pub fn exempt() {}
+
+    pub struct Checked; //~ ERROR missing documentation for a struct (pub_struct)
+
+    #[allow(missing_docs(pub_struct))]
+    pub mod no_structs_either {
+        //! Some docs
+
+        pub fn exempt() {}
+
+        pub struct Exempt;
+
+        pub enum Checked { //~ ERROR missing documentation for an enum (pub_enum)
+            /// A documented variant
+            CheckedA
+        }
+    }
+}
+
+/// Some docs
+#[allow(missing_docs(pub_enum, pub_variant))]
+pub mod only_enums_exempt {
+    pub enum Exempt { ExemptA }
+
+    pub fn checked() {} //~ ERROR missing documentation for a function (pub_fn)
+}
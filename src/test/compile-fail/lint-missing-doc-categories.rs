@@ -0,0 +1,50 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Only public functions and structs need documentation here, and the misses
+// say which of those categories they fall in.
+
+#![deny(missing_docs(pub_fn, pub_struct))]
+#![allow(dead_code)]
+#![crate_type="lib"]
+
+pub fn undocumented() {} //~ ERROR missing documentation for a function (pub_fn)
+
+pub struct Undocumented; //~ ERROR missing documentation for a struct (pub_struct)
+
+pub enum NotChecked { NotCheckedA }
+
+pub trait AlsoNotChecked {
+    fn not_checked(&self);
+}
+
+pub mod not_checked_either {
+    pub fn inner() {} //~ ERROR missing documentation for a function (pub_fn)
+
+    #[deny(missing_docs(pub_enum))]
+    pub mod only_enums {
+        pub fn not_checked() {}
+
+        pub enum Checked { //~ ERROR missing documentation for an enum (pub_enum)
+            /// A documented variant
+            CheckedA
+        }
+    }
+
+    #[deny(missing_docs)]
+    pub mod everything { //~ ERROR missing documentation for a module (pub_mod)
+        pub trait Checked { //~ ERROR missing documentation for a trait (pub_trait)
+            fn checked(&self); //~ ERROR missing documentation for a type method (pub_trait_method)
+        }
+    }
+}
+
+#[deny(missing_docs(pub_fns))] //~ ERROR unknown missing_docs category `pub_fns`
+pub mod misspelled {}
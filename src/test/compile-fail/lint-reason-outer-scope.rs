@@ -0,0 +1,31 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The reason given in a lint attribute is kept with the level it sets, so
+// lints in the nodes inside it report it.
+
+#![deny(unused_variables, reason = "every binding should be used")]
+//~^ NOTE reason given: every binding should be used
+
+mod inner {
+    pub fn f() {
+        let x = 1i; //~ ERROR unused variable
+    }
+
+    #[warn(unused_variables)]
+    pub fn g() {
+        let y = 1i; //~ WARN unused variable
+    }
+}
+
+fn main() {
+    inner::f();
+    inner::g();
+}
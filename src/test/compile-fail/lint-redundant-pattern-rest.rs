@@ -0,0 +1,35 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(redundant_pattern_rest)]
+
+struct Foo {
+    x: int,
+    y: int,
+}
+
+enum Bar {
+    Baz { a: int },
+}
+
+fn main() {
+    let Foo { x, y, .. } = Foo { x: 1, y: 2 };
+    //~^ ERROR every field of `Foo` is named in the pattern, so `..` has nothing to ignore
+    let Foo { x: _, .. } = Foo { x: x, y: y };
+    match (Bar::Baz { a: x + y }, 0i) {
+        (Bar::Baz { a, .. }, _) => {}
+        //~^ ERROR is named in the pattern, so `..` has nothing to ignore
+    }
+}
+
+#[allow(redundant_pattern_rest)]
+fn allowed(Foo { x, y, .. }: Foo) -> int {
+    x + y
+}
@@ -0,0 +1,37 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_rules)]
+#![deny(unused_macros)]
+
+macro_rules! unused(() => ()) //~ ERROR unused macro definition: `unused`
+
+macro_rules! used(() => ())
+
+#[macro_export]
+macro_rules! exported(() => ())
+
+#[allow(unused_macros)]
+macro_rules! allowed(() => ())
+
+#[allow(unused)]
+macro_rules! allowed_by_group(() => ())
+
+mod inner {
+    #![allow(unused_macros)]
+
+    macro_rules! allowed_in_module(() => ())
+}
+
+fn main() {
+    used!();
+
+    macro_rules! unused_local(() => ()) //~ ERROR unused macro definition: `unused_local`
+}
@@ -10,6 +10,7 @@
 
 // Exercise the unused_unsafe attribute in some positive and negative cases
 
+#![feature(asm, macro_rules)]
 #![allow(dead_code)]
 #![deny(unused_unsafe)]
 
@@ -23,6 +24,8 @@ mod foo {
 fn callback<T>(_f: || -> T) -> T { panic!() }
 unsafe fn unsf() {}
 
+macro_rules! call_unsf(() => (unsafe { unsf() }))
+
 fn bad1() { unsafe {} }                  //~ ERROR: unnecessary `unsafe` block
 fn bad2() { unsafe { bad1() } }          //~ ERROR: unnecessary `unsafe` block
 unsafe fn bad3() { unsafe {} }           //~ ERROR: unnecessary `unsafe` block
@@ -61,6 +64,19 @@ fn good2() {
 unsafe fn good3() { foo::bar() }
 fn good4() { unsafe { foo::bar() } }
 
+// The block in the macro is the one needed, wherever the macro is invoked
+fn good5() { call_unsf!() }
+unsafe fn good6() { call_unsf!() }
+fn bad8() { unsafe { call_unsf!() } }    //~ ERROR: unnecessary `unsafe` block
+
+// A block passed to a macro is the user's, and is reported as usual
+macro_rules! id(($e:expr) => ($e))
+fn bad9() { unsafe { id!(unsafe { unsf() }) } } //~ ERROR: unnecessary `unsafe` block
+fn bad10() { id!(unsafe {}) }            //~ ERROR: unnecessary `unsafe` block
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn good7() { unsafe { asm!("nop") } }
+
 #[allow(unused_unsafe)] fn allowed() { unsafe {} }
 
 fn main() {}
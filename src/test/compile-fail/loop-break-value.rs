@@ -0,0 +1,35 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(loop_break_value)]
+
+fn main() {
+    let _x = loop {
+        if true {
+            break 1i;
+        }
+        break "one"; //~ ERROR mismatched types
+    };
+
+    let mut n = 0i;
+    while n < 3 {
+        n += 1;
+        break 1i; //~ ERROR `break` with a value is only allowed inside a `loop`
+    }
+
+    let mut v = vec![1i, 2];
+    while let Some(_) = v.pop() {
+        break 1i; //~ ERROR `break` with a value is only allowed inside a `loop`
+    }
+
+    for _ in range(0i, 3) {
+        break 1i; //~ ERROR `break` with a value is only allowed inside a `loop`
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod limits {
+    pub const LOW: int = 20;
+    pub const HIGH: int = 10;
+}
+
+fn main() {
+    match 15i {
+        limits::LOW ... limits::HIGH => {}
+        //~^ ERROR lower range bound must be less than or equal to upper
+        _ => {}
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that an ambiguous method call lists each applicable candidate and
+// suggests calling it through the path of its trait.
+
+mod shapes {
+    pub trait Area {
+        fn size(&self) -> uint; //~ NOTE candidate #1 is defined in the trait `shapes::Area`
+    }
+    pub trait Length {
+        fn size(&self) -> uint; //~ NOTE candidate #2 is defined in the trait `shapes::Length`
+    }
+}
+
+use shapes::{Area, Length};
+
+struct Square(uint);
+
+impl Area for Square {
+    fn size(&self) -> uint { 0 }
+    //~^ NOTE candidate #1 is defined in an impl of the trait `shapes::Area` for the type `Square`
+}
+
+impl Length for Square {
+    fn size(&self) -> uint { 0 }
+    //~^ NOTE candidate #2 is defined in an impl of the trait `shapes::Length` for the type `Square`
+}
+
+fn by_value(s: Square) {
+    s.size(); //~ ERROR E0034
+    //~^ HELP to use candidate #1, write `shapes::Area::size(&s)` instead
+    //~^^ HELP to use candidate #2, write `shapes::Length::size(&s)` instead
+}
+
+fn by_reference<T: Area + Length>(t: &T) {
+    t.size(); //~ ERROR E0034
+    //~^ HELP to use candidate #1, write `shapes::Area::size(t)` instead
+    //~^^ HELP to use candidate #2, write `shapes::Length::size(t)` instead
+}
+
+fn main() {}
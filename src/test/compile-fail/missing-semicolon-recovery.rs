@@ -0,0 +1,22 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A missing `;` at the end of a line is reported where it belongs, and
+// parsing carries on so that later mistakes are reported too.
+
+fn foo(x: int) -> int { x }
+
+fn main() {
+    let x = 1i //~ ERROR expected `;`, found `let`
+    //~^ HELP insert `;` at the end of this statement
+    let y = x + 1 //~ ERROR expected `;`, found `foo`
+    foo(y) //~ ERROR expected `;`, found `foo`
+    foo(x + y);
+}
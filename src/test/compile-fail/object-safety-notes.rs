@@ -0,0 +1,40 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// Test that converting to an object of a trait that isn't object-safe points
+// at each method that is the reason.
+
+trait Bad {
+    fn by_value(self);
+    //~^ NOTE `by_value` cannot be called through a trait object because it takes `self` by value
+    fn generic<T>(&self, x: T);
+    //~^ NOTE `generic` cannot be called through a trait object because it has type parameters
+    fn returns_self(&self) -> Self;
+    //~^ NOTE because it references the `Self` type in its return type
+    fn takes_self(&self, other: &Self, x: Self) -> Self;
+    //~^ NOTE it references the `Self` type in its arguments and references the `Self` type in
+    fn fine(&self);
+    fn also_fine();
+}
+
+impl Bad for int {
+    fn by_value(self) {}
+    fn generic<T>(&self, _: T) {}
+    fn returns_self(&self) -> int { *self }
+    fn takes_self(&self, _: &int, x: int) -> int { x }
+    fn fine(&self) {}
+    fn also_fine() {}
+}
+
+fn main() {
+    let x = &1i as &Bad;
+    //~^ ERROR cannot convert to a trait object because trait `Bad` is not object-safe
+}
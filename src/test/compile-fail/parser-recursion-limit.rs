@@ -0,0 +1,17 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(parser_recursion_limit)]
+#![parser_recursion_limit = "8"]
+
+fn main() {
+    let x = ((((((((((1i)))))))))); //~ ERROR expression nesting exceeds limit 8
+    //~^ HELP add `#![parser_recursion_limit = "16"]`
+}
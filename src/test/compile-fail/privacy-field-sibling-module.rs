@@ -0,0 +1,56 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Private fields are only accessible from the module that defines the struct,
+// not from its sibling modules.
+
+mod a {
+    pub struct S {
+        x: int,
+        pub y: int,
+    }
+
+    pub enum E {
+        V { z: int },
+    }
+
+    pub fn new() -> S {
+        S { x: 1, y: 2 }
+    }
+}
+
+mod b {
+    use a;
+
+    pub fn read(s: &a::S) -> int {
+        s.x //~ ERROR field `x` of struct `a::S` is private
+    }
+
+    pub fn read_public(s: &a::S) -> int {
+        s.y
+    }
+
+    pub fn build() -> a::S {
+        a::S { x: 1, y: 2 } //~ ERROR field `x` of struct `a::S` is private
+    }
+
+    pub fn read_variant(e: a::E) -> int {
+        match e {
+            a::E::V { z } => z,
+        }
+    }
+}
+
+fn main() {
+    let s = a::new();
+    b::read(&s);
+    b::read_public(&s);
+    b::build();
+}
@@ -15,5 +15,5 @@ struct Foo;
 fn main() {
     let x = Foo;
     x[..]; //~ ERROR incorrect slicing expression: `[..]`
-    //~^ NOTE use `expr[]` to construct a slice of the whole of expr
+    //~^ NOTE use `x[]` to construct a slice of the whole of `x`
 }
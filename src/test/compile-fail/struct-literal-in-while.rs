@@ -20,7 +20,8 @@ impl Foo {
 
 fn main() {
     while Foo {
-        x: 3    //~ ERROR expected one of `;`, `}`
+        x: 3    //~ ERROR expected type, found `3`
+        //~^ HELP if this is a field of a struct literal
     }.hi() {
         println!("yo");
     }
@@ -0,0 +1,20 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+struct Foo { a: int }
+
+fn main() {
+    let x = Foo { a: 1 };
+    // Struct literals are not allowed in an `if` condition, so the braces
+    // below start the `if` block and `a: 1` looks like a type ascription.
+    if x.a == Foo { a: 1 }.a { //~ ERROR expected type, found `1`
+    //~^ HELP if this is a field of a struct literal
+    }
+}
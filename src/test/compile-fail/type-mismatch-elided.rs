@@ -0,0 +1,32 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// Components the mismatched types have in common are left out of the error.
+
+struct Pair<A, B>(A, B);
+
+fn make() -> Pair<String, Vec<int>> {
+    Pair(String::new(), Vec::new())
+}
+
+fn make_tuple() -> (Box<Option<uint>>, String, bool) {
+    (box None, String::new(), true)
+}
+
+fn main() {
+    let _x: Pair<String, Vec<uint>> = make();
+    //~^ ERROR expected `Pair<_, collections::vec::Vec<uint>>`, found `Pair<_, collections::vec
+    //~^^ NOTE in full, expected `Pair<collections::string::String, collections::vec::Vec<uint>>`
+
+    let _y: (Box<Option<int>>, String, bool) = make_tuple();
+    //~^ ERROR expected `(Box<core::option::Option<int>>, _, bool)`, found `(Box<core::option
+    //~^^ NOTE in full
+}
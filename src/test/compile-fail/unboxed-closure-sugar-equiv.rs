@@ -13,7 +13,7 @@
 // angle brackets. This test covers only simple types and in
 // particular doesn't test bound regions.
 
-#![feature(unboxed_closures)]
+#![feature(unboxed_closures, higher_ranked_trait_bounds)]
 #![allow(dead_code)]
 
 trait Foo<T,U> {
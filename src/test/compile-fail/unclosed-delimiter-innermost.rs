@@ -0,0 +1,24 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Only the innermost unclosed delimiter is pointed at, along with the first
+// closing brace whose indentation doesn't match the one it closes. Other
+// delimiters are often closed on a line of their own at any indentation.
+
+mod foo { //~ NOTE unclosed delimiter `{` opened here
+    fn bar() {
+        let x = (
+            1
+                );
+        if true { //~ NOTE the delimiter it closes is opened here
+    } //~ NOTE this delimiter is indented differently
+}
+
+fn main() {} //~ ERROR this file contains an un-closed delimiter
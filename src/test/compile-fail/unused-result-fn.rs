@@ -0,0 +1,55 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![deny(unused_must_use)]
+#![allow(dead_code)]
+
+use std::num::Int;
+
+#[must_use]
+fn need_to_use_it() -> bool { true }
+
+#[must_use = "it's the only copy"]
+fn need_to_use_this_value() -> int { 1 }
+
+fn ignorable() -> int { 1 }
+
+struct S;
+
+trait Checked {
+    #[must_use = "it says whether the check passed"]
+    fn check(&self) -> bool;
+}
+
+impl S {
+    #[must_use]
+    fn need_to_use_method(&self) -> int { 1 }
+}
+
+impl Checked for S {
+    fn check(&self) -> bool { true }
+}
+
+fn main() {
+    need_to_use_it(); //~ ERROR unused return value of `need_to_use_it` which must be used
+    need_to_use_this_value();
+    //~^ ERROR `need_to_use_this_value` which must be used: it's the only copy
+    ignorable();
+
+    let s = S;
+    s.need_to_use_method(); //~ ERROR unused return value of
+    s.check(); //~ ERROR which must be used: it says whether the check passed
+
+    1u8.checked_add(2); //~ ERROR which must be used: a `None` result would go unnoticed
+
+    let _ = need_to_use_it();
+    let _x = need_to_use_this_value();
+}
@@ -0,0 +1,30 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a region error caused by the variance of a type's parameters
+// explains which parameter was inferred to be invariant.
+
+struct Invariant<'a> { //~ NOTE `Invariant` is invariant over its lifetime parameter `'a`
+    f: &'a mut &'a int
+}
+
+struct InvariantType<T> { //~ NOTE `InvariantType` is invariant over its type parameter `T`
+    f: *mut T
+}
+
+fn use_region<'b>(c: Invariant<'b>) {
+    let _: Invariant<'static> = c; //~ ERROR mismatched types
+}
+
+fn use_type<'b>(c: InvariantType<&'b int>) {
+    let _: InvariantType<&'static int> = c; //~ ERROR mismatched types
+}
+
+fn main() { }
@@ -0,0 +1,47 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(associated_types, where_clause_equality)]
+
+trait Get {
+    type Value;
+    fn get(&self) -> &<Self as Get>::Value;
+}
+
+struct Text(String);
+
+impl Get for Text {
+    type Value = String;
+    fn get(&self) -> &String {
+        let Text(ref s) = *self;
+        s
+    }
+}
+
+fn double<T: Get>(x: &T) -> int where <T as Get>::Value == int {
+    *x.get() * 2
+}
+
+fn wrong_body<T: Get>(x: &T) -> uint where <T as Get>::Value == int {
+    *x.get() //~ ERROR mismatched types
+}
+
+fn not_assoc<T, U>(_: T, _: U) where T == U {}
+//~^ ERROR only an associated type of a type parameter
+
+fn twice<T: Get>(_: T) where <T as Get>::Value == int, <T as Get>::Value == uint {}
+//~^ ERROR is already constrained to equal another type
+
+struct S<T> where T == int { x: T }
+//~^ ERROR equality constraints are only supported on functions, impls
+
+fn main() {
+    double(&Text("x".to_string())); //~ ERROR is not implemented for the type `Text`
+}
@@ -0,0 +1,36 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// Test that bounds in `where` clauses on structs and enums are enforced just
+// like inline bounds.
+
+trait Trait {}
+
+struct Pair<T>(T, T) where T: Trait;
+
+struct Named<T> where T: Trait {
+    x: T,
+}
+
+enum Wrapper<T> where T: Trait {
+    Wrapped(T),
+}
+
+fn pair(x: Pair<u32>) {}
+//~^ ERROR not implemented
+
+fn named(x: Named<u32>) {}
+//~^ ERROR not implemented
+
+fn wrapper(x: Wrapper<u32>) {}
+//~^ ERROR not implemented
+
+fn main() {}
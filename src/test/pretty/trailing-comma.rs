@@ -0,0 +1,20 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// pp-exact:trailing-comma.pp
+// pretty-compare-only
+// Testing that trailing commas are accepted and dropped when printing
+
+#[allow(dead_code,)]
+fn f<'a, T,>(x: &'a T,) where T: Clone, { }
+
+fn g(h: |int, int,| -> int) -> int { h(1, 2,) }
+
+fn main() { let [a, b,] = [1i, 2,]; }
@@ -0,0 +1,25 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// pp-exact
+
+#![feature(while_let)]
+
+fn f(v: &mut Vec<int>) -> int {
+    let mut n = 0;
+    'a: while let Some(e) = v.pop() {
+        n += e;
+        if e == 1 { break 'a ; }
+    }
+
+    n
+}
+
+fn main() { }
@@ -0,0 +1,30 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+fn near() {
+    let mut x = 1u;
+    let y = &mut x;
+    let z = &mut x;
+}
+
+fn far() {
+    let mut x = 1u;
+    let y = &x;
+    let a2 = 2u;
+    let a3 = 3u;
+    let a4 = 4u;
+    let a5 = 5u;
+    let a6 = 6u;
+    let a7 = 7u;
+    let z = &mut x;
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+macro_rules! double(
+    ($e:expr) => ({
+        let x = $e;
+        x + x
+    })
+)
+
+#[inline(never)]
+fn id(x: uint) -> uint { x }
+
+fn main() {
+    let y = double!(id(21));
+    assert_eq!(y, 42);
+    let z = double!(
+        id(2)
+    );
+    assert_eq!(z, 4);
+}
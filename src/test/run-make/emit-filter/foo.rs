@@ -0,0 +1,24 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+pub mod keep {
+    #[inline(never)]
+    pub fn first() -> uint { 8675309 }
+}
+
+pub mod other {
+    #[inline(never)]
+    pub fn second() -> uint { 11235813 }
+
+    #[inline(never)]
+    pub fn third() -> uint { 31415926 }
+}
@@ -0,0 +1,28 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+#![feature(macro_rules)]
+
+#[macro_export]
+macro_rules! add_str {
+    () => (1i + "two")
+}
+
+macro_rules! make_add_str_twice {
+    () => (
+        #[macro_export]
+        macro_rules! add_str_twice {
+            () => (2i + "two")
+        }
+    )
+}
+
+make_add_str_twice!()
@@ -0,0 +1,48 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cmp;
+use std::intrinsics::{likely, unlikely, prefetch_read_data, prefetch_write_data};
+
+const LOCALITY: i32 = 3;
+
+#[inline(never)]
+fn sum(xs: &[u32]) -> u32 {
+    let mut total = 0;
+    for (i, x) in xs.iter().enumerate() {
+        unsafe {
+            // Stay within the slice, or `offset` is undefined behaviour
+            let ahead = cmp::min(i + 8, xs.len() - 1);
+            prefetch_read_data(xs.as_ptr().offset(ahead as int), LOCALITY);
+            if unlikely(*x == 0) {
+                continue;
+            }
+        }
+        total += *x;
+    }
+    total
+}
+
+#[inline(never)]
+fn clear(xs: &mut [u32]) {
+    unsafe { prefetch_write_data(xs.as_ptr(), 0); }
+    for x in xs.iter_mut() {
+        *x = 0;
+    }
+}
+
+fn main() {
+    let mut xs = [1u32, 0, 2, 3, 0, 4];
+    assert_eq!(sum(xs.as_slice()), 10);
+    assert!(unsafe { likely(true) });
+    assert!(!unsafe { unlikely(false) });
+    clear(xs.as_mut_slice());
+    assert_eq!(sum(xs.as_slice()), 0);
+}
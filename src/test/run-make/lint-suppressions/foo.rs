@@ -0,0 +1,23 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unused_variables, reason = "the bindings document the API")]
+
+#[allow(dead_code, reason = "kept for the FFI tests")]
+fn unused() {}
+
+#[allow(non_snake_case)]
+fn Main() {}
+
+#[deny(unused_imports, reason = "not a suppression")]
+fn main() {
+    let x = 1i;
+    Main();
+}
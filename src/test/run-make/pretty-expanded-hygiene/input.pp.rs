@@ -13,4 +13,8 @@
 #![no_std]
 
 
-fn bar /* 62#0 */() { let x /* 59#2 */ = 1; y /* 61#4 */ + x /* 59#5 */ }
+fn bar /* bar#0 => bar/62 */() {
+    let x /* x#2: rename x#0 to x/63 => x/63 */ = 1;
+    y /* y#4: rename x#0 to x/63, mark 1 => y/61 */ +
+        x /* x#5: rename x#0 to x/63 => x/63 */
+}
@@ -0,0 +1,24 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_rules)]
+#![crate_type = "lib"]
+
+macro_rules! double {
+    ($e:expr) => { $e * 2 }
+}
+
+macro_rules! quadruple {
+    ($e:expr) => { double!(double!($e)) }
+}
+
+pub fn eight() -> int {
+    quadruple!(2)
+}
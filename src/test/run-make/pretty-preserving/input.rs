@@ -0,0 +1,24 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+pub fn   add(a: int,
+             b: int) -> int { a+b } // keep this layout
+
+#[deriving(Clone)]
+pub struct Point { x: int,   y: int }
+
+pub mod inner {
+    pub fn   sub(a: int,
+                 b: int) -> int {
+        a-b
+    }
+}
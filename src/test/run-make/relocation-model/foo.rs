@@ -8,4 +8,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-pub fn main() {}
+#![feature(thread_local)]
+
+#[thread_local]
+static mut COUNTER: uint = 0;
+
+fn main() {
+    unsafe {
+        COUNTER += 1;
+        assert_eq!(COUNTER, 1);
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::task;
+
+static mut dropped: bool = false;
+
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        unsafe { dropped = true; }
+    }
+}
+
+#[inline(never)]
+fn fill(buf: &mut [u8], n: uint) {
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    if n > 3 {
+        panic!("overflow");
+    }
+}
+
+fn main() {
+    let ret = task::try(proc() {
+        let _guard = Guard;
+        let mut buf = [0u8, ..16];
+        fill(buf.as_mut_slice(), 4);
+    });
+    assert!(ret.is_err());
+    unsafe { assert!(dropped); }
+
+    let mut buf = [0u8, ..16];
+    fill(buf.as_mut_slice(), 0);
+    assert_eq!(buf[15], 15);
+}
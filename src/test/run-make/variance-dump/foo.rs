@@ -0,0 +1,32 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+use std::kinds::marker;
+
+pub struct Covariant<'a, T> {
+    x: &'a T,
+}
+
+pub struct Contravariant<'a> {
+    f: fn(&'a int),
+}
+
+pub struct Invariant<'a> {
+    x: &'a mut &'a int,
+}
+
+pub struct Bivariant<T>;
+
+pub struct Marked<T> {
+    x: *const T,
+    marker: marker::InvariantType<T>,
+}
@@ -23,6 +23,7 @@ fn foo() -> AFakeTypeThatHadBetterGoAway {}
 pub fn main() {
     assert_eq!(1, make_a_1!());
     assert_eq!(2, exported_macro!());
+    assert_eq!(4, reserved_ids!());
 
     assert_eq!(Foo::Bar, Foo::Bar);
     test(None::<Foo>);
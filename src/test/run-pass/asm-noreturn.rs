@@ -0,0 +1,33 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-android
+
+#![feature(asm)]
+
+#[cfg(any(target_arch = "x86",
+          target_arch = "x86_64"))]
+fn trap(x: uint) -> uint {
+    if x < 10 {
+        return x * 2;
+    }
+    // Nothing follows the trap, and no value is needed after it.
+    unsafe { asm!("ud2" :::: "noreturn") }
+}
+
+#[cfg(any(target_arch = "x86",
+          target_arch = "x86_64"))]
+pub fn main() {
+    assert_eq!(trap(4), 8);
+}
+
+#[cfg(not(any(target_arch = "x86",
+              target_arch = "x86_64")))]
+pub fn main() {}
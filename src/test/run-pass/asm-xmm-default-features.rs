@@ -0,0 +1,29 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that xmm registers can be used without enabling SSE explicitly, on
+// 32-bit x86 as well, where the target CPU provides it.
+
+#![feature(asm)]
+
+#[cfg(any(target_arch = "x86",
+          target_arch = "x86_64"))]
+pub fn main() {
+    let x = 5u32;
+    let y: u32;
+    unsafe {
+        asm!("movd $1, %xmm0\n\tmovd %xmm0, $0" : "=r"(y) : "r"(x) : "xmm0");
+    }
+    assert_eq!(y, 5);
+}
+
+#[cfg(not(any(target_arch = "x86",
+              target_arch = "x86_64")))]
+pub fn main() {}
@@ -0,0 +1,55 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:associated_consts_xc.rs
+
+// Test that associated constants can be used from another crate, and that
+// its traits can be implemented here, leaving out the constants that have
+// defaults.
+
+#![feature(associated_consts)]
+
+extern crate associated_consts_xc;
+
+use associated_consts_xc::{Bounded, Buffer, Custom, HasId, Id};
+
+struct Local;
+
+impl Bounded for Local {
+    const MAX: Local = Local;
+}
+
+impl HasId for Local {}
+
+fn max<T: Bounded>() -> T {
+    Bounded::MAX
+}
+
+fn id<T: HasId>() -> uint {
+    let id: Id<T> = HasId::ID;
+    id.n
+}
+
+static BYTE_MAX: u8 = Bounded::MAX;
+
+fn main() {
+    let a: u8 = Bounded::MAX;
+    assert_eq!(a, 255);
+    assert_eq!(max::<u8>(), 255);
+    assert_eq!(BYTE_MAX, 255);
+    let Local = max::<Local>();
+
+    assert_eq!(id::<Custom>(), 7);
+    assert_eq!(id::<Local>(), 7);
+
+    assert_eq!(Buffer::SIZE, 4);
+    let buf = [0u8, ..Buffer::SIZE];
+    assert_eq!(buf.len(), 4);
+}
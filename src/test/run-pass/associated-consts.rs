@@ -0,0 +1,78 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(associated_consts)]
+
+trait Bounded {
+    const MAX: Self;
+}
+
+impl Bounded for u8 {
+    const MAX: u8 = 255;
+}
+
+impl Bounded for i16 {
+    const MAX: i16 = 32767;
+}
+
+// Tags an id with the type it belongs to, so that using the id picks the
+// impl to take it from.
+struct Id<T> {
+    n: uint,
+}
+
+trait HasId {
+    const ID: Id<Self> = Id { n: 0 };
+}
+
+struct Custom;
+
+// Leaving out a constant with a default is fine.
+impl HasId for Custom {}
+
+struct Numbered;
+
+impl HasId for Numbered {
+    const ID: Id<Numbered> = Id { n: 3 };
+}
+
+struct Buffer;
+
+impl Buffer {
+    const SIZE: uint = 4;
+}
+
+fn max<T: Bounded>() -> T {
+    Bounded::MAX
+}
+
+fn id<T: HasId>() -> uint {
+    let id: Id<T> = HasId::ID;
+    id.n
+}
+
+static BYTE_MAX: u8 = Bounded::MAX;
+
+fn main() {
+    let a: u8 = Bounded::MAX;
+    assert_eq!(a, 255);
+    let b: i16 = max();
+    assert_eq!(b, 32767);
+    assert_eq!(max::<u8>(), 255);
+    assert_eq!(BYTE_MAX, 255);
+
+    assert_eq!(id::<Custom>(), 0);
+    assert_eq!(id::<Numbered>(), 3);
+
+    assert_eq!(Buffer::SIZE, 4);
+    let buf = [0u8, ..Buffer::SIZE];
+    assert_eq!(buf.len(), 4);
+}
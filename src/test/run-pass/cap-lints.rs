@@ -0,0 +1,24 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --cap-lints warn
+
+// Denied and forbidden lints are only warnings under `--cap-lints warn`, and
+// allowing a forbidden lint isn't an error.
+
+#![deny(unused_variables)]
+#![forbid(non_camel_case_types)]
+
+#[allow(non_camel_case_types)]
+struct snake_case;
+
+fn main() {
+    let x = 1i;
+}
@@ -0,0 +1,30 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-arm
+// ignore-aarch64
+// compile-flags: -C target-feature=+avx,-avx,+sse4.2
+
+// Features passed with `-C target-feature` are exposed to `cfg`, along with
+// the features they imply; a later `-feature` cancels an earlier `+feature`.
+// The features are x86 ones, so other targets don't know them.
+
+#[cfg(target_feature = "sse4.2")]
+fn sse42() -> bool { true }
+
+#[cfg(not(target_feature = "sse4.2"))]
+fn sse42() -> bool { false }
+
+pub fn main() {
+    assert!(sse42());
+    assert!(cfg!(target_feature = "sse4.1"));
+    assert!(cfg!(target_feature = "sse"));
+    assert!(!cfg!(target_feature = "avx"));
+}
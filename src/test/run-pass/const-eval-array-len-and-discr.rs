@@ -0,0 +1,40 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:const_eval_cross_crate.rs
+
+// Check that array lengths and enum discriminants can use arithmetic,
+// shifts and casts, and constants defined in another crate.
+
+extern crate const_eval_cross_crate;
+
+use const_eval_cross_crate::{BASE, MASK, SHIFT};
+use std::mem::size_of;
+
+const N: uint = 4u;
+
+enum Discr {
+    Small = 1 << 2,
+    Negative = BASE as int * 2,
+    Masked = (MASK & 0x0f) as int,
+    Cast = (N as u8 - 1) as int,
+}
+
+pub fn main() {
+    assert_eq!(size_of::<[u8, ..N * 2]>(), 8);
+    assert_eq!(size_of::<[u8, ..1 << SHIFT]>(), 8);
+    assert_eq!(size_of::<[u8, ..(BASE as u8) as uint - 250]>(), 5);
+    assert_eq!(size_of::<[u8, ..MASK]>(), 30);
+
+    assert_eq!(Discr::Small as int, 4);
+    assert_eq!(Discr::Negative as int, -2);
+    assert_eq!(Discr::Masked as int, 14);
+    assert_eq!(Discr::Cast as int, 3);
+}
@@ -0,0 +1,19 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that `!` on an unsigned constant only sets the bits of its type,
+// so arithmetic on the result isn't taken to overflow.
+
+use std::mem::size_of;
+
+pub fn main() {
+    assert_eq!(size_of::<[u8, ..(!0u8 - 1) as uint]>(), 254);
+    assert_eq!(size_of::<[u8, ..(!0u16 >> 8) as uint]>(), 255);
+}
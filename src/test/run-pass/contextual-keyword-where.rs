@@ -0,0 +1,33 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `where` is a contextual keyword: it introduces a where clause after a
+// generic item's signature, and is an ordinary identifier elsewhere.
+
+#![allow(contextual_keyword_idents)]
+
+trait Locate {
+    fn where(&self) -> uint;
+}
+
+struct Here;
+
+impl Locate for Here {
+    fn where(&self) -> uint { 1 }
+}
+
+fn find<T>(t: &T) -> uint where T: Locate {
+    let where = t.where();
+    where + 1
+}
+
+pub fn main() {
+    assert_eq!(find(&Here), 2);
+}
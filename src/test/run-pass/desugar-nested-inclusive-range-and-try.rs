@@ -0,0 +1,40 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks that `...` and `?` are desugared wherever they are nested, including
+// under nodes that expansion otherwise only folds through.
+
+#![feature(inclusive_range_syntax, question_mark)]
+
+struct Pair {
+    a: Vec<u8>,
+    b: uint,
+}
+
+fn parse(s: &str) -> Result<uint, String> {
+    from_str(s).ok_or(format!("bad number: {}", s))
+}
+
+fn pair(x: &str, y: &str) -> Result<Pair, String> {
+    Ok(Pair {
+        a: (0u8...parse(x)? as u8).collect(),
+        b: [parse(y)?, 1][0] + Some(parse(x)?).unwrap(),
+    })
+}
+
+pub fn main() {
+    let p = pair("3", "4").unwrap();
+    assert_eq!(p.a, vec![0, 1, 2, 3]);
+    assert_eq!(p.b, 7);
+    assert!(pair("3", "x").is_err());
+
+    let total = (1u...4).map(|i| (i...i + 1).count()).fold(0, |a, b| a + b);
+    assert_eq!(total, 8);
+}
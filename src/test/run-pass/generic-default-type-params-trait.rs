@@ -0,0 +1,49 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// Omitted type parameters of traits fall back to their defaults, in bounds,
+// impls and object types alike.
+
+#![feature(default_type_params)]
+
+trait Convert<T = int> {
+    fn convert(&self) -> T;
+}
+
+impl Convert for u8 {
+    fn convert(&self) -> int {
+        *self as int
+    }
+}
+
+impl Convert<char> for u8 {
+    fn convert(&self) -> char {
+        *self as char
+    }
+}
+
+fn convert_default<C: Convert>(c: &C) -> int {
+    c.convert()
+}
+
+fn convert_char<C: Convert<char>>(c: &C) -> char {
+    c.convert()
+}
+
+fn convert_object(c: &Convert) -> int {
+    c.convert()
+}
+
+fn main() {
+    assert_eq!(convert_default(&3u8), 3);
+    assert_eq!(convert_char(&97u8), 'a');
+    assert_eq!(convert_object(&3u8 as &Convert), 3);
+}
@@ -10,6 +10,8 @@
 
 // A basic test of using a higher-ranked trait bound.
 
+#![feature(higher_ranked_trait_bounds)]
+
 trait FnLike<A,R> {
     fn call(&self, arg: A) -> R;
 }
@@ -11,7 +11,7 @@
 // Test that we can parse all the various places that a `for` keyword
 // can appear representing universal quantification.
 
-#![feature(unboxed_closures)]
+#![feature(unboxed_closures, higher_ranked_trait_bounds)]
 #![allow(unused_variables)]
 #![allow(dead_code)]
 
@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-#![feature(unboxed_closures)]
+#![feature(unboxed_closures, higher_ranked_trait_bounds)]
 
 // A basic test of using a higher-ranked trait bound.
 
@@ -0,0 +1,37 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(inclusive_range_syntax)]
+
+pub fn main() {
+    let mut sum = 0u;
+    for i in 1u...10 {
+        sum += i;
+    }
+    assert_eq!(sum, 55);
+
+    // The upper bound is reachable even when it is the type's maximum.
+    let mut count = 0u;
+    for b in 250u8...255 {
+        count += 1;
+        assert!(b >= 250);
+    }
+    assert_eq!(count, 6);
+
+    let n = 3i;
+    let v: Vec<int> = (-n...n * 2 - 3).collect();
+    assert_eq!(v, vec![-3, -2, -1, 0, 1, 2, 3]);
+
+    // Range patterns use the same syntax.
+    match 5u {
+        1...5 => {}
+        _ => panic!(),
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// Test that integer literals whose type isn't constrained default to `i32`,
+// and that the choice is made the same way wherever they appear.
+
+trait Describe {
+    fn describe(&self) -> &'static str;
+}
+
+impl Describe for i32 {
+    fn describe(&self) -> &'static str { "i32" }
+}
+
+impl Describe for i64 {
+    fn describe(&self) -> &'static str { "i64" }
+}
+
+fn describe<T: Describe>(x: T) -> &'static str {
+    x.describe()
+}
+
+static SIZE: uint = 3;
+
+fn main() {
+    assert_eq!(describe(1), "i32");
+    assert_eq!(describe(1 + 2), "i32");
+
+    // A constrained literal isn't affected.
+    let x: i64 = 5;
+    assert_eq!(describe(x), "i64");
+
+    let v = [0, ..SIZE];
+    assert_eq!(std::mem::size_of_val(&v[0]), 4);
+
+    let y = 3;
+    let z = y * 2;
+    assert_eq!(std::mem::size_of_val(&z), 4);
+}
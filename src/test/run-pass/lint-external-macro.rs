@@ -0,0 +1,25 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Style lints don't fire on code from macros defined in other crates, which
+// the user can't change.
+
+//aux-build:lint_external_macro.rs
+//ignore-stage1
+
+#![feature(phase)]
+#![deny(non_camel_case_types, non_snake_case, unused_parens)]
+
+#[phase(plugin)]
+extern crate lint_external_macro;
+
+badly_named!()
+
+pub fn main() {}
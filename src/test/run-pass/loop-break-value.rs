@@ -0,0 +1,53 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(loop_break_value)]
+
+fn first_square_above(n: uint) -> uint {
+    let mut i = 0u;
+    loop {
+        if i * i > n {
+            break i * i;
+        }
+        i += 1;
+    }
+}
+
+fn labeled() -> (int, String) {
+    'outer: loop {
+        let mut j = 0i;
+        loop {
+            j += 1;
+            if j == 3 {
+                break 'outer (j, "three".to_string());
+            }
+        }
+    }
+}
+
+pub fn main() {
+    assert_eq!(first_square_above(10), 16);
+    assert_eq!(labeled(), (3, "three".to_string()));
+
+    let v = loop { break vec![1u, 2, 3]; };
+    assert_eq!(v.len(), 3);
+
+    // A loop that breaks without a value has type `()`.
+    let () = loop { break; };
+
+    let mut n = 0i;
+    let s: &str = loop {
+        n += 1;
+        if n < 5 { continue; }
+        break "done";
+    };
+    assert_eq!(s, "done");
+    assert_eq!(n, 5);
+}
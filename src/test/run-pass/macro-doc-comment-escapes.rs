@@ -0,0 +1,41 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Doc comments passed through a macro are turned back into `#[doc]`
+// attributes; their text must survive quotes and backslashes, and inner
+// doc comments must stay inner.
+
+#![feature(macro_rules)]
+
+macro_rules! documented {
+    ($(#[$attr:meta])* fn $name:ident() $body:block) => {
+        $(#[$attr])* fn $name() $body
+    }
+}
+
+documented! {
+    /// Returns "one", unlike C:\path\to\two.
+    /** A block doc comment with a "quote" and a \ backslash. */
+    fn one() {}
+}
+
+macro_rules! item_body {
+    ($($t:tt)*) => { mod m { $($t)* } }
+}
+
+item_body! {
+    //! Inner docs with a "quote".
+    pub fn two() {}
+}
+
+pub fn main() {
+    one();
+    m::two();
+}
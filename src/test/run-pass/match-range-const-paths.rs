@@ -0,0 +1,56 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Range patterns may use paths to constants for either endpoint.
+
+mod limits {
+    pub const LOW: int = 10;
+    pub const HIGH: int = 20;
+}
+
+const LOWER_A: char = 'a';
+const LOWER_Z: char = 'z';
+
+enum Level {
+    Low = 1,
+    High = 3,
+}
+
+const LEVEL_LOW: int = Level::Low as int;
+const LEVEL_HIGH: int = Level::High as int;
+
+fn classify(x: int) -> &'static str {
+    match x {
+        limits::LOW ... limits::HIGH => "in range",
+        0 ... limits::LOW => "below",
+        _ => "other",
+    }
+}
+
+fn level(x: int) -> bool {
+    match x {
+        LEVEL_LOW ... LEVEL_HIGH => true,
+        _ => false,
+    }
+}
+
+pub fn main() {
+    assert_eq!(classify(15), "in range");
+    assert_eq!(classify(5), "below");
+    assert_eq!(classify(25), "other");
+
+    match 'q' {
+        LOWER_A ... LOWER_Z => {}
+        _ => panic!(),
+    }
+
+    assert!(level(2));
+    assert!(!level(4));
+}
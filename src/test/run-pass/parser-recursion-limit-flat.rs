@@ -0,0 +1,24 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Operators of the same precedence are folded iteratively, so a long flat
+// chain of them doesn't count as nesting.
+
+#![feature(parser_recursion_limit)]
+#![parser_recursion_limit = "16"]
+
+pub fn main() {
+    let a = 1i;
+    let x = a + a + a + a + a + a + a + a + a + a + a + a + a + a + a + a +
+            a + a + a + a + a + a + a + a + a + a + a + a + a + a + a + a +
+            a + a + a + a + a + a + a + a + a + a + a + a + a + a + a + a +
+            a + a + a + a + a + a + a + a + a + a + a + a + a + a + a + a;
+    assert!(x == 64);
+}
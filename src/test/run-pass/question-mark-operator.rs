@@ -0,0 +1,47 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(question_mark)]
+
+use std::error::FromError;
+
+#[deriving(PartialEq, Show)]
+struct MyError(String);
+
+impl FromError<String> for MyError {
+    fn from_error(s: String) -> MyError { MyError(s) }
+}
+
+fn half(x: int) -> Result<int, String> {
+    if x % 2 == 0 { Ok(x / 2) } else { Err(format!("{} is odd", x)) }
+}
+
+fn quarter(x: int) -> Result<int, String> {
+    Ok(half(half(x)?)?)
+}
+
+fn converted(x: int) -> Result<int, MyError> {
+    let h = half(x)?;
+    Ok(h + 1)
+}
+
+fn in_expression(x: int) -> Result<int, String> {
+    // `?` binds tighter than binary operators.
+    Ok(half(x)? * 10 + half(x + 2)?)
+}
+
+pub fn main() {
+    assert_eq!(quarter(8), Ok(2));
+    assert_eq!(quarter(6), Err("3 is odd".to_string()));
+    assert_eq!(converted(4), Ok(3));
+    assert_eq!(converted(5), Err(MyError("5 is odd".to_string())));
+    assert_eq!(in_expression(4), Ok(23));
+    assert_eq!(in_expression(3), Err("3 is odd".to_string()));
+}
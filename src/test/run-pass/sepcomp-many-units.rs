@@ -0,0 +1,41 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C codegen-units=64
+
+// Test that a crate split into more compilation units than there are CPUs to
+// optimize them on still builds and links correctly, with the units shared out
+// among the worker threads.
+
+fn one() -> uint { 1 }
+
+mod a {
+    pub fn two() -> uint { ::one() + ::one() }
+}
+
+mod b {
+    pub fn three() -> uint { ::one() + ::a::two() }
+}
+
+mod c {
+    pub fn four() -> uint { ::b::three() + ::one() }
+}
+
+mod d {
+    pub fn five() -> uint { ::c::four() + ::one() }
+}
+
+fn main() {
+    assert_eq!(one(), 1);
+    assert_eq!(a::two(), 2);
+    assert_eq!(b::three(), 3);
+    assert_eq!(c::four(), 4);
+    assert_eq!(d::five(), 5);
+}
@@ -0,0 +1,33 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(field_init_shorthand)]
+
+#[deriving(PartialEq, Show)]
+struct Point {
+    x: int,
+    y: int,
+    z: int,
+}
+
+fn make(x: int, y: int) -> Point {
+    let z = x + y;
+    Point { x, y: y * 2, z }
+}
+
+pub fn main() {
+    let p = make(1, 2);
+    assert_eq!(p, Point { x: 1, y: 4, z: 3 });
+
+    let x = 5;
+    let q = Point { x, ..p };
+    assert_eq!(q.x, 5);
+    assert_eq!(q.y, 4);
+}
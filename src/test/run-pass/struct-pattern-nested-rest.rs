@@ -0,0 +1,47 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `..` in struct patterns works at any depth, including inside enum
+// variants and tuples, and may be used even when every field is named.
+
+struct Inner {
+    a: int,
+    b: int,
+    c: int,
+}
+
+enum Outer {
+    One(Inner),
+    Two(int, Inner),
+    Three { inner: Inner, extra: int },
+}
+
+fn sum(o: &Outer) -> int {
+    match *o {
+        Outer::One(Inner { a, .. }) => a,
+        Outer::Two(n, Inner { ref b, .. }) => n + *b,
+        Outer::Three { inner: Inner { c, .. }, .. } => c,
+    }
+}
+
+pub fn main() {
+    let i = Inner { a: 1, b: 2, c: 3 };
+    assert_eq!(sum(&Outer::One(i)), 1);
+
+    let i = Inner { a: 1, b: 2, c: 3 };
+    assert_eq!(sum(&Outer::Two(10, i)), 12);
+
+    let i = Inner { a: 1, b: 2, c: 3 };
+    assert_eq!(sum(&Outer::Three { inner: i, extra: 0 }), 3);
+
+    let pair = (Inner { a: 4, b: 5, c: 6 }, 7i);
+    let (Inner { a, b, c, .. }, _) = pair;
+    assert_eq!(a + b + c, 15);
+}
@@ -12,6 +12,16 @@ fn f<T,>(_: T,) {}
 
 struct Foo<T,>;
 
+struct Pair<'a, 'b,> {
+    a: &'a int,
+    b: &'b int,
+}
+
+#[allow(dead_code,)]
+fn g<'a, T,>(_: &'a T,) where T: Clone, {}
+
+fn h(f: |int, int,| -> int) -> int { f(1, 2,) }
+
 struct Bar;
 
 impl Bar {
@@ -35,4 +45,16 @@ pub fn main() {
     Bar.h();
 
     let x = Baz::Qux(1,);
+
+    let p = Pair { a: &1, b: &2, };
+    let Pair { a, b, } = p;
+    assert_eq!(*a + *b, 3);
+
+    g::<int,>(&0i,);
+    assert_eq!(h(|x, y,| x + y), 3);
+
+    let v = [1i, 2, 3,];
+    match v {
+        [a, b, c,] => assert_eq!(a + b + c, 6),
+    }
 }
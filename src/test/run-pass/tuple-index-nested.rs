@@ -0,0 +1,27 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(tuple_indexing)]
+
+struct Point(int, (int, int));
+
+fn main() {
+    let mut x = ((1i, (2i, 3i)), 4i);
+    assert_eq!(x.0.0, 1);
+    assert_eq!(x.0.1.0, 2);
+    assert_eq!(x.0.1.1, 3);
+    x.0.1.1 += 10;
+    assert_eq!(x.0.1.1, 13);
+    assert_eq!((x.0).1, (2, 13));
+
+    let p = Point(5, (6, 7));
+    assert_eq!(p.1.0, 6);
+    assert_eq!(p.1.1, 7);
+}
@@ -0,0 +1,39 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(type_ascription)]
+
+use std::default::Default;
+
+macro_rules! default_of {
+    ($t:ty) => { Default::default(): $t }
+}
+
+fn len(v: &[uint]) -> uint { v.len() }
+
+pub fn main() {
+    let x = 1: u8;
+    assert_eq!(x, 1u8);
+
+    // Ascription fixes the type that inference would otherwise pick.
+    let v = Vec::new(): Vec<uint>;
+    assert_eq!(v.len(), 0);
+
+    let zero = default_of!(int);
+    assert_eq!(zero, 0i);
+
+    // Ascription is a coercion site.
+    let arr = [1u, 2, 3];
+    assert_eq!(len(&arr: &[uint]), 3);
+
+    // It binds as tightly as `as`.
+    let y = 2u + 3: uint * 4;
+    assert_eq!(y, 14);
+}
@@ -0,0 +1,47 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `..tail` binds the remainder of a slice just like `tail..`.
+
+#![feature(advanced_slice_patterns)]
+
+fn sum(values: &[int]) -> int {
+    match values {
+        [head, ..tail] => head + sum(tail),
+        [] => 0
+    }
+}
+
+fn increment_tail(values: &mut [int]) {
+    match values {
+        [_, ..tail] => {
+            for x in tail.iter_mut() {
+                *x += 1;
+            }
+        }
+        [] => {}
+    }
+}
+
+pub fn main() {
+    let mut x = [1i, 2, 3, 4];
+    assert_eq!(sum(&x), 10);
+
+    increment_tail(&mut x);
+    assert_eq!(x.as_slice(), [1i, 3, 4, 5].as_slice());
+
+    match x {
+        [first, ..middle, last] => {
+            assert_eq!(first, 1);
+            assert_eq!(middle, [3i, 4].as_slice());
+            assert_eq!(last, 5);
+        }
+    }
+}
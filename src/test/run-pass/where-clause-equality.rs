@@ -0,0 +1,55 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(associated_types, where_clause_equality)]
+
+trait Get {
+    type Value;
+    fn get(&self) -> &<Self as Get>::Value;
+}
+
+struct Struct {
+    x: int,
+}
+
+impl Get for Struct {
+    type Value = int;
+    fn get(&self) -> &int {
+        &self.x
+    }
+}
+
+// The constraint makes the associated type usable as an `int` in the body.
+fn double<T: Get>(x: &T) -> int where <T as Get>::Value == int {
+    *x.get() * 2
+}
+
+// It can also tie the associated type to another type parameter.
+fn copied<V: Clone, T: Get>(x: &T) -> V where <T as Get>::Value == V {
+    x.get().clone()
+}
+
+struct Doubler<T> {
+    inner: T,
+}
+
+impl<T: Get> Doubler<T> where <T as Get>::Value == int {
+    fn value(&self) -> int {
+        double(&self.inner)
+    }
+}
+
+fn main() {
+    let s = Struct { x: 21 };
+    assert_eq!(double(&s), 42);
+    let v: int = copied(&s);
+    assert_eq!(v, 21);
+    assert_eq!(Doubler { inner: s }.value(), 42);
+}
@@ -0,0 +1,94 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// Test that `where` clauses are accepted on every kind of generic item and
+// behave like the equivalent inline bounds.
+
+#![feature(associated_types)]
+
+trait Double {
+    fn double(&self) -> Self;
+}
+
+impl Double for int {
+    fn double(&self) -> int { *self * 2 }
+}
+
+trait Doubler {
+    fn double_of<T>(&self, x: T) -> T where T: Double;
+}
+
+struct D;
+
+impl Doubler for D {
+    fn double_of<T>(&self, x: T) -> T where T: Double {
+        x.double()
+    }
+}
+
+struct Pair<T>(T, T) where T: Double;
+
+struct Named<T> where T: Double {
+    x: T,
+}
+
+enum Either<A, B> where A: Double, B: Double {
+    Left(A),
+    Right(B),
+}
+
+impl<T> Pair<T> where T: Double {
+    fn double(&self) -> Pair<T> {
+        let Pair(ref a, ref b) = *self;
+        Pair(a.double(), b.double())
+    }
+}
+
+#[deriving(Clone, PartialEq, Show)]
+struct Wrapped<T> where T: Clone {
+    x: T,
+}
+
+trait Get {
+    type Value;
+    fn get(&self) -> &<Self as Get>::Value;
+}
+
+impl Get for Named<int> {
+    type Value = int;
+    fn get(&self) -> &int {
+        &self.x
+    }
+}
+
+fn grab<T>(x: &T) -> &<T as Get>::Value where T: Get {
+    x.get()
+}
+
+fn main() {
+    assert_eq!(D.double_of(3i), 6);
+
+    let Pair(a, b) = Pair(1i, 2i).double();
+    assert_eq!((a, b), (2, 4));
+
+    let named = Named { x: 5i };
+    assert_eq!(named.x.double(), 10);
+    assert_eq!(*grab(&named), 5);
+
+    let either: Either<int, int> = Either::Left(7);
+    match either {
+        Either::Left(x) => assert_eq!(x.double(), 14),
+        Either::Right(_) => panic!(),
+    }
+
+    let w = Wrapped { x: 1i };
+    assert_eq!(w.clone(), w);
+}